@@ -0,0 +1,2 @@
+#include "../consts.glsl"
+vec4 light() { return vec4(INTENSITY); }
@@ -0,0 +1 @@
+#define INTENSITY 1.0
@@ -0,0 +1,6 @@
+#version 450
+#include "common/light.glsl"
+
+void main() {
+    color = light();
+}
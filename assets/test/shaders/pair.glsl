@@ -0,0 +1,2 @@
+#include "pair.h"
+float pair() { return PAIR; }
@@ -0,0 +1 @@
+#define PAIR 1
@@ -1,22 +1,60 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
 };
 use syn::parse::{Parse, ParseStream};
 
 
-pub struct Input(PathBuf);
+pub struct Input {
+    paths: Vec<PathBuf>,
+    compression: Compression,
+    filter: Filter,
+}
 
 impl Parse for Input {
     fn parse(input: ParseStream) -> Result<Self, syn::Error> {
-        let lit_path = input.parse::<syn::LitStr>()?;
+        let lit_paths = if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            content.parse_terminated::<_, syn::Token![,]>(|input| input.parse::<syn::LitStr>())?.into_iter().collect()
+        } else {
+            vec![input.parse::<syn::LitStr>()?]
+        };
 
-        match Path::new(&lit_path.value()).canonicalize() {
-            Ok(path) => Ok(Input(path)),
-            Err(e) => Err(syn::Error::new(lit_path.span(), e))
+        if lit_paths.is_empty() {
+            return Err(syn::Error::new(Span::call_site(), "expected at least one directory"));
         }
+
+        let paths = lit_paths.iter().map(|lit_path| {
+            Path::new(&lit_path.value()).canonicalize().map_err(|e| syn::Error::new(lit_path.span(), e))
+        }).collect::<Result<_, _>>()?;
+
+        let mut compression = Compression::None;
+        let mut filter = Filter::default();
+
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let name = input.parse::<syn::Ident>()?;
+            input.parse::<syn::Token![=]>()?;
+
+            match &*name.to_string() {
+                "compress" => {
+                    let value = input.parse::<syn::LitStr>()?;
+                    compression = Compression::parse(&value)?;
+                }
+                "include" => filter.include = parse_patterns(input)?,
+                "exclude" => filter.exclude = parse_patterns(input)?,
+                _ => return Err(syn::Error::new(name.span(), format!("unknown argument `{}`", name))),
+            }
+        }
+
+        Ok(Input { paths, compression, filter })
     }
 }
 
@@ -26,10 +64,18 @@ impl Input {
         let mut content = Content::new();
         content.push_dir(Id::new());
 
-        read_dir(&self.0, &mut content, Id::new(), &mut errors);
+        // Earlier directories take priority over later ones
+        for path in &self.paths {
+            let root = Root { path, filter: &self.filter };
+            read_dir(&root, path, &mut content, Id::new(), &mut errors);
+        }
+
+        if !self.filter.is_empty() {
+            content.remove_empty_dirs();
+        }
 
         if errors.is_empty() {
-            Ok(content.to_token_stream())
+            content.to_token_stream(self.compression).map_err(|err| vec![err])
         } else {
             Err(errors)
         }
@@ -37,6 +83,141 @@ impl Input {
 }
 
 
+#[derive(Clone, Copy)]
+enum Compression {
+    None,
+    #[cfg(feature = "deflate")]
+    Deflate,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    fn parse(value: &syn::LitStr) -> Result<Compression, syn::Error> {
+        #[allow(unused)]
+        let missing_feature = |feature: &str| -> Result<Compression, syn::Error> {
+            let msg = format!("`{}` compression requires feature `{}`", value.value(), feature);
+            Err(syn::Error::new(value.span(), msg))
+        };
+
+        match &*value.value() {
+            "none" => Ok(Compression::None),
+
+            #[cfg(feature = "deflate")]
+            "deflate" => Ok(Compression::Deflate),
+            #[cfg(not(feature = "deflate"))]
+            "deflate" => missing_feature("embedded-deflate"),
+
+            #[cfg(feature = "zstd")]
+            "zstd" => Ok(Compression::Zstd),
+            #[cfg(not(feature = "zstd"))]
+            "zstd" => missing_feature("embedded-zstd"),
+
+            other => {
+                let msg = format!("unknown compression `{}`, expected `none`, `deflate` or `zstd`", other);
+                Err(syn::Error::new(value.span(), msg))
+            }
+        }
+    }
+
+    fn path(self) -> TokenStream {
+        match self {
+            Compression::None => quote! { assets_manager::source::Compression::None },
+            #[cfg(feature = "deflate")]
+            Compression::Deflate => quote! { assets_manager::source::Compression::Deflate },
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => quote! { assets_manager::source::Compression::Zstd },
+        }
+    }
+
+    fn compress(self, path: &Path) -> std::io::Result<Vec<u8>> {
+        let content = std::fs::read(path)?;
+
+        match self {
+            Compression::None => Ok(content),
+            #[cfg(feature = "deflate")]
+            Compression::Deflate => {
+                use std::io::Write;
+
+                let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(&content)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd_crate::stream::encode_all(&*content, 19),
+        }
+    }
+}
+
+
+/// Glob patterns to select the files to embed.
+///
+/// Patterns without `/` are matched against file names, and other ones
+/// against paths relative to the embedded directory.
+#[derive(Default)]
+struct Filter {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl Filter {
+    fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    fn matches(patterns: &[glob::Pattern], path: &str) -> bool {
+        const OPTIONS: glob::MatchOptions = glob::MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+
+        let name = path.rsplit('/').next().unwrap_or(path);
+
+        patterns.iter().any(|pattern| {
+            if pattern.as_str().contains('/') {
+                pattern.matches_with(path, OPTIONS)
+            } else {
+                pattern.matches_with(name, OPTIONS)
+            }
+        })
+    }
+
+    fn include_dir(&self, path: &str) -> bool {
+        !Self::matches(&self.exclude, path)
+    }
+
+    fn include_file(&self, path: &str) -> bool {
+        (self.include.is_empty() || Self::matches(&self.include, path)) && !Self::matches(&self.exclude, path)
+    }
+}
+
+fn parse_patterns(input: ParseStream) -> Result<Vec<glob::Pattern>, syn::Error> {
+    let content;
+    syn::bracketed!(content in input);
+
+    let patterns = content.parse_terminated::<_, syn::Token![,]>(|input| input.parse::<syn::LitStr>())?;
+    patterns.iter().map(|lit| {
+        glob::Pattern::new(&lit.value()).map_err(|err| syn::Error::new(lit.span(), err))
+    }).collect()
+}
+
+
+struct Root<'a> {
+    path: &'a Path,
+    filter: &'a Filter,
+}
+
+impl Root<'_> {
+    /// Returns the path relative to the root, with `/` as separator.
+    fn relative(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(self.path).ok()?;
+        let components: Option<Vec<&str>> = relative.iter().map(|c| c.to_str()).collect();
+        Some(components?.join("/"))
+    }
+}
+
+
 fn extension_of(path: &Path) -> Option<&str> {
     match path.extension() {
         Some(ext) => ext.to_str(),
@@ -48,7 +229,7 @@ fn push_error<T: std::fmt::Display>(errors: &mut Vec<syn::Error>, err: T) {
     errors.push(syn::Error::new(Span::call_site(), err));
 }
 
-fn read_dir(path: &Path, content: &mut Content, id: Id, errors: &mut Vec<syn::Error>) {
+fn read_dir(root: &Root, path: &Path, content: &mut Content, id: Id, errors: &mut Vec<syn::Error>) {
     let dir = match path.read_dir() {
         Ok(dir) => dir,
         Err(e) => {
@@ -66,13 +247,20 @@ fn read_dir(path: &Path, content: &mut Content, id: Id, errors: &mut Vec<syn::Er
             }
         };
 
+        let relative = match root.relative(&path) {
+            Some(relative) => relative,
+            None => continue,
+        };
+
         if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
             let this_id = id.clone().push(stem);
 
             if path.is_dir() {
-                content.push_dir(this_id.clone());
-                read_dir(&path, content, this_id, errors);
-            } else if path.is_file() {
+                if root.filter.include_dir(&relative) {
+                    content.push_dir(this_id.clone());
+                    read_dir(root, &path, content, this_id, errors);
+                }
+            } else if path.is_file() && root.filter.include_file(&relative) {
                 if let Some(ext) = extension_of(&path) {
                     let ext = ext.to_owned();
                     let stem = stem.to_owned();
@@ -108,6 +296,7 @@ struct FileDesc(Id, String, PathBuf);
 struct Content {
     files: Vec<FileDesc>,
     dirs: HashMap<Id, Vec<(String, String)>>,
+    seen: HashSet<(Id, String)>,
 }
 
 impl Content {
@@ -115,25 +304,65 @@ impl Content {
         Content {
             files: Vec::new(),
             dirs: HashMap::new(),
+            seen: HashSet::new(),
         }
     }
 
     fn push_file(&mut self, desc: FileDesc, stem: String, dir_id: &Id) {
+        if !self.seen.insert((desc.0.clone(), desc.1.clone())) {
+            return;
+        }
+
         self.dirs.get_mut(dir_id).expect("File without directory").push((stem, desc.1.clone()));
         self.files.push(desc);
     }
 
     fn push_dir(&mut self, id: Id) {
-        self.dirs.insert(id, Vec::new());
+        self.dirs.entry(id).or_default();
+    }
+
+    /// Removes directories that contain no file, directly or in a
+    /// subdirectory. The root directory is always kept.
+    fn remove_empty_dirs(&mut self) {
+        let mut kept = HashSet::new();
+        for (Id(id), files) in &self.dirs {
+            if files.is_empty() {
+                continue;
+            }
+            let mut id = id.as_str();
+            while kept.insert(id.to_owned()) {
+                match id.rfind('.') {
+                    Some(pos) => id = &id[..pos],
+                    None => break,
+                }
+            }
+        }
+
+        self.dirs.retain(|Id(id), _| id.is_empty() || kept.contains(id));
     }
 
-    fn to_token_stream(&self) -> TokenStream {
+    fn to_token_stream(&self, compression: Compression) -> Result<TokenStream, syn::Error> {
         let files = self.files.iter().map(|FileDesc(Id(id), ext, path)| {
-            let path = path.display().to_string();
-            quote! {
-                ((#id, #ext), (include_bytes!(#path) as &[u8]))
+            let path_str = path.display().to_string();
+
+            if matches!(compression, Compression::None) {
+                return Ok(quote! {
+                    ((#id, #ext), (include_bytes!(#path_str) as &[u8]))
+                });
             }
-        });
+
+            // `include_bytes!` is still used so that the crate is rebuilt
+            // when the file changes.
+            let compressed = compression.compress(path)
+                .map_err(|err| syn::Error::new(Span::call_site(), format!("{}: {}", path.display(), err)))?;
+            let compressed = proc_macro2::Literal::byte_string(&compressed);
+            Ok(quote! {
+                ((#id, #ext), {
+                    const _: &[u8] = include_bytes!(#path_str);
+                    #compressed as &[u8]
+                })
+            })
+        }).collect::<Result<Vec<_>, syn::Error>>()?;
 
         let dirs = self.dirs.iter().map(|(Id(id), files)| {
             let files = files.iter().map(|(id, ext)| quote!{ (#id, #ext) });
@@ -142,7 +371,9 @@ impl Content {
             }
         });
 
-        quote! {
+        let compression = compression.path();
+
+        Ok(quote! {
             assets_manager::source::RawEmbedded {
                 files: &[
                     #(#files),*
@@ -150,7 +381,8 @@ impl Content {
                 dirs: &[
                     #(#dirs),*
                 ],
+                compression: #compression,
             }
-        }
+        })
     }
 }
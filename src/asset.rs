@@ -4,6 +4,7 @@ use crate::{
     AssetCache,
     Error,
     loader,
+    saver,
     cache::load_from_source,
     source::Source,
     utils::PrivateMarker,
@@ -13,7 +14,7 @@ use crate::{
 #[allow(unused)]
 use serde::{Deserialize, Serialize};
 
-use std::sync::Arc;
+use std::{borrow::Cow, marker::PhantomData, sync::Arc};
 
 
 /// An asset is a type loadable from a file.
@@ -254,6 +255,164 @@ where
 }
 
 
+/// An asset type that is loaded from a file, but can load other assets too.
+///
+/// This is useful for formats that reference other files, such as material
+/// definitions referencing textures. The loader is given the content of the
+/// file and the cache, so it can load other assets from it.
+///
+/// Rust does not allow to implement [`Compound`] for these types
+/// automatically, so you have to implement it yourself with
+/// [`load_contextual`].
+///
+/// # Hot-reloading
+///
+/// The file of the asset and the assets loaded from the cache are registered as
+/// dependencies, so the asset is reloaded when any of them changes.
+///
+/// # Example
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{
+///     Asset, AssetCache, BoxedError, Compound, Error,
+///     asset::{self, ContextualAsset},
+///     loader::{self, ContextualLoader, Loader},
+///     source::Source,
+/// };
+/// use serde::Deserialize;
+/// use std::borrow::Cow;
+///
+/// struct Texture(Vec<u8>);
+///
+/// impl Asset for Texture {
+///     const EXTENSION: &'static str = "png";
+///     type Loader = loader::LoadFrom<Vec<u8>, loader::BytesLoader>;
+/// }
+/// # impl From<Vec<u8>> for Texture {
+/// #     fn from(bytes: Vec<u8>) -> Texture { Texture(bytes) }
+/// # }
+///
+/// #[derive(Deserialize)]
+/// struct MaterialDef {
+///     texture: String,
+///     shininess: f32,
+/// }
+///
+/// struct Material {
+///     texture: Texture,
+///     shininess: f32,
+/// }
+///
+/// struct MaterialLoader;
+///
+/// impl ContextualLoader<Material> for MaterialLoader {
+///     fn load<S: Source>(content: Cow<[u8]>, ext: &str, _: &str, cache: &AssetCache<S>) -> Result<Material, BoxedError> {
+///         let def: MaterialDef = loader::RonLoader::load(content, ext)?;
+///         Ok(Material {
+///             texture: cache.load_owned(&def.texture)?,
+///             shininess: def.shininess,
+///         })
+///     }
+/// }
+///
+/// impl ContextualAsset for Material {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = MaterialLoader;
+/// }
+///
+/// impl Compound for Material {
+///     fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Material, Error> {
+///         asset::load_contextual(cache, id)
+///     }
+/// }
+/// # }}
+/// ```
+pub trait ContextualAsset: Sized + Send + Sync + 'static {
+    /// Use this field if your asset only uses one extension.
+    ///
+    /// This value is ignored if you set `EXTENSIONS` too.
+    const EXTENSION: &'static str = "";
+
+    /// This field enables you to specify multiple extension for an asset.
+    ///
+    /// Unlike [`Asset`]s, only the first existing file is used, even if it
+    /// fails to load.
+    const EXTENSIONS: &'static [&'static str] = &[Self::EXTENSION];
+
+    /// Specifies a way to convert raw bytes into the asset.
+    type Loader: loader::ContextualLoader<Self>;
+
+    /// Specifies a eventual default value to use if an asset fails to load.
+    ///
+    /// See [`Asset::default_value`] for more informations.
+    #[inline]
+    #[allow(unused_variables)]
+    fn default_value(id: &str, error: Error) -> Result<Self, Error> {
+        Err(error)
+    }
+}
+
+/// Loads a [`ContextualAsset`].
+///
+/// This function is meant to be used to implement [`Compound::load`].
+pub fn load_contextual<A, S>(cache: &AssetCache<S>, id: &str) -> Result<A, Error>
+where
+    A: ContextualAsset,
+    S: Source,
+{
+    use loader::ContextualLoader;
+
+    let result = cache.load_owned::<Content<A>>(id).and_then(|content| {
+        let ext = content.ext;
+        let asset = A::Loader::load(Cow::Owned(content.bytes), ext, id, cache)
+            .map_err(|err| crate::LoadError::new::<A::Loader>(id, ext, err))?;
+        Ok(asset)
+    });
+
+    result.or_else(|err| A::default_value(id, err))
+}
+
+/// The raw content of the file of a `ContextualAsset`.
+///
+/// Loading this from the cache registers the file for hot-reloading.
+struct Content<A> {
+    bytes: Vec<u8>,
+    ext: &'static str,
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A: ContextualAsset> Asset for Content<A> {
+    const EXTENSIONS: &'static [&'static str] = A::EXTENSIONS;
+    type Loader = ContentLoader;
+}
+
+struct ContentLoader;
+
+impl<A: ContextualAsset> loader::Loader<Content<A>> for ContentLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Content<A>, crate::BoxedError> {
+        let ext = A::EXTENSIONS.iter().find(|e| **e == ext).copied().unwrap_or("");
+
+        Ok(Content {
+            bytes: content.into_owned(),
+            ext,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// An asset that can be saved back to its source.
+///
+/// Assets implementing this trait can be written with [`AssetCache::save`],
+/// using the first extension of [`Asset::EXTENSIONS`].
+///
+/// See [`saver::Saver`] for an example.
+pub trait SavableAsset: Asset {
+    /// Specifies a way to convert the asset into raw bytes.
+    type Saver: saver::Saver<Self>;
+}
+
+
 /// Mark an asset as not being hot-reloaded.
 ///
 /// At the moment, the only use of this trait is to enable `Handle::get` for
@@ -273,6 +432,7 @@ macro_rules! serde_assets {
             #[cfg(feature = $feature:literal)]
             struct $name:ident => (
                 $loader:path,
+                $saver:path,
                 [$($ext:literal),*],
             );
         )*
@@ -315,6 +475,14 @@ macro_rules! serde_assets {
                 type Loader = loader::LoadFrom<T, $loader>;
             }
 
+            #[cfg(feature = $feature)]
+            impl<T> SavableAsset for $name<T>
+            where
+                T: for<'de> serde::Deserialize<'de> + serde::Serialize + Send + Sync + 'static,
+            {
+                type Saver = $saver;
+            }
+
             #[cfg(feature = $feature)]
             impl<T> AsRef<T> for $name<T> {
                 #[inline]
@@ -331,6 +499,7 @@ serde_assets! {
     #[cfg(feature = "json")]
     struct Json => (
         loader::JsonLoader,
+        saver::JsonSaver,
         ["json"],
     );
 
@@ -338,6 +507,7 @@ serde_assets! {
     #[cfg(feature = "ron")]
     struct Ron => (
         loader::RonLoader,
+        saver::RonSaver,
         ["ron"],
     );
 
@@ -345,6 +515,7 @@ serde_assets! {
     #[cfg(feature = "toml")]
     struct Toml => (
         loader::TomlLoader,
+        saver::TomlSaver,
         ["toml"],
     );
 
@@ -352,6 +523,1102 @@ serde_assets! {
     #[cfg(feature = "yaml")]
     struct Yaml => (
         loader::YamlLoader,
+        saver::YamlSaver,
         ["yaml", "yml"],
     );
 }
+
+/// The source code of a shader, with includes resolved.
+///
+/// This type can directly be used to load shaders from an [`AssetCache`].
+///
+/// See [`loader::ShaderLoader`] for more informations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shader(pub String);
+
+impl Shader {
+    /// Unwraps the inner source.
+    #[inline]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl ContextualAsset for Shader {
+    const EXTENSIONS: &'static [&'static str] = &["glsl", "wgsl", "hlsl", "vert", "frag", "comp", "geom"];
+    type Loader = loader::ShaderLoader;
+}
+
+impl Compound for Shader {
+    fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Self, Error> {
+        load_contextual(cache, id)
+    }
+}
+
+impl AsRef<str> for Shader {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A glyph of a [`BitmapFont`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Glyph {
+    /// The horizontal position of the glyph in its texture page.
+    pub x: u16,
+
+    /// The vertical position of the glyph in its texture page.
+    pub y: u16,
+
+    /// The width of the glyph in its texture page.
+    pub width: u16,
+
+    /// The height of the glyph in its texture page.
+    pub height: u16,
+
+    /// The horizontal offset to apply when drawing the glyph.
+    pub x_offset: i16,
+
+    /// The vertical offset to apply when drawing the glyph.
+    pub y_offset: i16,
+
+    /// How much the cursor advances after drawing the glyph.
+    pub x_advance: i16,
+
+    /// The index of the texture page of the glyph.
+    pub page: u8,
+
+    /// The texture channels where the glyph is found, as a bit field.
+    pub channel: u8,
+}
+
+/// A zip or tar archive, with all its files in memory.
+///
+/// This is useful when a bundle of files is logically a single asset, such as
+/// a mod package. To read assets from an archive, use a [`Source`] instead.
+///
+/// This type can directly be used as an [`Asset`] to load archives from an
+/// [`AssetCache`].
+///
+/// See [`loader::ArchiveLoader`] for more informations.
+///
+/// [`Source`]: crate::source::Source
+#[cfg(any(feature = "zip", feature = "tar"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "zip", feature = "tar"))))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Archive {
+    /// The files of the archive, indexed by their path relative to the root
+    /// of the archive, with `/` separators.
+    pub entries: std::collections::HashMap<String, Vec<u8>>,
+}
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+impl Archive {
+    /// Returns the content of the file at the given path, if any.
+    #[inline]
+    pub fn get(&self, path: &str) -> Option<&[u8]> {
+        self.entries.get(path).map(Vec::as_slice)
+    }
+
+    /// Returns `true` if the archive contains a file at the given path.
+    #[inline]
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Returns an iterator over the paths of the files of the archive, in
+    /// arbitrary order.
+    #[inline]
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Returns the number of files in the archive.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the archive contains no files.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+impl Asset for Archive {
+    #[cfg(all(feature = "zip", feature = "tar"))]
+    const EXTENSIONS: &'static [&'static str] = &["zip", "tar"];
+    #[cfg(all(feature = "zip", not(feature = "tar")))]
+    const EXTENSIONS: &'static [&'static str] = &["zip"];
+    #[cfg(all(feature = "tar", not(feature = "zip")))]
+    const EXTENSIONS: &'static [&'static str] = &["tar"];
+
+    type Loader = loader::ArchiveLoader;
+}
+
+/// A bitmap font loaded from an AngelCode `.fnt` file.
+///
+/// This type can directly be used as an [`Asset`] to load fonts from an
+/// [`AssetCache`].
+///
+/// See [`loader::FntLoader`] for more informations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitmapFont {
+    /// The name of the font.
+    pub face: String,
+
+    /// The size of the font. It is negative if it matches the character
+    /// height rather than the cell height.
+    pub size: i16,
+
+    /// The distance between two lines of text.
+    pub line_height: u16,
+
+    /// The distance from the top of a line to the baseline.
+    pub base: u16,
+
+    /// The width of the texture pages.
+    pub scale_w: u16,
+
+    /// The height of the texture pages.
+    pub scale_h: u16,
+
+    /// The file names of the texture pages, relative to the font file.
+    pub pages: Vec<String>,
+
+    /// The glyphs of the font, indexed by character.
+    pub glyphs: std::collections::HashMap<u32, Glyph>,
+
+    /// The kerning amounts between pairs of characters.
+    pub kernings: std::collections::HashMap<(u32, u32), i16>,
+}
+
+impl BitmapFont {
+    /// Returns the glyph of a character, if any.
+    #[inline]
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&(c as u32))
+    }
+
+    /// Returns the kerning amount between two characters.
+    #[inline]
+    pub fn kerning(&self, first: char, second: char) -> i16 {
+        self.kernings.get(&(first as u32, second as u32)).copied().unwrap_or(0)
+    }
+}
+
+impl Asset for BitmapFont {
+    const EXTENSION: &'static str = "fnt";
+    type Loader = loader::FntLoader;
+}
+
+/// A color palette, loaded from a GIMP palette or from a list of hexadecimal
+/// colors.
+///
+/// This type can directly be used as an [`Asset`] to load palettes from an
+/// [`AssetCache`].
+///
+/// See [`loader::PaletteLoader`] for more informations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Palette {
+    /// The name of the palette, if the file has one.
+    pub name: Option<String>,
+
+    /// The colors of the palette as RGBA values, in the order of the file.
+    pub colors: Vec<[u8; 4]>,
+}
+
+impl Palette {
+    /// Returns the number of colors in the palette.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Returns `true` if the palette has no colors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Returns the color at the given index, if any.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<[u8; 4]> {
+        self.colors.get(index).copied()
+    }
+}
+
+impl Asset for Palette {
+    const EXTENSIONS: &'static [&'static str] = &["gpl", "hex"];
+    type Loader = loader::PaletteLoader;
+}
+
+/// A rectangle in a [`SpriteSheet`].
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    /// The horizontal position of the rectangle.
+    pub x: i32,
+
+    /// The vertical position of the rectangle.
+    pub y: i32,
+
+    /// The width of the rectangle.
+    pub width: u32,
+
+    /// The height of the rectangle.
+    pub height: u32,
+}
+
+/// A frame of a [`SpriteSheet`].
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpriteFrame {
+    /// The name of the frame.
+    pub name: String,
+
+    /// The position of the frame in the texture.
+    pub rect: Rect,
+
+    /// Whether the frame is rotated in the texture.
+    pub rotated: bool,
+
+    /// Whether transparent borders of the frame were removed.
+    pub trimmed: bool,
+
+    /// The position of the trimmed frame in the original sprite.
+    pub source_rect: Rect,
+
+    /// The size of the original sprite.
+    pub source_size: (u32, u32),
+
+    /// How long the frame is displayed.
+    pub duration: std::time::Duration,
+}
+
+/// The direction in which an [`AnimationTag`] is played.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationDirection {
+    /// From the first frame to the last.
+    Forward,
+
+    /// From the last frame to the first.
+    Reverse,
+
+    /// Forward, then backward.
+    PingPong,
+
+    /// Backward, then forward.
+    PingPongReverse,
+}
+
+/// A named range of frames of a [`SpriteSheet`], usually an animation.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnimationTag {
+    /// The name of the tag.
+    pub name: String,
+
+    /// The index of the first frame of the tag.
+    pub from: usize,
+
+    /// The index of the last frame of the tag (inclusive).
+    pub to: usize,
+
+    /// The direction of the animation.
+    pub direction: AnimationDirection,
+
+    /// How many times the animation is played, if specified.
+    pub repeat: Option<u32>,
+}
+
+/// The bounds of a [`SpriteSlice`] from a given frame.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SliceKey {
+    /// The index of the first frame using this key.
+    pub frame: usize,
+
+    /// The bounds of the slice.
+    pub bounds: Rect,
+
+    /// The center of the slice, for 9-patches.
+    pub center: Option<Rect>,
+
+    /// The pivot of the slice.
+    pub pivot: Option<(i32, i32)>,
+}
+
+/// A named region of a [`SpriteSheet`].
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpriteSlice {
+    /// The name of the slice.
+    pub name: String,
+
+    /// The color of the slice in the editor, as a `#rrggbbaa` string.
+    pub color: String,
+
+    /// The keys of the slice, sorted by frame.
+    pub keys: Vec<SliceKey>,
+}
+
+/// A sprite sheet exported by Aseprite.
+///
+/// This type can directly be used as an [`Asset`] to load sprite sheets from
+/// an [`AssetCache`].
+///
+/// See [`loader::AsepriteLoader`] for more informations.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpriteSheet {
+    /// The file name of the texture, relative to the sprite sheet file.
+    pub image: String,
+
+    /// The size of the texture.
+    pub size: (u32, u32),
+
+    /// The frames of the sprite sheet.
+    pub frames: Vec<SpriteFrame>,
+
+    /// The animation tags of the sprite sheet.
+    pub tags: Vec<AnimationTag>,
+
+    /// The slices of the sprite sheet.
+    pub slices: Vec<SpriteSlice>,
+}
+
+#[cfg(feature = "json")]
+impl SpriteSheet {
+    /// Returns the animation tag with the given name, if any.
+    pub fn tag(&self, name: &str) -> Option<&AnimationTag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    /// Returns the frames of the animation tag with the given name, if any.
+    pub fn tag_frames(&self, name: &str) -> Option<&[SpriteFrame]> {
+        let tag = self.tag(name)?;
+        self.frames.get(tag.from..=tag.to)
+    }
+
+    /// Returns the slice with the given name, if any.
+    pub fn slice(&self, name: &str) -> Option<&SpriteSlice> {
+        self.slices.iter().find(|slice| slice.name == name)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Asset for SpriteSheet {
+    const EXTENSION: &'static str = "json";
+    type Loader = loader::AsepriteLoader;
+}
+
+/// A tile of an [`LdtkLayer`].
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LdtkTile {
+    /// The position of the tile in the layer, in pixels.
+    pub position: (i32, i32),
+
+    /// The position of the tile in the tileset, in pixels.
+    pub source: (i32, i32),
+
+    /// Whether the tile is flipped: bit 0 is horizontal, bit 1 is vertical.
+    pub flip: u8,
+
+    /// The id of the tile in the tileset.
+    pub id: u32,
+}
+
+/// An entity of an [`LdtkLayer`].
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LdtkEntity {
+    /// The name of the entity definition.
+    pub identifier: String,
+
+    /// The unique id of the entity.
+    pub iid: String,
+
+    /// The position of the entity in the layer, in pixels.
+    pub position: (i32, i32),
+
+    /// The width of the entity, in pixels.
+    pub width: u32,
+
+    /// The height of the entity, in pixels.
+    pub height: u32,
+
+    /// The custom fields of the entity, by name.
+    pub fields: Vec<(String, serde_json::Value)>,
+}
+
+/// A layer of an [`LdtkLevel`].
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LdtkLayer {
+    /// The name of the layer.
+    pub identifier: String,
+
+    /// The type of the layer: `IntGrid`, `Entities`, `Tiles` or `AutoLayer`.
+    pub kind: String,
+
+    /// The size of a cell of the layer, in pixels.
+    pub grid_size: u32,
+
+    /// The width of the layer, in cells.
+    pub width: u32,
+
+    /// The height of the layer, in cells.
+    pub height: u32,
+
+    /// The offset of the layer, in pixels.
+    pub offset: (i32, i32),
+
+    /// The path of the tileset, relative to the project file.
+    pub tileset_path: Option<String>,
+
+    /// The values of the cells of an `IntGrid` layer, row by row.
+    pub int_grid: Vec<i64>,
+
+    /// The tiles of the layer, including automatic ones.
+    pub tiles: Vec<LdtkTile>,
+
+    /// The entities of an `Entities` layer.
+    pub entities: Vec<LdtkEntity>,
+}
+
+/// A level of an LDtk project.
+///
+/// This type can directly be used as an [`Asset`] to load levels saved in
+/// separate `.ldtkl` files from an [`AssetCache`].
+///
+/// See [`loader::LdtkLoader`] for more informations.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LdtkLevel {
+    /// The name of the level.
+    pub identifier: String,
+
+    /// The unique id of the level.
+    pub iid: String,
+
+    /// The horizontal position of the level in the world, in pixels.
+    pub world_x: i32,
+
+    /// The vertical position of the level in the world, in pixels.
+    pub world_y: i32,
+
+    /// The width of the level, in pixels.
+    pub width: u32,
+
+    /// The height of the level, in pixels.
+    pub height: u32,
+
+    /// The path of the level file, relative to the project file, if the level
+    /// is saved separately.
+    pub external_path: Option<String>,
+
+    /// The custom fields of the level, by name.
+    pub fields: Vec<(String, serde_json::Value)>,
+
+    /// The layers of the level, from top to bottom.
+    ///
+    /// This is `None` if the level is saved separately and was not loaded.
+    pub layers: Option<Vec<LdtkLayer>>,
+}
+
+#[cfg(feature = "json")]
+impl LdtkLevel {
+    /// Returns the layer with the given name, if any.
+    pub fn layer(&self, name: &str) -> Option<&LdtkLayer> {
+        self.layers.as_ref()?.iter().find(|layer| layer.identifier == name)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Asset for LdtkLevel {
+    const EXTENSION: &'static str = "ldtkl";
+    type Loader = loader::LdtkLoader;
+}
+
+/// A project of the LDtk level editor, with its levels.
+///
+/// This type can directly be used to load projects from an [`AssetCache`].
+/// Levels saved in separate files are loaded too.
+///
+/// See [`loader::LdtkLoader`] for more informations.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LdtkProject {
+    /// The version of the file format.
+    pub json_version: String,
+
+    /// The levels of the project.
+    pub levels: Vec<LdtkLevel>,
+
+    /// The definitions of the project (layers, entities, tilesets, enums),
+    /// left as raw JSON.
+    pub defs: serde_json::Value,
+}
+
+#[cfg(feature = "json")]
+impl LdtkProject {
+    /// Returns the level with the given name, if any.
+    pub fn level(&self, name: &str) -> Option<&LdtkLevel> {
+        self.levels.iter().find(|level| level.identifier == name)
+    }
+}
+
+#[cfg(feature = "json")]
+impl ContextualAsset for LdtkProject {
+    const EXTENSION: &'static str = "ldtk";
+    type Loader = loader::LdtkLoader;
+}
+
+#[cfg(feature = "json")]
+impl Compound for LdtkProject {
+    fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Self, Error> {
+        load_contextual(cache, id)
+    }
+}
+
+/// A compiled JSON schema.
+///
+/// This type can directly be used as an [`Asset`] to load schemas from an
+/// [`AssetCache`]. It is used by [`loader::SchemaValidatedLoader`] to validate
+/// assets before deserializing them.
+///
+/// See [`loader::JsonSchemaLoader`] for more informations.
+#[cfg(feature = "json-schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json-schema")))]
+#[derive(Debug)]
+pub struct JsonSchema {
+    schema: serde_json::Value,
+    validator: jsonschema::Validator,
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchema {
+    pub(crate) fn new(schema: serde_json::Value) -> Result<Self, crate::BoxedError> {
+        let validator = jsonschema::validator_for(&schema).map_err(|err| err.to_string())?;
+        Ok(Self { schema, validator })
+    }
+
+    /// Returns the schema as a JSON value.
+    #[inline]
+    pub fn schema(&self) -> &serde_json::Value {
+        &self.schema
+    }
+
+    /// Returns the compiled schema.
+    #[inline]
+    pub fn validator(&self) -> &jsonschema::Validator {
+        &self.validator
+    }
+
+    /// Validates a value against the schema.
+    ///
+    /// On failure, the error lists all mismatches, each one on its own line and
+    /// prefixed with its location in the value as a JSON pointer.
+    pub fn validate(&self, value: &serde_json::Value) -> Result<(), crate::BoxedError> {
+        let errors: Vec<_> = self.validator.iter_errors(value)
+            .map(|err| {
+                let path = err.instance_path().as_str();
+                format!("{}: {}", if path.is_empty() { "/" } else { path }, err)
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Value does not match the schema:\n{}", errors.join("\n")).into())
+        }
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl Asset for JsonSchema {
+    const EXTENSION: &'static str = "json";
+    type Loader = loader::JsonSchemaLoader;
+}
+
+/// Loads an image from a PNG, JPEG or TGA file.
+///
+/// This type can directly be used as an [`Asset`] to load images from an
+/// [`AssetCache`].
+///
+/// See [`loader::ImageLoader`] for more informations.
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+#[derive(Debug, Clone)]
+pub struct Image(pub image_crate::DynamicImage);
+
+#[cfg(feature = "image")]
+impl From<image_crate::DynamicImage> for Image {
+    #[inline]
+    fn from(image: image_crate::DynamicImage) -> Self {
+        Self(image)
+    }
+}
+
+#[cfg(feature = "image")]
+impl Image {
+    /// Unwraps the inner image.
+    #[inline]
+    pub fn into_inner(self) -> image_crate::DynamicImage {
+        self.0
+    }
+}
+
+#[cfg(feature = "image")]
+impl Asset for Image {
+    const EXTENSIONS: &'static [&'static str] = &["png", "jpg", "jpeg", "tga"];
+    type Loader = loader::LoadFrom<image_crate::DynamicImage, loader::ImageLoader>;
+}
+
+#[cfg(feature = "image")]
+impl AsRef<image_crate::DynamicImage> for Image {
+    #[inline]
+    fn as_ref(&self) -> &image_crate::DynamicImage {
+        &self.0
+    }
+}
+
+/// A sound decoded from a WAV, OGG Vorbis or FLAC file.
+///
+/// This type can directly be used as an [`Asset`] to load sounds from an
+/// [`AssetCache`].
+///
+/// See [`loader::SoundLoader`] for more informations.
+#[cfg(feature = "audio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "audio")))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sound {
+    /// The number of channels.
+    pub channels: u16,
+
+    /// The number of samples per second and per channel.
+    pub sample_rate: u32,
+
+    /// The interleaved samples, between `-1.0` and `1.0`.
+    pub samples: Vec<f32>,
+}
+
+#[cfg(feature = "audio")]
+impl Sound {
+    /// Returns the duration of the sound.
+    pub fn duration(&self) -> std::time::Duration {
+        let frames = self.samples.len() as f64 / f64::from(self.channels.max(1));
+        std::time::Duration::from_secs_f64(frames / f64::from(self.sample_rate.max(1)))
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Asset for Sound {
+    const EXTENSIONS: &'static [&'static str] = &["ogg", "wav", "flac"];
+    type Loader = loader::SoundLoader;
+}
+
+/// A compiled regular expression.
+///
+/// This type can directly be used as an [`Asset`] to load patterns from an
+/// [`AssetCache`].
+///
+/// See [`loader::RegexLoader`] for more informations.
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[derive(Debug, Clone)]
+pub struct Regex(pub regex_crate::Regex);
+
+#[cfg(feature = "regex")]
+impl From<regex_crate::Regex> for Regex {
+    #[inline]
+    fn from(regex: regex_crate::Regex) -> Self {
+        Self(regex)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Regex {
+    /// Unwraps the inner regex.
+    #[inline]
+    pub fn into_inner(self) -> regex_crate::Regex {
+        self.0
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Asset for Regex {
+    const EXTENSION: &'static str = "regex";
+    type Loader = loader::LoadFrom<regex_crate::Regex, loader::RegexLoader>;
+}
+
+#[cfg(feature = "regex")]
+impl AsRef<regex_crate::Regex> for Regex {
+    #[inline]
+    fn as_ref(&self) -> &regex_crate::Regex {
+        &self.0
+    }
+}
+
+/// A compiled Rhai script.
+///
+/// This type can directly be used as an [`Asset`] to load scripts from an
+/// [`AssetCache`].
+///
+/// See [`loader::RhaiLoader`] for more informations.
+#[cfg(feature = "rhai")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rhai")))]
+#[derive(Debug, Clone)]
+pub struct RhaiScript(pub rhai_crate::AST);
+
+#[cfg(feature = "rhai")]
+impl RhaiScript {
+    /// Unwraps the inner AST.
+    #[inline]
+    pub fn into_inner(self) -> rhai_crate::AST {
+        self.0
+    }
+}
+
+#[cfg(feature = "rhai")]
+impl Asset for RhaiScript {
+    const EXTENSION: &'static str = "rhai";
+    type Loader = loader::RhaiLoader;
+}
+
+#[cfg(feature = "rhai")]
+impl AsRef<rhai_crate::AST> for RhaiScript {
+    #[inline]
+    fn as_ref(&self) -> &rhai_crate::AST {
+        &self.0
+    }
+}
+
+/// A parsed SVG image.
+///
+/// This type can directly be used as an [`Asset`] to load images from an
+/// [`AssetCache`]. Compressed `.svgz` files are also supported.
+///
+/// See [`loader::SvgLoader`] for more informations.
+#[cfg(feature = "svg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "svg")))]
+#[derive(Debug, Clone)]
+pub struct Svg(pub usvg::Tree);
+
+#[cfg(feature = "svg")]
+impl Svg {
+    /// Unwraps the inner tree.
+    #[inline]
+    pub fn into_inner(self) -> usvg::Tree {
+        self.0
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Asset for Svg {
+    const EXTENSIONS: &'static [&'static str] = &["svg", "svgz"];
+    type Loader = loader::SvgLoader;
+}
+
+#[cfg(feature = "svg")]
+impl AsRef<usvg::Tree> for Svg {
+    #[inline]
+    fn as_ref(&self) -> &usvg::Tree {
+        &self.0
+    }
+}
+
+/// A compiled Handlebars template.
+///
+/// This type can directly be used as an [`Asset`] to load templates from an
+/// [`AssetCache`].
+///
+/// The template is rendered without HTML escaping, as it is meant to generate
+/// text rather than web pages. To use it with other templates or helpers,
+/// register it to a [`handlebars::Handlebars`] registry.
+///
+/// See [`loader::TemplateLoader`] for more informations.
+#[cfg(feature = "templates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "templates")))]
+#[derive(Debug)]
+pub struct HandlebarsTemplate {
+    registry: handlebars::Handlebars<'static>,
+}
+
+#[cfg(feature = "templates")]
+impl HandlebarsTemplate {
+    const NAME: &'static str = "template";
+
+    pub(crate) fn new(template: handlebars::Template) -> Self {
+        let mut registry = handlebars::Handlebars::new();
+        registry.register_escape_fn(handlebars::no_escape);
+        registry.register_template(Self::NAME, template);
+        Self { registry }
+    }
+
+    /// Returns the compiled template.
+    #[inline]
+    pub fn template(&self) -> &handlebars::Template {
+        self.registry.get_template(Self::NAME).unwrap()
+    }
+
+    /// Renders the template with the given data.
+    #[inline]
+    pub fn render<T: serde::Serialize>(&self, data: &T) -> Result<String, handlebars::RenderError> {
+        self.registry.render(Self::NAME, data)
+    }
+}
+
+#[cfg(feature = "templates")]
+impl Asset for HandlebarsTemplate {
+    const EXTENSIONS: &'static [&'static str] = &["hbs", "handlebars"];
+    type Loader = loader::TemplateLoader;
+}
+
+/// A compiled Tera template.
+///
+/// This type can directly be used as an [`Asset`] to load templates from an
+/// [`AssetCache`].
+///
+/// The template is rendered without HTML escaping, as it is meant to generate
+/// text rather than web pages. To use it with other templates, filters or
+/// functions, add it to a [`tera::Tera`] instance with [`tera::Tera::extend`].
+///
+/// See [`loader::TemplateLoader`] for more informations.
+#[cfg(feature = "templates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "templates")))]
+#[derive(Debug, Clone)]
+pub struct TeraTemplate {
+    tera: tera::Tera,
+}
+
+#[cfg(feature = "templates")]
+impl TeraTemplate {
+    const NAME: &'static str = "template";
+
+    pub(crate) fn new(source: &str) -> Result<Self, tera::Error> {
+        let mut tera = tera::Tera::default();
+        tera.autoescape_on(Vec::new());
+        tera.add_raw_template(Self::NAME, source)?;
+        Ok(Self { tera })
+    }
+
+    /// Returns a `Tera` instance that contains only this template, under the
+    /// name `"template"`.
+    #[inline]
+    pub fn as_tera(&self) -> &tera::Tera {
+        &self.tera
+    }
+
+    /// Renders the template with the given data.
+    ///
+    /// The data must serialize to a map, such as a struct.
+    pub fn render<T: serde::Serialize>(&self, data: &T) -> Result<String, tera::Error> {
+        let context = tera::Context::from_serialize(data)?;
+        self.tera.render(Self::NAME, &context)
+    }
+}
+
+#[cfg(feature = "templates")]
+impl Asset for TeraTemplate {
+    const EXTENSION: &'static str = "tera";
+    type Loader = loader::TemplateLoader;
+}
+
+/// A validated WebAssembly module.
+///
+/// This type can directly be used as an [`Asset`] to load modules from an
+/// [`AssetCache`]. The module is not compiled, so it can be given to any
+/// runtime.
+///
+/// See [`loader::WasmModuleLoader`] for more informations.
+#[cfg(feature = "wasm-plugin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm-plugin")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmModule {
+    /// The binary content of the module.
+    pub bytes: Vec<u8>,
+
+    /// The imports of the module, as `(module, name)` pairs.
+    pub imports: Vec<(String, String)>,
+
+    /// The names of the exports of the module.
+    pub exports: Vec<String>,
+}
+
+#[cfg(feature = "wasm-plugin")]
+impl Asset for WasmModule {
+    const EXTENSION: &'static str = "wasm";
+    type Loader = loader::WasmModuleLoader;
+}
+
+#[cfg(feature = "wasm-plugin")]
+impl AsRef<[u8]> for WasmModule {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// A triangle mesh loaded from a Wavefront OBJ file.
+///
+/// Positions, normals and texture coordinates share the same indices. Normals
+/// and texture coordinates are empty if the file does not contain them, and
+/// zeroed for the vertices of objects that do not have them.
+///
+/// This type can directly be used as an [`Asset`] to load meshes from an
+/// [`AssetCache`].
+///
+/// See [`loader::ObjLoader`] for more informations.
+#[cfg(feature = "obj")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obj")))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    /// The positions of the vertices.
+    pub positions: Vec<[f32; 3]>,
+
+    /// The normals of the vertices.
+    pub normals: Vec<[f32; 3]>,
+
+    /// The texture coordinates of the vertices.
+    pub uvs: Vec<[f32; 2]>,
+
+    /// The indices of the vertices of each triangle.
+    pub indices: Vec<u32>,
+}
+
+#[cfg(feature = "obj")]
+impl Asset for Mesh {
+    const EXTENSION: &'static str = "obj";
+    type Loader = loader::ObjLoader;
+}
+
+/// A glTF scene, with its buffers and images.
+///
+/// This type can directly be used to load scenes from an [`AssetCache`].
+///
+/// See [`loader::GltfLoader`] for more informations.
+#[cfg(feature = "gltf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+#[derive(Debug, Clone)]
+pub struct Gltf {
+    /// The glTF document.
+    pub document: gltf_crate::Document,
+
+    /// The content of the buffers, in the order of the document.
+    pub buffers: Vec<Vec<u8>>,
+
+    /// The encoded content of the images, in the order of the document.
+    pub images: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "gltf")]
+impl ContextualAsset for Gltf {
+    const EXTENSIONS: &'static [&'static str] = &["gltf", "glb"];
+    type Loader = loader::GltfLoader;
+}
+
+#[cfg(feature = "gltf")]
+impl Compound for Gltf {
+    fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Self, Error> {
+        load_contextual(cache, id)
+    }
+}
+
+/// A Tiled map, with its tilesets.
+///
+/// This type can directly be used to load maps from an [`AssetCache`].
+///
+/// See [`loader::TiledLoader`] for more informations.
+#[cfg(feature = "tiled")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tiled")))]
+#[derive(Debug)]
+pub struct TiledMap(pub tiled_crate::Map);
+
+#[cfg(feature = "tiled")]
+impl TiledMap {
+    /// Unwraps the inner map.
+    #[inline]
+    pub fn into_inner(self) -> tiled_crate::Map {
+        self.0
+    }
+}
+
+#[cfg(feature = "tiled")]
+impl ContextualAsset for TiledMap {
+    const EXTENSION: &'static str = "tmx";
+    type Loader = loader::TiledLoader;
+}
+
+#[cfg(feature = "tiled")]
+impl Compound for TiledMap {
+    fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Self, Error> {
+        load_contextual(cache, id)
+    }
+}
+
+#[cfg(feature = "tiled")]
+impl AsRef<tiled_crate::Map> for TiledMap {
+    #[inline]
+    fn as_ref(&self) -> &tiled_crate::Map {
+        &self.0
+    }
+}
+
+/// A Tiled tileset.
+///
+/// This type can directly be used to load tilesets from an [`AssetCache`].
+///
+/// See [`loader::TiledLoader`] for more informations.
+#[cfg(feature = "tiled")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tiled")))]
+#[derive(Debug, Clone)]
+pub struct TiledTileset(pub tiled_crate::Tileset);
+
+#[cfg(feature = "tiled")]
+impl TiledTileset {
+    /// Unwraps the inner tileset.
+    #[inline]
+    pub fn into_inner(self) -> tiled_crate::Tileset {
+        self.0
+    }
+}
+
+#[cfg(feature = "tiled")]
+impl ContextualAsset for TiledTileset {
+    const EXTENSION: &'static str = "tsx";
+    type Loader = loader::TiledLoader;
+}
+
+#[cfg(feature = "tiled")]
+impl Compound for TiledTileset {
+    fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Self, Error> {
+        load_contextual(cache, id)
+    }
+}
+
+#[cfg(feature = "tiled")]
+impl AsRef<tiled_crate::Tileset> for TiledTileset {
+    #[inline]
+    fn as_ref(&self) -> &tiled_crate::Tileset {
+        &self.0
+    }
+}
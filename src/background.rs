@@ -0,0 +1,280 @@
+//! Loading assets in the background.
+
+use std::{
+    fmt, io,
+    marker::PhantomData,
+    panic,
+    sync::{mpsc, Arc, Condvar, Mutex, MutexGuard, OnceLock},
+    thread,
+};
+
+use crate::{
+    AssetCache, Asset, Error, Handle,
+    source::Source,
+    utils::OwnedKey,
+};
+
+
+/// A thread pool on which assets can be decoded.
+///
+/// It is used by [`AssetCache::load_background_with`]. This trait is
+/// implemented for closures, so existing pools can be used easily, for example
+/// with `|task| rayon::spawn(task)`.
+pub trait ThreadPool {
+    /// Runs a task on the pool.
+    fn spawn(&self, task: Box<dyn FnOnce() + Send + 'static>);
+}
+
+impl<F> ThreadPool for F
+where
+    F: Fn(Box<dyn FnOnce() + Send + 'static>) + ?Sized,
+{
+    #[inline]
+    fn spawn(&self, task: Box<dyn FnOnce() + Send + 'static>) {
+        self(task)
+    }
+}
+
+type Task = Box<dyn FnOnce() + Send + 'static>;
+
+/// The pool used by [`AssetCache::load_background`].
+///
+/// Workers are started on first use, one per available CPU. If threads cannot
+/// be spawned, as on WebAssembly, tasks are run by the calling thread.
+pub(crate) struct DefaultPool {
+    sender: Option<Mutex<mpsc::Sender<Task>>>,
+}
+
+impl DefaultPool {
+    pub(crate) fn get() -> &'static DefaultPool {
+        static POOL: OnceLock<DefaultPool> = OnceLock::new();
+        POOL.get_or_init(DefaultPool::start)
+    }
+
+    fn start() -> DefaultPool {
+        let (sender, receiver) = mpsc::channel::<Task>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let count = thread::available_parallelism().map_or(1, |n| n.get());
+
+        let mut started = false;
+        for i in 0..count {
+            let receiver = receiver.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("assets_manager loader {}", i))
+                .spawn(move || loop {
+                    let task = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => break,
+                    };
+                    match task {
+                        // A panicking loader should not kill the worker
+                        Ok(task) => drop(panic::catch_unwind(panic::AssertUnwindSafe(task))),
+                        Err(_) => break,
+                    }
+                });
+            started |= spawned.is_ok();
+        }
+
+        DefaultPool {
+            sender: if started { Some(Mutex::new(sender)) } else { None },
+        }
+    }
+}
+
+impl ThreadPool for DefaultPool {
+    fn spawn(&self, task: Task) {
+        let task = match &self.sender {
+            Some(sender) => match sender.lock().unwrap().send(task) {
+                Ok(()) => return,
+                Err(mpsc::SendError(task)) => task,
+            },
+            None => task,
+        };
+        task()
+    }
+}
+
+/// The state of an asset being loaded in the background.
+enum Status {
+    Loading,
+    Loaded,
+    /// The error is shared by all the handles that are joined.
+    Failed(Arc<Error>),
+}
+
+/// An asset being loaded in the background, shared by the loading task and by
+/// the handles waiting for it.
+pub(crate) struct Pending {
+    status: Mutex<Status>,
+    finished: Condvar,
+}
+
+impl Pending {
+    pub(crate) fn new() -> Arc<Pending> {
+        Arc::new(Pending {
+            status: Mutex::new(Status::Loading),
+            finished: Condvar::new(),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Status> {
+        self.status.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn is_finished(&self) -> bool {
+        !matches!(*self.lock(), Status::Loading)
+    }
+
+    /// Waits for the task to finish, and returns its error if it failed.
+    fn wait(&self) -> Option<Error> {
+        let mut status = self.lock();
+        loop {
+            match &*status {
+                Status::Loading => status = self.finished.wait(status).unwrap_or_else(|err| err.into_inner()),
+                Status::Loaded => return None,
+                Status::Failed(err) => return Some(Error::share(err)),
+            }
+        }
+    }
+}
+
+/// Marks the loading as finished when the task is dropped, with an error if
+/// it did not complete, eg if the loader panicked or if the pool was shut down.
+struct Completion<S> {
+    cache: Arc<AssetCache<S>>,
+    key: OwnedKey,
+    pending: Arc<Pending>,
+    result: Option<Result<(), Error>>,
+}
+
+impl<S> Completion<S> {
+    fn finish(mut self, result: Result<(), Error>) {
+        self.result = Some(result);
+    }
+}
+
+impl<S> Drop for Completion<S> {
+    fn drop(&mut self) {
+        // Removing the entry first ensures that new loads either find the
+        // asset in the cache or start a new task.
+        {
+            let mut background = self.cache.background.lock();
+            if background.get(&self.key).is_some_and(|p| Arc::ptr_eq(p, &self.pending)) {
+                background.remove(&self.key);
+            }
+        }
+
+        let result = self.result.take().unwrap_or_else(|| Err(Error::Io(io::Error::other("background loading did not complete"))));
+        *self.pending.lock() = match result {
+            Ok(()) => Status::Loaded,
+            Err(err) => Status::Failed(Arc::new(err)),
+        };
+        self.pending.finished.notify_all();
+    }
+}
+
+/// A handle to an asset being loaded in the background.
+///
+/// It is created by [`AssetCache::load_background`]. The handle keeps the
+/// cache alive, and can be joined several times.
+pub struct LoadHandle<A, S> {
+    cache: Arc<AssetCache<S>>,
+    id: Box<str>,
+    pending: Option<Arc<Pending>>,
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A, S> LoadHandle<A, S>
+where
+    A: Asset,
+    S: Source + Send + Sync + 'static,
+{
+    pub(crate) fn cached(cache: &Arc<AssetCache<S>>, id: &str) -> Self {
+        LoadHandle { cache: cache.clone(), id: id.into(), pending: None, _marker: PhantomData }
+    }
+
+    pub(crate) fn pending(cache: &Arc<AssetCache<S>>, id: &str, pending: Arc<Pending>) -> Self {
+        LoadHandle { cache: cache.clone(), id: id.into(), pending: Some(pending), _marker: PhantomData }
+    }
+
+    /// Spawns the task that reads the files of the asset, decodes it and adds
+    /// it to the cache.
+    ///
+    /// `pending` must be registered in the cache with the given key.
+    pub(crate) fn spawn<P>(cache: &Arc<AssetCache<S>>, id: &str, key: OwnedKey, pending: Arc<Pending>, pool: &P) -> Self
+    where
+        P: ThreadPool + ?Sized,
+    {
+        let completion = Completion { cache: cache.clone(), key, pending: pending.clone(), result: None };
+        let task_id = String::from(id);
+        pool.spawn(Box::new(move || {
+            let cache = &completion.cache;
+            let result = crate::cache::load_from_source::<A, S>(cache.source(), &task_id);
+            let result = result.map(|asset| {
+                cache.insert_loaded(&task_id, asset);
+            });
+            completion.finish(result);
+        }));
+
+        LoadHandle::pending(cache, id, pending)
+    }
+
+    /// Returns the id of the asset.
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns `true` if the asset was loaded, or if loading it failed.
+    ///
+    /// If this function returns `true`, [`join`](Self::join) will not block.
+    pub fn is_finished(&self) -> bool {
+        match &self.pending {
+            Some(pending) => pending.is_finished(),
+            None => true,
+        }
+    }
+
+    /// Returns the loaded asset if it is ready, or `None` otherwise.
+    pub fn try_join(&self) -> Option<Result<Handle<'_, A>, Error>> {
+        if self.is_finished() {
+            Some(self.join())
+        } else {
+            None
+        }
+    }
+
+    /// Waits for the asset to be loaded and returns it.
+    ///
+    /// If loading failed, every handle to the asset returns the error.
+    ///
+    /// When called in [`Compound::load`](crate::Compound::load), the asset is
+    /// recorded as a dependency, as with [`AssetCache::load`].
+    pub fn join(&self) -> Result<Handle<'_, A>, Error> {
+        if let Some(pending) = &self.pending {
+            if let Some(err) = pending.wait() {
+                return Err(err);
+            }
+        }
+
+        // Assets cannot be removed while the cache is shared, so the asset is
+        // still there.
+        match self.cache.load_cached(&self.id) {
+            Some(handle) => Ok(handle),
+            None => Err(Error::Io(io::Error::other("the asset was removed from the cache"))),
+        }
+    }
+}
+
+impl<A, S> fmt::Debug for LoadHandle<A, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let finished = match &self.pending {
+            Some(pending) => pending.is_finished(),
+            None => true,
+        };
+        f.debug_struct("LoadHandle")
+            .field("id", &self.id)
+            .field("finished", &finished)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,21 +1,27 @@
 //! Definition of the cache
 
 use crate::{
-    Asset, Error, Compound, Handle,
-    dirs::{CachedDir, DirReader},
+    Asset, Error, Compound, Handle, LoadError,
+    background::{DefaultPool, LoadHandle, Pending, ThreadPool},
+    dirs::{CachedDir, DirReader, Recursive},
     entry::CacheEntry,
+    asset::SavableAsset,
     loader::Loader,
-    utils::{HashMap, Key, OwnedKey, Private, RwLock},
-    source::{FileSystem, Source},
+    saver::Saver,
+    utils::{HashMap, Key, Mutex, OwnedKey, Private, RwLock},
+    source::{FileSystem, Source, SourceError, WritableSource},
 };
 
 #[cfg(doc)]
 use crate::{AssetGuard, ReadDir, ReadAllDir};
 
 use std::{
+    any::{Any, TypeId},
     fmt,
     io,
+    mem,
     path::Path,
+    sync::Arc,
 };
 
 #[cfg(feature = "hot-reloading")]
@@ -102,6 +108,9 @@ pub struct AssetCache<S=FileSystem> {
 
     pub(crate) assets: RwLock<HashMap<OwnedKey, CacheEntry>>,
     pub(crate) dirs: RwLock<HashMap<OwnedKey, CachedDir>>,
+    pub(crate) background: Mutex<HashMap<OwnedKey, Arc<Pending>>>,
+
+    loaders: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
 }
 
 impl AssetCache<FileSystem> {
@@ -127,7 +136,9 @@ where
         AssetCache {
             assets: RwLock::new(HashMap::new()),
             dirs: RwLock::new(HashMap::new()),
+            background: Mutex::new(HashMap::new()),
 
+            loaders: HashMap::new(),
             source,
         }
     }
@@ -138,6 +149,24 @@ where
         &self.source
     }
 
+    /// Registers a loader instance in the cache.
+    ///
+    /// Registered loaders are used by [`loader::Stateful`] to load assets. If a
+    /// loader of the same type was already registered, it is replaced.
+    ///
+    /// Note that already loaded assets are not reloaded.
+    ///
+    /// [`loader::Stateful`]: crate::loader::Stateful
+    pub fn register_loader<L: Send + Sync + 'static>(&mut self, loader: L) {
+        self.loaders.insert(TypeId::of::<L>(), Box::new(loader));
+    }
+
+    /// Returns the loader of type `L` registered in the cache, if any.
+    #[inline]
+    pub fn loader<L: Send + Sync + 'static>(&self) -> Option<&L> {
+        self.loaders.get(&TypeId::of::<L>())?.downcast_ref()
+    }
+
     #[cfg(feature = "hot-reloading")]
     pub(crate) fn record_load<A: Compound>(&self, id: &str) -> Result<(A, HashSet<OwnedKey>), Error> {
         let mut record = Record {
@@ -222,7 +251,7 @@ where
 
     /// Adds a directory to the cache.
     #[cold]
-    fn add_dir<A: Asset>(&self, id: &str) -> Result<DirReader<A, S>, io::Error> {
+    fn add_dir<A: Asset>(&self, id: &str) -> Result<DirReader<A, S>, SourceError> {
         #[cfg(feature = "hot-reloading")]
         self.source._add_dir::<A, Private>(id);
 
@@ -254,6 +283,177 @@ where
         }
     }
 
+    /// Loads an asset asynchronously.
+    ///
+    /// If the asset is not found in the cache, it is read from the source with
+    /// its [`AsyncSource`] implementation, and decoded with the
+    /// [`AsyncLoader`] of the asset, so the calling task is never blocked.
+    ///
+    /// To use a regular [`Source`], wrap it in an [`Unblock`] or a
+    /// [`Blocking`] adapter when creating the cache.
+    ///
+    /// # Errors
+    ///
+    /// Errors can occur in the same cases as [`load`](Self::load).
+    ///
+    /// # Hot-reloading
+    ///
+    /// The asset is looked up in the cache when this function is called, so
+    /// when it is called in [`Compound::load`], the asset is recorded as a
+    /// dependency as with [`load`](Self::load), even if the returned future is
+    /// polled elsewhere.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use assets_manager::{Asset, BoxedError, loader::{AsyncLoader, BoxFuture, Loader}};
+    /// # use std::borrow::Cow;
+    /// # struct Level(Vec<u8>);
+    /// # struct LevelLoader;
+    /// # impl Loader<Level> for LevelLoader {
+    /// #     fn load(content: Cow<[u8]>, _: &str) -> Result<Level, BoxedError> { Ok(Level(content.into_owned())) }
+    /// # }
+    /// # impl AsyncLoader<Level> for LevelLoader {
+    /// #     fn load<'a>(content: Cow<'a, [u8]>, _: &'a str) -> BoxFuture<'a, Result<Level, BoxedError>> {
+    /// #         Box::pin(async move { Ok(Level(content.into_owned())) })
+    /// #     }
+    /// # }
+    /// # impl Asset for Level { const EXTENSION: &'static str = "x"; type Loader = LevelLoader; }
+    /// use assets_manager::{AssetCache, source::{FileSystem, Unblock}};
+    ///
+    /// let cache = AssetCache::with_source(Unblock::new(FileSystem::new("assets")?));
+    ///
+    /// # pollster::block_on(async {
+    /// let level = cache.load_async::<Level>("test.b").await?;
+    /// # Ok::<(), assets_manager::Error>(())
+    /// # })?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`AsyncSource`]: crate::source::AsyncSource
+    /// [`AsyncLoader`]: crate::loader::AsyncLoader
+    /// [`Unblock`]: crate::source::Unblock
+    /// [`Blocking`]: crate::source::Blocking
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn load_async<'a, A>(&'a self, id: &'a str) -> impl std::future::Future<Output = Result<Handle<'a, A>, Error>> + 'a
+    where
+        A: Asset,
+        A::Loader: crate::loader::AsyncLoader<A>,
+        S: crate::source::AsyncSource,
+    {
+        // This is not done in the future, so that it is recorded in the
+        // context of the caller.
+        let cached = self.load_cached(id);
+
+        async move {
+            if let Some(handle) = cached {
+                return Ok(handle);
+            }
+
+            let asset = load_from_source_async::<A, S>(&self.source, id).await?;
+            Ok(self.insert_loaded(id, asset))
+        }
+    }
+
+    /// Loads an asset in the background.
+    ///
+    /// The files of the asset are read and decoded on an internal thread pool,
+    /// and the asset is added to the cache as soon as it is loaded. The
+    /// returned [`LoadHandle`] can be polled with [`is_finished`] and joined to
+    /// get the asset, so that loading large assets does not stall the calling
+    /// thread.
+    ///
+    /// If the asset is already in the cache, the handle is finished
+    /// immediately. If it is already being loaded in the background, the
+    /// handle waits for the same task.
+    ///
+    /// The cache has to be shared in an [`Arc`], so that the pool can use it
+    /// while the asset is loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::{AssetCache, loader::{LoadFrom, ParseLoader}};
+    /// use std::sync::Arc;
+    ///
+    /// # struct Level(i32);
+    /// # impl From<i32> for Level { fn from(n: i32) -> Level { Level(n) } }
+    /// # impl assets_manager::Asset for Level {
+    /// #     const EXTENSION: &'static str = "x";
+    /// #     type Loader = LoadFrom<i32, ParseLoader>;
+    /// # }
+    /// let cache = Arc::new(AssetCache::new("assets")?);
+    ///
+    /// let mut loading = Some(cache.load_background::<Level>("test.b"));
+    ///
+    /// // In the game loop
+    /// while let Some(handle) = loading.take() {
+    ///     match handle.try_join() {
+    ///         Some(level) => {
+    ///             let level = level?;
+    ///             assert_eq!(level.read().0, -7);
+    ///         }
+    ///         None => loading = Some(handle),
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`is_finished`]: LoadHandle::is_finished
+    pub fn load_background<A: Asset>(self: &Arc<Self>, id: &str) -> LoadHandle<A, S>
+    where
+        S: Send + Sync + 'static,
+    {
+        self.load_background_with(id, DefaultPool::get())
+    }
+
+    /// Loads an asset in the background, on the given thread pool.
+    ///
+    /// See [`load_background`](Self::load_background) for more details.
+    pub fn load_background_with<A, P>(self: &Arc<Self>, id: &str, pool: &P) -> LoadHandle<A, S>
+    where
+        A: Asset,
+        P: ThreadPool + ?Sized,
+        S: Send + Sync + 'static,
+    {
+        if self.load_cached::<A>(id).is_some() {
+            return LoadHandle::cached(self, id);
+        }
+
+        let key = OwnedKey::new::<A>(id.into());
+        let mut background = self.background.lock();
+
+        if let Some(pending) = background.get(&key) {
+            return LoadHandle::pending(self, id, pending.clone());
+        }
+        // The asset may have been loaded since it was looked up
+        if self.contains::<A>(id) {
+            return LoadHandle::cached(self, id);
+        }
+
+        let pending = Pending::new();
+        background.insert(key.clone(), pending.clone());
+        drop(background);
+
+        LoadHandle::spawn(self, id, key, pending, pool)
+    }
+
+    /// Adds an asset loaded out of the cache.
+    pub(crate) fn insert_loaded<A: Asset>(&self, id: &str, asset: A) -> Handle<'_, A> {
+        #[cfg(feature = "hot-reloading")]
+        if A::HOT_RELOADED {
+            self.source._add_asset::<A, Private>(id);
+        }
+
+        let key = OwnedKey::new::<A>(id.into());
+        let mut assets = self.assets.write();
+
+        let entry = assets.entry(key).or_insert_with(|| CacheEntry::new(asset, id.into()));
+
+        unsafe { entry.handle() }
+    }
+
     /// Loads an asset from the cache.
     ///
     /// This function does not attempt to load the asset from the source if it
@@ -321,13 +521,42 @@ where
     /// An error is returned if the given id does not match a valid readable
     /// directory.
     #[inline]
-    pub fn load_dir<A: Asset>(&self, id: &str) -> io::Result<DirReader<A, S>> {
+    pub fn load_dir<A: Asset>(&self, id: &str) -> Result<DirReader<A, S>, SourceError> {
         match self.load_cached_dir(id) {
             Some(dir) => Ok(dir),
             None => self.add_dir(id),
         }
     }
 
+    /// Loads all assets of a given type from a directory and its
+    /// subdirectories.
+    ///
+    /// This is the same as [`load_dir`](Self::load_dir), but assets in
+    /// subdirectories are loaded too, using [`Source::read_dir_recursive`].
+    ///
+    /// When hot-reloading is used, loaded assets are reloaded, but added or
+    /// removed files are not taken into account.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the given id does not match a valid readable
+    /// directory.
+    pub fn load_dir_recursive<A: Asset>(&self, id: &str) -> Result<DirReader<'_, A, S>, SourceError> {
+        let key: &dyn Key = &<dyn Key>::new::<Recursive<A>>(id);
+        if let Some(dir) = self.dirs.read().get(key) {
+            return unsafe { Ok(dir.read(self)) };
+        }
+
+        let dir = self.no_record(|| CachedDir::load_recursive::<A, S>(self, id))?;
+
+        let key = OwnedKey::new::<Recursive<A>>(id.into());
+        let mut dirs = self.dirs.write();
+
+        let dir = dirs.entry(key).or_insert(dir);
+
+        unsafe { Ok(dir.read(self)) }
+    }
+
     /// Loads an directory from the cache.
     ///
     /// This function does not attempt to load the asset from the source if it
@@ -399,6 +628,46 @@ where
         #[cfg(feature = "hot-reloading")]
         self.source._clear::<Private>();
     }
+
+    /// Replaces the source of the cache, and returns the previous one.
+    ///
+    /// The cache is [cleared](Self::clear), so assets are loaded from the new
+    /// source the next time they are requested. Registered loaders are kept.
+    ///
+    /// To switch between different kinds of sources, for example from
+    /// embedded defaults to a downloaded content pack, use an
+    /// [`AnySource`](crate::source::AnySource) or a boxed source.
+    ///
+    /// Note that you need a mutable reference to the cache, so you cannot have
+    /// any [`Handle`], [`AssetGuard`], etc when you call this function. To
+    /// replace the source while assets are in use, and reload them from the
+    /// new source, use a [`SwappableSource`](crate::source::SwappableSource).
+    pub fn set_source(&mut self, source: S) -> S {
+        self.clear();
+        mem::replace(&mut self.source, source)
+    }
+}
+
+impl<S> AssetCache<S>
+where
+    S: WritableSource,
+{
+    /// Saves an asset to the source of the cache.
+    ///
+    /// The asset is converted with its [`Saver`](SavableAsset::Saver) and
+    /// written using the first of its extensions. Assets already in the cache
+    /// are not modified, but they will be updated on the next hot-reload if it
+    /// is enabled.
+    pub fn save<A: SavableAsset>(&self, id: &str, asset: &A) -> Result<(), Error> {
+        let ext = match A::EXTENSIONS.first() {
+            Some(ext) => *ext,
+            None => return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, "asset has no extension"))),
+        };
+
+        let content = A::Saver::save(asset, ext)?;
+        self.source.write(id, ext, &content).map_err(|err| err.with_file(id, ext).with_source::<S>())?;
+        Ok(())
+    }
 }
 
 impl AssetCache<FileSystem> {
@@ -453,8 +722,8 @@ impl<S> fmt::Debug for AssetCache<S> {
 
 #[inline]
 fn load_single<A: Asset, S: Source>(source: &S, id: &str, ext: &str) -> Result<A, Error> {
-    let content = source.read(id, ext)?;
-    let asset = A::Loader::load(content, ext)?;
+    let result = A::Loader::_load_from_source(source, id, ext).map_err(|err| err.with_file(id, ext).with_source::<S>())?;
+    let asset = result.map_err(|err| LoadError::new::<A::Loader>(id, ext, err))?;
     Ok(asset)
 }
 
@@ -470,3 +739,30 @@ pub(crate) fn load_from_source<A: Asset, S: Source>(source: &S, id: &str) -> Res
 
     A::default_value(id, error)
 }
+
+#[cfg(feature = "async")]
+async fn load_from_source_async<A, S>(source: &S, id: &str) -> Result<A, Error>
+where
+    A: Asset,
+    A::Loader: crate::loader::AsyncLoader<A>,
+    S: crate::source::AsyncSource,
+{
+    use crate::{loader::AsyncLoader, source::AsyncSource};
+
+    let mut error = Error::NoDefaultValue;
+
+    for ext in A::EXTENSIONS {
+        let result = match AsyncSource::read(source, id, ext).await {
+            Ok(content) => <A::Loader as AsyncLoader<A>>::load(content, ext).await
+                .map_err(|err| LoadError::new::<A::Loader>(id, ext, err).into()),
+            Err(err) => Err(Error::Source(err.with_file(id, ext).with_source::<S>())),
+        };
+
+        match result {
+            Err(err) => error = err.or(error),
+            asset => return asset,
+        }
+    }
+
+    A::default_value(id, error)
+}
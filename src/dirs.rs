@@ -3,13 +3,12 @@ use crate::{
     AssetCache,
     Error,
     Handle,
-    source::Source,
+    source::{Source, SourceError},
     utils::{RwLock, RwLockReadGuard},
 };
 
 use std::{
     iter::FusedIterator,
-    io,
     fmt,
     marker::PhantomData,
     sync::Arc
@@ -87,13 +86,17 @@ impl ExactSizeIterator for StringIter<'_> {
 
 impl FusedIterator for StringIter<'_> {}
 
+/// A marker type to differenciate directories loaded recursively from others
+/// in the cache.
+pub(crate) struct Recursive<A>(PhantomData<A>);
+
 pub(crate) struct CachedDir {
     assets: Box<StringList>,
 }
 
 impl CachedDir {
-    pub fn load<A: Asset, S: Source>(cache: &AssetCache<S>, dir_id: &str) -> io::Result<Self> {
-        let names = cache.source().read_dir(dir_id, A::EXTENSIONS)?;
+    pub fn load<A: Asset, S: Source>(cache: &AssetCache<S>, dir_id: &str) -> Result<Self, SourceError> {
+        let names = cache.source().read_dir(dir_id, A::EXTENSIONS).map_err(|err| err.with_id(dir_id).with_source::<S>())?;
         let mut ids = Vec::with_capacity(names.len());
 
         for mut id in names {
@@ -110,6 +113,20 @@ impl CachedDir {
         })
     }
 
+    pub fn load_recursive<A: Asset, S: Source>(cache: &AssetCache<S>, dir_id: &str) -> Result<Self, SourceError> {
+        let ids = cache.source().read_dir_recursive(dir_id, A::EXTENSIONS).map_err(|err| err.with_id(dir_id).with_source::<S>())?;
+        let ids = ids.into_iter()
+            .map(|id| {
+                let _ = cache.load::<A>(&id);
+                id.into()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            assets: Box::new(ids.into()),
+        })
+    }
+
     #[cfg(feature = "hot-reloading")]
     #[inline]
     pub fn contains(&self, id: &str) -> bool {
@@ -1,4 +1,6 @@
-use std::{io, fmt};
+use std::{io, fmt, sync::Arc};
+
+use crate::source::SourceError;
 
 
 /// A boxed error
@@ -6,6 +8,7 @@ pub type BoxedError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 /// The error type which is used when loading an asset.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// An asset without extension was loaded.
     NoDefaultValue,
@@ -13,8 +16,17 @@ pub enum Error {
     /// An I/O error occured.
     Io(io::Error),
 
+    /// The source of the cache failed to give the content of the asset.
+    Source(SourceError),
+
     /// The conversion from raw bytes failed.
     Conversion(BoxedError),
+
+    /// The loader of an asset failed.
+    ///
+    /// Unlike [`Error::Conversion`], this error knows which file and which
+    /// loader were used.
+    Load(Box<LoadError>),
 }
 
 impl Error {
@@ -23,17 +35,85 @@ impl Error {
 
         match (self, other) {
             (NoDefaultValue, other) => other,
-            (Io(_), other @ Conversion(_)) => other,
+            (Io(_) | Source(_), other @ Conversion(_)) => other,
+            (Io(_) | Source(_), other @ Load(_)) => other,
             (this, _) => this,
         }
     }
+
+    /// Creates an error equivalent to a shared one.
+    ///
+    /// The variant and its context are kept, and the cause is replaced by a
+    /// reference to the original one, so that messages stay the same.
+    pub(crate) fn share(this: &Arc<Error>) -> Error {
+        let cause = || -> BoxedError { Box::new(SharedCause(this.clone())) };
+
+        match &**this {
+            Error::NoDefaultValue => Error::NoDefaultValue,
+            Error::Io(err) => match err.get_ref() {
+                Some(_) => Error::Io(io::Error::new(err.kind(), SharedCause(this.clone()))),
+                None => Error::Io(err.kind().into()),
+            },
+            Error::Source(err) => {
+                // Keep the I/O error kind of the cause
+                let shared = err.error().map(|inner| match inner.downcast_ref::<io::Error>() {
+                    Some(io_err) => Box::new(io::Error::new(io_err.kind(), SharedCause(this.clone()))),
+                    None => cause(),
+                });
+                Error::Source(err.with_cause(shared))
+            }
+            Error::Conversion(_) => Error::Conversion(cause()),
+            Error::Load(err) => Error::Load(Box::new(LoadError {
+                id: err.id.clone(),
+                ext: err.ext.clone(),
+                loader: err.loader,
+                error: cause(),
+            })),
+        }
+    }
+}
+
+/// The cause of an error shared with [`Error::share`].
+struct SharedCause(Arc<Error>);
+
+impl SharedCause {
+    fn cause(&self) -> &(dyn std::error::Error + 'static) {
+        let cause = match &*self.0 {
+            Error::Io(err) => err.get_ref().map(|err| err as _),
+            Error::Source(err) => err.error().map(|err| err as _),
+            Error::Conversion(err) => Some(&**err as _),
+            Error::Load(err) => Some(&*err.error as _),
+            Error::NoDefaultValue => None,
+        };
+        cause.unwrap_or(&*self.0)
+    }
+}
+
+impl fmt::Debug for SharedCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.cause(), f)
+    }
+}
+
+impl fmt::Display for SharedCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.cause(), f)
+    }
+}
+
+impl std::error::Error for SharedCause {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause().source()
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Io(err) => f.write_fmt(format_args!("IO error: {}", err)),
+            Self::Source(err) => f.write_fmt(format_args!("Source error: {}", err)),
             Self::Conversion(err) => f.write_fmt(format_args!("Conversion error: {}", err)),
+            Self::Load(err) => fmt::Display::fmt(err, f),
             Self::NoDefaultValue => f.pad("No default value provided"),
         }
     }
@@ -43,7 +123,9 @@ impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Io(err) => Some(err),
+            Self::Source(err) => Some(err),
             Self::Conversion(err) => Some(&**err),
+            Self::Load(err) => Some(&*err.error),
             Self::NoDefaultValue => None,
         }
     }
@@ -55,8 +137,84 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<SourceError> for Error {
+    fn from(err: SourceError) -> Self {
+        Self::Source(err)
+    }
+}
+
 impl From<BoxedError> for Error {
     fn from(err: BoxedError) -> Self {
         Self::Conversion(err)
     }
 }
+
+impl From<LoadError> for Error {
+    fn from(err: LoadError) -> Self {
+        Self::Load(Box::new(err))
+    }
+}
+
+/// An error returned by a loader, with the context in which it occurred.
+#[derive(Debug)]
+pub struct LoadError {
+    id: Box<str>,
+    ext: Box<str>,
+    loader: &'static str,
+    error: BoxedError,
+}
+
+impl LoadError {
+    /// Creates a new `LoadError` from the error returned by loader `L` when
+    /// loading the file with given id and extension.
+    pub fn new<L: ?Sized>(id: &str, ext: &str, error: BoxedError) -> Self {
+        Self {
+            id: id.into(),
+            ext: ext.into(),
+            loader: std::any::type_name::<L>(),
+            error,
+        }
+    }
+
+    /// The id of the asset that failed to load.
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The extension of the file that failed to load.
+    #[inline]
+    pub fn ext(&self) -> &str {
+        &self.ext
+    }
+
+    /// The type name of the loader that failed.
+    #[inline]
+    pub fn loader(&self) -> &'static str {
+        self.loader
+    }
+
+    /// The error returned by the loader.
+    #[inline]
+    pub fn error(&self) -> &BoxedError {
+        &self.error
+    }
+
+    /// Unwraps the error returned by the loader.
+    #[inline]
+    pub fn into_inner(self) -> BoxedError {
+        self.error
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to load \"{}\" (extension \"{}\") with {}: {}", self.id, self.ext, self.loader, self.error)
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.error)
+    }
+}
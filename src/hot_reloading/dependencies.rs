@@ -1,5 +1,6 @@
 use crate::{
     AssetCache,
+    source::FileSystem,
     utils::{HashMap, HashSet, OwnedKey},
 };
 
@@ -7,13 +8,13 @@ use std::collections::hash_map::Entry;
 
 use super::paths::ReloadFn;
 
-struct AssetDeps {
-    reload: Option<ReloadFn>,
+struct AssetDeps<S> {
+    reload: Option<ReloadFn<S>>,
     rdeps: HashSet<OwnedKey>,
     deps: HashSet<OwnedKey>,
 }
 
-impl Default for AssetDeps {
+impl<S> Default for AssetDeps<S> {
     fn default() -> Self {
         AssetDeps {
             reload: None,
@@ -23,8 +24,8 @@ impl Default for AssetDeps {
     }
 }
 
-impl AssetDeps {
-    fn new(reload: Option<ReloadFn>, deps: HashSet<OwnedKey>) -> Self {
+impl<S> AssetDeps<S> {
+    fn new(reload: Option<ReloadFn<S>>, deps: HashSet<OwnedKey>) -> Self {
         AssetDeps {
             reload,
             deps,
@@ -33,14 +34,14 @@ impl AssetDeps {
     }
 }
 
-pub(crate) struct Dependencies(HashMap<OwnedKey, AssetDeps>);
+pub(crate) struct Dependencies<S = FileSystem>(HashMap<OwnedKey, AssetDeps<S>>);
 
-impl Dependencies {
+impl<S> Dependencies<S> {
     pub fn new() -> Self {
         Dependencies(HashMap::new())
     }
 
-    pub fn insert(&mut self, asset_key: OwnedKey, deps: HashSet<OwnedKey>, reload: Option<ReloadFn>) {
+    pub fn insert(&mut self, asset_key: OwnedKey, deps: HashSet<OwnedKey>, reload: Option<ReloadFn<S>>) {
         for key in deps.iter() {
             let entry = self.0.entry(key.clone()).or_insert_with(AssetDeps::default);
             entry.rdeps.insert(asset_key.clone());
@@ -75,7 +76,7 @@ struct TopologicalSortData {
     list: Vec<OwnedKey>,
 }
 
-fn visit(dep_graph: &Dependencies, sort: &mut TopologicalSortData, key: &OwnedKey, add_self: bool) {
+fn visit<S>(dep_graph: &Dependencies<S>, sort: &mut TopologicalSortData, key: &OwnedKey, add_self: bool) {
     if sort.visited.contains(key) {
         return;
     }
@@ -99,7 +100,7 @@ pub(crate) struct AssetDepGraph(Vec<OwnedKey>);
 
 
 impl AssetDepGraph {
-    pub fn new<'a, I: IntoIterator<Item=&'a OwnedKey>>(dep_graph: &Dependencies, iter: I) -> Self {
+    pub fn new<'a, S, I: IntoIterator<Item=&'a OwnedKey>>(dep_graph: &Dependencies<S>, iter: I) -> Self {
         let mut sort = TopologicalSortData {
             visited: HashSet::new(),
             list: Vec::new(),
@@ -112,7 +113,7 @@ impl AssetDepGraph {
         AssetDepGraph(sort.list)
     }
 
-    pub fn update(&self, deps: &mut Dependencies, cache: &AssetCache) {
+    pub fn update<S>(&self, deps: &mut Dependencies<S>, cache: &AssetCache<S>) {
         for key in self.0.iter().rev() {
             if let Some(entry) = deps.0.get_mut(key) {
                 if let Some(reload) = entry.reload {
@@ -0,0 +1,108 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use notify::{RecursiveMode, Watcher};
+
+
+/// Finds symbolic links in a directory and its subdirectories, with their
+/// targets.
+///
+/// Links are not followed, and links whose target does not exist are ignored.
+pub(crate) fn find_symlinks(root: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let mut links = Vec::new();
+    let mut dirs = vec![root.to_owned()];
+
+    while let Some(dir) = dirs.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(_) => continue,
+            };
+
+            if file_type.is_symlink() {
+                if let Ok(target) = fs::canonicalize(entry.path()) {
+                    links.push((entry.path(), target));
+                }
+            } else if file_type.is_dir() {
+                dirs.push(entry.path());
+            }
+        }
+    }
+
+    links
+}
+
+struct Link {
+    path: PathBuf,
+    target: PathBuf,
+    watched: bool,
+}
+
+/// Symbolic links whose targets are watched.
+///
+/// Events on targets are translated to paths through the links, so that
+/// assets loaded through a link are reloaded.
+pub(crate) struct Links {
+    roots: Vec<PathBuf>,
+    links: Vec<Link>,
+}
+
+impl Links {
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        Links {
+            roots,
+            links: Vec::new(),
+        }
+    }
+
+    pub fn watch<W: Watcher>(&mut self, watcher: &mut W, links: Vec<(PathBuf, PathBuf)>) {
+        for (path, target) in links {
+            // Targets in root directories are already watched. On Linux,
+            // notify also follows links to directories by itself.
+            let in_roots = self.roots.iter().any(|root| target.starts_with(root));
+            let watched = !(in_roots || cfg!(target_os = "linux") && target.is_dir());
+
+            if watched {
+                if let Err(err) = watcher.watch(&target, RecursiveMode::Recursive) {
+                    log::warn!("Unable to watch \"{}\": {}", target.display(), err);
+                    continue;
+                }
+            }
+
+            self.links.push(Link { path, target, watched });
+        }
+    }
+
+    pub fn unwatch<W: Watcher>(&mut self, watcher: &mut W) {
+        for link in self.links.drain(..) {
+            if link.watched {
+                let _ = watcher.unwatch(&link.target);
+            }
+        }
+    }
+
+    /// Returns the given path, and the paths of the same file through links.
+    pub fn paths_of(&self, path: PathBuf) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+
+        for link in &self.links {
+            if let Ok(relative) = path.strip_prefix(&link.target) {
+                if relative.as_os_str().is_empty() {
+                    paths.push(link.path.clone());
+                } else {
+                    paths.push(link.path.join(relative));
+                }
+            }
+        }
+
+        paths.push(path);
+        paths
+    }
+}
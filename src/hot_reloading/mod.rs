@@ -1,3 +1,4 @@
+mod links;
 mod paths;
 
 pub mod dependencies;
@@ -5,14 +6,17 @@ pub mod dependencies;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use links::find_symlinks;
 pub(crate) use paths::{UpdateMessage, AssetReloadInfos, CompoundReloadInfos};
+pub(crate) use paths::{Ext, LoadFn, LocalCache, ReloadFn, load, reload};
+use links::Links;
 use paths::HotReloadingData;
 
 use crossbeam_channel::{self as channel, Receiver, Sender};
 
 use std::{
     fmt,
-    path::Path,
+    path::PathBuf,
     ptr::NonNull,
     sync::mpsc,
     thread,
@@ -58,7 +62,7 @@ pub(crate) struct HotReloader {
 }
 
 impl HotReloader {
-    pub fn start(path: &Path) -> Result<Self, notify::Error> {
+    pub fn start(roots: &[PathBuf]) -> Result<Self, notify::Error> {
         let (notify_tx, notify_rx) = std_crossbeam_channel();
 
         let (ptr_tx, ptr_rx) = channel::unbounded();
@@ -66,13 +70,18 @@ impl HotReloader {
         let (updates_tx, updates_rx) = channel::unbounded();
 
         let mut watcher = notify::watcher(notify_tx, Duration::from_millis(50))?;
-        watcher.watch(path, RecursiveMode::Recursive)?;
+        for root in roots {
+            watcher.watch(root, RecursiveMode::Recursive)?;
+        }
+
+        let roots = roots.to_owned();
 
         thread::spawn(move || {
             log::trace!("Starting hot-reloading");
 
             // Keep the notify Watcher alive as long as the thread is running
-            let _watcher = watcher;
+            let mut watcher = watcher;
+            let mut links = Links::new(roots);
 
             // At the beginning, we select over three channels:
             // - One to notify that we can update the `AssetCache` or that we
@@ -110,14 +119,22 @@ impl HotReloader {
                             DebouncedEvent::Write(path)
                             | DebouncedEvent::Chmod(path)
                             | DebouncedEvent::Create(path) => {
-                                cache.load(path);
+                                for path in links.paths_of(path) {
+                                    cache.load(path);
+                                }
                             },
                             DebouncedEvent::Remove(path) => {
-                                cache.remove(path);
+                                for path in links.paths_of(path) {
+                                    cache.remove(path);
+                                }
                             },
                             DebouncedEvent::Rename(src, dst) => {
-                                cache.load(dst);
-                                cache.remove(src);
+                                for dst in links.paths_of(dst) {
+                                    cache.load(dst);
+                                }
+                                for src in links.paths_of(src) {
+                                    cache.remove(src);
+                                }
                             },
                             _ => (),
                         },
@@ -128,6 +145,8 @@ impl HotReloader {
                     },
 
                     2 => match ready.recv(&updates_rx) {
+                        Ok(UpdateMessage::WatchLinks(new_links)) => links.watch(&mut watcher, new_links),
+                        Ok(UpdateMessage::UnwatchLinks) => links.unwatch(&mut watcher),
                         Ok(msg) => cache.recv_update(msg),
                         Err(_) => break,
                     },
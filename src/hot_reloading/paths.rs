@@ -1,6 +1,7 @@
 use std::{
     any::{Any, TypeId},
     borrow::Cow,
+    fmt,
     fs,
     path::{Path, PathBuf},
     sync::Arc,
@@ -12,6 +13,7 @@ use crate::{
     Compound,
     loader::Loader,
     entry::CacheEntry,
+    source::{FileSystem, Source, Version},
     utils::{BorrowedKey, HashMap, HashSet, Key, OwnedKey},
 };
 
@@ -37,7 +39,7 @@ fn extension_of(path: &Path) -> Option<&str> {
 }
 
 
-unsafe trait AnyAsset: Any + Send + Sync {
+pub(crate) unsafe trait AnyAsset: Any + Send + Sync {
     unsafe fn reload(self: Box<Self>, entry: &CacheEntry);
     fn create(self: Box<Self>, id: Arc<str>) -> CacheEntry;
 }
@@ -56,21 +58,22 @@ unsafe impl<A: Asset> AnyAsset for A {
     }
 }
 
-type LoadFn = fn(content: Cow<[u8]>, ext: &str, id: &str, path: &Path) -> Option<Box<dyn AnyAsset>>;
+pub(crate) type LoadFn = fn(content: Cow<[u8]>, ext: &str, id: &str, origin: &dyn fmt::Display) -> Option<Box<dyn AnyAsset>>;
 
-fn load<A: Asset>(content: Cow<[u8]>, ext: &str, id: &str, path: &Path) -> Option<Box<dyn AnyAsset>> {
+pub(crate) fn load<A: Asset>(content: Cow<[u8]>, ext: &str, id: &str, origin: &dyn fmt::Display) -> Option<Box<dyn AnyAsset>> {
     match A::Loader::load(content, ext) {
         Ok(asset) => Some(Box::new(asset)),
         Err(err) => {
-            log::warn!("Error reloading \"{}\" from \"{}\": {}", id, path.display(), err);
+            let err = crate::LoadError::new::<A::Loader>(id, ext, err);
+            log::warn!("Error reloading from \"{}\": {}", origin, err);
             None
         },
     }
 }
 
-pub(crate) type ReloadFn = fn(cache: &AssetCache, id: &str) -> Option<HashSet<OwnedKey>>;
+pub(crate) type ReloadFn<S = FileSystem> = fn(cache: &AssetCache<S>, id: &str) -> Option<HashSet<OwnedKey>>;
 
-fn reload<T: Compound>(cache: &AssetCache, id: &str) -> Option<HashSet<OwnedKey>> {
+pub(crate) fn reload<T: Compound, S: Source>(cache: &AssetCache<S>, id: &str) -> Option<HashSet<OwnedKey>> {
     let key: &dyn Key = &Key::new::<T>(id);
     let handle = unsafe { cache.assets.read().get(key)?.handle::<T>() };
     let entry = handle.either(
@@ -95,18 +98,20 @@ fn reload<T: Compound>(cache: &AssetCache, id: &str) -> Option<HashSet<OwnedKey>
 }
 
 
-type Ext = &'static [&'static str];
+pub(crate) type Ext = &'static [&'static str];
 
 /// This struct is responsible of the safety of the whole module.
 ///
 /// Its invariant is that the TypeId is the same as the one of the value
 /// returned by the LoadFn.
-pub(crate) struct AssetReloadInfos(PathBuf, Arc<str>, TypeId, LoadFn);
+///
+/// The version is the one of the file when the asset was loaded, if known.
+pub(crate) struct AssetReloadInfos(PathBuf, Arc<str>, TypeId, LoadFn, Option<Version>);
 
 impl AssetReloadInfos {
     #[inline]
-    pub fn of<A: Asset>(path: PathBuf, id: Arc<str>) -> Self {
-        AssetReloadInfos(path, id, TypeId::of::<A>(), load::<A>)
+    pub fn of<A: Asset>(path: PathBuf, id: Arc<str>, version: Option<Version>) -> Self {
+        AssetReloadInfos(path, id, TypeId::of::<A>(), load::<A>, version)
     }
 }
 
@@ -116,7 +121,7 @@ impl CompoundReloadInfos {
     #[inline]
     pub fn of<A: Compound>(id: Arc<str>, deps: HashSet<OwnedKey>) -> Self {
         let key = OwnedKey::new::<A>(id);
-        CompoundReloadInfos(key, deps, reload::<A>)
+        CompoundReloadInfos(key, deps, reload::<A, FileSystem>)
     }
 }
 
@@ -127,6 +132,8 @@ pub(crate) enum UpdateMessage {
     AddAsset(AssetReloadInfos),
     AddDir(AssetReloadInfos, Ext),
     AddCompound(CompoundReloadInfos),
+    WatchLinks(Vec<(PathBuf, PathBuf)>),
+    UnwatchLinks,
 }
 
 /// A map type -> `T`
@@ -163,6 +170,8 @@ impl<T> Types<T> {
 struct WatchedPath<T> {
     id: Arc<str>,
     types: Types<T>,
+    /// The version of the file when it was last reloaded
+    version: Option<Version>,
 }
 
 impl<T> WatchedPath<T> {
@@ -170,6 +179,7 @@ impl<T> WatchedPath<T> {
         Self {
             id,
             types: Types::new(),
+            version: None,
         }
     }
 }
@@ -191,13 +201,17 @@ impl AssetPaths {
     }
 
     fn add_asset(&mut self, id: AssetReloadInfos) {
-        let AssetReloadInfos(path, id, type_id, load) = id;
+        let AssetReloadInfos(path, id, type_id, load, version) = id;
         let watched = self.assets.entry(path).or_insert_with(|| WatchedPath::new(id));
         watched.types.insert(type_id, load);
+        // The asset was just loaded from this version of the file
+        if version.is_some() {
+            watched.version = version;
+        }
     }
 
     fn add_dir(&mut self, id: AssetReloadInfos, ext: Ext) {
-        let AssetReloadInfos(path, id, type_id, load) = id;
+        let AssetReloadInfos(path, id, type_id, load, _) = id;
         let watched = self.dirs.entry(path).or_insert_with(|| WatchedPath::new(id));
         watched.types.insert(type_id, (load, ext));
     }
@@ -210,16 +224,42 @@ enum Action {
 }
 
 /// Store assets until we can sync with the `AssetCache`.
-pub struct LocalCache {
+pub(crate) struct LocalCache {
     changed: HashMap<OwnedKey, Box<dyn AnyAsset>>,
     changed_dirs: Vec<(OwnedKey, Arc<str>, Action)>,
 }
 
 impl LocalCache {
+    pub fn new() -> Self {
+        LocalCache {
+            changed: HashMap::new(),
+            changed_dirs: Vec::new(),
+        }
+    }
+
     fn clear(&mut self) {
         self.changed.clear();
         self.changed_dirs.clear();
     }
+
+    /// Reload an asset at the next update.
+    ///
+    /// # Safety
+    ///
+    /// `key.type_id == asset.type_id()`
+    pub unsafe fn reload(&mut self, key: OwnedKey, asset: Box<dyn AnyAsset>) {
+        self.changed.insert(key, asset);
+    }
+
+    /// Add an asset to a directory at the next update.
+    pub fn add(&mut self, dir_key: OwnedKey, id: Arc<str>) {
+        self.changed_dirs.push((dir_key, id, Action::Add));
+    }
+
+    /// Remove an asset from a directory at the next update.
+    pub fn remove(&mut self, dir_key: OwnedKey, id: Arc<str>) {
+        self.changed_dirs.push((dir_key, id, Action::Remove));
+    }
 }
 
 enum CacheKind {
@@ -245,7 +285,7 @@ impl CacheKind {
                 to_reload.push(key.to_owned());
             },
             CacheKind::Local(cache) => {
-                cache.changed.insert(key.to_owned(), asset);
+                cache.reload(key.to_owned(), asset);
             },
         }
     }
@@ -263,7 +303,7 @@ impl CacheKind {
                 }
             },
             CacheKind::Local(cache) => {
-                cache.changed_dirs.push((dir_key.to_owned(), id, Action::Add));
+                cache.add(dir_key.to_owned(), id);
             },
         }
     }
@@ -281,7 +321,7 @@ impl CacheKind {
                 }
             },
             CacheKind::Local(cache) => {
-                cache.changed_dirs.push((dir_key.to_owned(), id, Action::Remove));
+                cache.remove(dir_key.to_owned(), id);
             },
         }
     }
@@ -295,10 +335,7 @@ pub(crate) struct HotReloadingData {
 
 impl HotReloadingData {
     pub fn new() -> Self {
-        let cache = LocalCache {
-            changed: HashMap::new(),
-            changed_dirs: Vec::new(),
-        };
+        let cache = LocalCache::new();
 
         HotReloadingData {
             paths: AssetPaths {
@@ -324,7 +361,7 @@ impl HotReloadingData {
     }
 
     fn load_asset(&mut self, path: &Path, file_ext: &str) {
-        if let Some(path_infos) = self.paths.assets.get(path) {
+        if let Some(path_infos) = self.paths.assets.get_mut(path) {
             let content = match fs::read(path) {
                 Ok(content) => content,
                 Err(err) => {
@@ -333,8 +370,15 @@ impl HotReloadingData {
                 }
             };
 
+            // Editors often write files several times, or without changing them
+            let version = Some(FileSystem::version_of(&content));
+            if path_infos.version == version {
+                return;
+            }
+            path_infos.version = version;
+
             for (type_id, load) in &path_infos.types.0 {
-                if let Some(asset) = load(Cow::Borrowed(&content), file_ext, &path_infos.id, path) {
+                if let Some(asset) = load(Cow::Borrowed(&content), file_ext, &path_infos.id, &path.display()) {
                     unsafe {
                         let key = Key::new_with(&path_infos.id, *type_id);
                         self.cache.update(key, asset);
@@ -421,6 +465,8 @@ impl HotReloadingData {
                 let CompoundReloadInfos(key, new_deps, reload) = infos;
                 self.deps.insert(key, new_deps, Some(reload));
             },
+            // Handled by the watcher
+            UpdateMessage::WatchLinks(_) | UpdateMessage::UnwatchLinks => (),
         }
     }
 }
@@ -428,7 +474,7 @@ impl HotReloadingData {
 impl LocalCache {
     /// Update the `AssetCache` with data collected in the `LocalCache` since
     /// the last reload.
-    fn update(&mut self, deps: &mut Dependencies, cache: &AssetCache) {
+    pub fn update<S: Source>(&mut self, deps: &mut Dependencies<S>, cache: &AssetCache<S>) {
         let to_update = super::dependencies::AssetDepGraph::new(&deps, self.changed.iter().map(|(k,_)| k));
 
         // Update assets
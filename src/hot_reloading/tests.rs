@@ -1,6 +1,6 @@
 use crate::{
     AssetCache,
-    tests::{X, Y, Z},
+    tests::{W, X, Y, Z},
 };
 use std::{
     fs::{self, File},
@@ -47,7 +47,7 @@ macro_rules! test_scenario {
 
             test_scenario!(@leak cache $is_static);
 
-            let path = cache.source().path_of(id, "x");
+            let path = cache.source().path_of(id, "x")?;
             write_i32(&path, $n)?;
 
             test_scenario!(@enhance cache $is_static);
@@ -136,6 +136,14 @@ test_scenario! {
     start_value: 57,
 }
 
+test_scenario! {
+    name: reload_contextual,
+    is_static: false,
+    type: W,
+    id: "h",
+    start_value: 65,
+}
+
 
 #[test]
 fn dir_remove_and_add() -> Res {
@@ -198,3 +206,369 @@ fn dir_remove_and_add_static() -> Res {
 
     Ok(())
 }
+
+#[cfg(unix)]
+#[test]
+fn symlink_target() -> Res {
+    let root = std::env::temp_dir().join(format!("assets_manager_hot_symlink_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("assets"))?;
+    fs::create_dir_all(root.join("shared"))?;
+    write_i32(&root.join("shared/a.x"), 1)?;
+    std::os::unix::fs::symlink(root.join("shared/a.x"), root.join("assets/a.x"))?;
+
+    let source = crate::source::FileSystem::new(root.join("assets"))?.watch_symlink_targets(true);
+    let cache = AssetCache::with_source(source);
+    sleep();
+
+    let asset = cache.load::<X>("a")?;
+    assert_eq!(asset.read().0, 1);
+
+    write_i32(&root.join("shared/a.x"), 2)?;
+    sleep();
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 2);
+
+    fs::remove_dir_all(&root)?;
+    Ok(())
+}
+
+#[test]
+fn several_roots() -> Res {
+    let root = std::env::temp_dir().join(format!("assets_manager_hot_roots_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("game/dir"))?;
+    fs::create_dir_all(root.join("engine/dir"))?;
+    write_i32(&root.join("engine/dir/a.x"), 1)?;
+
+    let source = crate::source::FileSystem::with_roots([root.join("game"), root.join("engine")])?;
+    let cache = AssetCache::with_source(source);
+
+    let asset = cache.load::<X>("dir.a")?;
+    let dir = cache.load_dir::<X>("dir")?;
+    assert_eq!(asset.read().0, 1);
+
+    write_i32(&root.join("engine/dir/a.x"), 2)?;
+    sleep();
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 2);
+
+    write_i32(&root.join("game/dir/b.x"), 3)?;
+    sleep();
+    cache.hot_reload();
+    let mut loaded: Vec<_> = dir.iter().map(|x| x.read().0).collect();
+    loaded.sort();
+    assert_eq!(loaded, [2, 3]);
+
+    fs::remove_dir_all(&root)?;
+    Ok(())
+}
+
+#[test]
+fn unchanged_file() -> Res {
+    let root = std::env::temp_dir().join(format!("assets_manager_hot_unchanged_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root)?;
+    write_i32(&root.join("a.x"), 1)?;
+
+    let cache = AssetCache::with_source(crate::source::FileSystem::new(&root)?);
+    let mut asset = cache.load::<X>("a")?;
+
+    // Saving the file without changing it does not reload it
+    write_i32(&root.join("a.x"), 1)?;
+    sleep();
+    cache.hot_reload();
+    assert!(!asset.reloaded());
+
+    write_i32(&root.join("a.x"), 2)?;
+    sleep();
+    cache.hot_reload();
+    assert!(asset.reloaded());
+    assert_eq!(asset.read().0, 2);
+
+    fs::remove_dir_all(&root)?;
+    Ok(())
+}
+
+#[test]
+fn polling() -> Res {
+    use crate::source::{Memory, Polling};
+
+    let memory = Memory::new();
+    memory.insert("a", "x", "1");
+    let source = Polling::new(memory).with_interval(std::time::Duration::from_secs(0));
+    let cache = AssetCache::with_source(source);
+
+    let asset = cache.load::<X>("a")?;
+    let compound = cache.load::<Z>("a")?;
+    let dir = cache.load_dir::<X>("")?;
+    cache.hot_reload();
+
+    cache.source().inner().insert("a", "x", "2");
+    cache.hot_reload();
+    assert_eq!(asset.read().0, 2);
+    assert_eq!(compound.read().0, 2);
+
+    cache.source().inner().insert("b", "x", "3");
+    cache.hot_reload();
+    let mut loaded: Vec<_> = dir.iter().map(|x| x.read().0).collect();
+    loaded.sort();
+    assert_eq!(loaded, [2, 3]);
+
+    cache.source().inner().remove("b", "x");
+    cache.hot_reload();
+    let loaded: Vec<_> = dir.iter().map(|x| x.read().0).collect();
+    assert_eq!(loaded, [2]);
+
+    Ok(())
+}
+
+#[test]
+fn swap_source() -> Res {
+    use crate::source::{Memory, Polling, Source, SwappableSource};
+
+    let memory = Memory::new();
+    memory.insert("a", "x", "1");
+    memory.insert("b", "x", "2");
+    let source = Polling::new(SwappableSource::new(memory));
+    let cache = AssetCache::with_source(source);
+
+    let a = cache.load::<X>("a")?;
+    let b = cache.load::<X>("b")?;
+    let compound = cache.load::<Z>("a")?;
+
+    let memory = Memory::new();
+    memory.insert("a", "x", "10");
+    let old = cache.swap_source(memory);
+    assert_eq!(&*old.read("a", "x")?, b"1");
+
+    assert_eq!(a.read().0, 10);
+    assert_eq!(compound.read().0, 10);
+    assert_eq!(b.read().0, 2);
+    assert!(cache.load::<X>("c").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn polling_external() -> Res {
+    use crate::{asset::Shader, source::{Memory, Polling}};
+
+    let memory = Memory::new();
+    memory.insert("main", "glsl", "#include \"main.h\"\nvoid main() {}");
+    memory.insert("main", "h", "// 1");
+    let source = Polling::new(memory).with_interval(std::time::Duration::from_secs(0));
+    let cache = AssetCache::with_source(source);
+
+    let shader = cache.load::<Shader>("main")?;
+    assert_eq!(shader.read().0, "// 1\nvoid main() {}\n");
+    cache.hot_reload();
+
+    cache.source().inner().insert("main", "h", "// 2");
+    cache.hot_reload();
+    assert_eq!(shader.read().0, "// 2\nvoid main() {}\n");
+
+    Ok(())
+}
+
+#[cfg(feature = "gltf")]
+#[test]
+fn polling_gltf() -> Res {
+    use crate::{asset::Gltf, source::{Memory, Polling}};
+
+    let memory = Memory::new();
+    memory.insert("scene", "gltf", r#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":4,"uri":"scene.data"}]}"#);
+    memory.insert("scene", "data", [1, 2, 3, 4]);
+    let source = Polling::new(memory).with_interval(std::time::Duration::from_secs(0));
+    let cache = AssetCache::with_source(source);
+
+    let scene = cache.load::<Gltf>("scene")?;
+    assert_eq!(scene.read().buffers, [[1, 2, 3, 4]]);
+    cache.hot_reload();
+
+    cache.source().inner().insert("scene", "data", [5, 6, 7, 8]);
+    cache.hot_reload();
+    assert_eq!(scene.read().buffers, [[5, 6, 7, 8]]);
+
+    Ok(())
+}
+
+#[cfg(feature = "tiled")]
+#[test]
+fn polling_tiled() -> Res {
+    use crate::{asset::TiledMap, source::{Memory, Polling}};
+
+    let tileset = |name: &str| format!(
+        r#"<tileset version="1.10" name="{}" tilewidth="16" tileheight="16" tilecount="1" columns="1"><image source="a.png" width="16" height="16"/></tileset>"#,
+        name,
+    );
+
+    let memory = Memory::new();
+    memory.insert("level", "tmx", r#"<map version="1.10" orientation="orthogonal" width="1" height="1" tilewidth="16" tileheight="16"><tileset firstgid="1" source="terrain.xml"/></map>"#);
+    memory.insert("terrain", "xml", tileset("a"));
+    let source = Polling::new(memory).with_interval(std::time::Duration::from_secs(0));
+    let cache = AssetCache::with_source(source);
+
+    let map = cache.load::<TiledMap>("level")?;
+    assert_eq!(map.read().0.tilesets()[0].name, "a");
+    cache.hot_reload();
+
+    cache.source().inner().insert("terrain", "xml", tileset("b"));
+    cache.hot_reload();
+    assert_eq!(map.read().0.tilesets()[0].name, "b");
+
+    Ok(())
+}
+
+#[cfg(feature = "pack")]
+#[test]
+fn polling_pack() -> Res {
+    use crate::source::{Pack, PackWriter, Polling};
+
+    let path = std::env::temp_dir().join(format!("assets_manager_polling_pack_{}", std::process::id()));
+    let write_pack = |a: &str, b: &str| -> io::Result<()> {
+        let mut writer = PackWriter::new();
+        writer.add("a", "x", a.as_bytes())?;
+        writer.add("b", "x", b.as_bytes())?;
+        writer.write_to_file(&path)
+    };
+
+    write_pack("1", "2")?;
+    let source = Polling::new(Pack::open(&path)?).with_interval(std::time::Duration::from_secs(0));
+    let cache = AssetCache::with_source(source);
+
+    let a = cache.load::<X>("a")?;
+    let b = cache.load::<X>("b")?;
+    cache.hot_reload();
+
+    write_pack("10", "2")?;
+    cache.hot_reload();
+    assert_eq!(a.read().0, 10);
+    assert_eq!(b.read().0, 2);
+    assert!(!cache.source().inner().reload()?);
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[cfg(feature = "tar")]
+#[test]
+fn polling_archive() -> Res {
+    use crate::source::{ArchiveSource, Polling};
+
+    let path = std::env::temp_dir().join(format!("assets_manager_polling_archive_{}.tar", std::process::id()));
+    let write_tar = |a: &str, b: &str| -> io::Result<()> {
+        let mut builder = tar_crate::Builder::new(File::create(&path)?);
+        for (name, content) in [("a.x", a), ("b.x", b)] {
+            let mut header = tar_crate::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, content.as_bytes())?;
+        }
+        builder.finish()
+    };
+
+    write_tar("1", "2")?;
+    let source = Polling::new(ArchiveSource::open(&path)?).with_interval(std::time::Duration::from_secs(0));
+    let cache = AssetCache::with_source(source);
+
+    let a = cache.load::<X>("a")?;
+    let b = cache.load::<X>("b")?;
+    cache.hot_reload();
+
+    write_tar("10", "2")?;
+    cache.hot_reload();
+    assert_eq!(a.read().0, 10);
+    assert_eq!(b.read().0, 2);
+    assert!(!cache.source().inner().reload()?);
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn load_background() -> Res {
+    use crate::{Compound, Error, LoadHandle, source::{Memory, Polling, Source}};
+    use std::cell::RefCell;
+
+    thread_local! {
+        static PENDING: RefCell<Option<LoadHandle<X, Polling<Memory>>>> = const { RefCell::new(None) };
+    }
+
+    // Only joins a handle created outside of `load` the first time
+    struct Joined(i32);
+
+    impl Compound for Joined {
+        fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Joined, Error> {
+            let n = match PENDING.with(|pending| pending.borrow_mut().take()) {
+                Some(handle) => handle.join()?.read().0,
+                None => cache.load::<X>(id)?.read().0,
+            };
+            Ok(Joined(n))
+        }
+    }
+
+    let memory = Memory::new();
+    memory.insert("a", "x", "1");
+    let source = Polling::new(memory).with_interval(std::time::Duration::from_secs(0));
+    let cache = std::sync::Arc::new(AssetCache::with_source(source));
+
+    let handle = cache.load_background::<X>("a");
+    PENDING.with(|pending| *pending.borrow_mut() = Some(handle));
+    let joined = cache.load::<Joined>("a")?;
+    assert_eq!(joined.read().0, 1);
+    cache.hot_reload();
+
+    cache.source().inner().insert("a", "x", "2");
+    cache.hot_reload();
+    assert_eq!(joined.read().0, 2);
+
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn load_async() -> Res {
+    use crate::{
+        Compound, Error,
+        source::{Blocking, Memory, Polling, Source},
+        tests::XA,
+        utils::OwnedKey,
+    };
+    use std::cell::Cell;
+
+    type Cache = AssetCache<Blocking<Polling<Memory>>>;
+
+    thread_local! {
+        static CACHE: Cell<Option<&'static Cache>> = const { Cell::new(None) };
+    }
+
+    // Polls the future on another thread
+    struct Async(i32);
+
+    impl Compound for Async {
+        fn load<S: Source>(_: &AssetCache<S>, id: &str) -> Result<Async, Error> {
+            let cache = CACHE.with(Cell::get).unwrap();
+            let future = cache.load_async::<XA>(id);
+            let n = std::thread::scope(|s| s.spawn(|| pollster::block_on(future)).join().unwrap())?.read().0;
+            Ok(Async(n))
+        }
+    }
+
+    let memory = Memory::new();
+    memory.insert("a", "x", "1");
+    let source = Blocking::new(Polling::new(memory));
+    let cache: &'static Cache = Box::leak(Box::new(AssetCache::with_source(source)));
+    CACHE.with(|c| c.set(Some(cache)));
+
+    let (asset, deps) = cache.record_load::<Async>("a")?;
+    assert_eq!(asset.0, 1);
+    assert!(deps.contains(&OwnedKey::new::<XA>("a".into())));
+
+    // Also when the asset is already in the cache
+    let (_, deps) = cache.record_load::<Async>("a")?;
+    assert!(deps.contains(&OwnedKey::new::<XA>("a".into())));
+
+    Ok(())
+}
@@ -8,17 +8,66 @@
 //! ## Cargo features
 //!
 //! - `hot-reloading`: Add hot-reloading
+//! - `async`: Add asynchronous loading and sources
 //! - `embedded`: Add embedded source
+//! - `embedded-deflate`, `embedded-zstd`: Compress embedded files at compile time
+//! - `embedded-build`: Generate embedded files from a build script
+//! - `test-utils`: Add a source backed by a temporary directory, to write tests
+//! - `include_dir`: Add a source reading files embedded with the `include_dir` crate
+//! - `mmap`: Allow the file system source to map large files in memory
+//! - `http`: Add a source to load assets from a web server
+//! - `s3`: Add a source to load assets from an S3-compatible object storage
+//! - `web`: Add a source to load assets with `fetch()` on WebAssembly
+//! - `dev-server`: Add a source receiving files pushed by a development server
+//! - `android`: Add a source to load assets from the APK on Android
+//! - `pack`: Add a source to load assets from a pack file, and a writer to create them
+//! - `sqlite`: Add a source to load assets from a SQLite database
+//! - `log`: Enable [`loader::Log`] to log errors, and [`source::Instrumented`]
+//!   to log accesses to a source
 //!
 //! ### Additional loaders
 //!
+//! - `audio`: WAV, OGG Vorbis and FLAC sounds
 //! - `bincode`: Bincode deserialization
+//! - `bytemuck`: Plain old data, with *bytemuck*
+//! - `bytes`: Shared byte buffers, with *bytes*
 //! - `cbor`: CBOR deserialization
-//! - `json`: JSON deserialization
+//! - `fluent`: Fluent localization resources
+//! - `gltf`: glTF scenes, with *gltf*
+//! - `image`: PNG, JPEG and TGA images, with *image*
+//! - `json`: JSON deserialization, Aseprite sprite sheets and LDtk projects
+//! - `json-schema`: JSON and YAML validated by a JSON schema, with *jsonschema*
+//! - `kdl`: KDL documents
+//! - `markdown`: Markdown documents, with *pulldown-cmark*
 //! - `msgpack`: MessagePack deserialization
+//! - `naga`: Validated WGSL and SPIR-V shaders, with *naga*
+//! - `obj`: Wavefront OBJ meshes, with *tobj*
+//! - `postcard`: Postcard deserialization
+//! - `regex`: Compiled regular expressions, with *regex*
+//! - `rhai`: Compiled Rhai scripts, with *rhai*
 //! - `ron`: RON deserialization
+//! - `svg`: SVG images, with *usvg*
+//! - `tar`: Tar archives as a single asset or as a source, with *tar*
+//! - `templates`: Handlebars and Tera templates, with *handlebars* and *tera*
+//! - `tiled`: Tiled maps and tilesets, with *tiled*
 //! - `toml`: TOML deserialization
+//! - `wasm-plugin`: Validated WebAssembly modules, with *wasmparser*
 //! - `yaml`: YAML deserialization
+//! - `zip`: Zip archives as a single asset or as a source, with *zip*
+//!
+//! ### Compression and encoding
+//!
+//! - `base64`: Base64 decoding
+//! - `brotli`: Brotli decompression
+//! - `gzip`: Gzip decompression
+//! - `lz4`: LZ4 frame decompression
+//! - `zstd`: Zstandard decompression
+//!
+//! ### Security
+//!
+//! - `checksum`: Checksum verification
+//! - `encryption`: Decryption of encrypted assets and sources
+//! - `signature`: Ed25519 signature verification
 //!
 //! ### Internal features
 //!
@@ -164,7 +213,10 @@
 extern crate self as assets_manager;
 
 pub mod asset;
-pub use asset::{Asset, Compound};
+pub use asset::{Asset, Compound, ContextualAsset, SavableAsset};
+
+mod background;
+pub use background::{LoadHandle, ThreadPool};
 
 mod cache;
 pub use cache::AssetCache;
@@ -173,10 +225,12 @@ mod dirs;
 pub use dirs::{DirReader, ReadAllDir, ReadDir};
 
 mod error;
-pub use error::{BoxedError, Error};
+pub use error::{BoxedError, Error, LoadError};
 
 pub mod loader;
 
+pub mod saver;
+
 mod entry;
 pub use entry::{AssetGuard, Handle};
 
@@ -0,0 +1,101 @@
+use crate::{BoxedError, asset::Archive};
+
+use super::Loader;
+
+use std::{borrow::Cow, collections::HashMap, io::Read};
+
+
+/// Loads zip and tar archives into memory.
+///
+/// The format is chosen from the extension of the file (`zip` or `tar`), and
+/// falls back to detecting it from the content of the file. Only regular files
+/// are kept: directories, links and other special entries are skipped.
+///
+/// Each feature enables one format:
+/// - `zip`: zip archives, with stored and deflated entries.
+/// - `tar`: uncompressed tar archives.
+///
+/// See [`asset::Archive`](crate::asset::Archive) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg_attr(docsrs, doc(cfg(any(feature = "zip", feature = "tar"))))]
+#[derive(Debug)]
+pub struct ArchiveLoader(());
+
+impl Loader<Archive> for ArchiveLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Archive, BoxedError> {
+        let entries = match ext {
+            #[cfg(feature = "zip")]
+            "zip" => load_zip(&content)?,
+            #[cfg(feature = "tar")]
+            "tar" => load_tar(&content)?,
+
+            #[cfg(feature = "zip")]
+            _ if content.starts_with(b"PK\x03\x04") || content.starts_with(b"PK\x05\x06") => load_zip(&content)?,
+            #[cfg(feature = "tar")]
+            _ if content.get(257..262) == Some(b"ustar") => load_tar(&content)?,
+
+            _ => return Err(format!("Unsupported archive format: \"{}\"", ext).into()),
+        };
+
+        Ok(Archive { entries })
+    }
+}
+
+#[cfg(feature = "zip")]
+pub(crate) fn load_zip(content: &[u8]) -> Result<HashMap<String, Vec<u8>>, BoxedError> {
+    let mut archive = zip_crate::ZipArchive::new(std::io::Cursor::new(content))?;
+    let mut entries = HashMap::with_capacity(archive.len());
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if !file.is_file() {
+            continue;
+        }
+
+        let path = normalize(&file.name()?)?;
+        let mut bytes = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut bytes)?;
+        entries.insert(path, bytes);
+    }
+
+    Ok(entries)
+}
+
+#[cfg(feature = "tar")]
+pub(crate) fn load_tar(content: &[u8]) -> Result<HashMap<String, Vec<u8>>, BoxedError> {
+    let mut archive = tar_crate::Archive::new(content);
+    let mut entries = HashMap::new();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?;
+        let path = normalize(&path.to_string_lossy())?;
+        let mut bytes = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut bytes)?;
+        entries.insert(path, bytes);
+    }
+
+    Ok(entries)
+}
+
+/// Converts the path of an entry into a relative path with `/` separators.
+///
+/// Paths that would escape the archive are rejected.
+fn normalize(path: &str) -> Result<String, BoxedError> {
+    let mut components = Vec::new();
+
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => (),
+            ".." => return Err(format!("Invalid path in archive: \"{}\"", path).into()),
+            _ => components.push(component),
+        }
+    }
+
+    Ok(components.join("/"))
+}
@@ -0,0 +1,212 @@
+use crate::{
+    BoxedError,
+    asset::{AnimationDirection, AnimationTag, SliceKey, SpriteFrame, SpriteSheet, SpriteSlice},
+};
+
+use super::{
+    Loader,
+    json::{array, boolean, field, integer, object, point, rect, size, string},
+};
+
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+
+use std::{borrow::Cow, convert::TryInto, fmt, time::Duration};
+
+
+/// Loads sprite sheets exported by Aseprite.
+///
+/// Both the "hash" and the "array" JSON formats are supported. In the "hash"
+/// format, frames are kept in the order of the file, so that animation tags
+/// refer to the right ones.
+///
+/// The texture of the sprite sheet is not loaded, only its file name is kept.
+///
+/// See [`asset::SpriteSheet`](crate::asset::SpriteSheet) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug)]
+pub struct AsepriteLoader(());
+
+impl Loader<SpriteSheet> for AsepriteLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<SpriteSheet, BoxedError> {
+        let Document { frames, meta } = serde_json::from_slice(&content)?;
+        let meta = match &meta {
+            Value::Object(meta) => meta,
+            _ => return Err("Missing \"meta\" object".into()),
+        };
+
+        let frames = frames.0.into_iter()
+            .map(|(name, frame)| load_frame(name, &frame))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tags = match meta.get("frameTags") {
+            Some(tags) => array(tags, "frameTags")?.iter().map(load_tag).collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
+
+        let slices = match meta.get("slices") {
+            Some(slices) => array(slices, "slices")?.iter().map(load_slice).collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
+
+        for tag in &tags {
+            if tag.from > tag.to || tag.to >= frames.len() {
+                return Err(format!("Invalid frame range for tag \"{}\"", tag.name).into());
+            }
+        }
+
+        Ok(SpriteSheet {
+            image: string(meta, "image")?.to_owned(),
+            size: size(field(meta, "size")?)?,
+            frames,
+            tags,
+            slices,
+        })
+    }
+}
+
+fn load_frame(name: Option<String>, frame: &Value) -> Result<SpriteFrame, BoxedError> {
+    let frame = object(frame, "frame")?;
+
+    let name = match name {
+        Some(name) => name,
+        None => string(frame, "filename")?.to_owned(),
+    };
+
+    Ok(SpriteFrame {
+        name,
+        rect: rect(field(frame, "frame")?)?,
+        rotated: boolean(frame, "rotated")?,
+        trimmed: boolean(frame, "trimmed")?,
+        source_rect: rect(field(frame, "spriteSourceSize")?)?,
+        source_size: size(field(frame, "sourceSize")?)?,
+        duration: Duration::from_millis(integer(frame, "duration")?),
+    })
+}
+
+fn load_tag(tag: &Value) -> Result<AnimationTag, BoxedError> {
+    let tag = object(tag, "tag")?;
+
+    let direction = match tag.get("direction").and_then(Value::as_str) {
+        None | Some("forward") => AnimationDirection::Forward,
+        Some("reverse") => AnimationDirection::Reverse,
+        Some("pingpong") => AnimationDirection::PingPong,
+        Some("pingpong_reverse") => AnimationDirection::PingPongReverse,
+        Some(other) => return Err(format!("Unknown animation direction: \"{}\"", other).into()),
+    };
+
+    // Aseprite exports the repeat count as a string
+    let repeat = match tag.get("repeat") {
+        None => None,
+        Some(Value::String(s)) => Some(s.parse()?),
+        Some(n) => Some(n.as_u64().ok_or("Invalid repeat count")?.try_into()?),
+    };
+
+    Ok(AnimationTag {
+        name: string(tag, "name")?.to_owned(),
+        from: integer(tag, "from")?.try_into()?,
+        to: integer(tag, "to")?.try_into()?,
+        direction,
+        repeat,
+    })
+}
+
+fn load_slice(slice: &Value) -> Result<SpriteSlice, BoxedError> {
+    let slice = object(slice, "slice")?;
+
+    let keys = array(field(slice, "keys")?, "keys")?.iter().map(|key| {
+        let key = object(key, "key")?;
+        Ok(SliceKey {
+            frame: integer(key, "frame")?.try_into()?,
+            bounds: rect(field(key, "bounds")?)?,
+            center: key.get("center").map(rect).transpose()?,
+            pivot: key.get("pivot").map(point).transpose()?,
+        })
+    }).collect::<Result<_, BoxedError>>()?;
+
+    Ok(SpriteSlice {
+        name: string(slice, "name")?.to_owned(),
+        color: slice.get("color").and_then(Value::as_str).unwrap_or_default().to_owned(),
+        keys,
+    })
+}
+
+/// The top-level object of the exported file.
+struct Document {
+    frames: Frames,
+    meta: Value,
+}
+
+impl<'de> Deserialize<'de> for Document {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DocumentVisitor;
+
+        impl<'de> Visitor<'de> for DocumentVisitor {
+            type Value = Document;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an Aseprite sprite sheet")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Document, A::Error> {
+                let mut frames = None;
+                let mut meta = None;
+
+                while let Some(key) = map.next_key::<Cow<str>>()? {
+                    match &*key {
+                        "frames" => frames = Some(map.next_value()?),
+                        "meta" => meta = Some(map.next_value()?),
+                        _ => { map.next_value::<IgnoredAny>()?; },
+                    }
+                }
+
+                Ok(Document {
+                    frames: frames.ok_or_else(|| de::Error::missing_field("frames"))?,
+                    meta: meta.ok_or_else(|| de::Error::missing_field("meta"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(DocumentVisitor)
+    }
+}
+
+/// The frames of the sprite sheet, in the order of the file.
+///
+/// In the "hash" format, frames are keyed by their name. `serde_json` sorts
+/// maps by default, so they have to be visited manually.
+struct Frames(Vec<(Option<String>, Value)>);
+
+impl<'de> Deserialize<'de> for Frames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = Frames;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map or a sequence of frames")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Frames, A::Error> {
+                let mut frames = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((name, frame)) = map.next_entry()? {
+                    frames.push((Some(name), frame));
+                }
+                Ok(Frames(frames))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Frames, A::Error> {
+                let mut frames = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(frame) = seq.next_element()? {
+                    frames.push((None, frame));
+                }
+                Ok(Frames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
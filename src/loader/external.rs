@@ -0,0 +1,81 @@
+//! Loading of files referenced by other assets.
+
+use crate::{AssetCache, BoxedError, source::Source};
+
+#[cfg(feature = "hot-reloading")]
+use super::{BytesLoader, LoadFrom};
+
+
+/// Converts a path relative to the asset `id` into an id and an extension.
+///
+/// Components are separated by `/`, and `..` refers to the parent directory.
+pub(super) fn resolve_path(id: &str, path: &str) -> Result<(String, String), BoxedError> {
+    let invalid = || format!("Cannot resolve path \"{}\"", path);
+
+    let mut components: Vec<&str> = id.split('.').collect();
+    components.pop();
+
+    let mut segments = path.split('/').peekable();
+    let mut ext = "";
+
+    while let Some(segment) = segments.next() {
+        match segment {
+            "" | "." => (),
+            ".." => {
+                components.pop().ok_or_else(invalid)?;
+            },
+            _ if segments.peek().is_none() => {
+                let (stem, file_ext) = match segment.rfind('.') {
+                    Some(pos) => (&segment[..pos], &segment[pos + 1..]),
+                    None => (segment, ""),
+                };
+                if stem.contains('.') {
+                    return Err(invalid().into());
+                }
+                components.push(stem);
+                ext = file_ext;
+            },
+            _ if segment.contains('.') => return Err(invalid().into()),
+            _ => components.push(segment),
+        }
+    }
+
+    Ok((components.join("."), ext.to_owned()))
+}
+
+/// An external file referenced by another asset.
+///
+/// External files are registered with the id `id.ext`, so a file can be
+/// recorded as a dependency whatever its extension. Only changes to the file
+/// matter, so its content is not kept.
+#[cfg(feature = "hot-reloading")]
+struct ExternalFile;
+
+#[cfg(feature = "hot-reloading")]
+impl From<Vec<u8>> for ExternalFile {
+    #[inline]
+    fn from(_: Vec<u8>) -> Self {
+        ExternalFile
+    }
+}
+
+#[cfg(feature = "hot-reloading")]
+impl crate::Asset for ExternalFile {
+    const EXTENSIONS: &'static [&'static str] = &[];
+    type Loader = LoadFrom<Vec<u8>, BytesLoader>;
+}
+
+/// Reads an external file from the source, and registers it as a dependency
+/// of the asset being loaded.
+pub(super) fn load_external<S: Source>(cache: &AssetCache<S>, id: &str, ext: &str) -> Result<Vec<u8>, BoxedError> {
+    let content = cache.source().read(id, ext).map_err(|err| err.with_file(id, ext).with_source::<S>())?;
+
+    #[cfg(feature = "hot-reloading")]
+    if cache.is_recording() {
+        let key = format!("{}.{}", id, ext);
+        cache.source()._add_file::<ExternalFile, crate::utils::Private>(&key, id, ext);
+        cache.add_record(crate::utils::OwnedKey::new::<ExternalFile>(key.into()));
+    }
+
+    Ok(content.into_owned())
+}
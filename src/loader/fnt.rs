@@ -0,0 +1,308 @@
+use crate::{
+    BoxedError,
+    asset::{BitmapFont, Glyph},
+};
+
+use super::Loader;
+
+use std::{
+    borrow::Cow,
+    convert::TryInto,
+    str,
+};
+
+
+/// Loads AngelCode bitmap font descriptors.
+///
+/// Both the text and the binary (version 3) variants of `.fnt` files are
+/// supported. The variant is detected from the content of the file. Texture
+/// pages are not loaded, only their file names are kept.
+///
+/// See [`asset::BitmapFont`](crate::asset::BitmapFont) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[derive(Debug)]
+pub struct FntLoader(());
+
+impl Loader<BitmapFont> for FntLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<BitmapFont, BoxedError> {
+        match content.strip_prefix(b"BMF") {
+            Some(content) => load_binary(content),
+            None => load_text(str::from_utf8(&content)?),
+        }
+    }
+}
+
+/// Glyphs store their page on a byte, so there cannot be more pages.
+const MAX_PAGES: usize = 256;
+
+fn load_text(content: &str) -> Result<BitmapFont, BoxedError> {
+    let mut font = BitmapFont::default();
+    let mut page_count = MAX_PAGES;
+
+    for line in content.lines() {
+        let mut tokens = Tokens(line.trim_start());
+        let tag = match tokens.next() {
+            Some((tag, None)) => tag,
+            Some((_, Some(_))) => return Err(format!("Invalid line: \"{}\"", line).into()),
+            None => continue,
+        };
+
+        match tag {
+            "info" => {
+                for (key, value) in tokens.pairs() {
+                    match key {
+                        "face" => font.face = value?.to_owned(),
+                        "size" => font.size = parse(key, value)?,
+                        _ => (),
+                    }
+                }
+            },
+            "common" => {
+                for (key, value) in tokens.pairs() {
+                    match key {
+                        "lineHeight" => font.line_height = parse(key, value)?,
+                        "base" => font.base = parse(key, value)?,
+                        "scaleW" => font.scale_w = parse(key, value)?,
+                        "scaleH" => font.scale_h = parse(key, value)?,
+                        "pages" => page_count = parse::<usize>(key, value)?.min(MAX_PAGES),
+                        _ => (),
+                    }
+                }
+            },
+            "page" => {
+                let mut id = None;
+                let mut file = None;
+                for (key, value) in tokens.pairs() {
+                    match key {
+                        "id" => id = Some(parse::<usize>(key, value)?),
+                        "file" => file = Some(value?.to_owned()),
+                        _ => (),
+                    }
+                }
+
+                let id = id.ok_or("Missing page id")?;
+                let file = file.ok_or("Missing page file")?;
+                if id >= page_count {
+                    return Err(format!("Invalid page id: {}", id).into());
+                }
+                if id >= font.pages.len() {
+                    font.pages.resize(id + 1, String::new());
+                }
+                font.pages[id] = file;
+            },
+            "char" => {
+                let mut id = None;
+                let mut glyph = Glyph::default();
+                for (key, value) in tokens.pairs() {
+                    match key {
+                        "id" => id = Some(parse(key, value)?),
+                        "x" => glyph.x = parse(key, value)?,
+                        "y" => glyph.y = parse(key, value)?,
+                        "width" => glyph.width = parse(key, value)?,
+                        "height" => glyph.height = parse(key, value)?,
+                        "xoffset" => glyph.x_offset = parse(key, value)?,
+                        "yoffset" => glyph.y_offset = parse(key, value)?,
+                        "xadvance" => glyph.x_advance = parse(key, value)?,
+                        "page" => glyph.page = parse(key, value)?,
+                        "chnl" => glyph.channel = parse(key, value)?,
+                        _ => (),
+                    }
+                }
+                font.glyphs.insert(id.ok_or("Missing char id")?, glyph);
+            },
+            "kerning" => {
+                let (mut first, mut second, mut amount) = (None, None, None);
+                for (key, value) in tokens.pairs() {
+                    match key {
+                        "first" => first = Some(parse(key, value)?),
+                        "second" => second = Some(parse(key, value)?),
+                        "amount" => amount = Some(parse(key, value)?),
+                        _ => (),
+                    }
+                }
+
+                match (first, second, amount) {
+                    (Some(first), Some(second), Some(amount)) => font.kernings.insert((first, second), amount),
+                    _ => return Err("Incomplete kerning pair".into()),
+                };
+            },
+            "chars" | "kernings" => (),
+            _ => return Err(format!("Unknown tag: \"{}\"", tag).into()),
+        }
+    }
+
+    Ok(font)
+}
+
+fn parse<T>(key: &str, value: Result<&str, BoxedError>) -> Result<T, BoxedError>
+where
+    T: str::FromStr,
+    T::Err: Into<BoxedError>,
+{
+    let value = value?;
+    value.parse().map_err(|err: T::Err| {
+        let err: BoxedError = err.into();
+        format!("Invalid value for \"{}\" ({}): {}", key, value, err).into()
+    })
+}
+
+/// Splits a line of a text font descriptor in `key=value` tokens.
+///
+/// Values can be enclosed in double quotes to contain spaces.
+struct Tokens<'a>(&'a str);
+
+impl<'a> Tokens<'a> {
+    /// Iterates on `key=value` pairs, failing on tokens without value.
+    fn pairs(self) -> impl Iterator<Item = (&'a str, Result<&'a str, BoxedError>)> {
+        self.map(|(key, value)| (key, value.ok_or_else(|| format!("Missing value for \"{}\"", key).into())))
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = (&'a str, Option<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.0.trim_start();
+        if line.is_empty() {
+            return None;
+        }
+
+        let key_end = line.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(line.len());
+        let key = &line[..key_end];
+        let rest = &line[key_end..];
+
+        let rest = match rest.strip_prefix('=') {
+            Some(rest) => rest,
+            None => {
+                self.0 = rest;
+                return Some((key, None));
+            },
+        };
+
+        let (value, rest) = match rest.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            },
+        };
+
+        self.0 = rest;
+        Some((key, Some(value)))
+    }
+}
+
+fn load_binary(content: &[u8]) -> Result<BitmapFont, BoxedError> {
+    let (&version, mut content) = content.split_first().ok_or("Missing version")?;
+    if version != 3 {
+        return Err(format!("Unsupported binary version: {}", version).into());
+    }
+
+    let mut font = BitmapFont::default();
+
+    while let Some((&kind, rest)) = content.split_first() {
+        let (size, rest) = split(rest, 4)?;
+        let size = u32::from_le_bytes(size.try_into()?) as usize;
+        let (block, rest) = split(rest, size)?;
+        content = rest;
+
+        match kind {
+            1 => {
+                let mut block = Reader(block);
+                font.size = block.i16()?;
+                block.skip(12)?;
+                font.face = block.c_str()?.to_owned();
+            },
+            2 => {
+                let mut block = Reader(block);
+                font.line_height = block.u16()?;
+                font.base = block.u16()?;
+                font.scale_w = block.u16()?;
+                font.scale_h = block.u16()?;
+            },
+            3 => {
+                let mut block = Reader(block);
+                while !block.0.is_empty() {
+                    font.pages.push(block.c_str()?.to_owned());
+                }
+            },
+            4 => {
+                let mut block = Reader(block);
+                while !block.0.is_empty() {
+                    let id = block.u32()?;
+                    let glyph = Glyph {
+                        x: block.u16()?,
+                        y: block.u16()?,
+                        width: block.u16()?,
+                        height: block.u16()?,
+                        x_offset: block.i16()?,
+                        y_offset: block.i16()?,
+                        x_advance: block.i16()?,
+                        page: block.u8()?,
+                        channel: block.u8()?,
+                    };
+                    font.glyphs.insert(id, glyph);
+                }
+            },
+            5 => {
+                let mut block = Reader(block);
+                while !block.0.is_empty() {
+                    let pair = (block.u32()?, block.u32()?);
+                    font.kernings.insert(pair, block.i16()?);
+                }
+            },
+            _ => return Err(format!("Unknown block type: {}", kind).into()),
+        }
+    }
+
+    Ok(font)
+}
+
+fn split(bytes: &[u8], at: usize) -> Result<(&[u8], &[u8]), BoxedError> {
+    if bytes.len() < at {
+        return Err("Unexpected end of file".into());
+    }
+    Ok(bytes.split_at(at))
+}
+
+/// Reads little-endian values from a block of a binary font descriptor.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], BoxedError> {
+        let (bytes, rest) = split(self.0, n)?;
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), BoxedError> {
+        self.take(n).map(drop)
+    }
+
+    fn u8(&mut self) -> Result<u8, BoxedError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BoxedError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, BoxedError> {
+        Ok(i16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, BoxedError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn c_str(&mut self) -> Result<&'a str, BoxedError> {
+        let len = self.0.iter().position(|&b| b == 0).ok_or("Unterminated string")?;
+        let s = str::from_utf8(self.take(len)?)?;
+        self.skip(1)?;
+        Ok(s)
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    AssetCache,
+    BoxedError,
+    asset::Gltf,
+    source::Source,
+};
+
+use super::{
+    ContextualLoader,
+    external::{load_external, resolve_path},
+};
+
+use std::borrow::Cow;
+
+
+/// Loads glTF scenes.
+///
+/// Both `.gltf` and `.glb` files are supported. External buffers and images
+/// are resolved relatively to the id of the scene and recorded as dependencies
+/// whatever their extension, so the scene is reloaded when any of them is
+/// modified. Data URIs are decoded too.
+///
+/// Images are not decoded.
+///
+/// See [`asset::Gltf`](crate::asset::Gltf) for the loaded type.
+///
+/// See trait [`ContextualLoader`] for more informations.
+#[cfg_attr(docsrs, doc(cfg(feature = "gltf")))]
+#[derive(Debug)]
+pub struct GltfLoader(());
+
+impl ContextualLoader<Gltf> for GltfLoader {
+    fn load<S: Source>(content: Cow<[u8]>, _: &str, id: &str, cache: &AssetCache<S>) -> Result<Gltf, BoxedError> {
+        let gltf_crate::Gltf { document, mut blob } = gltf_crate::Gltf::from_slice(&content)?;
+
+        let mut buffers = Vec::with_capacity(document.buffers().len());
+        for buffer in document.buffers() {
+            let data = match buffer.source() {
+                gltf_crate::buffer::Source::Bin => blob.take().ok_or("Missing binary chunk")?,
+                gltf_crate::buffer::Source::Uri(uri) => load_uri(cache, id, uri)?,
+            };
+
+            if data.len() < buffer.length() {
+                return Err(format!("Buffer {} is too short", buffer.index()).into());
+            }
+            buffers.push(data);
+        }
+
+        let mut images = Vec::with_capacity(document.images().len());
+        for image in document.images() {
+            let data = match image.source() {
+                gltf_crate::image::Source::View { view, .. } => {
+                    let buffer = &buffers[view.buffer().index()];
+                    let range = view.offset()..view.offset() + view.length();
+                    buffer.get(range).ok_or("Invalid buffer view")?.to_vec()
+                },
+                gltf_crate::image::Source::Uri { uri, .. } => load_uri(cache, id, uri)?,
+            };
+            images.push(data);
+        }
+
+        Ok(Gltf { document, buffers, images })
+    }
+}
+
+/// Loads the content of an URI relative to the asset `id`.
+fn load_uri<S: Source>(cache: &AssetCache<S>, id: &str, uri: &str) -> Result<Vec<u8>, BoxedError> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        let data = match data.find(";base64,") {
+            Some(pos) => &data[pos + 8..],
+            None => return Err("Only base64 data URIs are supported".into()),
+        };
+        return Ok(super::base64_decode(data.as_bytes())?);
+    }
+
+    if uri.contains(':') {
+        return Err(format!("Unsupported URI: \"{}\"", uri).into());
+    }
+
+    let (id, ext) = resolve_path(id, &percent_decode(uri)?)?;
+    load_external(cache, &id, &ext)
+}
+
+fn percent_decode(uri: &str) -> Result<String, BoxedError> {
+    let mut bytes = Vec::with_capacity(uri.len());
+    let mut iter = uri.bytes();
+
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next().unwrap_or(0), iter.next().unwrap_or(0)];
+            let hex = std::str::from_utf8(&hex)?;
+            bytes.push(u8::from_str_radix(hex, 16)?);
+        } else {
+            bytes.push(b);
+        }
+    }
+
+    Ok(String::from_utf8(bytes)?)
+}
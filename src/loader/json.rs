@@ -0,0 +1,60 @@
+//! Helpers to extract values from JSON documents.
+
+use crate::{BoxedError, asset::Rect};
+
+use serde_json::{Map, Value};
+
+use std::convert::TryInto;
+
+
+pub(super) fn field<'a>(obj: &'a Map<String, Value>, key: &str) -> Result<&'a Value, BoxedError> {
+    obj.get(key).ok_or_else(|| format!("Missing field \"{}\"", key).into())
+}
+
+pub(super) fn object<'a>(value: &'a Value, what: &str) -> Result<&'a Map<String, Value>, BoxedError> {
+    value.as_object().ok_or_else(|| format!("Expected an object for \"{}\"", what).into())
+}
+
+pub(super) fn array<'a>(value: &'a Value, what: &str) -> Result<&'a Vec<Value>, BoxedError> {
+    value.as_array().ok_or_else(|| format!("Expected an array for \"{}\"", what).into())
+}
+
+pub(super) fn string<'a>(obj: &'a Map<String, Value>, key: &str) -> Result<&'a str, BoxedError> {
+    field(obj, key)?.as_str().ok_or_else(|| format!("Expected a string for \"{}\"", key).into())
+}
+
+pub(super) fn boolean(obj: &Map<String, Value>, key: &str) -> Result<bool, BoxedError> {
+    match obj.get(key) {
+        Some(value) => value.as_bool().ok_or_else(|| format!("Expected a boolean for \"{}\"", key).into()),
+        None => Ok(false),
+    }
+}
+
+pub(super) fn integer(obj: &Map<String, Value>, key: &str) -> Result<u64, BoxedError> {
+    field(obj, key)?.as_u64().ok_or_else(|| format!("Expected an integer for \"{}\"", key).into())
+}
+
+pub(super) fn signed(obj: &Map<String, Value>, key: &str) -> Result<i32, BoxedError> {
+    let n = field(obj, key)?.as_i64().ok_or_else(|| format!("Expected an integer for \"{}\"", key))?;
+    Ok(n.try_into()?)
+}
+
+pub(super) fn rect(value: &Value) -> Result<Rect, BoxedError> {
+    let obj = object(value, "rect")?;
+    Ok(Rect {
+        x: signed(obj, "x")?,
+        y: signed(obj, "y")?,
+        width: integer(obj, "w")?.try_into()?,
+        height: integer(obj, "h")?.try_into()?,
+    })
+}
+
+pub(super) fn size(value: &Value) -> Result<(u32, u32), BoxedError> {
+    let obj = object(value, "size")?;
+    Ok((integer(obj, "w")?.try_into()?, integer(obj, "h")?.try_into()?))
+}
+
+pub(super) fn point(value: &Value) -> Result<(i32, i32), BoxedError> {
+    let obj = object(value, "point")?;
+    Ok((signed(obj, "x")?, signed(obj, "y")?))
+}
@@ -0,0 +1,197 @@
+use crate::{
+    AssetCache,
+    BoxedError,
+    asset::{LdtkEntity, LdtkLayer, LdtkLevel, LdtkProject, LdtkTile},
+    source::Source,
+};
+
+use super::{
+    ContextualLoader,
+    Loader,
+    external::resolve_path,
+    json::{array, field, integer, object, signed, string},
+};
+
+use serde_json::{Map, Value};
+
+use std::{borrow::Cow, convert::TryInto};
+
+
+/// Loads LDtk projects and levels.
+///
+/// Projects are loaded from `.ldtk` files, and levels saved separately are
+/// loaded from `.ldtkl` files.
+///
+/// When used as a [`ContextualLoader`], levels saved separately are resolved
+/// relatively to the project and loaded from the cache, so the project is
+/// reloaded when any of them is modified. When used as a [`Loader`], these
+/// levels are not loaded and their `layers` are `None`.
+///
+/// See [`asset::LdtkProject`](crate::asset::LdtkProject) and
+/// [`asset::LdtkLevel`](crate::asset::LdtkLevel) for the loaded types.
+///
+/// See traits [`Loader`] and [`ContextualLoader`] for more informations.
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug)]
+pub struct LdtkLoader(());
+
+impl Loader<LdtkProject> for LdtkLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<LdtkProject, BoxedError> {
+        let project: Value = serde_json::from_slice(&content)?;
+        let project = object(&project, "project")?;
+
+        Ok(LdtkProject {
+            json_version: string(project, "jsonVersion")?.to_owned(),
+            levels: array(field(project, "levels")?, "levels")?.iter().map(load_level).collect::<Result<_, _>>()?,
+            defs: project.get("defs").cloned().unwrap_or(Value::Null),
+        })
+    }
+}
+
+impl ContextualLoader<LdtkProject> for LdtkLoader {
+    fn load<S: Source>(content: Cow<[u8]>, ext: &str, id: &str, cache: &AssetCache<S>) -> Result<LdtkProject, BoxedError> {
+        let mut project = <Self as Loader<LdtkProject>>::load(content, ext)?;
+
+        for level in &mut project.levels {
+            let path = match (&level.layers, &level.external_path) {
+                (None, Some(path)) => path,
+                _ => continue,
+            };
+
+            let (level_id, level_ext) = resolve_path(id, path)?;
+            if level_ext != "ldtkl" {
+                return Err(format!("Invalid level path: \"{}\"", path).into());
+            }
+
+            let external = cache.load_owned::<LdtkLevel>(&level_id)?;
+            if external.iid != level.iid {
+                return Err(format!("Level \"{}\" does not match the project", path).into());
+            }
+            level.layers = external.layers;
+        }
+
+        Ok(project)
+    }
+}
+
+impl Loader<LdtkLevel> for LdtkLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<LdtkLevel, BoxedError> {
+        let level: Value = serde_json::from_slice(&content)?;
+        load_level(&level)
+    }
+}
+
+fn load_level(level: &Value) -> Result<LdtkLevel, BoxedError> {
+    let level = object(level, "level")?;
+
+    let layers = match level.get("layerInstances") {
+        None | Some(Value::Null) => None,
+        Some(layers) => Some(array(layers, "layerInstances")?.iter().map(load_layer).collect::<Result<_, _>>()?),
+    };
+
+    Ok(LdtkLevel {
+        identifier: string(level, "identifier")?.to_owned(),
+        iid: string(level, "iid")?.to_owned(),
+        world_x: signed(level, "worldX")?,
+        world_y: signed(level, "worldY")?,
+        width: integer(level, "pxWid")?.try_into()?,
+        height: integer(level, "pxHei")?.try_into()?,
+        external_path: optional_string(level, "externalRelPath")?,
+        fields: load_fields(level)?,
+        layers,
+    })
+}
+
+fn load_layer(layer: &Value) -> Result<LdtkLayer, BoxedError> {
+    let layer = object(layer, "layer")?;
+
+    let int_grid = match layer.get("intGridCsv") {
+        Some(values) => array(values, "intGridCsv")?.iter()
+            .map(|v| v.as_i64().ok_or("Expected an integer in \"intGridCsv\""))
+            .collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+
+    let mut tiles = Vec::new();
+    for key in &["gridTiles", "autoLayerTiles"] {
+        if let Some(values) = layer.get(*key) {
+            for tile in array(values, key)? {
+                tiles.push(load_tile(tile)?);
+            }
+        }
+    }
+
+    let entities = match layer.get("entityInstances") {
+        Some(values) => array(values, "entityInstances")?.iter().map(load_entity).collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+
+    Ok(LdtkLayer {
+        identifier: string(layer, "__identifier")?.to_owned(),
+        kind: string(layer, "__type")?.to_owned(),
+        grid_size: integer(layer, "__gridSize")?.try_into()?,
+        width: integer(layer, "__cWid")?.try_into()?,
+        height: integer(layer, "__cHei")?.try_into()?,
+        offset: (signed(layer, "__pxTotalOffsetX")?, signed(layer, "__pxTotalOffsetY")?),
+        tileset_path: optional_string(layer, "__tilesetRelPath")?,
+        int_grid,
+        tiles,
+        entities,
+    })
+}
+
+fn load_tile(tile: &Value) -> Result<LdtkTile, BoxedError> {
+    let tile = object(tile, "tile")?;
+    Ok(LdtkTile {
+        position: pair(field(tile, "px")?)?,
+        source: pair(field(tile, "src")?)?,
+        flip: integer(tile, "f")?.try_into()?,
+        id: integer(tile, "t")?.try_into()?,
+    })
+}
+
+fn load_entity(entity: &Value) -> Result<LdtkEntity, BoxedError> {
+    let entity = object(entity, "entity")?;
+    Ok(LdtkEntity {
+        identifier: string(entity, "__identifier")?.to_owned(),
+        iid: string(entity, "iid")?.to_owned(),
+        position: pair(field(entity, "px")?)?,
+        width: integer(entity, "width")?.try_into()?,
+        height: integer(entity, "height")?.try_into()?,
+        fields: load_fields(entity)?,
+    })
+}
+
+/// Collects the `fieldInstances` of a level or an entity.
+fn load_fields(obj: &Map<String, Value>) -> Result<Vec<(String, Value)>, BoxedError> {
+    let fields = match obj.get("fieldInstances") {
+        Some(fields) => array(fields, "fieldInstances")?,
+        None => return Ok(Vec::new()),
+    };
+
+    fields.iter().map(|f| {
+        let f = object(f, "field")?;
+        let value = f.get("__value").cloned().unwrap_or(Value::Null);
+        Ok((string(f, "__identifier")?.to_owned(), value))
+    }).collect()
+}
+
+fn optional_string(obj: &Map<String, Value>, key: &str) -> Result<Option<String>, BoxedError> {
+    match obj.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(_) => Ok(Some(string(obj, key)?.to_owned())),
+    }
+}
+
+/// Reads a `[x, y]` array.
+fn pair(value: &Value) -> Result<(i32, i32), BoxedError> {
+    let coords = array(value, "coordinates")?;
+    match &coords[..] {
+        [x, y] => {
+            let x = x.as_i64().ok_or("Expected an integer coordinate")?;
+            let y = y.as_i64().ok_or("Expected an integer coordinate")?;
+            Ok((x.try_into()?, y.try_into()?))
+        },
+        _ => Err("Expected two coordinates".into()),
+    }
+}
@@ -11,10 +11,12 @@
 //!
 //! [assets]: `crate::Asset`
 
-use crate::BoxedError;
+use crate::{AssetCache, BoxedError, source::{Source, SourceError}};
 
 use std::{
     borrow::Cow,
+    convert::TryInto,
+    io::{BufRead, BufReader},
     marker::PhantomData,
     str::{self, FromStr},
 };
@@ -23,6 +25,49 @@ use std::{
 #[cfg(test)]
 mod tests;
 
+mod external;
+#[cfg(feature = "json")]
+mod json;
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+pub(crate) mod archive;
+#[cfg(any(feature = "zip", feature = "tar"))]
+pub use archive::ArchiveLoader;
+
+#[cfg(feature = "json")]
+mod aseprite;
+#[cfg(feature = "json")]
+pub use aseprite::AsepriteLoader;
+
+mod fnt;
+pub use fnt::FntLoader;
+
+mod palette;
+pub use palette::PaletteLoader;
+
+#[cfg(feature = "json-schema")]
+mod schema;
+#[cfg(feature = "json-schema")]
+pub use schema::{JsonSchemaLoader, Schema, SchemaValidatedLoader};
+
+#[cfg(feature = "gltf")]
+mod gltf;
+#[cfg(feature = "gltf")]
+pub use self::gltf::GltfLoader;
+
+#[cfg(feature = "json")]
+mod ldtk;
+#[cfg(feature = "json")]
+pub use ldtk::LdtkLoader;
+
+mod shader;
+pub use shader::ShaderLoader;
+
+#[cfg(feature = "tiled")]
+mod tiled;
+#[cfg(feature = "tiled")]
+pub use self::tiled::TiledLoader;
+
 /// Specifies how an asset is loaded.
 ///
 /// With this trait, you can easily specify how you want your data to be loaded.
@@ -103,8 +148,163 @@ mod tests;
 pub trait Loader<T> {
     /// Loads an asset from its raw bytes representation.
     fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError>;
+
+    #[doc(hidden)]
+    fn _load_from_source<S: Source>(source: &S, id: &str, ext: &str) -> Result<Result<T, BoxedError>, SourceError> {
+        let content = source.read(id, ext)?;
+        Ok(Self::load(content, ext))
+    }
+}
+
+
+/// Specifies how an asset is loaded from a stream of bytes.
+///
+/// This is the same as [`Loader`], but the content is given as a reader instead
+/// of a buffer, so huge assets can be decoded without being entirely read into
+/// memory first.
+///
+/// Use [`Streamed`] to use these loaders as regular [`Loader`]s.
+pub trait StreamLoader<T> {
+    /// Loads an asset from a reader.
+    fn load_stream(reader: &mut dyn BufRead, ext: &str) -> Result<T, BoxedError>;
+}
+
+/// A boxed future, as returned by [`AsyncLoader`].
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
+
+/// Specifies how an asset is loaded asynchronously.
+///
+/// This is the asynchronous counterpart of [`Loader`], which is used by
+/// [`AssetCache::load_async`]. It enables CPU-heavy decoding to be awaited, for
+/// example by offloading it to a thread pool of an async runtime.
+///
+/// A loader has to implement [`Loader`] too, which is used for hot-reloading.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{Asset, BoxedError, loader::{AsyncLoader, BoxFuture, Loader}};
+/// use std::borrow::Cow;
+///
+/// struct Image(Vec<u8>);
+///
+/// struct ImageLoader;
+///
+/// fn decode(content: &[u8]) -> Result<Image, BoxedError> {
+///     // Some expensive decoding
+///     Ok(Image(content.to_vec()))
+/// }
+///
+/// impl Loader<Image> for ImageLoader {
+///     fn load(content: Cow<[u8]>, _: &str) -> Result<Image, BoxedError> {
+///         decode(&content)
+///     }
+/// }
+///
+/// impl AsyncLoader<Image> for ImageLoader {
+///     fn load<'a>(content: Cow<'a, [u8]>, _: &'a str) -> BoxFuture<'a, Result<Image, BoxedError>> {
+///         // With tokio, you would use `tokio::task::spawn_blocking` here
+///         Box::pin(async move { decode(&content) })
+///     }
+/// }
+///
+/// impl Asset for Image {
+///     const EXTENSION: &'static str = "png";
+///     type Loader = ImageLoader;
+/// }
+/// ```
+///
+/// [`AssetCache::load_async`]: crate::AssetCache::load_async
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub trait AsyncLoader<T> {
+    /// Loads an asset from its raw bytes representation.
+    fn load<'a>(content: Cow<'a, [u8]>, ext: &'a str) -> BoxFuture<'a, Result<T, BoxedError>>;
+}
+
+/// Specifies how an asset is loaded, with access to the cache.
+///
+/// This is the same as [`Loader`], but it also receives the id of the asset
+/// and the [`AssetCache`] it is loaded into, so other assets can be loaded
+/// from it, for example to resolve references to other files.
+///
+/// This trait is used by [`ContextualAsset`]s, see this trait for an example.
+///
+/// [`ContextualAsset`]: crate::ContextualAsset
+pub trait ContextualLoader<T> {
+    /// Loads an asset from its raw bytes representation.
+    fn load<S: Source>(content: Cow<[u8]>, ext: &str, id: &str, cache: &AssetCache<S>) -> Result<T, BoxedError>;
+}
+
+/// Specifies how an asset is loaded by a loader instance.
+///
+/// Unlike [`Loader`], implementors of this trait can carry runtime state, such
+/// as a decryption key or a string interner. Instances are registered in the
+/// cache with [`AssetCache::register_loader`], and used with [`Stateful`].
+pub trait StatefulLoader<T>: Send + Sync + 'static {
+    /// Loads an asset from its raw bytes representation.
+    fn load(&self, content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError>;
 }
 
+/// Loads assets with a loader instance registered in the cache.
+///
+/// This is a [`ContextualLoader`] that fetches the instance of `L` from the
+/// cache and uses it to load the asset. An error is returned if no instance of
+/// `L` was registered with [`AssetCache::register_loader`].
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{
+///     AssetCache, BoxedError, Compound, Error,
+///     asset::{self, ContextualAsset},
+///     loader::{Stateful, StatefulLoader},
+///     source::Source,
+/// };
+/// use std::borrow::Cow;
+///
+/// struct Secret(Vec<u8>);
+///
+/// struct XorLoader {
+///     key: u8,
+/// }
+///
+/// impl StatefulLoader<Secret> for XorLoader {
+///     fn load(&self, content: Cow<[u8]>, _: &str) -> Result<Secret, BoxedError> {
+///         Ok(Secret(content.iter().map(|b| b ^ self.key).collect()))
+///     }
+/// }
+///
+/// impl ContextualAsset for Secret {
+///     const EXTENSION: &'static str = "bin";
+///     type Loader = Stateful<XorLoader>;
+/// }
+///
+/// impl Compound for Secret {
+///     fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Secret, Error> {
+///         asset::load_contextual(cache, id)
+///     }
+/// }
+///
+/// let mut cache = AssetCache::new("assets")?;
+/// cache.register_loader(XorLoader { key: 0x5A });
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Stateful<L>(PhantomData<L>);
+impl<T, L> ContextualLoader<T> for Stateful<L>
+where
+    L: StatefulLoader<T>,
+{
+    fn load<S: Source>(content: Cow<[u8]>, ext: &str, _: &str, cache: &AssetCache<S>) -> Result<T, BoxedError> {
+        match cache.loader::<L>() {
+            Some(loader) => loader.load(content, ext),
+            None => Err(format!("Loader \"{}\" is not registered", std::any::type_name::<L>()).into()),
+        }
+    }
+}
 
 /// Loads assets from another type.
 ///
@@ -142,123 +342,2292 @@ where
     }
 }
 
+/// Loads assets with a [`StreamLoader`].
+///
+/// The content of the file is given to `L` as a reader. When an asset is
+/// loaded by an [`AssetCache`], the file is opened with [`Source::open`], so it
+/// is not entirely read into memory first if the source supports streaming.
+#[derive(Debug)]
+pub struct Streamed<L>(PhantomData<L>);
+impl<T, L> Loader<T> for Streamed<L>
+where
+    L: StreamLoader<T>,
+{
+    #[inline]
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        L::load_stream(&mut &*content, ext)
+    }
+
+    fn _load_from_source<S: Source>(source: &S, id: &str, ext: &str) -> Result<Result<T, BoxedError>, SourceError> {
+        let mut reader = BufReader::new(source.open(id, ext)?);
+        Ok(L::load_stream(&mut reader, ext))
+    }
+}
+
 /// Loads assets from another asset.
 pub type LoadFromAsset<A> = LoadFrom<A, <A as crate::Asset>::Loader>;
 
-/// Loads assets as raw bytes.
+/// Loads assets from another type with a fallible conversion.
 ///
-/// This Loader cannot be used to implement the Asset trait, but can be used by
-/// [`LoadFrom`].
+/// This is the same as [`LoadFrom`], but uses [`TryInto`] instead of [`Into`].
+/// If the conversion fails, its error is returned.
+///
+/// This can be used to validate values when they are loaded.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{Asset, loader::{LoadTryFrom, ParseLoader}};
+/// use std::convert::TryFrom;
+///
+/// struct Percentage(u8);
+///
+/// impl TryFrom<u8> for Percentage {
+///     type Error = String;
+///
+///     fn try_from(n: u8) -> Result<Percentage, String> {
+///         if n <= 100 {
+///             Ok(Percentage(n))
+///         } else {
+///             Err(format!("{} is not a valid percentage", n))
+///         }
+///     }
+/// }
+///
+/// impl Asset for Percentage {
+///     const EXTENSION: &'static str = "txt";
+///     type Loader = LoadTryFrom<u8, ParseLoader>;
+/// }
+/// ```
 #[derive(Debug)]
-pub struct BytesLoader(());
-impl Loader<Vec<u8>> for BytesLoader {
-    fn load(content: Cow<[u8]>, _: &str) -> Result<Vec<u8>, BoxedError> {
-        Ok(content.into_owned())
+pub struct LoadTryFrom<U, L>(PhantomData<(U, L)>);
+impl<T, U, L> Loader<T> for LoadTryFrom<U, L>
+where
+    U: TryInto<T>,
+    U::Error: Into<BoxedError>,
+    L: Loader<U>,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        L::load(content, ext)?.try_into().map_err(Into::into)
     }
 }
-impl Loader<Box<[u8]>> for BytesLoader {
-    fn load(content: Cow<[u8]>, _: &str) -> Result<Box<[u8]>, BoxedError> {
-        Ok(content.into())
-    }
+
+/// A function applied to a loaded value by [`MapLoader`].
+///
+/// As loaders are types, closures cannot be used directly: this trait has to
+/// be implemented on a marker type instead.
+pub trait Mapper<T> {
+    /// The type of the value given to the function.
+    type Input;
+
+    /// Transforms the loaded value.
+    fn map(input: Self::Input) -> T;
 }
 
-/// Loads assets as a String.
+/// Loads assets with a loader, then post-processes them with a function.
 ///
-/// The file content is parsed as UTF-8.
+/// `L` loads a value of type `M::Input`, which is then given to `M::map`.
 ///
-/// This Loader cannot be used to implement the Asset trait, but can be used by
-/// [`LoadFrom`].
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{Asset, loader::{MapLoader, Mapper, RonLoader}};
+///
+/// struct SortedList(Vec<i32>);
+///
+/// struct Sort;
+///
+/// impl Mapper<SortedList> for Sort {
+///     type Input = Vec<i32>;
+///
+///     fn map(mut list: Vec<i32>) -> SortedList {
+///         list.sort_unstable();
+///         SortedList(list)
+///     }
+/// }
+///
+/// impl Asset for SortedList {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = MapLoader<RonLoader, Sort>;
+/// }
+/// # }}
+/// ```
 #[derive(Debug)]
-pub struct StringLoader(());
-impl Loader<String> for StringLoader {
-    fn load(content: Cow<[u8]>, _: &str) -> Result<String, BoxedError> {
-        Ok(String::from_utf8(content.into_owned())?)
+pub struct MapLoader<L, M>(PhantomData<(L, M)>);
+impl<T, L, M> Loader<T> for MapLoader<L, M>
+where
+    M: Mapper<T>,
+    L: Loader<M::Input>,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        Ok(M::map(L::load(content, ext)?))
     }
 }
-impl Loader<Box<str>> for StringLoader {
-    fn load(content: Cow<[u8]>, ext: &str) -> Result<Box<str>, BoxedError> {
-        StringLoader::load(content, ext).map(String::into_boxed_str)
-    }
+
+/// Values that can be checked after being loaded.
+///
+/// This is used by [`ValidatedLoader`] to reject values that are well-formed
+/// but semantically invalid.
+pub trait Validate {
+    /// Checks that the value is valid.
+    fn validate(&self) -> Result<(), BoxedError>;
 }
 
-/// Loads assets that can be parsed with `FromStr`.
+/// Loads assets with a loader, then checks them with [`Validate`].
 ///
-/// Do not use this loader to load `String`s, prefer using [`StringLoader`],
-/// which is more efficient.
+/// If validation fails, the error is returned and the asset is not loaded (or
+/// not reloaded when hot-reloading).
 ///
-/// If you want your custom type to work with this loader, make sure that
-/// `FromStr::Err` meets the requirement.
+/// # Example
 ///
-/// See trait [`Loader`] for more informations.
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{Asset, BoxedError, loader::{RonLoader, Validate, ValidatedLoader}};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Weapon {
+///     min_damage: u32,
+///     max_damage: u32,
+/// }
+///
+/// impl Validate for Weapon {
+///     fn validate(&self) -> Result<(), BoxedError> {
+///         if self.min_damage > self.max_damage {
+///             return Err("min_damage is greater than max_damage".into());
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// impl Asset for Weapon {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = ValidatedLoader<RonLoader>;
+/// }
+/// # }}
+/// ```
 #[derive(Debug)]
-pub struct ParseLoader(());
-impl<T> Loader<T> for ParseLoader
+pub struct ValidatedLoader<L>(PhantomData<L>);
+impl<T, L> Loader<T> for ValidatedLoader<L>
 where
-    T: FromStr,
-    BoxedError: From<<T as FromStr>::Err>
+    T: Validate,
+    L: Loader<T>,
 {
-    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
-        Ok(str::from_utf8(&content)?.parse()?)
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        let value = L::load(content, ext)?;
+        value.validate()?;
+        Ok(value)
     }
 }
 
-macro_rules! serde_loaders {
-    (
-        $(
-            #[doc = $doc:literal]
-            #[cfg(feature = $feature:literal)]
-            struct $name:ident => $fun:path;
-        )*
-    ) => {
-        $(
-            #[doc = $doc]
-            ///
-            /// See trait [`Loader`] for more informations.
-            #[cfg(feature = $feature)]
-            #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
-            #[derive(Debug)]
-            pub struct $name(());
-
-            #[cfg(feature = $feature)]
-            impl<T> Loader<T> for $name
-            where
-                T: for<'de> serde::Deserialize<'de>,
-            {
-                #[inline]
-                fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
-                    Ok($fun(&*content)?)
-                }
-            }
-        )*
+/// Tries to load assets with a loader, and falls back to another one if it
+/// fails.
+///
+/// `L1` is tried first, and `L2` is only used if `L1` returns an error. If both
+/// fail, the returned error contains both errors.
+///
+/// # Example
+///
+/// This is useful during a format migration, to support both formats for the
+/// same type:
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(all(feature = "json", feature = "ron"))] {
+/// use assets_manager::{Asset, loader::{FallbackLoader, JsonLoader, RonLoader}};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl Asset for Point {
+///     const EXTENSIONS: &'static [&'static str] = &["ron", "json"];
+///     type Loader = FallbackLoader<RonLoader, JsonLoader>;
+/// }
+/// # }}
+/// ```
+#[derive(Debug)]
+pub struct FallbackLoader<L1, L2>(PhantomData<(L1, L2)>);
+impl<T, L1, L2> Loader<T> for FallbackLoader<L1, L2>
+where
+    L1: Loader<T>,
+    L2: Loader<T>,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        match L1::load(Cow::Borrowed(&content), ext) {
+            Ok(asset) => Ok(asset),
+            Err(first) => L2::load(content, ext).map_err(|second| {
+                format!("{}, and fallback failed too: {}", first, second).into()
+            }),
+        }
     }
 }
 
-serde_loaders! {
-    /// Loads assets from Bincode encoded files.
-    #[cfg(feature = "bincode")]
-    struct BincodeLoader => serde_bincode::deserialize;
+/// Loaders associated with file extensions.
+///
+/// This is used by [`ExtLoader`] to choose a loader based on the extension of
+/// the file. It is implemented by most loaders of this module for their usual
+/// extensions, and can be overridden with [`WithExtensions`].
+pub trait Extensions {
+    /// The extensions handled by the loader, without the leading dot.
+    const EXTENSIONS: &'static [&'static str];
+}
 
-    /// Loads assets from CBOR encoded files.
-    #[cfg(feature = "cbor")]
-    struct CborLoader => serde_cbor::from_slice;
+/// Associates a loader with other extensions.
+///
+/// Extensions are given by `E`, and `L` is used to load the asset.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::loader::{Extensions, RonLoader, WithExtensions};
+///
+/// struct Config;
+///
+/// impl Extensions for Config {
+///     const EXTENSIONS: &'static [&'static str] = &["cfg"];
+/// }
+///
+/// type ConfigLoader = WithExtensions<Config, RonLoader>;
+/// # }}
+/// ```
+#[derive(Debug)]
+pub struct WithExtensions<E, L>(PhantomData<(E, L)>);
+impl<E: Extensions, L> Extensions for WithExtensions<E, L> {
+    const EXTENSIONS: &'static [&'static str] = E::EXTENSIONS;
+}
+impl<T, E, L> Loader<T> for WithExtensions<E, L>
+where
+    L: Loader<T>,
+{
+    #[inline]
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        L::load(content, ext)
+    }
+}
 
-    /// Loads assets from JSON files.
-    #[cfg(feature = "json")]
-    struct JsonLoader => serde_json::from_slice;
+/// Chooses a loader based on the extension of the file.
+///
+/// `L` is a tuple of loaders implementing [`Extensions`]. The first one that
+/// handles the extension of the file is used. If none does, an error is
+/// returned.
+///
+/// Note that the asset still has to declare all extensions in
+/// `Asset::EXTENSIONS`.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(all(feature = "json", feature = "ron"))] {
+/// use assets_manager::{Asset, loader::{ExtLoader, JsonLoader, RonLoader}};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl Asset for Point {
+///     const EXTENSIONS: &'static [&'static str] = &["ron", "json"];
+///     type Loader = ExtLoader<(RonLoader, JsonLoader)>;
+/// }
+/// # }}
+/// ```
+#[derive(Debug)]
+pub struct ExtLoader<L>(PhantomData<L>);
 
-    /// Loads assets from MessagePack files.
-    #[cfg(feature = "msgpack")]
-    struct MessagePackLoader => serde_msgpack::decode::from_read;
+macro_rules! ext_loader_tuples {
+    ( $( ( $($l:ident),+ ) )* ) => {
+        $(
+            impl<T, $($l),+> Loader<T> for ExtLoader<($($l,)+)>
+            where
+                $( $l: Loader<T> + Extensions, )+
+            {
+                fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+                    $(
+                        if <$l as Extensions>::EXTENSIONS.contains(&ext) {
+                            return $l::load(content, ext);
+                        }
+                    )+
+                    Err(format!("No loader for extension \"{}\"", ext).into())
+                }
+            }
+        )*
+    }
+}
 
-    /// Loads assets from RON files.
-    #[cfg(feature = "ron")]
-    struct RonLoader => serde_ron::de::from_bytes;
+ext_loader_tuples! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+}
 
-    /// Loads assets from TOML files.
-    #[cfg(feature = "toml")]
-    struct TomlLoader => serde_toml::de::from_slice;
+macro_rules! loader_extensions {
+    ( $( $(#[cfg(feature = $feature:literal)])? $name:ident => [$($ext:literal),*]; )* ) => {
+        $(
+            $(#[cfg(feature = $feature)])?
+            impl Extensions for $name {
+                const EXTENSIONS: &'static [&'static str] = &[$($ext),*];
+            }
+        )*
+    }
+}
 
-    /// Loads assets from YAML files.
+loader_extensions! {
+    StringLoader => ["txt"];
+    #[cfg(feature = "cbor")]
+    CborLoader => ["cbor"];
+    #[cfg(feature = "json")]
+    JsonLoader => ["json"];
+    #[cfg(feature = "msgpack")]
+    MessagePackLoader => ["msgpack"];
+    #[cfg(feature = "ron")]
+    RonLoader => ["ron"];
+    #[cfg(feature = "toml")]
+    TomlLoader => ["toml"];
     #[cfg(feature = "yaml")]
-    struct YamlLoader => serde_yaml::from_slice;
+    YamlLoader => ["yaml", "yml"];
+    #[cfg(feature = "kdl")]
+    KdlLoader => ["kdl"];
+    #[cfg(feature = "fluent")]
+    FluentLoader => ["ftl"];
+    #[cfg(feature = "markdown")]
+    MarkdownLoader => ["md", "markdown"];
+}
+
+/// Loaders that can recognize their format from the content of a file.
+///
+/// This is used by [`SniffLoader`] to choose a loader based on magic bytes.
+pub trait Sniff {
+    /// Returns `true` if the content looks like something this loader can
+    /// load.
+    ///
+    /// This should be cheap, so it should only look at the first bytes.
+    fn sniff(content: &[u8]) -> bool;
+}
+
+/// Chooses a loader based on the content of the file.
+///
+/// `L` is a tuple of loaders implementing [`Sniff`]. The first one that
+/// recognizes the content of the file is used. If none does, an error is
+/// returned.
+///
+/// This is useful to mix files with the same extension but different formats,
+/// for example compressed and plain ones.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(all(feature = "gzip", feature = "json"))] {
+/// use assets_manager::{Asset, loader::{GzLoader, JsonLoader, SniffLoader}};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Level {
+///     name: String,
+///     tiles: Vec<u32>,
+/// }
+///
+/// impl Asset for Level {
+///     const EXTENSION: &'static str = "json";
+///     type Loader = SniffLoader<(GzLoader<JsonLoader>, JsonLoader)>;
+/// }
+/// # }}
+/// ```
+#[derive(Debug)]
+pub struct SniffLoader<L>(PhantomData<L>);
+
+macro_rules! sniff_loader_tuples {
+    ( $( ( $($l:ident),+ ) )* ) => {
+        $(
+            impl<T, $($l),+> Loader<T> for SniffLoader<($($l,)+)>
+            where
+                $( $l: Loader<T> + Sniff, )+
+            {
+                fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+                    $(
+                        if $l::sniff(&content) {
+                            return $l::load(content, ext);
+                        }
+                    )+
+                    Err("Unrecognized file format".into())
+                }
+            }
+        )*
+    }
+}
+
+sniff_loader_tuples! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+}
+
+/// Returns the first non-whitespace byte of a text, skipping the BOM.
+#[cfg(any(feature = "json", feature = "ron"))]
+fn first_char(content: &[u8]) -> Option<u8> {
+    let content = if content.starts_with(b"\xEF\xBB\xBF") {
+        &content[3..]
+    } else {
+        content
+    };
+    content.iter().copied().find(|b| !b.is_ascii_whitespace())
+}
+
+impl Sniff for BytesLoader {
+    #[inline]
+    fn sniff(_: &[u8]) -> bool {
+        true
+    }
+}
+
+impl Sniff for StringLoader {
+    #[inline]
+    fn sniff(content: &[u8]) -> bool {
+        str::from_utf8(content).is_ok()
+    }
+}
+
+#[cfg(feature = "json")]
+impl Sniff for JsonLoader {
+    #[inline]
+    fn sniff(content: &[u8]) -> bool {
+        matches!(first_char(content), Some(b'{') | Some(b'['))
+    }
+}
+
+#[cfg(feature = "ron")]
+impl Sniff for RonLoader {
+    #[inline]
+    fn sniff(content: &[u8]) -> bool {
+        first_char(content) == Some(b'(')
+    }
+}
+
+macro_rules! magic_bytes {
+    ( $( #[cfg(feature = $feature:literal)] $name:ident => $magic:literal; )* ) => {
+        $(
+            #[cfg(feature = $feature)]
+            impl<L> Sniff for $name<L> {
+                #[inline]
+                fn sniff(content: &[u8]) -> bool {
+                    content.starts_with($magic)
+                }
+            }
+        )*
+    }
+}
+
+magic_bytes! {
+    #[cfg(feature = "gzip")]
+    GzLoader => b"\x1F\x8B";
+    #[cfg(feature = "lz4")]
+    Lz4Loader => b"\x04\x22\x4D\x18";
+    #[cfg(feature = "zstd")]
+    ZstdLoader => b"\x28\xB5\x2F\xFD";
+}
+
+macro_rules! forward_loader_traits {
+    ( $( $name:ident<$($p:ident),+> => $l:ident; )* ) => {
+        $(
+            impl<$($p),+> Extensions for $name<$($p),+>
+            where
+                $l: Extensions,
+            {
+                const EXTENSIONS: &'static [&'static str] = $l::EXTENSIONS;
+            }
+
+            impl<$($p),+> Sniff for $name<$($p),+>
+            where
+                $l: Sniff,
+            {
+                #[inline]
+                fn sniff(content: &[u8]) -> bool {
+                    $l::sniff(content)
+                }
+            }
+        )*
+    }
+}
+
+forward_loader_traits! {
+    LoadFrom<U, L> => L;
+    LoadTryFrom<U, L> => L;
+    MapLoader<L, M> => L;
+    ValidatedLoader<L> => L;
+}
+
+/// Specifies what to do with errors ignored by [`LoadOrDefault`].
+pub trait ErrorReporter {
+    /// Reports the error.
+    fn report(error: &BoxedError);
+}
+
+/// An [`ErrorReporter`] that silently ignores errors.
+#[derive(Debug)]
+pub struct Ignore(());
+impl ErrorReporter for Ignore {
+    #[inline]
+    fn report(_: &BoxedError) {}
+}
+
+/// An [`ErrorReporter`] that logs errors as warnings.
+#[cfg(feature = "log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+#[derive(Debug)]
+pub struct Log(());
+
+#[cfg(feature = "log")]
+impl ErrorReporter for Log {
+    fn report(error: &BoxedError) {
+        log::warn!("Error loading asset, using default value: {}", error);
+    }
+}
+
+/// Loads assets with a loader, and uses the default value if it fails.
+///
+/// If the file is empty or if `L` fails to load the asset, `T::default()` is
+/// returned instead of an error. Errors are given to `R`, which ignores them by
+/// default.
+///
+/// This is useful for optional overrides, such as a configuration file that
+/// may be left blank.
+///
+/// Note that this loader is not used when the file is missing. If you want a
+/// default value in this case too, see [`Asset::default_value`].
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{Asset, loader::{LoadOrDefault, RonLoader}};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Default)]
+/// struct Overrides {
+///     volume: Option<f32>,
+/// }
+///
+/// impl Asset for Overrides {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = LoadOrDefault<RonLoader>;
+/// }
+/// # }}
+/// ```
+///
+/// [`Asset::default_value`]: crate::Asset::default_value
+#[derive(Debug)]
+pub struct LoadOrDefault<L, R = Ignore>(PhantomData<(L, R)>);
+impl<T, L, R> Loader<T> for LoadOrDefault<L, R>
+where
+    T: Default,
+    L: Loader<T>,
+    R: ErrorReporter,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        if content.is_empty() {
+            return Ok(T::default());
+        }
+
+        match L::load(content, ext) {
+            Ok(asset) => Ok(asset),
+            Err(err) => {
+                R::report(&err);
+                Ok(T::default())
+            },
+        }
+    }
+}
+
+/// Loads assets as raw bytes.
+///
+/// This Loader cannot be used to implement the Asset trait, but can be used by
+/// [`LoadFrom`].
+#[derive(Debug)]
+pub struct BytesLoader(());
+impl Loader<Vec<u8>> for BytesLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Vec<u8>, BoxedError> {
+        Ok(content.into_owned())
+    }
+}
+impl Loader<Box<[u8]>> for BytesLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Box<[u8]>, BoxedError> {
+        Ok(content.into())
+    }
+}
+
+/// Loads assets as `bytes::Bytes`.
+///
+/// This is a convenience conversion for code that works with `bytes::Bytes`,
+/// which can be cheaply cloned and sliced afterwards. It does not avoid
+/// copying the file: when the source lends its content (for example
+/// `Embedded`), the bytes are copied into a new buffer, the same way as with
+/// [`BytesLoader`]. A buffer owned by the source is reused as is.
+///
+/// This Loader cannot be used to implement the Asset trait, but can be used by
+/// [`LoadFrom`].
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+#[derive(Debug)]
+pub struct IntoBytesLoader(());
+
+#[cfg(feature = "bytes")]
+impl Loader<bytes_crate::Bytes> for IntoBytesLoader {
+    #[inline]
+    fn load(content: Cow<[u8]>, _: &str) -> Result<bytes_crate::Bytes, BoxedError> {
+        Ok(match content {
+            Cow::Owned(vec) => bytes_crate::Bytes::from(vec),
+            Cow::Borrowed(slice) => bytes_crate::Bytes::copy_from_slice(slice),
+        })
+    }
+}
+
+/// Loads plain old data types from their raw binary representation.
+///
+/// The file must contain exactly the bytes of a value of type `T`, which must
+/// implement `bytemuck::Pod`. This is typically a `#[repr(C)]` struct. The
+/// content does not need to be aligned.
+///
+/// To load a list of values, see [`PodSliceLoader`].
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+#[derive(Debug)]
+pub struct PodLoader(());
+
+#[cfg(feature = "bytemuck")]
+impl<T> Loader<T> for PodLoader
+where
+    T: bytemuck_crate::Pod,
+{
+    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+        let size = std::mem::size_of::<T>();
+        if content.len() != size {
+            return Err(format!("Invalid size: expected {} bytes, found {}", size, content.len()).into());
+        }
+
+        Ok(bytemuck_crate::pod_read_unaligned(&content))
+    }
+}
+
+/// Loads lists of plain old data types from their raw binary representation.
+///
+/// The file must contain the bytes of contiguous values of type `T`, which
+/// must implement `bytemuck::Pod`, so its size must be a multiple of the size
+/// of `T`. The content does not need to be aligned.
+///
+/// This is useful for heightmaps or baked lookup tables.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "bytemuck")] {
+/// use assets_manager::{Asset, loader::{LoadFrom, PodSliceLoader}};
+///
+/// struct Heightmap(Vec<f32>);
+///
+/// impl From<Vec<f32>> for Heightmap {
+///     fn from(heights: Vec<f32>) -> Heightmap {
+///         Heightmap(heights)
+///     }
+/// }
+///
+/// impl Asset for Heightmap {
+///     const EXTENSION: &'static str = "raw";
+///     type Loader = LoadFrom<Vec<f32>, PodSliceLoader>;
+/// }
+/// # }}
+/// ```
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+#[derive(Debug)]
+pub struct PodSliceLoader(());
+
+#[cfg(feature = "bytemuck")]
+impl<T> Loader<Vec<T>> for PodSliceLoader
+where
+    T: bytemuck_crate::Pod,
+{
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Vec<T>, BoxedError> {
+        let size = std::mem::size_of::<T>();
+        if size == 0 {
+            return Err("Cannot load zero-sized types".into());
+        }
+        let (count, remainder) = (content.len() / size, content.len() % size);
+        if remainder != 0 {
+            return Err(format!("Invalid size: {} bytes is not a multiple of {}", content.len(), size).into());
+        }
+
+        let mut values = vec![T::zeroed(); count];
+        bytemuck_crate::cast_slice_mut(&mut values).copy_from_slice(&content);
+        Ok(values)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<T> Loader<Box<[T]>> for PodSliceLoader
+where
+    T: bytemuck_crate::Pod,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Box<[T]>, BoxedError> {
+        PodSliceLoader::load(content, ext).map(Vec::into_boxed_slice)
+    }
+}
+
+/// Loads assets as a String.
+///
+/// The file content is parsed as UTF-8.
+///
+/// This Loader cannot be used to implement the Asset trait, but can be used by
+/// [`LoadFrom`].
+#[derive(Debug)]
+pub struct StringLoader(());
+impl Loader<String> for StringLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<String, BoxedError> {
+        Ok(String::from_utf8(content.into_owned())?)
+    }
+}
+impl Loader<Box<str>> for StringLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Box<str>, BoxedError> {
+        StringLoader::load(content, ext).map(String::into_boxed_str)
+    }
+}
+
+impl StreamLoader<Vec<u8>> for BytesLoader {
+    fn load_stream(reader: &mut dyn BufRead, _: &str) -> Result<Vec<u8>, BoxedError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl StreamLoader<String> for StringLoader {
+    fn load_stream(reader: &mut dyn BufRead, _: &str) -> Result<String, BoxedError> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string)?;
+        Ok(string)
+    }
+}
+
+/// Loads assets as a String, detecting the text encoding.
+///
+/// Unlike [`StringLoader`], this loader handles byte order marks: a UTF-8 BOM
+/// is stripped, and files starting with a UTF-16 BOM (little or big endian) are
+/// transcoded. Files without BOM are parsed as UTF-8.
+///
+/// This is useful for files edited with tools that add BOMs.
+///
+/// This Loader cannot be used to implement the Asset trait, but can be used by
+/// [`LoadFrom`].
+#[derive(Debug)]
+pub struct AnyTextLoader(());
+impl Loader<String> for AnyTextLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<String, BoxedError> {
+        if content.starts_with(b"\xEF\xBB\xBF") {
+            Ok(str::from_utf8(&content[3..])?.to_owned())
+        } else if content.starts_with(b"\xFF\xFE") {
+            decode_utf16(&content[2..], u16::from_le_bytes)
+        } else if content.starts_with(b"\xFE\xFF") {
+            decode_utf16(&content[2..], u16::from_be_bytes)
+        } else {
+            StringLoader::load(content, ext)
+        }
+    }
+}
+impl Loader<Box<str>> for AnyTextLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<Box<str>, BoxedError> {
+        AnyTextLoader::load(content, ext).map(String::into_boxed_str)
+    }
+}
+
+fn decode_utf16(content: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String, BoxedError> {
+    let chunks = content.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return Err("Invalid UTF-16: odd number of bytes".into());
+    }
+
+    let units = chunks.map(|c| from_bytes([c[0], c[1]]));
+    Ok(std::char::decode_utf16(units).collect::<Result<String, _>>()?)
+}
+
+/// Loads assets that can be parsed with `FromStr`.
+///
+/// Do not use this loader to load `String`s, prefer using [`StringLoader`],
+/// which is more efficient.
+///
+/// If you want your custom type to work with this loader, make sure that
+/// `FromStr::Err` meets the requirement.
+///
+/// See trait [`Loader`] for more informations.
+#[derive(Debug)]
+pub struct ParseLoader(());
+impl<T> Loader<T> for ParseLoader
+where
+    T: FromStr,
+    BoxedError: From<<T as FromStr>::Err>
+{
+    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+        Ok(str::from_utf8(&content)?.parse()?)
+    }
+}
+
+macro_rules! decoding_loaders {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            #[cfg(feature = $feature:literal)]
+            struct $name:ident => $fun:path;
+        )*
+    ) => {
+        $(
+            $( #[doc = $doc] )*
+            ///
+            /// See trait [`Loader`] for more informations.
+            #[cfg(feature = $feature)]
+            #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
+            #[derive(Debug)]
+            pub struct $name<L>(PhantomData<L>);
+
+            #[cfg(feature = $feature)]
+            impl<T, L> Loader<T> for $name<L>
+            where
+                L: Loader<T>,
+            {
+                fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+                    let decompressed = $fun(&*content)?;
+                    L::load(decompressed.into(), ext)
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn gzip_decompress(content: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decompressed = Vec::new();
+    flate2::read::MultiGzDecoder::new(content).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(feature = "gzip")]
+impl<T, L> StreamLoader<T> for GzLoader<L>
+where
+    L: StreamLoader<T>,
+{
+    fn load_stream(reader: &mut dyn BufRead, ext: &str) -> Result<T, BoxedError> {
+        let decoder = flate2::bufread::MultiGzDecoder::new(reader);
+        L::load_stream(&mut std::io::BufReader::new(decoder), ext)
+    }
+}
+
+#[cfg(feature = "brotli")]
+fn brotli_decompress(content: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decompressed = Vec::new();
+    brotli_crate::Decompressor::new(content, 4096).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(feature = "lz4")]
+fn lz4_decompress(content: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decompressed = Vec::new();
+    lz4_flex::frame::FrameDecoder::new(content).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(feature = "base64")]
+fn base64_decode(content: &[u8]) -> Result<Vec<u8>, base64_crate::DecodeError> {
+    use base64_crate::Engine;
+
+    let content: Vec<u8> = content.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+    base64_crate::engine::general_purpose::STANDARD.decode(content)
+}
+
+decoding_loaders! {
+    /// Loads Base64-encoded assets.
+    ///
+    /// The content is decoded, then the loader `L` is used to load the asset
+    /// from the decoded bytes. Whitespace, such as line breaks, is ignored.
+    #[cfg(feature = "base64")]
+    struct Base64Loader => base64_decode;
+
+    /// Loads Brotli-compressed assets.
+    ///
+    /// The content is decompressed, then the loader `L` is used to load the
+    /// asset from the decompressed bytes.
+    ///
+    /// This is especially useful for WebAssembly builds, where assets fetched
+    /// from a web server are often compressed with Brotli.
+    #[cfg(feature = "brotli")]
+    struct BrotliLoader => brotli_decompress;
+
+    /// Loads gzip-compressed assets.
+    ///
+    /// The content is decompressed, then the loader `L` is used to load the
+    /// asset from the decompressed bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # cfg_if::cfg_if! { if #[cfg(all(feature = "gzip", feature = "json"))] {
+    /// use assets_manager::{Asset, loader};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Level {
+    ///     tiles: Vec<u32>,
+    /// }
+    ///
+    /// impl Asset for Level {
+    ///     const EXTENSION: &'static str = "gz";
+    ///     type Loader = loader::GzLoader<loader::JsonLoader>;
+    /// }
+    /// # }}
+    /// ```
+    #[cfg(feature = "gzip")]
+    struct GzLoader => gzip_decompress;
+
+    /// Loads LZ4-compressed assets, using the LZ4 frame format.
+    ///
+    /// The content is decompressed, then the loader `L` is used to load the
+    /// asset from the decompressed bytes.
+    ///
+    /// LZ4 compresses less than other formats, but is much faster to
+    /// decompress.
+    #[cfg(feature = "lz4")]
+    struct Lz4Loader => lz4_decompress;
+
+    /// Loads Zstandard-compressed assets.
+    ///
+    /// The content is decompressed, then the loader `L` is used to load the
+    /// asset from the decompressed bytes.
+    #[cfg(feature = "zstd")]
+    struct ZstdLoader => zstd_crate::stream::decode_all;
+}
+
+/// Provides the key used by an [`EncryptedLoader`].
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::loader::DecryptionKey;
+///
+/// struct GameKey;
+///
+/// impl DecryptionKey for GameKey {
+///     fn key() -> [u8; 32] {
+///         [
+///             0x3c, 0x91, 0x5e, 0x0a, 0xd2, 0x47, 0x18, 0xbb, 0x6f, 0x20, 0x84, 0xe9, 0x33, 0x7d, 0xc5, 0x02,
+///             0x9a, 0x61, 0xf8, 0x4e, 0x15, 0xb0, 0x27, 0xdc, 0x73, 0x0e, 0xa9, 0x56, 0xcb, 0x38, 0xe4, 0x1f,
+///         ]
+///     }
+/// }
+/// ```
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+pub trait DecryptionKey {
+    /// Returns the 256-bit key used to decrypt assets.
+    fn key() -> [u8; 32];
+}
+
+/// Loads encrypted assets.
+///
+/// Assets are encrypted with ChaCha20-Poly1305, with the key given by `K`. The
+/// content is decrypted, then the loader `L` is used to load the asset from
+/// the decrypted bytes. If the content was not encrypted with the right key or
+/// if it was tampered with, loading fails.
+///
+/// Encrypted files are made of the 12-byte nonce followed by the encrypted data
+/// and the 16-byte authentication tag. A nonce must never be used twice with
+/// the same key.
+///
+/// Note that the key has to be stored in the program to decrypt assets, so this
+/// can only be used as obfuscation.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+#[derive(Debug)]
+pub struct EncryptedLoader<K, L>(PhantomData<(K, L)>);
+
+#[cfg(feature = "encryption")]
+impl<T, K, L> Loader<T> for EncryptedLoader<K, L>
+where
+    K: DecryptionKey,
+    L: Loader<T>,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        use chacha20poly1305::{aead::{Aead, KeyInit}, ChaCha20Poly1305, Nonce};
+
+        const NONCE_LEN: usize = 12;
+
+        if content.len() < NONCE_LEN {
+            return Err("Encrypted asset is too short".into());
+        }
+        let (nonce, encrypted) = content.split_at(NONCE_LEN);
+
+        let cipher = ChaCha20Poly1305::new(&K::key().into());
+        let decrypted = cipher.decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|_| "Failed to decrypt asset")?;
+
+        L::load(decrypted.into(), ext)
+    }
+}
+
+/// A checksum algorithm, used by [`ChecksumLoader`].
+#[cfg(feature = "checksum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+pub trait Checksum {
+    /// The name of the algorithm, used in error messages.
+    const NAME: &'static str;
+
+    /// The length of the checksum, in bytes.
+    const LEN: usize;
+
+    /// Computes the checksum of the given data.
+    ///
+    /// The returned `Vec` must be `LEN` bytes long.
+    fn compute(data: &[u8]) -> Vec<u8>;
+}
+
+/// The CRC32 checksum algorithm, stored in big-endian.
+#[cfg(feature = "checksum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+#[derive(Debug)]
+pub struct Crc32(());
+
+#[cfg(feature = "checksum")]
+impl Checksum for Crc32 {
+    const NAME: &'static str = "CRC32";
+    const LEN: usize = 4;
+
+    fn compute(data: &[u8]) -> Vec<u8> {
+        crc32fast::hash(data).to_be_bytes().to_vec()
+    }
+}
+
+/// The SHA-256 hash algorithm.
+#[cfg(feature = "checksum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+#[derive(Debug)]
+pub struct Sha256(());
+
+#[cfg(feature = "checksum")]
+impl Checksum for Sha256 {
+    const NAME: &'static str = "SHA-256";
+    const LEN: usize = 32;
+
+    fn compute(data: &[u8]) -> Vec<u8> {
+        use sha2::Digest;
+        sha2::Sha256::digest(data).to_vec()
+    }
+}
+
+/// Verifies the checksum of assets before loading them.
+///
+/// The content must start with a checksum of the rest of the data, computed
+/// with the algorithm `C` (eg [`Crc32`] or [`Sha256`]). If it does not match,
+/// loading fails with a descriptive error. Else, the loader `L` is used to
+/// load the asset from the data that follows the checksum.
+///
+/// This is useful to detect corrupted files, such as broken downloads, but it
+/// does not protect against malicious modifications.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "checksum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "checksum")))]
+#[derive(Debug)]
+pub struct ChecksumLoader<C, L>(PhantomData<(C, L)>);
+
+#[cfg(feature = "checksum")]
+impl<T, C, L> Loader<T> for ChecksumLoader<C, L>
+where
+    C: Checksum,
+    L: Loader<T>,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        fn hex(bytes: &[u8]) -> String {
+            bytes.iter().map(|b| format!("{:02x}", b)).collect()
+        }
+
+        if content.len() < C::LEN {
+            return Err(format!("Missing {} checksum", C::NAME).into());
+        }
+
+        let (expected, data) = content.split_at(C::LEN);
+        let found = C::compute(data);
+
+        if found != expected {
+            let msg = format!("{} checksum mismatch: expected {}, found {}", C::NAME, hex(expected), hex(&found));
+            return Err(msg.into());
+        }
+
+        L::load(skip_bytes(content, C::LEN), ext)
+    }
+}
+
+/// Provides the public key used by a [`SignedLoader`].
+#[cfg(feature = "signature")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signature")))]
+pub trait PublicKey {
+    /// Returns the Ed25519 public key used to verify signatures.
+    fn public_key() -> [u8; 32];
+}
+
+/// Verifies the signature of assets before loading them.
+///
+/// The content must start with the 64-byte Ed25519 signature of the rest of
+/// the data, made with the private key matching the public key given by `K`.
+/// If the signature is invalid, loading fails. Else, the loader `L` is used to
+/// load the asset from the data that follows the signature.
+///
+/// This enables you to accept third-party content, such as mods, while
+/// rejecting tampered files.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "signature")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signature")))]
+#[derive(Debug)]
+pub struct SignedLoader<K, L>(PhantomData<(K, L)>);
+
+#[cfg(feature = "signature")]
+impl<T, K, L> Loader<T> for SignedLoader<K, L>
+where
+    K: PublicKey,
+    L: Loader<T>,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        use ed25519_dalek::{Signature, VerifyingKey, SIGNATURE_LENGTH};
+        use std::convert::TryInto;
+
+        if content.len() < SIGNATURE_LENGTH {
+            return Err("Missing signature".into());
+        }
+
+        let (signature, data) = content.split_at(SIGNATURE_LENGTH);
+        let signature = Signature::from_bytes(signature.try_into()?);
+        let key = VerifyingKey::from_bytes(&K::public_key())?;
+        key.verify_strict(data, &signature)?;
+
+        L::load(skip_bytes(content, SIGNATURE_LENGTH), ext)
+    }
+}
+
+/// Removes the first `n` bytes of the content.
+#[cfg(any(feature = "checksum", feature = "signature"))]
+fn skip_bytes(content: Cow<[u8]>, n: usize) -> Cow<[u8]> {
+    match content {
+        Cow::Borrowed(content) => Cow::Borrowed(&content[n..]),
+        Cow::Owned(mut content) => {
+            content.drain(..n);
+            Cow::Owned(content)
+        },
+    }
+}
+
+macro_rules! serde_loaders {
+    (
+        $(
+            #[doc = $doc:literal]
+            #[cfg(feature = $feature:literal)]
+            struct $name:ident => $fun:path;
+        )*
+    ) => {
+        $(
+            #[doc = $doc]
+            ///
+            /// See trait [`Loader`] for more informations.
+            #[cfg(feature = $feature)]
+            #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
+            #[derive(Debug)]
+            pub struct $name(());
+
+            #[cfg(feature = $feature)]
+            impl<T> Loader<T> for $name
+            where
+                T: for<'de> serde::Deserialize<'de>,
+            {
+                #[inline]
+                fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+                    Ok($fun(&*content)?)
+                }
+            }
+        )*
+    }
+}
+
+serde_loaders! {
+    /// Loads assets from Bincode encoded files.
+    #[cfg(feature = "bincode")]
+    struct BincodeLoader => serde_bincode::deserialize;
+
+    /// Loads assets from CBOR encoded files.
+    #[cfg(feature = "cbor")]
+    struct CborLoader => serde_cbor::from_slice;
+
+    /// Loads assets from JSON files.
+    #[cfg(feature = "json")]
+    struct JsonLoader => serde_json::from_slice;
+
+    /// Loads assets from MessagePack files.
+    #[cfg(feature = "msgpack")]
+    struct MessagePackLoader => serde_msgpack::decode::from_read;
+
+    /// Loads assets from Postcard encoded files.
+    #[cfg(feature = "postcard")]
+    struct PostcardLoader => serde_postcard::from_bytes;
+
+    /// Loads assets from RON files.
+    #[cfg(feature = "ron")]
+    struct RonLoader => serde_ron::de::from_bytes;
+
+    /// Loads assets from TOML files.
+    #[cfg(feature = "toml")]
+    struct TomlLoader => serde_toml::de::from_slice;
+
+    /// Loads assets from YAML files.
+    #[cfg(feature = "yaml")]
+    struct YamlLoader => serde_yaml::from_slice;
+}
+
+macro_rules! serde_stream_loaders {
+    ( $( #[cfg(feature = $feature:literal)] $name:ident => $fun:path; )* ) => {
+        $(
+            #[cfg(feature = $feature)]
+            impl<T> StreamLoader<T> for $name
+            where
+                T: for<'de> serde::Deserialize<'de>,
+            {
+                #[inline]
+                fn load_stream(reader: &mut dyn BufRead, _: &str) -> Result<T, BoxedError> {
+                    Ok($fun(reader)?)
+                }
+            }
+        )*
+    }
+}
+
+serde_stream_loaders! {
+    #[cfg(feature = "bincode")]
+    BincodeLoader => serde_bincode::deserialize_from;
+    #[cfg(feature = "cbor")]
+    CborLoader => serde_cbor::from_reader;
+    #[cfg(feature = "json")]
+    JsonLoader => serde_json::from_reader;
+    #[cfg(feature = "msgpack")]
+    MessagePackLoader => serde_msgpack::decode::from_read;
+    #[cfg(feature = "ron")]
+    RonLoader => serde_ron::de::from_reader;
+    #[cfg(feature = "yaml")]
+    YamlLoader => serde_yaml::from_reader;
+}
+
+/// Self-describing documents that can be migrated before being deserialized.
+///
+/// This is used by [`VersionedLoader`], and is implemented for the generic
+/// values of JSON, RON, TOML and YAML.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub trait VersionedDocument: Sized {
+    /// Reads the version of the document from a top-level field.
+    ///
+    /// Returns `Ok(None)` if the field does not exist.
+    fn version(&self, field: &str) -> Result<Option<u64>, BoxedError>;
+
+    /// Deserializes the document into a value.
+    fn deserialize<T: serde::de::DeserializeOwned>(self) -> Result<T, BoxedError>;
+}
+
+#[cfg(feature = "json")]
+impl VersionedDocument for serde_json::Value {
+    fn version(&self, field: &str) -> Result<Option<u64>, BoxedError> {
+        match self.get(field) {
+            Some(version) => Ok(Some(version.as_u64().ok_or("Invalid version")?)),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn deserialize<T: serde::de::DeserializeOwned>(self) -> Result<T, BoxedError> {
+        Ok(serde_json::from_value(self)?)
+    }
+}
+
+#[cfg(feature = "ron")]
+impl VersionedDocument for serde_ron::Value {
+    fn version(&self, field: &str) -> Result<Option<u64>, BoxedError> {
+        let map = match self {
+            serde_ron::Value::Map(map) => map,
+            _ => return Ok(None),
+        };
+
+        let version = map.iter().find(|(key, _)| match key {
+            serde_ron::Value::String(key) => key == field,
+            _ => false,
+        });
+
+        match version {
+            Some((_, serde_ron::Value::Number(serde_ron::Number::Integer(n)))) => Ok(Some((*n).try_into()?)),
+            Some(_) => Err("Invalid version".into()),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn deserialize<T: serde::de::DeserializeOwned>(self) -> Result<T, BoxedError> {
+        Ok(self.into_rust()?)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl VersionedDocument for serde_toml::Value {
+    fn version(&self, field: &str) -> Result<Option<u64>, BoxedError> {
+        match self.get(field) {
+            Some(version) => Ok(Some(version.as_integer().ok_or("Invalid version")?.try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn deserialize<T: serde::de::DeserializeOwned>(self) -> Result<T, BoxedError> {
+        Ok(self.try_into()?)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl VersionedDocument for serde_yaml::Value {
+    fn version(&self, field: &str) -> Result<Option<u64>, BoxedError> {
+        match self.get(field) {
+            Some(version) => Ok(Some(version.as_u64().ok_or("Invalid version")?)),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn deserialize<T: serde::de::DeserializeOwned>(self) -> Result<T, BoxedError> {
+        Ok(serde_yaml::from_value(self)?)
+    }
+}
+
+/// A migration of a [`VersionedDocument`] to the next version.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub type Migration<D> = fn(&mut D) -> Result<(), BoxedError>;
+
+/// Types whose file format changed over time.
+///
+/// See [`VersionedLoader`] for more informations.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub trait Versioned: serde::de::DeserializeOwned {
+    /// The generic document type of the format, eg `serde_json::Value`.
+    type Document: VersionedDocument + 'static;
+
+    /// The name of the field that contains the version of the file.
+    const VERSION_FIELD: &'static str = "version";
+
+    /// The migrations between versions.
+    ///
+    /// `MIGRATIONS[n]` upgrades a document from version `n` to version `n + 1`,
+    /// so the current version is the number of migrations.
+    const MIGRATIONS: &'static [Migration<Self::Document>];
+}
+
+/// Loads assets saved by older versions of a program.
+///
+/// `L` loads the file as a [`VersionedDocument`], which version is read from
+/// the field [`Versioned::VERSION_FIELD`]. Files without this field are
+/// considered to be version 0. Then all migrations from this version to the
+/// current one are applied in order, and the document is deserialized.
+///
+/// Files with a version greater than the current one are rejected.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "json")] {
+/// use assets_manager::{Asset, BoxedError, loader::{JsonLoader, Migration, Versioned, VersionedLoader}};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Monster {
+///     name: String,
+///     health: u32,
+/// }
+///
+/// // Version 1 renamed `hp` to `health`
+/// fn rename_hp(doc: &mut serde_json::Value) -> Result<(), BoxedError> {
+///     let map = doc.as_object_mut().ok_or("expected an object")?;
+///     if let Some(hp) = map.remove("hp") {
+///         map.insert("health".into(), hp);
+///     }
+///     Ok(())
+/// }
+///
+/// impl Versioned for Monster {
+///     type Document = serde_json::Value;
+///     const MIGRATIONS: &'static [Migration<serde_json::Value>] = &[rename_hp];
+/// }
+///
+/// impl Asset for Monster {
+///     const EXTENSION: &'static str = "json";
+///     type Loader = VersionedLoader<JsonLoader>;
+/// }
+/// # }}
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug)]
+pub struct VersionedLoader<L>(PhantomData<L>);
+
+#[cfg(feature = "serde")]
+impl<T, L> Loader<T> for VersionedLoader<L>
+where
+    T: Versioned,
+    L: Loader<T::Document>,
+{
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<T, BoxedError> {
+        let mut doc = L::load(content, ext)?;
+
+        let current = T::MIGRATIONS.len();
+        let version = doc.version(T::VERSION_FIELD)?.unwrap_or(0);
+        let version = match version.try_into() {
+            Ok(version) if version <= current => version,
+            _ => return Err(format!("Unsupported version {} (current is {})", version, current).into()),
+        };
+
+        for migrate in &T::MIGRATIONS[version..] {
+            migrate(&mut doc)?;
+        }
+
+        doc.deserialize()
+    }
+}
+
+/// Loads all documents of a multi-document YAML stream.
+///
+/// Unlike [`YamlLoader`], which only reads a single document, this loader reads
+/// every document separated by `---` and returns them in order.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "yaml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+#[derive(Debug)]
+pub struct YamlMultiLoader(());
+
+#[cfg(feature = "yaml")]
+impl<T> Loader<Vec<T>> for YamlMultiLoader
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Vec<T>, BoxedError> {
+        let documents = serde_yaml::Deserializer::from_slice(&content);
+        let values = documents.map(T::deserialize).collect::<Result<_, _>>()?;
+        Ok(values)
+    }
+}
+
+/// Loads assets from TOML files, reporting where parsing failed.
+///
+/// This loader works like [`TomlLoader`], but parse failures return a
+/// [`TomlSpannedError`], which gives the line and the column of the error and
+/// shows the faulty line when displayed.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "toml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+#[derive(Debug)]
+pub struct TomlSpannedLoader(());
+
+#[cfg(feature = "toml")]
+impl<T> Loader<T> for TomlSpannedLoader
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+        let text = str::from_utf8(&content)?;
+        serde_toml::from_str(text).map_err(|error| TomlSpannedError::new(error, text).into())
+    }
+}
+
+/// The error returned by [`TomlSpannedLoader`].
+#[cfg(feature = "toml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+#[derive(Debug)]
+pub struct TomlSpannedError {
+    error: serde_toml::de::Error,
+    line: Option<String>,
+}
+
+#[cfg(feature = "toml")]
+impl TomlSpannedError {
+    fn new(error: serde_toml::de::Error, text: &str) -> Self {
+        let line = error.line_col().and_then(|(line, _)| text.lines().nth(line)).map(String::from);
+        Self { error, line }
+    }
+
+    /// Returns the line (starting at 1) where the error occured, if known.
+    #[inline]
+    pub fn line(&self) -> Option<usize> {
+        self.error.line_col().map(|(line, _)| line + 1)
+    }
+
+    /// Returns the column (starting at 1) where the error occured, if known.
+    #[inline]
+    pub fn column(&self) -> Option<usize> {
+        self.error.line_col().map(|(_, col)| col + 1)
+    }
+
+    /// Returns the underlying TOML error.
+    #[inline]
+    pub fn inner(&self) -> &serde_toml::de::Error {
+        &self.error
+    }
+}
+
+#[cfg(feature = "toml")]
+impl std::fmt::Display for TomlSpannedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.error, f)?;
+
+        if let (Some(text), Some((line, col))) = (&self.line, self.error.line_col()) {
+            let number = (line + 1).to_string();
+            let margin = " ".repeat(number.len());
+            write!(f, "\n{} |\n{} | {}\n{} | {}^", margin, number, text, margin, " ".repeat(col))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "toml")]
+impl std::error::Error for TomlSpannedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Specifies the options used by a [`BincodeLoaderWith`].
+///
+/// Bincode's defaults may not match files produced by other tools. This trait
+/// enables you to choose the integer encoding, the endianness, whether trailing
+/// bytes are allowed, etc, with bincode's [`Options`](serde_bincode::Options)
+/// API.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "bincode")] {
+/// # extern crate serde_bincode as bincode;
+/// use assets_manager::{Asset, loader::{BincodeLoaderWith, BincodeOptions}};
+/// use bincode::Options;
+/// use serde::Deserialize;
+///
+/// struct VarintBigEndian;
+///
+/// impl BincodeOptions for VarintBigEndian {
+///     type Options = bincode::config::WithOtherEndian<
+///         bincode::config::WithOtherIntEncoding<bincode::DefaultOptions, bincode::config::VarintEncoding>,
+///         bincode::config::BigEndian,
+///     >;
+///
+///     fn options() -> Self::Options {
+///         bincode::DefaultOptions::new()
+///             .with_varint_encoding()
+///             .with_big_endian()
+///     }
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Data {
+///     values: Vec<u64>,
+/// }
+///
+/// impl Asset for Data {
+///     const EXTENSION: &'static str = "data";
+///     type Loader = BincodeLoaderWith<VarintBigEndian>;
+/// }
+/// # }}
+/// ```
+#[cfg(feature = "bincode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+pub trait BincodeOptions {
+    /// The type of the options.
+    type Options: serde_bincode::Options;
+
+    /// Returns the options used to deserialize assets.
+    fn options() -> Self::Options;
+}
+
+/// Loads assets from Bincode encoded files with custom options.
+///
+/// Unlike [`BincodeLoader`], which uses bincode's default configuration, the
+/// options are given by `O`.
+///
+/// See traits [`Loader`] and [`BincodeOptions`] for more informations.
+#[cfg(feature = "bincode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+#[derive(Debug)]
+pub struct BincodeLoaderWith<O>(PhantomData<O>);
+
+#[cfg(feature = "bincode")]
+impl<T, O> Loader<T> for BincodeLoaderWith<O>
+where
+    T: for<'de> serde::Deserialize<'de>,
+    O: BincodeOptions,
+{
+    #[inline]
+    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+        use serde_bincode::Options;
+        Ok(O::options().deserialize(&content)?)
+    }
+}
+
+/// Specifies the options used by a [`RonLoaderWith`].
+///
+/// This is useful to enable RON extensions by default, so files that use them
+/// don't have to start with `#![enable(...)]`.
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// # extern crate serde_ron as ron;
+/// use assets_manager::{Asset, loader::{RonLoaderWith, RonOptions}};
+/// use ron::extensions::Extensions;
+/// use serde::Deserialize;
+///
+/// struct ImplicitSome;
+///
+/// impl RonOptions for ImplicitSome {
+///     fn options() -> ron::Options {
+///         ron::Options::default()
+///             .with_default_extension(Extensions::IMPLICIT_SOME)
+///     }
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     volume: Option<f32>,
+/// }
+///
+/// impl Asset for Settings {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = RonLoaderWith<ImplicitSome>;
+/// }
+/// # }}
+/// ```
+#[cfg(feature = "ron")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ron")))]
+pub trait RonOptions {
+    /// Returns the options used to deserialize assets.
+    fn options() -> serde_ron::Options;
+}
+
+/// Loads assets from RON files with custom options.
+///
+/// Unlike [`RonLoader`], which uses RON's default options, the options are
+/// given by `O`.
+///
+/// See traits [`Loader`] and [`RonOptions`] for more informations.
+#[cfg(feature = "ron")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ron")))]
+#[derive(Debug)]
+pub struct RonLoaderWith<O>(PhantomData<O>);
+
+#[cfg(feature = "ron")]
+impl<T, O> Loader<T> for RonLoaderWith<O>
+where
+    T: for<'de> serde::Deserialize<'de>,
+    O: RonOptions,
+{
+    #[inline]
+    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+        Ok(O::options().from_bytes(&content)?)
+    }
+}
+
+/// Specifies the options used by a [`MessagePackLoaderWith`].
+///
+/// MessagePack implementations do not all encode values the same way, so this
+/// enables reading files produced by other languages or by older versions of
+/// `rmp-serde`. Structs are always accepted both as maps (with named fields)
+/// and as arrays (with positional fields).
+///
+/// The same options are used by
+/// [`MessagePackSaverWith`](crate::saver::MessagePackSaverWith).
+///
+/// # Example
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(feature = "msgpack")] {
+/// use assets_manager::{Asset, loader::{MessagePackLoaderWith, MessagePackOptions}};
+/// use serde::Deserialize;
+/// use std::net::IpAddr;
+///
+/// // Addresses are written as strings rather than as bytes
+/// struct HumanReadable;
+///
+/// impl MessagePackOptions for HumanReadable {
+///     const HUMAN_READABLE: bool = true;
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Server {
+///     address: IpAddr,
+/// }
+///
+/// impl Asset for Server {
+///     const EXTENSION: &'static str = "msgpack";
+///     type Loader = MessagePackLoaderWith<HumanReadable>;
+/// }
+/// # }}
+/// ```
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+pub trait MessagePackOptions {
+    /// Whether types that have both a human-readable and a compact
+    /// representation, such as IP addresses, use the human-readable one.
+    ///
+    /// Defaults to `false`.
+    const HUMAN_READABLE: bool = false;
+
+    /// Whether structs are saved as maps with named fields rather than as
+    /// arrays with positional fields. This does not change how files are
+    /// loaded.
+    ///
+    /// Defaults to `true`.
+    const NAMED_FIELDS: bool = true;
+}
+
+/// Loads assets from MessagePack files with custom options.
+///
+/// Unlike [`MessagePackLoader`], which uses the default configuration of
+/// `rmp-serde`, the options are given by `O`.
+///
+/// See traits [`Loader`] and [`MessagePackOptions`] for more informations.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+#[derive(Debug)]
+pub struct MessagePackLoaderWith<O>(PhantomData<O>);
+
+#[cfg(feature = "msgpack")]
+impl<T, O> Loader<T> for MessagePackLoaderWith<O>
+where
+    T: for<'de> serde::Deserialize<'de>,
+    O: MessagePackOptions,
+{
+    fn load(content: Cow<[u8]>, _: &str) -> Result<T, BoxedError> {
+        use serde_msgpack::Deserializer;
+
+        let de = Deserializer::from_read_ref(&*content);
+        let value = if O::HUMAN_READABLE {
+            T::deserialize(&mut de.with_human_readable())?
+        } else {
+            T::deserialize(&mut de.with_binary())?
+        };
+        Ok(value)
+    }
+}
+
+/// Loads Fluent localization resources (`.ftl` files).
+///
+/// If the resource contains syntax errors, loading fails and the error lists
+/// all of them.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "fluent")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+#[derive(Debug)]
+pub struct FluentLoader(());
+
+#[cfg(feature = "fluent")]
+impl Loader<fluent_bundle::FluentResource> for FluentLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<fluent_bundle::FluentResource, BoxedError> {
+        let text = String::from_utf8(content.into_owned())?;
+
+        fluent_bundle::FluentResource::try_new(text).map_err(|(_, errors)| {
+            let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+            format!("Invalid Fluent resource: {}", errors.join(", ")).into()
+        })
+    }
+}
+
+/// Loads images.
+///
+/// PNG, JPEG and TGA images are supported. The format is chosen from the
+/// extension of the file, or guessed from its content if the extension is
+/// unknown.
+///
+/// See [`asset::Image`](crate::asset::Image) for a ready-made asset type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+#[derive(Debug)]
+pub struct ImageLoader(());
+
+#[cfg(feature = "image")]
+impl Loader<image_crate::DynamicImage> for ImageLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<image_crate::DynamicImage, BoxedError> {
+        let image = match image_crate::ImageFormat::from_extension(ext) {
+            Some(format) => image_crate::load_from_memory_with_format(&content, format)?,
+            None => image_crate::load_from_memory(&content)?,
+        };
+        Ok(image)
+    }
+}
+
+/// Loads sounds.
+///
+/// WAV, OGG Vorbis and FLAC files are supported, and are decoded to PCM
+/// samples. The format is chosen from the extension of the file, or guessed
+/// from its content if the extension is unknown.
+///
+/// If you want to keep the encoded stream instead, for example to give it to
+/// `rodio`, use [`BytesLoader`] or [`IntoBytesLoader`].
+///
+/// See [`asset::Sound`](crate::asset::Sound) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "audio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "audio")))]
+#[derive(Debug)]
+pub struct SoundLoader(());
+
+#[cfg(feature = "audio")]
+impl Loader<crate::asset::Sound> for SoundLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<crate::asset::Sound, BoxedError> {
+        match ext {
+            "wav" => decode_wav(&content),
+            "ogg" => decode_ogg(&content),
+            "flac" => decode_flac(&content),
+            _ if content.starts_with(b"RIFF") => decode_wav(&content),
+            _ if content.starts_with(b"OggS") => decode_ogg(&content),
+            _ if content.starts_with(b"fLaC") => decode_flac(&content),
+            _ => Err("Unknown sound format".into()),
+        }
+    }
+}
+
+/// Converts a signed integer sample to a float in `[-1, 1]`.
+#[cfg(feature = "audio")]
+#[inline]
+fn int_to_float(sample: i32, bits: u32) -> f32 {
+    sample as f32 / (1u32 << (bits - 1)) as f32
+}
+
+#[cfg(feature = "audio")]
+fn decode_wav(content: &[u8]) -> Result<crate::asset::Sound, BoxedError> {
+    let reader = hound::WavReader::new(content)?;
+    let spec = reader.spec();
+
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let bits = u32::from(spec.bits_per_sample);
+            reader.into_samples::<i32>()
+                .map(|s| s.map(|s| int_to_float(s, bits)))
+                .collect::<Result<_, _>>()?
+        },
+    };
+
+    Ok(crate::asset::Sound {
+        channels: spec.channels,
+        sample_rate: spec.sample_rate,
+        samples,
+    })
+}
+
+#[cfg(feature = "audio")]
+fn decode_ogg(content: &[u8]) -> Result<crate::asset::Sound, BoxedError> {
+    let mut reader = lewton::inside_ogg::OggStreamReader::new(std::io::Cursor::new(content))?;
+
+    let mut samples = Vec::new();
+    while let Some(packet) = reader.read_dec_packet_itl()? {
+        samples.extend(packet.into_iter().map(|s| int_to_float(s.into(), 16)));
+    }
+
+    Ok(crate::asset::Sound {
+        channels: reader.ident_hdr.audio_channels.into(),
+        sample_rate: reader.ident_hdr.audio_sample_rate,
+        samples,
+    })
+}
+
+#[cfg(feature = "audio")]
+fn decode_flac(content: &[u8]) -> Result<crate::asset::Sound, BoxedError> {
+    let mut reader = claxon::FlacReader::new(content)?;
+    let info = reader.streaminfo();
+
+    let samples = reader.samples()
+        .map(|s| s.map(|s| int_to_float(s, info.bits_per_sample)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(crate::asset::Sound {
+        channels: info.channels as u16,
+        sample_rate: info.sample_rate,
+        samples,
+    })
+}
+
+/// Loads WGSL and SPIR-V shaders as `naga` modules.
+///
+/// The module is validated when it is loaded, so errors are reported with the
+/// asset instead of later by the GPU driver. The format is chosen from the
+/// extension of the file (`wgsl` or `spv`), or guessed from its content if the
+/// extension is unknown.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "naga")]
+#[cfg_attr(docsrs, doc(cfg(feature = "naga")))]
+#[derive(Debug)]
+pub struct NagaLoader(());
+
+#[cfg(feature = "naga")]
+impl Loader<naga_crate::Module> for NagaLoader {
+    fn load(content: Cow<[u8]>, ext: &str) -> Result<naga_crate::Module, BoxedError> {
+        use naga_crate::valid::{Capabilities, ValidationFlags, Validator};
+
+        let is_spirv = match ext {
+            "spv" => true,
+            "wgsl" => false,
+            _ => content.starts_with(&[0x03, 0x02, 0x23, 0x07]),
+        };
+
+        let (module, source) = if is_spirv {
+            let options = naga_crate::front::spv::Options::default();
+            let module = naga_crate::front::spv::parse_u8_slice(&content, &options)
+                .map_err(|err| format!("Invalid SPIR-V: {}", err))?;
+            (module, "")
+        } else {
+            let source = str::from_utf8(&content)?;
+            let module = naga_crate::front::wgsl::parse_str(source)
+                .map_err(|err| err.emit_to_string(source))?;
+            (module, source)
+        };
+
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .map_err(|err| err.emit_to_string(source))?;
+
+        Ok(module)
+    }
+}
+
+/// Compiles regular expressions.
+///
+/// The whole file is used as the pattern, except for a trailing line break.
+/// Patterns are compiled when they are loaded, so invalid patterns are reported
+/// with the asset.
+///
+/// This loader can also load a [`regex::RegexSet`](regex_crate::RegexSet), in
+/// which case each non-empty line of the file is a pattern.
+///
+/// See [`asset::Regex`](crate::asset::Regex) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[derive(Debug)]
+pub struct RegexLoader(());
+
+#[cfg(feature = "regex")]
+impl Loader<regex_crate::Regex> for RegexLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<regex_crate::Regex, BoxedError> {
+        let pattern = str::from_utf8(&content)?;
+        let pattern = pattern.strip_suffix('\n').unwrap_or(pattern);
+        let pattern = pattern.strip_suffix('\r').unwrap_or(pattern);
+        Ok(regex_crate::Regex::new(pattern)?)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Loader<regex_crate::RegexSet> for RegexLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<regex_crate::RegexSet, BoxedError> {
+        let patterns = str::from_utf8(&content)?;
+        let patterns = patterns.lines().filter(|line| !line.is_empty());
+        Ok(regex_crate::RegexSet::new(patterns)?)
+    }
+}
+
+/// Compiles Rhai scripts.
+///
+/// Scripts are compiled when they are loaded, so syntax errors are reported
+/// with the asset, and hot-reloading replaces the compiled script at once.
+///
+/// Scripts are compiled with a default [`rhai::Engine`](rhai_crate::Engine),
+/// so custom syntax and custom operators are not available.
+///
+/// See [`asset::RhaiScript`](crate::asset::RhaiScript) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "rhai")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rhai")))]
+#[derive(Debug)]
+pub struct RhaiLoader(());
+
+#[cfg(feature = "rhai")]
+impl Loader<crate::asset::RhaiScript> for RhaiLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<crate::asset::RhaiScript, BoxedError> {
+        let source = str::from_utf8(&content)?;
+        let ast = rhai_crate::Engine::new().compile(source)?;
+        Ok(crate::asset::RhaiScript(ast))
+    }
+}
+
+/// Parses SVG images into a [`usvg::Tree`](usvg::Tree).
+///
+/// The tree is simplified by *usvg*, so it can be rasterized or tessellated
+/// directly. Text is not supported and is ignored. Images embedded as data
+/// URLs are kept, but external images are not loaded.
+///
+/// Compressed `.svgz` files are also supported.
+///
+/// See [`asset::Svg`](crate::asset::Svg) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "svg")]
+#[cfg_attr(docsrs, doc(cfg(feature = "svg")))]
+#[derive(Debug)]
+pub struct SvgLoader(());
+
+#[cfg(feature = "svg")]
+impl Loader<crate::asset::Svg> for SvgLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<crate::asset::Svg, BoxedError> {
+        let options = usvg::Options {
+            image_href_resolver: usvg::ImageHrefResolver {
+                resolve_string: Box::new(|_, _| None),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let tree = usvg::Tree::from_data(&content, &options)?;
+        Ok(crate::asset::Svg(tree))
+    }
+}
+
+/// Compiles Handlebars and Tera templates.
+///
+/// Templates are compiled when they are loaded, so syntax errors are reported
+/// with the asset, and hot-reloading replaces the compiled template at once.
+///
+/// Each template is compiled on its own, so partials, includes and
+/// inheritance between templates are not supported.
+///
+/// See [`asset::HandlebarsTemplate`](crate::asset::HandlebarsTemplate) and
+/// [`asset::TeraTemplate`](crate::asset::TeraTemplate) for the loaded types.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "templates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "templates")))]
+#[derive(Debug)]
+pub struct TemplateLoader(());
+
+#[cfg(feature = "templates")]
+impl Loader<crate::asset::HandlebarsTemplate> for TemplateLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<crate::asset::HandlebarsTemplate, BoxedError> {
+        let source = str::from_utf8(&content)?;
+        let template = handlebars::Template::compile(source)?;
+        Ok(crate::asset::HandlebarsTemplate::new(template))
+    }
+}
+
+#[cfg(feature = "templates")]
+impl Loader<crate::asset::TeraTemplate> for TemplateLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<crate::asset::TeraTemplate, BoxedError> {
+        let source = str::from_utf8(&content)?;
+        Ok(crate::asset::TeraTemplate::new(source)?)
+    }
+}
+
+/// Loads and validates WebAssembly modules.
+///
+/// The module is fully validated when it is loaded, so invalid modules are
+/// reported with the asset and a hot-reloaded module can be instantiated
+/// safely by a runtime such as *wasmtime* or *wasmer*. Components are not
+/// supported.
+///
+/// See [`asset::WasmModule`](crate::asset::WasmModule) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "wasm-plugin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm-plugin")))]
+#[derive(Debug)]
+pub struct WasmModuleLoader(());
+
+#[cfg(feature = "wasm-plugin")]
+impl Loader<crate::asset::WasmModule> for WasmModuleLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<crate::asset::WasmModule, BoxedError> {
+        let types = wasmparser::Validator::new().validate_all(&content)?;
+        let types = types.as_ref();
+
+        let imports = match types.core_imports() {
+            Some(imports) => imports.map(|(module, name, _)| (module.to_owned(), name.to_owned())).collect(),
+            None => return Err("WebAssembly components are not supported".into()),
+        };
+        let exports = match types.core_exports() {
+            Some(exports) => exports.map(|(name, _)| name.to_owned()).collect(),
+            None => return Err("WebAssembly components are not supported".into()),
+        };
+
+        Ok(crate::asset::WasmModule {
+            bytes: content.into_owned(),
+            imports,
+            exports,
+        })
+    }
+}
+
+/// Loads Wavefront OBJ meshes.
+///
+/// Faces are triangulated, and vertices are deduplicated so that positions,
+/// normals and texture coordinates share the same indices. If the file
+/// contains several objects, they are merged in a single mesh. Materials are
+/// ignored.
+///
+/// See [`asset::Mesh`](crate::asset::Mesh) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "obj")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obj")))]
+#[derive(Debug)]
+pub struct ObjLoader(());
+
+#[cfg(feature = "obj")]
+impl Loader<crate::asset::Mesh> for ObjLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<crate::asset::Mesh, BoxedError> {
+        let options = tobj::LoadOptions {
+            single_index: true,
+            triangulate: true,
+            ignore_points: true,
+            ignore_lines: true,
+        };
+        let (models, _) = tobj::load_obj_buf(&mut &*content, &options, |_| {
+            Err(tobj::LoadError::OpenFileFailed)
+        })?;
+
+        let mut mesh = crate::asset::Mesh::default();
+
+        for model in models {
+            let model = model.mesh;
+            let offset = mesh.positions.len();
+
+            // Objects without normals or texture coordinates get zeroed ones
+            // if other objects have them, so that all attributes keep the
+            // same indices.
+            if !model.normals.is_empty() {
+                mesh.normals.resize(offset, [0.0; 3]);
+            }
+            if !model.texcoords.is_empty() {
+                mesh.uvs.resize(offset, [0.0; 2]);
+            }
+
+            mesh.positions.extend(model.positions.chunks_exact(3).map(|p| [p[0], p[1], p[2]]));
+            mesh.normals.extend(model.normals.chunks_exact(3).map(|n| [n[0], n[1], n[2]]));
+            mesh.uvs.extend(model.texcoords.chunks_exact(2).map(|t| [t[0], t[1]]));
+            mesh.indices.extend(model.indices.iter().map(|&i| i + offset as u32));
+        }
+
+        let len = mesh.positions.len();
+        if !mesh.normals.is_empty() {
+            mesh.normals.resize(len, [0.0; 3]);
+        }
+        if !mesh.uvs.is_empty() {
+            mesh.uvs.resize(len, [0.0; 2]);
+        }
+
+        Ok(mesh)
+    }
+}
+
+/// Loads KDL documents.
+///
+/// Documents are loaded as `kdl::KdlDocument`s. Deserializing them into serde
+/// types is not supported, because KDL has no standard mapping to the serde
+/// data model: nodes, arguments and properties can be interpreted in several
+/// ways. Such assets can be built from the document with a [`Compound`] or
+/// with [`LoadFrom`].
+///
+/// See trait [`Loader`] for more informations.
+///
+/// [`Compound`]: crate::Compound
+#[cfg(feature = "kdl")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kdl")))]
+#[derive(Debug)]
+pub struct KdlLoader(());
+
+#[cfg(feature = "kdl")]
+impl Loader<kdl_crate::KdlDocument> for KdlLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<kdl_crate::KdlDocument, BoxedError> {
+        Ok(str::from_utf8(&content)?.parse()?)
+    }
+}
+
+/// Loads Markdown documents.
+///
+/// The document can either be rendered to an HTML `String`, or be loaded as
+/// a list of `pulldown_cmark::Event`s, which is an owned representation of the
+/// parsed document.
+///
+/// In addition to CommonMark, tables, footnotes, strikethrough, task lists and
+/// heading attributes are supported.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg(feature = "markdown")]
+#[cfg_attr(docsrs, doc(cfg(feature = "markdown")))]
+#[derive(Debug)]
+pub struct MarkdownLoader(());
+
+#[cfg(feature = "markdown")]
+fn markdown_parser(text: &str) -> pulldown_cmark::Parser<'_> {
+    use pulldown_cmark::Options;
+
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES;
+
+    pulldown_cmark::Parser::new_ext(text, options)
+}
+
+#[cfg(feature = "markdown")]
+impl Loader<String> for MarkdownLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<String, BoxedError> {
+        let text = str::from_utf8(&content)?;
+        let mut html = String::with_capacity(text.len());
+        pulldown_cmark::html::push_html(&mut html, markdown_parser(text));
+        Ok(html)
+    }
+}
+
+#[cfg(feature = "markdown")]
+impl Loader<Vec<pulldown_cmark::Event<'static>>> for MarkdownLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Vec<pulldown_cmark::Event<'static>>, BoxedError> {
+        let text = str::from_utf8(&content)?;
+        Ok(markdown_parser(text).map(pulldown_cmark::Event::into_static).collect())
+    }
 }
@@ -0,0 +1,95 @@
+use crate::{BoxedError, asset::Palette};
+
+use super::Loader;
+
+use std::{borrow::Cow, str};
+
+
+/// Loads color palettes.
+///
+/// Two formats are supported:
+/// - GIMP palettes (`.gpl`), with either RGB or RGBA colors.
+/// - Lists of hexadecimal colors (`.hex`), one per line, as exported by
+///   Lospec. Colors are written `RRGGBB` or `RRGGBBAA`, with an optional
+///   leading `#`. Lines starting with `;` are comments.
+///
+/// The format is detected from the content of the file.
+///
+/// See [`asset::Palette`](crate::asset::Palette) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[derive(Debug)]
+pub struct PaletteLoader(());
+
+impl Loader<Palette> for PaletteLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<Palette, BoxedError> {
+        let content = str::from_utf8(&content)?;
+        let mut lines = content.lines().map(str::trim);
+
+        match lines.next() {
+            Some("GIMP Palette") => load_gpl(lines),
+            _ => load_hex(content.lines().map(str::trim)),
+        }
+    }
+}
+
+fn load_gpl<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Palette, BoxedError> {
+    let mut palette = Palette::default();
+    let mut alpha = false;
+
+    for line in lines {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("Name:") {
+            palette.name = Some(name.trim().to_owned());
+        } else if let Some(channels) = line.strip_prefix("Channels:") {
+            alpha = match channels.trim() {
+                "RGB" => false,
+                "RGBA" => true,
+                other => return Err(format!("Unsupported channels: \"{}\"", other).into()),
+            };
+        } else if line.starts_with("Columns:") {
+            // Only used for display in GIMP
+        } else {
+            let mut values = line.split_whitespace();
+            let mut next = || -> Result<u8, BoxedError> {
+                let value = values.next().ok_or_else(|| format!("Invalid color: \"{}\"", line))?;
+                Ok(value.parse()?)
+            };
+
+            let (r, g, b) = (next()?, next()?, next()?);
+            let a = if alpha { next()? } else { 255 };
+            palette.colors.push([r, g, b, a]);
+        }
+    }
+
+    Ok(palette)
+}
+
+fn load_hex<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Palette, BoxedError> {
+    let mut palette = Palette::default();
+
+    for line in lines {
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        let hex = line.strip_prefix('#').unwrap_or(line);
+        let value = match hex.len() {
+            6 | 8 if hex.bytes().all(|b| b.is_ascii_hexdigit()) => u32::from_str_radix(hex, 16)?,
+            _ => return Err(format!("Invalid color: \"{}\"", line).into()),
+        };
+
+        let color = if hex.len() == 6 {
+            let [_, r, g, b] = value.to_be_bytes();
+            [r, g, b, 255]
+        } else {
+            value.to_be_bytes()
+        };
+        palette.colors.push(color);
+    }
+
+    Ok(palette)
+}
@@ -0,0 +1,114 @@
+use crate::{
+    AssetCache,
+    BoxedError,
+    asset::JsonSchema,
+    source::Source,
+};
+
+use super::{ContextualLoader, Loader};
+
+use serde_json::Value;
+
+use std::borrow::Cow;
+
+
+/// Loads JSON schemas.
+///
+/// The schema is compiled when it is loaded, so invalid schemas are reported
+/// with the asset. References to other schemas are only resolved within the
+/// same file.
+///
+/// See [`asset::JsonSchema`](crate::asset::JsonSchema) for the loaded type.
+///
+/// See trait [`Loader`] for more informations.
+#[cfg_attr(docsrs, doc(cfg(feature = "json-schema")))]
+#[derive(Debug)]
+pub struct JsonSchemaLoader(());
+
+impl Loader<JsonSchema> for JsonSchemaLoader {
+    fn load(content: Cow<[u8]>, _: &str) -> Result<JsonSchema, BoxedError> {
+        let schema: Value = serde_json::from_slice(&content)?;
+        JsonSchema::new(schema)
+    }
+}
+
+/// Types which files are validated against a JSON schema before being
+/// deserialized.
+///
+/// This is used by [`SchemaValidatedLoader`].
+///
+/// # Example
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "json-schema")] {
+/// use assets_manager::{
+///     AssetCache, Compound, Error,
+///     asset::{self, ContextualAsset},
+///     loader::{Schema, SchemaValidatedLoader},
+///     source::Source,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     volume: f32,
+/// }
+///
+/// impl Schema for Settings {
+///     // `schemas/settings.json` contains a JSON schema
+///     const SCHEMA: &'static str = "schemas.settings";
+/// }
+///
+/// impl ContextualAsset for Settings {
+///     const EXTENSION: &'static str = "json";
+///     type Loader = SchemaValidatedLoader;
+/// }
+///
+/// impl Compound for Settings {
+///     fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Self, Error> {
+///         asset::load_contextual(cache, id)
+///     }
+/// }
+///
+/// let cache = AssetCache::new("assets")?;
+/// let settings = cache.load::<Settings>("common.settings")?;
+/// # }}
+/// # Ok::<(), assets_manager::BoxedError>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "json-schema")))]
+pub trait Schema: serde::de::DeserializeOwned {
+    /// The id of the schema, which is loaded as a
+    /// [`JsonSchema`](crate::asset::JsonSchema).
+    const SCHEMA: &'static str;
+}
+
+/// Loads JSON or YAML files and validates them against a JSON schema before
+/// deserializing them.
+///
+/// The schema is given by the [`Schema`] implementation of the loaded type.
+/// It is loaded from the cache, so the asset is reloaded when the schema is
+/// modified. If the file does not match the schema, the error lists all
+/// mismatches with their location in the file.
+///
+/// YAML files (with extension `yaml` or `yml`) require feature `yaml`. Other
+/// files are parsed as JSON.
+///
+/// See traits [`ContextualLoader`] and [`Schema`] for more informations.
+#[cfg_attr(docsrs, doc(cfg(feature = "json-schema")))]
+#[derive(Debug)]
+pub struct SchemaValidatedLoader(());
+
+impl<T: Schema> ContextualLoader<T> for SchemaValidatedLoader {
+    fn load<S: Source>(content: Cow<[u8]>, ext: &str, _: &str, cache: &AssetCache<S>) -> Result<T, BoxedError> {
+        let value: Value = match ext {
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => serde_yaml::from_slice(&content)?,
+            _ => serde_json::from_slice(&content)?,
+        };
+
+        let schema = cache.load::<JsonSchema>(T::SCHEMA)?;
+        schema.read().validate(&value)?;
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
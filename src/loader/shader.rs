@@ -0,0 +1,85 @@
+use crate::{
+    AssetCache,
+    BoxedError,
+    asset::Shader,
+    source::Source,
+};
+
+use super::{
+    ContextualLoader,
+    external::{load_external, resolve_path},
+};
+
+use std::{borrow::Cow, str};
+
+
+/// Loads shader sources, resolving `#include` directives.
+///
+/// Lines of the form `#include "path"` are replaced by the content of the
+/// referenced file, which is resolved relatively to the including file. For
+/// example, `#include "../common/light.glsl"` in asset `shaders.main` includes
+/// asset `common.light` with extension `glsl`. Includes are processed
+/// recursively, but cycles are an error.
+///
+/// Included files are recorded as dependencies, so the shader is reloaded when
+/// any of them is modified, whatever their extension.
+///
+/// This works with any textual shader language, such as GLSL, WGSL or HLSL.
+///
+/// See [`asset::Shader`](crate::asset::Shader) for the loaded type.
+///
+/// See trait [`ContextualLoader`] for more informations.
+#[derive(Debug)]
+pub struct ShaderLoader(());
+
+impl ContextualLoader<Shader> for ShaderLoader {
+    fn load<S: Source>(content: Cow<[u8]>, ext: &str, id: &str, cache: &AssetCache<S>) -> Result<Shader, BoxedError> {
+        let mut source = String::with_capacity(content.len());
+        let mut stack = vec![(id.to_owned(), ext.to_owned())];
+        expand(cache, str::from_utf8(&content)?, &mut stack, &mut source)?;
+        Ok(Shader(source))
+    }
+}
+
+/// Appends `text` to `out`, replacing includes by their content.
+///
+/// `stack` contains the ids and extensions of the files being expanded.
+fn expand<S: Source>(cache: &AssetCache<S>, text: &str, stack: &mut Vec<(String, String)>, out: &mut String) -> Result<(), BoxedError> {
+    for line in text.lines() {
+        let path = match parse_include(line) {
+            Some(path) => path,
+            None => {
+                out.push_str(line);
+                out.push('\n');
+                continue;
+            },
+        };
+
+        let current = stack.last().map_or("", |(id, _)| id.as_str());
+        let file = resolve_path(current, path?)?;
+        if stack.contains(&file) {
+            return Err(format!("Cyclic include of \"{}\" in \"{}\"", file.0, current).into());
+        }
+
+        let content = load_external(cache, &file.0, &file.1)
+            .map_err(|err| format!("Failed to include \"{}\" in \"{}\": {}", file.0, current, err))?;
+
+        stack.push(file);
+        expand(cache, str::from_utf8(&content)?, stack, out)?;
+        stack.pop();
+    }
+
+    Ok(())
+}
+
+/// Returns the path of an include directive, if the line is one.
+fn parse_include(line: &str) -> Option<Result<&str, BoxedError>> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start().strip_prefix("include")?;
+
+    let path = rest.trim()
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| format!("Invalid include directive: {}", line.trim()).into());
+
+    Some(path)
+}
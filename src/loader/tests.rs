@@ -64,6 +64,527 @@ fn from_other() {
     assert_eq!(loaded, X(n));
 }
 
+#[test]
+fn try_from_other() {
+    use std::num::NonZeroU32;
+
+    let loaded: NonZeroU32 = LoadTryFrom::<u32, ParseLoader>::load(raw("42"), "").unwrap();
+    assert_eq!(loaded.get(), 42);
+
+    let loaded: Result<NonZeroU32, _> = LoadTryFrom::<u32, ParseLoader>::load(raw("0"), "");
+    assert!(loaded.is_err());
+
+    let loaded: Result<u8, _> = LoadTryFrom::<u32, ParseLoader>::load(raw("300"), "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn into_bytes_loader() {
+    let vec = b"Hello".to_vec();
+    let ptr = vec.as_ptr();
+    let loaded: bytes_crate::Bytes = IntoBytesLoader::load(vec.into(), "").unwrap();
+    assert_eq!(loaded, &b"Hello"[..]);
+    assert_eq!(loaded.as_ptr(), ptr);
+
+    let loaded: bytes_crate::Bytes = IntoBytesLoader::load(raw("Hello"), "").unwrap();
+    assert_eq!(loaded, &b"Hello"[..]);
+}
+
+#[cfg(feature = "bytemuck")]
+mod pod {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, bytemuck_crate::Pod, bytemuck_crate::Zeroable)]
+    #[bytemuck(crate = "bytemuck_crate")]
+    #[repr(C)]
+    struct Point {
+        x: u16,
+        y: u16,
+    }
+
+    #[test]
+    fn pod_loader_ok() {
+        let content = [1, 0, 2, 0];
+        let loaded: Point = PodLoader::load(content[..].into(), "").unwrap();
+        assert_eq!(loaded, Point { x: 1, y: 2 });
+
+        let content = [0, 1, 0, 2, 0];
+        let loaded: Point = PodLoader::load(content[1..].into(), "").unwrap();
+        assert_eq!(loaded, Point { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn pod_loader_err() {
+        let loaded: Result<Point, _> = PodLoader::load(b"\x01\x00\x02"[..].into(), "");
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn pod_slice_loader_ok() {
+        let content = [0, 1, 0, 2, 0, 3, 0, 4, 0];
+        let loaded: Vec<Point> = PodSliceLoader::load(content[1..].into(), "").unwrap();
+        assert_eq!(loaded, [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]);
+
+        let loaded: Box<[u8]> = PodSliceLoader::load(raw(""), "").unwrap();
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn pod_slice_loader_err() {
+        let loaded: Result<Vec<Point>, _> = PodSliceLoader::load(b"\x01\x00\x02\x00\x03"[..].into(), "");
+        assert!(loaded.is_err());
+
+        let loaded: Result<Vec<()>, _> = PodSliceLoader::load(raw(""), "");
+        assert!(loaded.is_err());
+    }
+}
+
+#[test]
+fn any_text_loader_ok() {
+    let loaded: String = AnyTextLoader::load(raw("Hé"), "").unwrap();
+    assert_eq!(loaded, "Hé");
+
+    let loaded: String = AnyTextLoader::load(raw("\u{FEFF}Hé"), "").unwrap();
+    assert_eq!(loaded, "Hé");
+
+    let loaded: String = AnyTextLoader::load(b"\xFF\xFEH\x00\xE9\x00"[..].into(), "").unwrap();
+    assert_eq!(loaded, "Hé");
+
+    let loaded: Box<str> = AnyTextLoader::load(b"\xFE\xFF\x00H\x00\xE9"[..].into(), "").unwrap();
+    assert_eq!(&*loaded, "Hé");
+}
+
+#[test]
+fn any_text_loader_err() {
+    let loaded: Result<String, _> = AnyTextLoader::load(b"\xFF\xFEH\x00\xE9"[..].into(), "");
+    assert!(loaded.is_err());
+
+    let loaded: Result<String, _> = AnyTextLoader::load(b"\xFF\xFE\x00\xD8"[..].into(), "");
+    assert!(loaded.is_err());
+
+    let loaded: Result<String, _> = AnyTextLoader::load(b"\xEF\xBB\xBF\xFF"[..].into(), "");
+    assert!(loaded.is_err());
+}
+
+#[test]
+fn streamed_loader() {
+    let loaded: String = Streamed::<StringLoader>::load(raw("Hello"), "").unwrap();
+    assert_eq!(loaded, "Hello");
+
+    let loaded: Vec<u8> = BytesLoader::load_stream(&mut &b"\xff"[..], "").unwrap();
+    assert_eq!(loaded, b"\xff");
+
+    let loaded: Result<String, _> = Streamed::<StringLoader>::load(b"\xff"[..].into(), "");
+    assert!(loaded.is_err());
+}
+
+#[test]
+fn map_loader() {
+    struct Double;
+    impl Mapper<X> for Double {
+        type Input = i32;
+
+        fn map(n: i32) -> X {
+            X(n * 2)
+        }
+    }
+
+    let loaded: X = MapLoader::<ParseLoader, Double>::load(raw("21"), "").unwrap();
+    assert_eq!(loaded, X(42));
+
+    let loaded: Result<X, _> = MapLoader::<ParseLoader, Double>::load(raw("x"), "");
+    assert!(loaded.is_err());
+}
+
+#[test]
+fn validated_loader() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Positive(i32);
+
+    impl From<i32> for Positive {
+        fn from(n: i32) -> Positive {
+            Positive(n)
+        }
+    }
+
+    impl Validate for Positive {
+        fn validate(&self) -> Result<(), BoxedError> {
+            if self.0 < 0 {
+                return Err("negative value".into());
+            }
+            Ok(())
+        }
+    }
+
+    type Validated = ValidatedLoader<LoadFrom<i32, ParseLoader>>;
+
+    let loaded: Positive = Validated::load(raw("42"), "").unwrap();
+    assert_eq!(loaded, Positive(42));
+
+    let loaded: Result<Positive, _> = Validated::load(raw("-1"), "");
+    assert_eq!(loaded.unwrap_err().to_string(), "negative value");
+
+    let loaded: Result<Positive, _> = Validated::load(raw("x"), "");
+    assert!(loaded.is_err());
+}
+
+#[test]
+fn fallback_loader_ok() {
+    let loaded: X = FallbackLoader::<LoadFrom<i32, ParseLoader>, LoadFrom<i32, ParseLoader>>::load(raw("42"), "").unwrap();
+    assert_eq!(loaded, X(42));
+
+    let loaded: Vec<u8> = FallbackLoader::<LoadFrom<String, StringLoader>, BytesLoader>::load(b"\xff"[..].into(), "").unwrap();
+    assert_eq!(loaded, b"\xff");
+}
+
+#[test]
+fn ext_loader() {
+    struct Number;
+    impl Extensions for Number {
+        const EXTENSIONS: &'static [&'static str] = &["n"];
+    }
+
+    struct Optional;
+    impl Extensions for Optional {
+        const EXTENSIONS: &'static [&'static str] = &["opt", "n"];
+    }
+
+    type Loader = ExtLoader<(
+        WithExtensions<Number, ParseLoader>,
+        WithExtensions<Optional, LoadOrDefault<ParseLoader>>,
+    )>;
+
+    let loaded: i32 = Loader::load(raw("42"), "n").unwrap();
+    assert_eq!(loaded, 42);
+
+    let loaded: Result<i32, _> = Loader::load(raw("x"), "n");
+    assert!(loaded.is_err());
+
+    let loaded: i32 = Loader::load(raw("x"), "opt").unwrap();
+    assert_eq!(loaded, 0);
+
+    let loaded: Result<i32, _> = Loader::load(raw("42"), "json");
+    assert!(loaded.is_err());
+}
+
+#[test]
+fn sniff_loader() {
+    let loaded: Vec<u8> = SniffLoader::<(LoadFrom<String, StringLoader>, BytesLoader)>::load(raw("abc"), "").unwrap();
+    assert_eq!(loaded, b"abc");
+
+    let loaded: Vec<u8> = SniffLoader::<(LoadFrom<String, StringLoader>, BytesLoader)>::load(b"\xff"[..].into(), "").unwrap();
+    assert_eq!(loaded, b"\xff");
+
+    let loaded: Result<String, _> = SniffLoader::<(StringLoader,)>::load(b"\xff"[..].into(), "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(all(feature = "gzip", feature = "json"))]
+#[test]
+fn sniff_loader_gzip() {
+    use std::io::Write;
+
+    type Loader = SniffLoader<(GzLoader<JsonLoader>, JsonLoader)>;
+
+    let json = b" [1, 2, 3]";
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(json).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let loaded: Vec<i32> = Loader::load(compressed.into(), "json").unwrap();
+    assert_eq!(loaded, [1, 2, 3]);
+
+    let loaded: Vec<i32> = Loader::load(json[..].into(), "json").unwrap();
+    assert_eq!(loaded, [1, 2, 3]);
+
+    let loaded: Result<Vec<i32>, _> = Loader::load(raw("1, 2, 3"), "json");
+    assert!(loaded.is_err());
+}
+
+#[test]
+fn fallback_loader_err() {
+    let loaded: Result<i32, _> = FallbackLoader::<ParseLoader, ParseLoader>::load(raw("x"), "");
+    assert!(loaded.is_err());
+}
+
+#[test]
+fn load_or_default() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static REPORTED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Count;
+    impl ErrorReporter for Count {
+        fn report(_: &BoxedError) {
+            REPORTED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let loaded: i32 = LoadOrDefault::<ParseLoader, Count>::load(raw("42"), "").unwrap();
+    assert_eq!(loaded, 42);
+
+    let loaded: i32 = LoadOrDefault::<ParseLoader, Count>::load(raw(""), "").unwrap();
+    assert_eq!(loaded, 0);
+    assert_eq!(REPORTED.load(Ordering::Relaxed), 0);
+
+    let loaded: i32 = LoadOrDefault::<ParseLoader, Count>::load(raw("x"), "").unwrap();
+    assert_eq!(loaded, 0);
+    assert_eq!(REPORTED.load(Ordering::Relaxed), 1);
+
+    let loaded: i32 = LoadOrDefault::<ParseLoader>::load(raw("x"), "").unwrap();
+    assert_eq!(loaded, 0);
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn base64_loader_ok() {
+    let raw = raw("SGVsbG8g\nV29ybGQh\n");
+
+    let loaded: String = Base64Loader::<StringLoader>::load(raw, "").unwrap();
+    assert_eq!(loaded, "Hello World!");
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn base64_loader_err() {
+    let loaded: Result<String, _> = Base64Loader::<StringLoader>::load(raw("Hello World!"), "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "brotli")]
+#[test]
+fn brotli_loader_ok() {
+    use std::io::Write;
+
+    let mut encoder = brotli_crate::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+    encoder.write_all(b"Hello World!").unwrap();
+    let raw = encoder.into_inner().into();
+
+    let loaded: String = BrotliLoader::<StringLoader>::load(raw, "").unwrap();
+    assert_eq!(loaded, "Hello World!");
+}
+
+#[cfg(feature = "brotli")]
+#[test]
+fn brotli_loader_err() {
+    let loaded: Result<String, _> = BrotliLoader::<StringLoader>::load(raw("Hello World!"), "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gz_loader_ok() {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"Hello World!").unwrap();
+    let raw = encoder.finish().unwrap().into();
+
+    let loaded: String = GzLoader::<StringLoader>::load(raw, "").unwrap();
+    assert_eq!(loaded, "Hello World!");
+}
+
+#[cfg(all(feature = "gzip", feature = "json"))]
+#[test]
+fn gz_stream_loader() {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"[1, 2, 3]").unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let loaded: Vec<i32> = GzLoader::<JsonLoader>::load_stream(&mut &compressed[..], "").unwrap();
+    assert_eq!(loaded, [1, 2, 3]);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gz_loader_err() {
+    let loaded: Result<String, _> = GzLoader::<StringLoader>::load(raw("Hello World!"), "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn lz4_loader_ok() {
+    use std::io::Write;
+
+    let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+    encoder.write_all(b"Hello World!").unwrap();
+    let raw = encoder.finish().unwrap().into();
+
+    let loaded: String = Lz4Loader::<StringLoader>::load(raw, "").unwrap();
+    assert_eq!(loaded, "Hello World!");
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn lz4_loader_err() {
+    let loaded: Result<String, _> = Lz4Loader::<StringLoader>::load(raw("Hello World!"), "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_loader_ok() {
+    let raw = zstd_crate::stream::encode_all(&b"Hello World!"[..], 0).unwrap().into();
+
+    let loaded: String = ZstdLoader::<StringLoader>::load(raw, "").unwrap();
+    assert_eq!(loaded, "Hello World!");
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_loader_err() {
+    let loaded: Result<String, _> = ZstdLoader::<StringLoader>::load(raw("Hello World!"), "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "checksum")]
+mod checksum {
+    use super::*;
+
+    fn with_checksum<C: Checksum>(data: &[u8]) -> Vec<u8> {
+        [&C::compute(data), data].concat()
+    }
+
+    #[test]
+    fn crc32_loader_ok() {
+        let raw = with_checksum::<Crc32>(b"Hello World!");
+        assert_eq!(raw[..4], [0x1c, 0x29, 0x1c, 0xa3]);
+
+        let loaded: String = ChecksumLoader::<Crc32, StringLoader>::load(raw.into(), "").unwrap();
+        assert_eq!(loaded, "Hello World!");
+    }
+
+    #[test]
+    fn sha256_loader_ok() {
+        let raw = with_checksum::<Sha256>(b"Hello World!");
+
+        let loaded: String = ChecksumLoader::<Sha256, StringLoader>::load(Cow::Borrowed(&raw), "").unwrap();
+        assert_eq!(loaded, "Hello World!");
+    }
+
+    #[test]
+    fn checksum_loader_err() {
+        let mut corrupted = with_checksum::<Sha256>(b"Hello World!");
+        *corrupted.last_mut().unwrap() = b'?';
+
+        let loaded: Result<String, _> = ChecksumLoader::<Sha256, StringLoader>::load(corrupted.into(), "");
+        assert!(loaded.unwrap_err().to_string().starts_with("SHA-256 checksum mismatch"));
+
+        let loaded: Result<String, _> = ChecksumLoader::<Crc32, StringLoader>::load(raw("abc"), "");
+        assert!(loaded.is_err());
+    }
+}
+
+#[cfg(feature = "encryption")]
+mod encryption {
+    use super::*;
+    use chacha20poly1305::{aead::{Aead, KeyInit}, ChaCha20Poly1305};
+
+    struct Key;
+
+    impl DecryptionKey for Key {
+        fn key() -> [u8; 32] {
+            [42; 32]
+        }
+    }
+
+    struct WrongKey;
+
+    impl DecryptionKey for WrongKey {
+        fn key() -> [u8; 32] {
+            [0; 32]
+        }
+    }
+
+    fn encrypt(content: &[u8]) -> Vec<u8> {
+        let nonce: [u8; 12] = rand::random();
+        let cipher = ChaCha20Poly1305::new(&Key::key().into());
+        let encrypted = cipher.encrypt(&nonce.into(), content).unwrap();
+        [&nonce[..], &encrypted].concat()
+    }
+
+    #[test]
+    fn encrypted_loader_ok() {
+        let raw = encrypt(b"Hello World!").into();
+
+        let loaded: String = EncryptedLoader::<Key, StringLoader>::load(raw, "").unwrap();
+        assert_eq!(loaded, "Hello World!");
+    }
+
+    #[test]
+    fn encrypted_loader_wrong_key() {
+        let raw = encrypt(b"Hello World!").into();
+
+        let loaded: Result<String, _> = EncryptedLoader::<WrongKey, StringLoader>::load(raw, "");
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn encrypted_loader_err() {
+        let mut encrypted = encrypt(b"Hello World!");
+        encrypted[15] ^= 1;
+
+        let loaded: Result<String, _> = EncryptedLoader::<Key, StringLoader>::load(encrypted.into(), "");
+        assert!(loaded.is_err());
+
+        let loaded: Result<String, _> = EncryptedLoader::<Key, StringLoader>::load(raw("short"), "");
+        assert!(loaded.is_err());
+    }
+}
+
+#[cfg(feature = "signature")]
+mod signature {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const SIGNING_KEY: [u8; 32] = [7; 32];
+
+    struct Key;
+
+    impl PublicKey for Key {
+        fn public_key() -> [u8; 32] {
+            SigningKey::from_bytes(&SIGNING_KEY).verifying_key().to_bytes()
+        }
+    }
+
+    fn sign(data: &[u8], key: [u8; 32]) -> Vec<u8> {
+        let signature = SigningKey::from_bytes(&key).sign(data);
+        [&signature.to_bytes(), data].concat()
+    }
+
+    #[test]
+    fn signed_loader_ok() {
+        let raw = sign(b"Hello World!", SIGNING_KEY).into();
+
+        let loaded: String = SignedLoader::<Key, StringLoader>::load(raw, "").unwrap();
+        assert_eq!(loaded, "Hello World!");
+    }
+
+    #[test]
+    fn signed_loader_wrong_key() {
+        let raw = sign(b"Hello World!", [8; 32]).into();
+
+        let loaded: Result<String, _> = SignedLoader::<Key, StringLoader>::load(raw, "");
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn signed_loader_err() {
+        let mut tampered = sign(b"Hello World!", SIGNING_KEY);
+        *tampered.last_mut().unwrap() = b'?';
+
+        let loaded: Result<String, _> = SignedLoader::<Key, StringLoader>::load(tampered.into(), "");
+        assert!(loaded.is_err());
+
+        let loaded: Result<String, _> = SignedLoader::<Key, StringLoader>::load(raw("Hello World!"), "");
+        assert!(loaded.is_err());
+    }
+}
+
 cfg_if::cfg_if! { if #[cfg(feature = "serde")] {
     use serde::{Serialize, Deserialize};
     use rand::{
@@ -88,6 +609,9 @@ cfg_if::cfg_if! { if #[cfg(feature = "serde")] {
 
     macro_rules! test_loader {
         ($name_ok:ident, $name_err:ident, $loader:ty, $ser:expr) => {
+            test_loader!($name_ok, $name_err, $loader, $ser, "\x12ec\x4b");
+        };
+        ($name_ok:ident, $name_err:ident, $loader:ty, $ser:expr, $invalid:expr) => {
             #[test]
             fn $name_ok() {
                 let point = rand::random::<Point>();
@@ -100,32 +624,1150 @@ cfg_if::cfg_if! { if #[cfg(feature = "serde")] {
 
             #[test]
             fn $name_err() {
-                let raw = raw("\x12ec\x4b".into());
+                let raw = raw($invalid);
                 let loaded: Result<Point, _> = <$loader>::load(raw, "");
 
                 assert!(loaded.is_err());
             }
         }
     }
+
+    macro_rules! test_saver {
+        ($name:ident, $saver:ty, $loader:ty) => {
+            #[test]
+            fn $name() {
+                use crate::saver::Saver;
+
+                let point = rand::random::<Point>();
+                let raw = <$saver>::save(&point, "").unwrap().into();
+
+                let loaded: Point = <$loader>::load(raw, "").unwrap();
+
+                assert_eq!(loaded, point);
+            }
+        }
+    }
 }}
 
 #[cfg(feature = "bincode")]
-test_loader!(bincode_loader_ok, bincode_loader_err, BincodeLoader, serde_bincode::serialize);
-
+test_saver!(bincode_saver, crate::saver::BincodeSaver, BincodeLoader);
 #[cfg(feature = "cbor")]
-test_loader!(cbor_loader_ok, cbor_loader_err, CborLoader, serde_cbor::to_vec);
+test_saver!(cbor_saver, crate::saver::CborSaver, CborLoader);
+#[cfg(feature = "json")]
+test_saver!(json_saver, crate::saver::JsonSaver, JsonLoader);
+#[cfg(feature = "msgpack")]
+test_saver!(msgpack_saver, crate::saver::MessagePackSaver, MessagePackLoader);
+#[cfg(feature = "postcard")]
+test_saver!(postcard_saver, crate::saver::PostcardSaver, PostcardLoader);
+#[cfg(feature = "ron")]
+test_saver!(ron_saver, crate::saver::RonSaver, RonLoader);
+#[cfg(feature = "toml")]
+test_saver!(toml_saver, crate::saver::TomlSaver, TomlLoader);
+#[cfg(feature = "yaml")]
+test_saver!(yaml_saver, crate::saver::YamlSaver, YamlLoader);
 
 #[cfg(feature = "json")]
-test_loader!(json_loader_ok, json_loader_err, JsonLoader, serde_json::to_vec);
+mod versioned_json {
+    use super::*;
 
-#[cfg(feature = "msgpack")]
-test_loader!(msgpack_loader_ok, msgpack_err, MessagePackLoader, serde_msgpack::encode::to_vec);
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Monster {
+        name: String,
+        health: u32,
+    }
 
-#[cfg(feature = "ron")]
-test_loader!(ron_loader_ok, ron_loader_err, RonLoader, |p| serde_ron::ser::to_string(p).map(String::into_bytes));
+    fn rename_hp(doc: &mut serde_json::Value) -> Result<(), BoxedError> {
+        let map = doc.as_object_mut().ok_or("expected an object")?;
+        let hp = map.remove("hp").ok_or("missing hp")?;
+        map.insert("health".into(), hp);
+        Ok(())
+    }
 
-#[cfg(feature = "toml")]
-test_loader!(toml_loader_ok, toml_loader_err, TomlLoader, serde_toml::ser::to_vec);
+    fn double_health(doc: &mut serde_json::Value) -> Result<(), BoxedError> {
+        let health = doc["health"].as_u64().ok_or("missing health")?;
+        doc["health"] = (health * 2).into();
+        Ok(())
+    }
+
+    impl Versioned for Monster {
+        type Document = serde_json::Value;
+        const MIGRATIONS: &'static [Migration<serde_json::Value>] = &[rename_hp, double_health];
+    }
+
+    type MonsterLoader = VersionedLoader<JsonLoader>;
+
+    #[test]
+    fn versioned_loader_ok() {
+        let expected = Monster { name: "bat".into(), health: 10 };
+
+        let loaded: Monster = MonsterLoader::load(raw(r#"{"name":"bat","hp":5}"#), "").unwrap();
+        assert_eq!(loaded, expected);
+
+        let loaded: Monster = MonsterLoader::load(raw(r#"{"version":1,"name":"bat","health":5}"#), "").unwrap();
+        assert_eq!(loaded, expected);
+
+        let loaded: Monster = MonsterLoader::load(raw(r#"{"version":2,"name":"bat","health":10}"#), "").unwrap();
+        assert_eq!(loaded, expected);
+    }
+
+    #[test]
+    fn versioned_loader_err() {
+        let invalid = [
+            r#"{"version":3,"name":"bat","health":10}"#,
+            r#"{"version":"1","name":"bat","health":10}"#,
+            r#"{"version":0,"name":"bat","health":5}"#,
+        ];
+
+        for content in &invalid {
+            let loaded: Result<Monster, _> = MonsterLoader::load(raw(content), "");
+            assert!(loaded.is_err(), "{}", content);
+        }
+    }
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn versioned_loader_ron() {
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Config {
+        name: String,
+        size: u32,
+    }
+
+    fn add_size(doc: &mut serde_ron::Value) -> Result<(), BoxedError> {
+        if let serde_ron::Value::Map(map) = doc {
+            map.insert(serde_ron::Value::String("size".into()), serde_ron::Value::Number(1.into()));
+        }
+        Ok(())
+    }
+
+    impl Versioned for Config {
+        type Document = serde_ron::Value;
+        const MIGRATIONS: &'static [Migration<serde_ron::Value>] = &[add_size];
+    }
+
+    let loaded: Config = VersionedLoader::<RonLoader>::load(raw("(name: \"a\")"), "").unwrap();
+    assert_eq!(loaded, Config { name: "a".into(), size: 1 });
+
+    let loaded: Config = VersionedLoader::<RonLoader>::load(raw("(version: 1, name: \"b\", size: 4)"), "").unwrap();
+    assert_eq!(loaded, Config { name: "b".into(), size: 4 });
+}
+
+#[cfg(feature = "bincode")]
+test_loader!(bincode_loader_ok, bincode_loader_err, BincodeLoader, serde_bincode::serialize);
+
+#[cfg(feature = "bincode")]
+mod bincode_with {
+    use super::*;
+    use serde_bincode::{
+        Options,
+        config::{BigEndian, VarintEncoding, WithOtherEndian, WithOtherIntEncoding},
+    };
+
+    struct Varint;
+
+    impl BincodeOptions for Varint {
+        type Options = WithOtherEndian<
+            WithOtherIntEncoding<serde_bincode::DefaultOptions, VarintEncoding>,
+            BigEndian,
+        >;
+
+        fn options() -> Self::Options {
+            serde_bincode::DefaultOptions::new()
+                .with_varint_encoding()
+                .with_big_endian()
+        }
+    }
+
+    struct Strict;
+
+    impl BincodeOptions for Strict {
+        type Options = serde_bincode::DefaultOptions;
+
+        fn options() -> Self::Options {
+            serde_bincode::DefaultOptions::new()
+        }
+    }
+
+    test_loader!(bincode_with_loader_ok, bincode_with_loader_err, BincodeLoaderWith<Varint>, |p| Varint::options().serialize(p), "");
+
+    #[test]
+    fn bincode_with_trailing_bytes() {
+        let mut raw = Strict::options().serialize("Hello World!").unwrap();
+        raw.push(0);
+
+        let loaded: Result<String, _> = BincodeLoaderWith::<Strict>::load(raw.into(), "");
+        assert!(loaded.is_err());
+    }
+}
+
+#[cfg(feature = "cbor")]
+test_loader!(cbor_loader_ok, cbor_loader_err, CborLoader, serde_cbor::to_vec);
+
+#[cfg(feature = "json")]
+test_loader!(json_loader_ok, json_loader_err, JsonLoader, serde_json::to_vec);
+
+#[cfg(feature = "msgpack")]
+test_loader!(msgpack_loader_ok, msgpack_err, MessagePackLoader, serde_msgpack::encode::to_vec);
+
+#[cfg(feature = "msgpack")]
+mod msgpack_with {
+    use super::*;
+    use crate::saver::{MessagePackSaverWith, Saver};
+    use std::net::{IpAddr, Ipv4Addr};
+
+    struct HumanReadable;
+
+    impl MessagePackOptions for HumanReadable {
+        const HUMAN_READABLE: bool = true;
+    }
+
+    struct Positional;
+
+    impl MessagePackOptions for Positional {
+        const NAMED_FIELDS: bool = false;
+    }
+
+    test_loader!(msgpack_with_loader_ok, msgpack_with_loader_err, MessagePackLoaderWith<HumanReadable>, |p| MessagePackSaverWith::<HumanReadable>::save(p, ""));
+
+    #[test]
+    fn msgpack_with_human_readable() {
+        let raw = serde_msgpack::encode::to_vec("127.0.0.1").unwrap();
+
+        let loaded: IpAddr = MessagePackLoaderWith::<HumanReadable>::load(raw.clone().into(), "").unwrap();
+        assert_eq!(loaded, IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+        let loaded: Result<IpAddr, _> = MessagePackLoader::load(raw.into(), "");
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn msgpack_with_positional_fields() {
+        let point = Point { x: 1, y: 2 };
+
+        let raw = MessagePackSaverWith::<Positional>::save(&point, "").unwrap();
+        assert_eq!(raw, [0x92, 1, 2]);
+        let loaded: Point = MessagePackLoaderWith::<Positional>::load(raw.into(), "").unwrap();
+        assert_eq!(loaded, point);
+
+        let raw = MessagePackSaverWith::<HumanReadable>::save(&point, "").unwrap();
+        assert_eq!(raw[0], 0x82);
+        let loaded: Point = MessagePackLoader::load(raw.into(), "").unwrap();
+        assert_eq!(loaded, point);
+    }
+}
+
+#[cfg(feature = "postcard")]
+test_loader!(postcard_loader_ok, postcard_loader_err, PostcardLoader, serde_postcard::to_allocvec, "");
+
+#[cfg(feature = "ron")]
+test_loader!(ron_loader_ok, ron_loader_err, RonLoader, |p| serde_ron::ser::to_string(p).map(String::into_bytes));
+
+#[cfg(feature = "ron")]
+mod ron_with {
+    use super::*;
+    use serde_ron::extensions::Extensions;
+
+    struct ImplicitSome;
+
+    impl RonOptions for ImplicitSome {
+        fn options() -> serde_ron::Options {
+            serde_ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+        }
+    }
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Optional {
+        x: Option<i32>,
+    }
+
+    test_loader!(ron_with_loader_ok, ron_with_loader_err, RonLoaderWith<ImplicitSome>, |p| serde_ron::ser::to_string(p).map(String::into_bytes));
+
+    #[test]
+    fn ron_with_extension() {
+        let loaded: Optional = RonLoaderWith::<ImplicitSome>::load(raw("(x: 5)"), "").unwrap();
+        assert_eq!(loaded, Optional { x: Some(5) });
+
+        let loaded: Result<Optional, _> = RonLoader::load(raw("(x: 5)"), "");
+        assert!(loaded.is_err());
+    }
+}
+
+#[cfg(feature = "toml")]
+test_loader!(toml_loader_ok, toml_loader_err, TomlLoader, serde_toml::ser::to_vec);
+
+#[cfg(feature = "toml")]
+test_loader!(toml_spanned_loader_ok, toml_spanned_loader_err, TomlSpannedLoader, serde_toml::ser::to_vec);
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_spanned_loader_position() {
+    let raw = raw("x = 1\ny 2\n");
+    let err = TomlSpannedLoader::load(raw, "").map(|_: Point| ()).unwrap_err();
+    let err = err.downcast::<TomlSpannedError>().unwrap();
+
+    assert_eq!(err.line(), Some(2));
+    assert_eq!(err.column(), Some(3));
+    assert!(err.to_string().ends_with("2 | y 2\n  |   ^"));
+}
 
 #[cfg(feature = "yaml")]
 test_loader!(yaml_loader_ok, yaml_loader_err, YamlLoader, serde_yaml::to_vec);
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_multi_loader_ok() {
+    let raw = raw("x: 1\ny: 2\n---\nx: -3\ny: 4\n");
+    let loaded: Vec<Point> = YamlMultiLoader::load(raw, "").unwrap();
+
+    assert_eq!(loaded, [Point { x: 1, y: 2 }, Point { x: -3, y: 4 }]);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_multi_loader_err() {
+    let raw = raw("x: 1\ny: 2\n---\nx: 3\n");
+    let loaded: Result<Vec<Point>, _> = YamlMultiLoader::load(raw, "");
+
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn fluent_loader_ok() {
+    let raw = raw("hello = Hello, { $name }!\n");
+    let resource: fluent_bundle::FluentResource = FluentLoader::load(raw, "").unwrap();
+
+    let mut bundle = fluent_bundle::FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).unwrap();
+
+    let mut args = fluent_bundle::FluentArgs::new();
+    args.set("name", "World");
+    let message = bundle.get_message("hello").unwrap().value().unwrap();
+    let text = bundle.format_pattern(message, Some(&args), &mut Vec::new());
+
+    assert_eq!(text, "Hello, World!");
+}
+
+#[cfg(feature = "fluent")]
+#[test]
+fn fluent_loader_err() {
+    let raw = raw("hello = { $name\n");
+    let loaded: Result<fluent_bundle::FluentResource, _> = FluentLoader::load(raw, "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image_loader_ok() {
+    let image = image_crate::RgbaImage::from_pixel(2, 3, image_crate::Rgba([1, 2, 3, 4]));
+    let mut png = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut png), image_crate::ImageFormat::Png).unwrap();
+
+    let loaded: image_crate::DynamicImage = ImageLoader::load(png[..].into(), "png").unwrap();
+    assert_eq!(loaded.to_rgba8(), image);
+
+    let loaded: image_crate::DynamicImage = ImageLoader::load(png.into(), "").unwrap();
+    assert_eq!(loaded.to_rgba8(), image);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image_loader_err() {
+    let loaded: Result<image_crate::DynamicImage, _> = ImageLoader::load(raw("not an image"), "png");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "audio")]
+#[test]
+fn sound_loader_wav() {
+    use crate::asset::Sound;
+
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 8000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut wav = std::io::Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut wav, spec).unwrap();
+    for &s in &[0i16, 16384, -16384, -32768] {
+        writer.write_sample(s).unwrap();
+    }
+    writer.finalize().unwrap();
+    let wav = wav.into_inner();
+
+    let expected = Sound {
+        channels: 2,
+        sample_rate: 8000,
+        samples: vec![0.0, 0.5, -0.5, -1.0],
+    };
+
+    let loaded: Sound = SoundLoader::load(wav[..].into(), "wav").unwrap();
+    assert_eq!(loaded, expected);
+    assert_eq!(loaded.duration(), std::time::Duration::from_micros(250));
+
+    let loaded: Sound = SoundLoader::load(wav.into(), "").unwrap();
+    assert_eq!(loaded, expected);
+}
+
+#[cfg(feature = "audio")]
+#[test]
+fn sound_loader_err() {
+    use crate::asset::Sound;
+
+    for ext in &["wav", "ogg", "flac", ""] {
+        let loaded: Result<Sound, _> = SoundLoader::load(raw("not a sound"), ext);
+        assert!(loaded.is_err());
+    }
+}
+
+#[cfg(feature = "naga")]
+#[test]
+fn naga_loader_ok() {
+    let raw = raw("@fragment fn main() -> @location(0) vec4<f32> { return vec4<f32>(1.0); }");
+    let module: naga_crate::Module = NagaLoader::load(raw, "wgsl").unwrap();
+    assert_eq!(module.entry_points.len(), 1);
+}
+
+#[cfg(feature = "naga")]
+#[test]
+fn naga_loader_err() {
+    let invalid = [
+        ("fn main( {}", "wgsl"),
+        ("fn main() -> f32 { return 1u; }", "wgsl"),
+        ("fn main() -> f32 { return 1u; }", ""),
+        ("not spirv", "spv"),
+    ];
+
+    for &(content, ext) in &invalid {
+        let loaded: Result<naga_crate::Module, _> = NagaLoader::load(raw(content), ext);
+        assert!(loaded.is_err(), "{}", content);
+    }
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_loader_ok() {
+    let regex: regex_crate::Regex = RegexLoader::load(raw("^a+b$\n"), "regex").unwrap();
+    assert!(regex.is_match("aab"));
+    assert!(!regex.is_match("aab\n"));
+
+    let set: regex_crate::RegexSet = RegexLoader::load(raw("^a\r\n\nb$\n"), "regex").unwrap();
+    assert_eq!(set.len(), 2);
+    assert!(set.is_match("ab"));
+    assert!(!set.is_match("c"));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_loader_err() {
+    let loaded: Result<regex_crate::Regex, _> = RegexLoader::load(raw("(a"), "regex");
+    assert!(loaded.is_err());
+
+    let loaded: Result<regex_crate::RegexSet, _> = RegexLoader::load(raw("a\n[b"), "regex");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "rhai")]
+#[test]
+fn rhai_loader_ok() {
+    let script: crate::asset::RhaiScript = RhaiLoader::load(raw("let x = 40; x + 2"), "rhai").unwrap();
+    let result: i64 = rhai_crate::Engine::new().eval_ast(&script.0).unwrap();
+    assert_eq!(result, 42);
+}
+
+#[cfg(feature = "rhai")]
+#[test]
+fn rhai_loader_err() {
+    let loaded: Result<crate::asset::RhaiScript, _> = RhaiLoader::load(raw("let = ;"), "rhai");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "svg")]
+#[test]
+fn svg_loader_ok() {
+    let raw = raw(concat!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">"#,
+        r#"<rect x="1" y="2" width="5" height="5" fill="red"/>"#,
+        r#"<image href="missing.png" width="4" height="4"/>"#,
+        r#"</svg>"#,
+    ));
+    let svg: crate::asset::Svg = SvgLoader::load(raw, "svg").unwrap();
+
+    let size = svg.0.size();
+    assert_eq!((size.width(), size.height()), (20.0, 10.0));
+    assert_eq!(svg.0.root().children().len(), 1);
+}
+
+#[cfg(feature = "svg")]
+#[test]
+fn svg_loader_err() {
+    let loaded: Result<crate::asset::Svg, _> = SvgLoader::load(raw("<svg"), "svg");
+    assert!(loaded.is_err());
+
+    let loaded: Result<crate::asset::Svg, _> = SvgLoader::load(raw("<html></html>"), "svg");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "templates")]
+#[test]
+fn template_loader_handlebars() {
+    let raw = raw("Hello {{name}}, you have {{#if gold}}{{gold}}{{else}}no{{/if}} gold!");
+    let template: crate::asset::HandlebarsTemplate = TemplateLoader::load(raw, "hbs").unwrap();
+
+    #[derive(serde::Serialize)]
+    struct Data {
+        name: &'static str,
+        gold: u32,
+    }
+
+    let text = template.render(&Data { name: "<Bob>", gold: 3 }).unwrap();
+    assert_eq!(text, "Hello <Bob>, you have 3 gold!");
+
+    let loaded: Result<crate::asset::HandlebarsTemplate, _> = TemplateLoader::load(self::raw("{{#if x}}"), "hbs");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "templates")]
+#[test]
+fn template_loader_tera() {
+    let raw = raw("Hello {{ name }}{% for i in items %}, {{ i }}{% endfor %}!");
+    let template: crate::asset::TeraTemplate = TemplateLoader::load(raw, "tera").unwrap();
+
+    #[derive(serde::Serialize)]
+    struct Data {
+        name: &'static str,
+        items: [u32; 2],
+    }
+
+    let text = template.render(&Data { name: "<Bob>", items: [1, 2] }).unwrap();
+    assert_eq!(text, "Hello <Bob>, 1, 2!");
+
+    let loaded: Result<crate::asset::TeraTemplate, _> = TemplateLoader::load(self::raw("{% for %}"), "tera");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "wasm-plugin")]
+#[test]
+fn wasm_module_loader_ok() {
+    let raw: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+        // Type section: `fn()`
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+        // Import section: `env.log`
+        0x02, 0x0b, 0x01, 0x03, b'e', b'n', b'v', 0x03, b'l', b'o', b'g', 0x00, 0x00,
+        // Function section
+        0x03, 0x02, 0x01, 0x00,
+        // Export section: `run`
+        0x07, 0x07, 0x01, 0x03, b'r', b'u', b'n', 0x00, 0x01,
+        // Code section
+        0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+    ];
+    let module: crate::asset::WasmModule = WasmModuleLoader::load(raw.into(), "wasm").unwrap();
+
+    assert_eq!(module.bytes, raw);
+    assert_eq!(module.imports, [("env".to_owned(), "log".to_owned())]);
+    assert_eq!(module.exports, ["run"]);
+}
+
+#[cfg(feature = "wasm-plugin")]
+#[test]
+fn wasm_module_loader_err() {
+    let invalid: &[&[u8]] = &[
+        b"not wasm",
+        // The function leaves a value on the stack
+        &[
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00,
+            0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, 0x00, 0x0b,
+        ],
+    ];
+
+    for content in invalid {
+        let loaded: Result<crate::asset::WasmModule, _> = WasmModuleLoader::load((*content).into(), "wasm");
+        assert!(loaded.is_err());
+    }
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn obj_loader_ok() {
+    use crate::asset::Mesh;
+
+    let raw = raw("
+        o square
+        v 0 0 0
+        v 1 0 0
+        v 1 1 0
+        v 0 1 0
+        vt 0 0
+        vt 1 1
+        vn 0 0 1
+        f 1/1/1 2/1/1 3/2/1 4/2/1
+
+        o triangle
+        v 0 0 1
+        v 1 0 1
+        v 0 1 1
+        f 5 6 7
+    ");
+    let mesh: Mesh = ObjLoader::load(raw, "obj").unwrap();
+
+    assert_eq!(mesh.positions.len(), 7);
+    assert_eq!(mesh.positions[4], [0.0, 0.0, 1.0]);
+    assert_eq!(mesh.normals[..4], [[0.0, 0.0, 1.0]; 4]);
+    assert_eq!(mesh.uvs[..4], [[0.0, 0.0], [0.0, 0.0], [1.0, 1.0], [1.0, 1.0]]);
+    assert_eq!(mesh.indices, [0, 1, 2, 0, 2, 3, 4, 5, 6]);
+
+    // Objects without some attributes get zeroed ones
+    assert_eq!(mesh.normals.len(), 7);
+    assert_eq!(mesh.normals[4..], [[0.0; 3]; 3]);
+    assert_eq!(mesh.uvs.len(), 7);
+    assert_eq!(mesh.uvs[4..], [[0.0; 2]; 3]);
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn obj_loader_missing_attributes() {
+    use crate::asset::Mesh;
+
+    let raw = raw("
+        o plain
+        v 0 0 0
+        v 1 0 0
+        v 0 1 0
+        f 1 2 3
+
+        o textured
+        v 0 0 1
+        v 1 0 1
+        v 0 1 1
+        vt 0.5 0.5
+        f 4/1 5/1 6/1
+    ");
+    let mesh: Mesh = ObjLoader::load(raw, "obj").unwrap();
+
+    assert_eq!(mesh.positions.len(), 6);
+    assert!(mesh.normals.is_empty());
+    assert_eq!(mesh.uvs, [[0.0, 0.0], [0.0, 0.0], [0.0, 0.0], [0.5, 0.5], [0.5, 0.5], [0.5, 0.5]]);
+    assert_eq!(mesh.indices, [0, 1, 2, 3, 4, 5]);
+}
+
+#[cfg(feature = "obj")]
+#[test]
+fn obj_loader_err() {
+    let loaded: Result<crate::asset::Mesh, _> = ObjLoader::load(raw("f 1 2 3"), "obj");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "gltf")]
+#[test]
+fn gltf_loader_ok() {
+    let cache = crate::AssetCache::new("assets").unwrap();
+    let scene = cache.load::<crate::asset::Gltf>("test.gltf.scene").unwrap();
+    let scene = scene.read();
+
+    assert_eq!(scene.document.buffers().len(), 2);
+    assert_eq!(scene.buffers, [&[1, 2, 3, 4][..], &[5, 6, 7]]);
+    assert_eq!(scene.images, [&b"png!"[..], &[6, 7]]);
+}
+
+#[cfg(feature = "gltf")]
+#[test]
+fn gltf_loader_err() {
+    use crate::asset::ContextualAsset;
+
+    let cache = crate::AssetCache::new("assets").unwrap();
+
+    let invalid = [
+        r#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":4,"uri":"missing.bin"}]}"#,
+        r#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":4,"uri":"../../../scene.bin"}]}"#,
+        r#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":4,"uri":"https://example.com/scene.bin"}]}"#,
+        r#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":8,"uri":"scene.bin"}]}"#,
+        r#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":1,"uri":"data:,AA=="}]}"#,
+        "not gltf",
+    ];
+
+    for content in &invalid {
+        let loaded = <crate::asset::Gltf as ContextualAsset>::Loader::load(raw(content), "gltf", "test.gltf.scene", &cache);
+        assert!(loaded.is_err(), "{}", content);
+    }
+}
+
+#[test]
+fn shader_loader_ok() {
+    let cache = crate::AssetCache::new("assets").unwrap();
+    let shader = cache.load::<crate::asset::Shader>("test.shaders.main").unwrap();
+
+    let expected = "#version 450\n#define INTENSITY 1.0\nvec4 light() { return vec4(INTENSITY); }\n\nvoid main() {\n    color = light();\n}\n";
+    assert_eq!(shader.read().0, expected);
+
+    // Files with the same id but another extension are not cycles
+    let shader = cache.load::<crate::asset::Shader>("test.shaders.pair").unwrap();
+    assert_eq!(shader.read().0, "#define PAIR 1\nfloat pair() { return PAIR; }\n");
+}
+
+#[test]
+fn shader_loader_err() {
+    let cache = crate::AssetCache::new("assets").unwrap();
+
+    assert!(cache.load::<crate::asset::Shader>("test.shaders.cycle_a").is_err());
+
+    let invalid = ["#include \"missing.glsl\"", "#include <light.glsl>", "#include \"../../../x.glsl\""];
+    for content in &invalid {
+        let loaded = <ShaderLoader as ContextualLoader<_>>::load(raw(content), "glsl", "test.shaders.main", &cache);
+        assert!(loaded.is_err(), "{}", content);
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn aseprite_loader_hash() {
+    use crate::asset::{AnimationDirection, Rect, SpriteSheet};
+
+    let frame = |x| format!(
+        r#"{{"frame":{{"x":{},"y":0,"w":16,"h":16}},"rotated":false,"trimmed":true,"spriteSourceSize":{{"x":1,"y":2,"w":16,"h":16}},"sourceSize":{{"w":18,"h":18}},"duration":100}}"#,
+        x,
+    );
+    let raw = format!(
+        r##"{{"frames":{{"hero 10":{},"hero 2":{},"hero 1":{}}},"meta":{{"image":"hero.png","size":{{"w":48,"h":16}},"frameTags":[{{"name":"walk","from":1,"to":2,"direction":"pingpong","repeat":"3"}}],"slices":[{{"name":"hit","color":"#0000ffff","keys":[{{"frame":0,"bounds":{{"x":2,"y":3,"w":4,"h":5}},"pivot":{{"x":-1,"y":1}}}}]}}]}}}}"##,
+        frame(0), frame(16), frame(32),
+    );
+    let sheet: SpriteSheet = AsepriteLoader::load(raw.into_bytes().into(), "json").unwrap();
+
+    assert_eq!(sheet.image, "hero.png");
+    assert_eq!(sheet.size, (48, 16));
+
+    let names: Vec<_> = sheet.frames.iter().map(|f| &*f.name).collect();
+    assert_eq!(names, ["hero 10", "hero 2", "hero 1"]);
+    assert_eq!(sheet.frames[1].rect, Rect { x: 16, y: 0, width: 16, height: 16 });
+    assert_eq!(sheet.frames[1].source_rect.y, 2);
+    assert!(sheet.frames[1].trimmed);
+    assert_eq!(sheet.frames[1].duration, std::time::Duration::from_millis(100));
+
+    let walk = sheet.tag("walk").unwrap();
+    assert_eq!(walk.direction, AnimationDirection::PingPong);
+    assert_eq!(walk.repeat, Some(3));
+    assert_eq!(sheet.tag_frames("walk").unwrap().len(), 2);
+
+    let hit = sheet.slice("hit").unwrap();
+    assert_eq!(hit.keys[0].bounds, Rect { x: 2, y: 3, width: 4, height: 5 });
+    assert_eq!(hit.keys[0].center, None);
+    assert_eq!(hit.keys[0].pivot, Some((-1, 1)));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn aseprite_loader_array() {
+    let raw = raw(r#"{
+        "frames": [
+            {"filename":"a","frame":{"x":0,"y":0,"w":8,"h":8},"spriteSourceSize":{"x":0,"y":0,"w":8,"h":8},"sourceSize":{"w":8,"h":8},"duration":50},
+            {"filename":"b","frame":{"x":8,"y":0,"w":8,"h":8},"spriteSourceSize":{"x":0,"y":0,"w":8,"h":8},"sourceSize":{"w":8,"h":8},"duration":60}
+        ],
+        "meta": {"image":"sheet.png","size":{"w":16,"h":8},"frameTags":[{"name":"all","from":0,"to":1,"direction":"reverse"}]}
+    }"#);
+    let sheet: crate::asset::SpriteSheet = AsepriteLoader::load(raw, "json").unwrap();
+
+    assert_eq!(sheet.frames.len(), 2);
+    assert_eq!(sheet.frames[1].name, "b");
+    assert_eq!(sheet.tags[0].direction, crate::asset::AnimationDirection::Reverse);
+    assert_eq!(sheet.tags[0].repeat, None);
+    assert!(sheet.slices.is_empty());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn aseprite_loader_err() {
+    let invalid = [
+        r#"{"frames":[]}"#,
+        r#"{"frames":[],"meta":{"size":{"w":1,"h":1}}}"#,
+        r#"{"frames":[{"filename":"a"}],"meta":{"image":"a.png","size":{"w":1,"h":1}}}"#,
+        r#"{"frames":[],"meta":{"image":"a.png","size":{"w":1,"h":1},"frameTags":[{"name":"t","from":0,"to":0}]}}"#,
+        r#"{"frames":[],"meta":{"image":"a.png","size":{"w":1,"h":1},"frameTags":[{"name":"t","from":0,"to":0,"direction":"up"}]}}"#,
+        r#"{"frames":42,"meta":{}}"#,
+    ];
+
+    for content in &invalid {
+        let loaded: Result<crate::asset::SpriteSheet, _> = AsepriteLoader::load(raw(content), "json");
+        assert!(loaded.is_err(), "{}", content);
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn ldtk_loader_ok() {
+    let cache = crate::AssetCache::new("assets").unwrap();
+    let project = cache.load::<crate::asset::LdtkProject>("test.ldtk.world").unwrap();
+    let project = project.read();
+
+    assert_eq!(project.json_version, "1.5.3");
+    assert_eq!(project.levels.len(), 2);
+
+    let level = project.level("Level_0").unwrap();
+    assert_eq!(level.fields, [("music".to_owned(), "intro".into())]);
+    let ground = level.layer("Ground").unwrap();
+    assert_eq!(ground.int_grid, [1, 0]);
+    assert_eq!(ground.tiles[0].source, (16, 0));
+    assert_eq!(ground.tileset_path.as_deref(), Some("tiles.png"));
+
+    let level = project.level("Level_1").unwrap();
+    assert_eq!(level.external_path.as_deref(), Some("world/Level_1.ldtkl"));
+    let player = &level.layer("Entities").unwrap().entities[0];
+    assert_eq!(player.identifier, "Player");
+    assert_eq!((player.position, player.width, player.height), ((8, 16), 16, 24));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn ldtk_loader_without_external() {
+    let raw = std::fs::read("assets/test/ldtk/world.ldtk").unwrap();
+    let project: crate::asset::LdtkProject = <LdtkLoader as Loader<_>>::load(raw.into(), "ldtk").unwrap();
+
+    assert!(project.levels[0].layers.is_some());
+    assert!(project.levels[1].layers.is_none());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn ldtk_loader_err() {
+    let cache = crate::AssetCache::new("assets").unwrap();
+
+    let level = r#"{"identifier":"L","iid":"x","worldX":0,"worldY":0,"pxWid":1,"pxHei":1,"layerInstances":null"#;
+    let invalid = [
+        r#"{"levels":[]}"#.to_owned(),
+        format!(r#"{{"jsonVersion":"1","levels":[{},"externalRelPath":"missing.ldtkl"}}]}}"#, level),
+        format!(r#"{{"jsonVersion":"1","levels":[{},"externalRelPath":"world/Level_1.json"}}]}}"#, level),
+        format!(r#"{{"jsonVersion":"1","levels":[{},"externalRelPath":"world/Level_1.ldtkl"}}]}}"#, level),
+    ];
+
+    for content in &invalid {
+        let loaded = <LdtkLoader as ContextualLoader<crate::asset::LdtkProject>>::load(raw(content), "ldtk", "test.ldtk.world", &cache);
+        assert!(loaded.is_err(), "{}", content);
+    }
+}
+
+#[cfg(feature = "json-schema")]
+mod schema_validated {
+    use super::*;
+    use crate::{AssetCache, Compound, Error, asset::ContextualAsset, source::Source};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Settings {
+        volume: f32,
+        name: Option<String>,
+    }
+
+    impl Schema for Settings {
+        const SCHEMA: &'static str = "test.schema.settings_schema";
+    }
+
+    impl ContextualAsset for Settings {
+        const EXTENSIONS: &'static [&'static str] = &["json", "yaml"];
+        type Loader = SchemaValidatedLoader;
+    }
+
+    impl Compound for Settings {
+        fn load<S: Source>(cache: &AssetCache<S>, id: &str) -> Result<Self, Error> {
+            crate::asset::load_contextual(cache, id)
+        }
+    }
+
+    fn load(content: &str, ext: &str) -> Result<Settings, BoxedError> {
+        let cache = AssetCache::new("assets").unwrap();
+        <SchemaValidatedLoader as ContextualLoader<Settings>>::load(raw(content), ext, "", &cache)
+    }
+
+    #[test]
+    fn schema_validated_loader_ok() {
+        let cache = AssetCache::new("assets").unwrap();
+        let settings = cache.load::<Settings>("test.schema.settings").unwrap();
+        assert_eq!(*settings.read(), Settings { volume: 0.5, name: Some("Default".to_owned()) });
+
+        let settings = load(r#"{"volume": 1}"#, "json").unwrap();
+        assert_eq!(settings, Settings { volume: 1.0, name: None });
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn schema_validated_loader_yaml() {
+        let settings = load("volume: 0.25\nname: Quiet\n", "yaml").unwrap();
+        assert_eq!(settings, Settings { volume: 0.25, name: Some("Quiet".to_owned()) });
+
+        assert!(load("volume: 2\n", "yaml").is_err());
+    }
+
+    #[test]
+    fn schema_validated_loader_err() {
+        let err = load(r#"{"volume": 2, "name": 3}"#, "json").unwrap_err().to_string();
+        assert!(err.contains("/volume: "), "{}", err);
+        assert!(err.contains("/name: "), "{}", err);
+
+        let err = load(r#"{"name": "x"}"#, "json").unwrap_err().to_string();
+        assert!(err.contains("/: "), "{}", err);
+
+        assert!(load("{", "json").is_err());
+
+        let raw = raw(r#"{"type": 5}"#);
+        let loaded: Result<crate::asset::JsonSchema, _> = JsonSchemaLoader::load(raw, "json");
+        assert!(loaded.is_err());
+    }
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn archive_loader_zip() {
+    use std::io::Write;
+
+    let mut writer = zip_crate::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip_crate::write::SimpleFileOptions::default();
+    writer.add_directory("data/", options).unwrap();
+    writer.start_file("data/a.txt", options).unwrap();
+    writer.write_all(b"Hello").unwrap();
+    writer.start_file("b.txt", options.compression_method(zip_crate::CompressionMethod::Deflated)).unwrap();
+    writer.write_all(b"World").unwrap();
+    let raw = writer.finish().unwrap().into_inner();
+
+    let archive: crate::asset::Archive = ArchiveLoader::load(raw.clone().into(), "zip").unwrap();
+    assert_eq!(archive.len(), 2);
+    assert_eq!(archive.get("data/a.txt"), Some(&b"Hello"[..]));
+    assert_eq!(archive.get("b.txt"), Some(&b"World"[..]));
+    assert!(!archive.contains("data"));
+
+    let archive: crate::asset::Archive = ArchiveLoader::load(raw.into(), "pak").unwrap();
+    assert_eq!(archive.len(), 2);
+}
+
+#[cfg(feature = "tar")]
+#[test]
+fn archive_loader_tar() {
+    let mut builder = tar_crate::Builder::new(Vec::new());
+    for (path, content) in &[("./data/a.txt", &b"Hello"[..]), ("b.txt", &b"World"[..])] {
+        let mut header = tar_crate::Header::new_ustar();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, path, *content).unwrap();
+    }
+    let raw = builder.into_inner().unwrap();
+
+    let archive: crate::asset::Archive = ArchiveLoader::load(raw.clone().into(), "tar").unwrap();
+    let mut paths: Vec<_> = archive.paths().collect();
+    paths.sort_unstable();
+    assert_eq!(paths, ["b.txt", "data/a.txt"]);
+    assert_eq!(archive.get("data/a.txt"), Some(&b"Hello"[..]));
+
+    let archive: crate::asset::Archive = ArchiveLoader::load(raw.into(), "pak").unwrap();
+    assert_eq!(archive.len(), 2);
+}
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+#[test]
+fn archive_loader_err() {
+    let loaded: Result<crate::asset::Archive, _> = ArchiveLoader::load(raw("Not an archive"), "pak");
+    assert!(loaded.is_err());
+
+    #[cfg(feature = "zip")]
+    {
+        let loaded: Result<crate::asset::Archive, _> = ArchiveLoader::load(raw("PK\x03\x04"), "zip");
+        assert!(loaded.is_err());
+    }
+}
+
+#[test]
+fn fnt_loader_text() {
+    let raw = raw(concat!(
+        "info face=\"Some Font\" size=-16 bold=0 padding=0,0,0,0\n",
+        "common lineHeight=18 base=14 scaleW=128 scaleH=64 pages=1 packed=0\n",
+        "page id=0 file=\"font 0.png\"\n",
+        "chars count=2\n",
+        "char id=65 x=1 y=2 width=7 height=9 xoffset=0 yoffset=-1 xadvance=8 page=0 chnl=15\n",
+        "char id=86   x=9 y=2 width=7 height=9 xoffset=1 yoffset=0 xadvance=7 page=0 chnl=15\n",
+        "kernings count=1\n",
+        "kerning first=65 second=86 amount=-2\n",
+    ));
+    let font: crate::asset::BitmapFont = FntLoader::load(raw, "fnt").unwrap();
+
+    assert_eq!(font.face, "Some Font");
+    assert_eq!(font.size, -16);
+    assert_eq!((font.line_height, font.base, font.scale_w, font.scale_h), (18, 14, 128, 64));
+    assert_eq!(font.pages, ["font 0.png"]);
+    assert_eq!(font.glyphs.len(), 2);
+    assert_eq!(font.glyph('V').unwrap().x, 9);
+    assert_eq!(font.glyph('A').unwrap().y_offset, -1);
+    assert_eq!(font.kerning('A', 'V'), -2);
+    assert_eq!(font.kerning('V', 'A'), 0);
+}
+
+#[test]
+fn fnt_loader_binary() {
+    let mut raw = b"BMF\x03".to_vec();
+
+    raw.extend_from_slice(&[1, 19, 0, 0, 0]);
+    raw.extend_from_slice(&[16, 0, 0, 0, 100, 0, 1, 0, 0, 0, 0, 1, 1, 0]);
+    raw.extend_from_slice(b"Font\0");
+
+    raw.extend_from_slice(&[2, 15, 0, 0, 0]);
+    raw.extend_from_slice(&[18, 0, 14, 0, 128, 0, 64, 0, 1, 0, 0, 0, 0, 0, 0]);
+
+    raw.extend_from_slice(&[3, 8, 0, 0, 0]);
+    raw.extend_from_slice(b"f_0.png\0");
+
+    raw.extend_from_slice(&[4, 20, 0, 0, 0]);
+    raw.extend_from_slice(&[65, 0, 0, 0, 1, 0, 2, 0, 7, 0, 9, 0, 0, 0, 0xff, 0xff, 8, 0, 0, 15]);
+
+    raw.extend_from_slice(&[5, 10, 0, 0, 0]);
+    raw.extend_from_slice(&[65, 0, 0, 0, 86, 0, 0, 0, 0xfe, 0xff]);
+
+    let font: crate::asset::BitmapFont = FntLoader::load(raw.into(), "fnt").unwrap();
+
+    assert_eq!(font.face, "Font");
+    assert_eq!(font.size, 16);
+    assert_eq!((font.line_height, font.base, font.scale_w, font.scale_h), (18, 14, 128, 64));
+    assert_eq!(font.pages, ["f_0.png"]);
+    let glyph = font.glyph('A').unwrap();
+    assert_eq!((glyph.x, glyph.y, glyph.width, glyph.height), (1, 2, 7, 9));
+    assert_eq!((glyph.y_offset, glyph.x_advance, glyph.channel), (-1, 8, 15));
+    assert_eq!(font.kerning('A', 'V'), -2);
+}
+
+#[test]
+fn fnt_loader_err() {
+    let invalid: &[&[u8]] = &[
+        b"char id=65 x=foo",
+        b"char x=1",
+        b"kerning first=65 second=86",
+        b"page id=18446744073709551615 file=\"a.png\"",
+        b"page id=4000000000 file=\"a.png\"",
+        b"common pages=1\npage id=1 file=\"a.png\"",
+        b"unknown a=1",
+        b"BMF\x02",
+        b"BMF\x03\x02\x0f\x00\x00\x00\x12",
+    ];
+
+    for content in invalid {
+        let loaded: Result<crate::asset::BitmapFont, _> = FntLoader::load((*content).into(), "fnt");
+        assert!(loaded.is_err(), "{:?}", content);
+    }
+}
+
+#[test]
+fn palette_loader_gpl() {
+    let raw = raw(concat!(
+        "GIMP Palette\n",
+        "Name: Some Palette\n",
+        "Columns: 2\n",
+        "# A comment\n",
+        "  0   0   0\tBlack\n",
+        "255 128  64\tOrange\n",
+    ));
+    let palette: crate::asset::Palette = PaletteLoader::load(raw, "gpl").unwrap();
+    assert_eq!(palette.name.as_deref(), Some("Some Palette"));
+    assert_eq!(palette.colors, [[0, 0, 0, 255], [255, 128, 64, 255]]);
+
+    let raw = self::raw("GIMP Palette\nChannels: RGBA\n1 2 3 4\n");
+    let palette: crate::asset::Palette = PaletteLoader::load(raw, "gpl").unwrap();
+    assert_eq!(palette.name, None);
+    assert_eq!(palette.get(0), Some([1, 2, 3, 4]));
+}
+
+#[test]
+fn palette_loader_hex() {
+    let raw = raw("; Some comment\r\nff8040\r\n\r\n#0000ff80\r\n");
+    let palette: crate::asset::Palette = PaletteLoader::load(raw, "hex").unwrap();
+    assert_eq!(palette.name, None);
+    assert_eq!(palette.colors, [[255, 128, 64, 255], [0, 0, 255, 128]]);
+}
+
+#[test]
+fn palette_loader_err() {
+    let invalid = [
+        "GIMP Palette\n0 0\n",
+        "GIMP Palette\n0 0 256\n",
+        "GIMP Palette\nChannels: CMYK\n",
+        "ff80\n",
+        "+f8040\n",
+        "black\n",
+    ];
+
+    for raw in invalid.iter() {
+        let loaded: Result<crate::asset::Palette, _> = PaletteLoader::load(self::raw(raw), "gpl");
+        assert!(loaded.is_err(), "{:?}", raw);
+    }
+}
+
+#[cfg(feature = "tiled")]
+#[test]
+fn tiled_loader_ok() {
+    let cache = crate::AssetCache::new("assets").unwrap();
+
+    let map = cache.load::<crate::asset::TiledMap>("test.tiled.maps.level").unwrap();
+    let map = &map.read().0;
+    assert_eq!((map.width, map.height), (2, 2));
+    assert_eq!(map.layers().len(), 1);
+    assert_eq!(map.tilesets()[0].name, "terrain");
+
+    let image = map.tilesets()[0].image.as_ref().unwrap();
+    assert_eq!(image.source, std::path::Path::new("test/tiled/maps/../tilesets/terrain.png"));
+
+    let tileset = cache.load::<crate::asset::TiledTileset>("test.tiled.tilesets.terrain").unwrap();
+    assert_eq!(tileset.read().0.tilecount, 2);
+}
+
+#[cfg(feature = "tiled")]
+#[test]
+fn tiled_loader_err() {
+    let cache = crate::AssetCache::new("assets").unwrap();
+    assert!(cache.load::<crate::asset::TiledMap>("test.tiled.maps.broken").is_err());
+
+    let loaded: Result<crate::asset::TiledMap, _> = TiledLoader::load(raw("<map"), "tmx", "test.tiled.maps.level", &cache);
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "kdl")]
+#[test]
+fn kdl_loader_ok() {
+    let raw = raw("node 1 \"two\" key=3\nother");
+    let doc: kdl_crate::KdlDocument = KdlLoader::load(raw, "").unwrap();
+
+    let node = doc.get("node").unwrap();
+    assert_eq!(node.entries().len(), 3);
+    assert!(doc.get("other").is_some());
+}
+
+#[cfg(feature = "kdl")]
+#[test]
+fn kdl_loader_err() {
+    let raw = raw("node \"unclosed");
+    let loaded: Result<kdl_crate::KdlDocument, _> = KdlLoader::load(raw, "");
+    assert!(loaded.is_err());
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn markdown_loader_html() {
+    let raw = raw("# Title\n\nSome *text*");
+    let html: String = MarkdownLoader::load(raw, "").unwrap();
+
+    assert_eq!(html, "<h1>Title</h1>\n<p>Some <em>text</em></p>\n");
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn markdown_loader_events() {
+    use pulldown_cmark::{Event, Tag, TagEnd};
+
+    let raw = raw("Some *text*");
+    let events: Vec<Event> = MarkdownLoader::load(raw, "").unwrap();
+
+    assert_eq!(events, [
+        Event::Start(Tag::Paragraph),
+        Event::Text("Some ".into()),
+        Event::Start(Tag::Emphasis),
+        Event::Text("text".into()),
+        Event::End(TagEnd::Emphasis),
+        Event::End(TagEnd::Paragraph),
+    ]);
+}
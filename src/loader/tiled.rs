@@ -0,0 +1,79 @@
+use crate::{
+    AssetCache,
+    BoxedError,
+    asset::{TiledMap, TiledTileset},
+    source::Source,
+};
+
+use super::{
+    ContextualLoader,
+    external::{load_external, resolve_path},
+};
+
+use std::{
+    borrow::Cow,
+    io::{self, Cursor},
+    path::Path,
+};
+
+
+/// Loads Tiled maps and tilesets.
+///
+/// Maps are loaded from `.tmx` files, and tilesets from `.tsx` files. External
+/// tilesets and object templates are resolved relatively to the file that
+/// references them, and recorded as dependencies whatever their extension, so
+/// the map is reloaded when any of them is modified.
+///
+/// Images are not loaded, only their paths are kept. These paths are relative
+/// to the root of the source.
+///
+/// See [`asset::TiledMap`](crate::asset::TiledMap) and
+/// [`asset::TiledTileset`](crate::asset::TiledTileset) for the loaded types.
+///
+/// See trait [`ContextualLoader`] for more informations.
+#[cfg_attr(docsrs, doc(cfg(feature = "tiled")))]
+#[derive(Debug)]
+pub struct TiledLoader(());
+
+impl ContextualLoader<TiledMap> for TiledLoader {
+    fn load<S: Source>(content: Cow<[u8]>, ext: &str, id: &str, cache: &AssetCache<S>) -> Result<TiledMap, BoxedError> {
+        let path = id_to_path(id, ext);
+        let reader = |p: &Path| read(cache, &content, &path, p);
+        Ok(TiledMap(tiled_crate::Loader::with_reader(reader).load_tmx_map(&path)?))
+    }
+}
+
+impl ContextualLoader<TiledTileset> for TiledLoader {
+    fn load<S: Source>(content: Cow<[u8]>, ext: &str, id: &str, cache: &AssetCache<S>) -> Result<TiledTileset, BoxedError> {
+        let path = id_to_path(id, ext);
+        let reader = |p: &Path| read(cache, &content, &path, p);
+        Ok(TiledTileset(tiled_crate::Loader::with_reader(reader).load_tsx_tileset(&path)?))
+    }
+}
+
+/// Converts an asset id into the path given to `tiled`.
+fn id_to_path(id: &str, ext: &str) -> String {
+    let mut path = id.replace('.', "/");
+    path.push('.');
+    path.push_str(ext);
+    path
+}
+
+/// Reads a file requested by `tiled`.
+///
+/// `root` is the path of the file being loaded, which content is already known.
+/// Other paths are converted back into ids and recorded as dependencies.
+fn read<'a, S: Source>(cache: &AssetCache<S>, content: &'a [u8], root: &str, path: &Path) -> io::Result<Cursor<Cow<'a, [u8]>>> {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path == root {
+        return Ok(Cursor::new(Cow::Borrowed(content)));
+    }
+
+    let (id, ext) = resolve_path("", &path).map_err(to_io_error)?;
+    let bytes = load_external(cache, &id, &ext).map_err(to_io_error)?;
+    Ok(Cursor::new(Cow::Owned(bytes)))
+}
+
+fn to_io_error(err: BoxedError) -> io::Error {
+    io::Error::other(err)
+}
@@ -0,0 +1,186 @@
+//! Generic asset saving definition
+//!
+//! This module defines a trait [`Saver`], the counterpart of [`Loader`], to
+//! specify how [assets] are written back to raw bytes.
+//!
+//! It also defines savers for standard traits and `serde` formats, which match
+//! the loaders of the [`loader`](crate::loader) module.
+//!
+//! Assets are saved with [`AssetCache::save`] when they implement
+//! [`SavableAsset`] and the source of the cache implements [`WritableSource`].
+//!
+//! [assets]: `crate::Asset`
+//! [`Loader`]: crate::loader::Loader
+//! [`AssetCache::save`]: crate::AssetCache::save
+//! [`SavableAsset`]: crate::asset::SavableAsset
+//! [`WritableSource`]: crate::source::WritableSource
+
+use crate::BoxedError;
+
+
+/// Specifies how an asset is saved.
+///
+/// This trait is the counterpart of [`Loader`](crate::loader::Loader): a value
+/// saved with a `Saver` should be loadable by the corresponding loader.
+///
+/// ## Example
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "ron")] {
+/// use assets_manager::{Asset, AssetCache, asset::SavableAsset, loader, saver};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Settings {
+///     volume: f32,
+/// }
+///
+/// impl Asset for Settings {
+///     const EXTENSION: &'static str = "ron";
+///     type Loader = loader::RonLoader;
+/// }
+///
+/// impl SavableAsset for Settings {
+///     type Saver = saver::RonSaver;
+/// }
+///
+/// let cache = AssetCache::new("assets")?;
+/// cache.save("settings", &Settings { volume: 0.5 })?;
+/// # }}
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait Saver<T> {
+    /// Converts an asset to its raw bytes representation.
+    fn save(value: &T, ext: &str) -> Result<Vec<u8>, BoxedError>;
+}
+
+/// Saves raw bytes.
+///
+/// See trait [`Saver`] for more informations.
+#[derive(Debug)]
+pub struct BytesSaver(());
+
+impl<T> Saver<T> for BytesSaver
+where
+    T: AsRef<[u8]>,
+{
+    #[inline]
+    fn save(value: &T, _: &str) -> Result<Vec<u8>, BoxedError> {
+        Ok(value.as_ref().to_vec())
+    }
+}
+
+/// Saves strings as UTF-8.
+///
+/// See trait [`Saver`] for more informations.
+#[derive(Debug)]
+pub struct StringSaver(());
+
+impl<T> Saver<T> for StringSaver
+where
+    T: AsRef<str>,
+{
+    #[inline]
+    fn save(value: &T, _: &str) -> Result<Vec<u8>, BoxedError> {
+        Ok(value.as_ref().as_bytes().to_vec())
+    }
+}
+
+macro_rules! serde_savers {
+    (
+        $(
+            #[doc = $doc:literal]
+            #[cfg(feature = $feature:literal)]
+            struct $name:ident => |$value:ident| $body:expr;
+        )*
+    ) => {
+        $(
+            #[doc = $doc]
+            ///
+            /// See trait [`Saver`] for more informations.
+            #[cfg(feature = $feature)]
+            #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
+            #[derive(Debug)]
+            pub struct $name(());
+
+            #[cfg(feature = $feature)]
+            impl<T> Saver<T> for $name
+            where
+                T: serde::Serialize,
+            {
+                #[inline]
+                fn save($value: &T, _: &str) -> Result<Vec<u8>, BoxedError> {
+                    Ok($body?)
+                }
+            }
+        )*
+    }
+}
+
+serde_savers! {
+    /// Saves assets to Bincode encoded files.
+    #[cfg(feature = "bincode")]
+    struct BincodeSaver => |value| serde_bincode::serialize(value);
+
+    /// Saves assets to CBOR encoded files.
+    #[cfg(feature = "cbor")]
+    struct CborSaver => |value| serde_cbor::to_vec(value);
+
+    /// Saves assets to pretty-printed JSON files.
+    #[cfg(feature = "json")]
+    struct JsonSaver => |value| serde_json::to_vec_pretty(value);
+
+    /// Saves assets to MessagePack files, with named struct fields.
+    #[cfg(feature = "msgpack")]
+    struct MessagePackSaver => |value| serde_msgpack::to_vec_named(value);
+
+    /// Saves assets to Postcard encoded files.
+    #[cfg(feature = "postcard")]
+    struct PostcardSaver => |value| serde_postcard::to_allocvec(value);
+
+    /// Saves assets to pretty-printed RON files.
+    #[cfg(feature = "ron")]
+    struct RonSaver => |value| serde_ron::ser::to_string_pretty(value, Default::default()).map(String::into_bytes);
+
+    /// Saves assets to TOML files.
+    #[cfg(feature = "toml")]
+    struct TomlSaver => |value| serde_toml::to_string_pretty(value).map(String::into_bytes);
+
+    /// Saves assets to YAML files.
+    #[cfg(feature = "yaml")]
+    struct YamlSaver => |value| serde_yaml::to_vec(value);
+}
+
+/// Saves assets to MessagePack files with custom options.
+///
+/// Unlike [`MessagePackSaver`], which always uses named fields, the options
+/// are given by `O`.
+///
+/// See traits [`Saver`] and
+/// [`MessagePackOptions`](crate::loader::MessagePackOptions) for more
+/// informations.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+#[derive(Debug)]
+pub struct MessagePackSaverWith<O>(std::marker::PhantomData<O>);
+
+#[cfg(feature = "msgpack")]
+impl<T, O> Saver<T> for MessagePackSaverWith<O>
+where
+    T: serde::Serialize,
+    O: crate::loader::MessagePackOptions,
+{
+    fn save(value: &T, _: &str) -> Result<Vec<u8>, BoxedError> {
+        use serde_msgpack::Serializer;
+
+        let mut buf = Vec::new();
+        let ser = Serializer::new(&mut buf);
+        match (O::NAMED_FIELDS, O::HUMAN_READABLE) {
+            (true, true) => value.serialize(&mut ser.with_struct_map().with_human_readable())?,
+            (true, false) => value.serialize(&mut ser.with_struct_map().with_binary())?,
+            (false, true) => value.serialize(&mut ser.with_struct_tuple().with_human_readable())?,
+            (false, false) => value.serialize(&mut ser.with_struct_tuple().with_binary())?,
+        }
+        Ok(buf)
+    }
+}
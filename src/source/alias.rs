@@ -0,0 +1,166 @@
+use crate::utils::HashMap;
+
+use std::{
+    borrow::Cow,
+    fmt,
+    io::Read,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, Version, WritableSource};
+
+
+type AliasFn = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// A [`Source`] that rewrites requested ids before reading from an inner
+/// source.
+///
+/// Aliases can be given as a map from an id to another, or as a callback.
+/// This enables to rename assets while keeping old ids working, without
+/// changing code that loads them.
+///
+/// An id is rewritten at most once: if an alias is found in the map, it is
+/// used as is. Otherwise, the callback is called, if any. Ids without alias
+/// are passed unchanged to the inner source. Aliases also apply to
+/// directories, but the names returned by `read_dir` are the ones of the
+/// inner source.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{AliasSource, FileSystem}};
+///
+/// let source = AliasSource::new(FileSystem::new("assets")?)
+///     // `textures.player` was renamed
+///     .with_alias("textures.player", "textures.characters.player")
+///     // The `legacy` directory was moved to `old`
+///     .with_fn(|id| id.strip_prefix("legacy.").map(|rest| format!("old.{}", rest)));
+///
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct AliasSource<S> {
+    source: S,
+    aliases: HashMap<String, String>,
+    callback: Option<Box<AliasFn>>,
+}
+
+impl<S> AliasSource<S> {
+    /// Creates a new `AliasSource` without any alias.
+    pub fn new(source: S) -> AliasSource<S> {
+        AliasSource {
+            source,
+            aliases: HashMap::new(),
+            callback: None,
+        }
+    }
+
+    /// Adds an alias, so that `from` is read as `to`.
+    ///
+    /// If there was already an alias for `from`, its target is returned.
+    pub fn add_alias<F: Into<String>, T: Into<String>>(&mut self, from: F, to: T) -> Option<String> {
+        self.aliases.insert(from.into(), to.into())
+    }
+
+    /// Adds an alias, so that `from` is read as `to`.
+    ///
+    /// This is the same as `add_alias`, but as a builder method.
+    pub fn with_alias<F: Into<String>, T: Into<String>>(mut self, from: F, to: T) -> AliasSource<S> {
+        self.add_alias(from, to);
+        self
+    }
+
+    /// Removes an alias, returning its target if there was one.
+    pub fn remove_alias(&mut self, from: &str) -> Option<String> {
+        self.aliases.remove(from)
+    }
+
+    /// Sets a callback used to rewrite ids that are not in the map.
+    ///
+    /// The callback returns `None` to keep an id unchanged. It replaces any
+    /// previously set callback.
+    pub fn with_fn<F>(mut self, f: F) -> AliasSource<S>
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.callback = Some(Box::new(f));
+        self
+    }
+
+    /// Returns the id that is read from the inner source when `id` is
+    /// requested.
+    pub fn resolve<'a>(&self, id: &'a str) -> Cow<'a, str> {
+        if let Some(alias) = self.aliases.get(id) {
+            return Cow::Owned(alias.clone());
+        }
+
+        match &self.callback {
+            Some(callback) => match callback(id) {
+                Some(alias) => Cow::Owned(alias),
+                None => Cow::Borrowed(id),
+            },
+            None => Cow::Borrowed(id),
+        }
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: Source> Source for AliasSource<S> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.source.read(&self.resolve(id), ext)
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir(&self.resolve(id), ext)
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read_dir_entries(&self.resolve(id), ext)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.source.metadata(&self.resolve(id), ext)
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.version(&self.resolve(id), ext)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.source.open(&self.resolve(id), ext)
+    }
+}
+
+impl<S: WritableSource> WritableSource for AliasSource<S> {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        self.source.write(&self.resolve(id), ext, content)
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        self.source.remove(&self.resolve(id), ext)
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for AliasSource<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AliasSource")
+            .field("source", &self.source)
+            .field("aliases", &self.aliases)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
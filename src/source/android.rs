@@ -0,0 +1,136 @@
+use ndk::asset::AssetManager;
+
+use std::{
+    borrow::Cow,
+    ffi::CString,
+    fmt,
+    io::{self, Read},
+};
+
+use super::{validate_id, Source, SourceError, SourceErrorKind};
+
+
+/// A [`Source`] to load assets from the `assets` directory of an Android
+/// application package (APK).
+///
+/// Files are read with the `AAssetManager` of the application, which can be
+/// obtained for example with `android_activity::AndroidApp::asset_manager`.
+/// This way, the same asset code works on Android without embedding assets in
+/// the binary.
+///
+/// ## Directories
+///
+/// The Android asset manager does not tell whether a directory exists, so
+/// reading a directory that does not exist returns an empty list instead of
+/// an error.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```ignore
+/// use assets_manager::{AssetCache, source::AndroidAssets};
+///
+/// fn android_main(app: android_activity::AndroidApp) {
+///     let source = AndroidAssets::new(app.asset_manager());
+///     let cache = AssetCache::with_source(source);
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(all(feature = "android", target_os = "android"))))]
+pub struct AndroidAssets {
+    manager: AssetManager,
+    root: String,
+}
+
+impl AndroidAssets {
+    /// Creates a new `AndroidAssets` reading from the root of the `assets`
+    /// directory.
+    pub fn new(manager: AssetManager) -> AndroidAssets {
+        AndroidAssets::with_root(manager, "")
+    }
+
+    /// Creates a new `AndroidAssets` reading from a subdirectory of the
+    /// `assets` directory.
+    pub fn with_root<P: Into<String>>(manager: AssetManager, root: P) -> AndroidAssets {
+        let mut root = root.into();
+        while root.ends_with('/') {
+            root.pop();
+        }
+
+        AndroidAssets { manager, root }
+    }
+
+    /// Returns a reference to the asset manager.
+    #[inline]
+    pub fn manager(&self) -> &AssetManager {
+        &self.manager
+    }
+
+    /// Returns the path in the `assets` directory of the (eventual) file
+    /// represented by an id and an extension.
+    pub fn path_of(&self, id: &str, ext: &str) -> String {
+        let mut path = self.root.clone();
+        for component in id.split('.').filter(|c| !c.is_empty()) {
+            if !path.is_empty() {
+                path.push('/');
+            }
+            path.push_str(component);
+        }
+        if !ext.is_empty() {
+            path.push('.');
+            path.push_str(ext);
+        }
+        path
+    }
+}
+
+fn to_c_string(path: String) -> io::Result<CString> {
+    CString::new(path).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path contains a nul byte"))
+}
+
+impl Source for AndroidAssets {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        validate_id(id, ext)?;
+        let path = to_c_string(self.path_of(id, ext))?;
+        let mut asset = self.manager.open(&path).ok_or(SourceErrorKind::NotFound)?;
+
+        let mut content = Vec::with_capacity(asset.length());
+        asset.read_to_end(&mut content)?;
+        Ok(Cow::Owned(content))
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        validate_id(id, "")?;
+        let path = to_c_string(self.path_of(id, ""))?;
+        let dir = self.manager.open_dir(&path).ok_or(SourceErrorKind::NotFound)?;
+
+        let mut entries = Vec::new();
+
+        // Only files are listed by the asset manager
+        for name in dir {
+            let name = match name.to_str() {
+                Ok(name) => name,
+                Err(_) => continue,
+            };
+
+            let (stem, file_ext) = match name.rfind('.') {
+                Some(pos) => (&name[..pos], &name[pos+1..]),
+                None => (name, ""),
+            };
+
+            if ext.contains(&file_ext) {
+                entries.push(stem.to_owned());
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+impl fmt::Debug for AndroidAssets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AndroidAssets").field("root", &self.root).finish()
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt,
+    io::Read,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, Version};
+
+
+/// A source that can be used as a trait object and downcast.
+trait DynSource: Source + Send + Sync {
+    fn as_any(&self) -> &(dyn Any + Send + Sync);
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any + Send + Sync>;
+}
+
+impl<S: Source + Send + Sync + 'static> DynSource for S {
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any + Send + Sync> {
+        self
+    }
+}
+
+/// A [`Source`] whose type is chosen at runtime.
+///
+/// The type of the source of an [`AssetCache`](crate::AssetCache) is usually
+/// known at compile time. This source enables to choose it at runtime, for
+/// example from a configuration file, while keeping a single cache type. The
+/// original source can be retrieved with [`downcast_ref`](Self::downcast_ref)
+/// or [`downcast`](Self::downcast).
+///
+/// `Box<dyn Source + Send + Sync>` can also be used as a source, but it
+/// cannot be downcast.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading, even if the inner source does.
+/// Wrap it in a [`Polling`](super::Polling) source to check it for changes
+/// regularly.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{AnySource, FileSystem, Memory}};
+///
+/// # let use_memory = false;
+/// let source = if use_memory {
+///     AnySource::new(Memory::new())
+/// } else {
+///     AnySource::new(FileSystem::new("assets")?)
+/// };
+///
+/// let cache: AssetCache<AnySource> = AssetCache::with_source(source);
+/// assert_eq!(cache.source().is::<Memory>(), use_memory);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct AnySource {
+    source: Box<dyn DynSource>,
+}
+
+impl AnySource {
+    /// Creates a new `AnySource` from a source.
+    #[inline]
+    pub fn new<S: Source + Send + Sync + 'static>(source: S) -> AnySource {
+        AnySource { source: Box::new(source) }
+    }
+
+    /// Returns `true` if the inner source is of type `S`.
+    #[inline]
+    pub fn is<S: 'static>(&self) -> bool {
+        (*self.source).as_any().is::<S>()
+    }
+
+    /// Returns a reference to the inner source if it is of type `S`.
+    #[inline]
+    pub fn downcast_ref<S: 'static>(&self) -> Option<&S> {
+        (*self.source).as_any().downcast_ref()
+    }
+
+    /// Returns the inner source if it is of type `S`, or `self` otherwise.
+    pub fn downcast<S: 'static>(self) -> Result<S, AnySource> {
+        if self.is::<S>() {
+            Ok(*self.source.into_any().downcast().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Source for AnySource {
+    #[inline]
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.source.read(id, ext)
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.source.metadata(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.version(id, ext)
+    }
+
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.source.open(id, ext)
+    }
+}
+
+impl fmt::Debug for AnySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnySource").finish_non_exhaustive()
+    }
+}
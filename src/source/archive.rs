@@ -0,0 +1,353 @@
+use crate::{loader::archive, utils::{Mutex, RwLock}};
+
+#[cfg(feature = "hot-reloading")]
+use crate::utils::PrivateMarker;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use super::{DirEntry, Memory, Metadata, Source, SourceError, Stamp, Version};
+
+
+/// The format of an archive read by an [`ArchiveSource`].
+#[cfg_attr(docsrs, doc(cfg(any(feature = "zip", feature = "tar"))))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArchiveFormat {
+    /// A zip archive, with stored and deflated entries (feature `zip`).
+    Zip,
+
+    /// An uncompressed tar archive (feature `tar`).
+    Tar,
+
+    /// A tar archive compressed with gzip (features `tar` and `gzip`).
+    TarGz,
+
+    /// A tar archive compressed with Zstandard (features `tar` and `zstd`).
+    TarZstd,
+
+    /// A [`Pack`](super::Pack) file (feature `pack`).
+    Pack,
+}
+
+impl ArchiveFormat {
+    /// Guesses the format of an archive from its file name.
+    ///
+    /// Recognized extensions are `.zip`, `.tar`, `.tar.gz`, `.tgz`,
+    /// `.tar.zst`, `.tzst` and `.pack`.
+    pub fn from_path(path: &Path) -> Option<ArchiveFormat> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        let format = if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            ArchiveFormat::TarGz
+        } else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            ArchiveFormat::TarZstd
+        } else if name.ends_with(".tar") {
+            ArchiveFormat::Tar
+        } else if name.ends_with(".zip") {
+            ArchiveFormat::Zip
+        } else if name.ends_with(".pack") {
+            ArchiveFormat::Pack
+        } else {
+            return None;
+        };
+        Some(format)
+    }
+
+    /// Detects the format of an archive from its first bytes.
+    ///
+    /// Compressed files are assumed to contain a tar archive.
+    pub fn detect(content: &[u8]) -> Option<ArchiveFormat> {
+        let format = if content.starts_with(b"PK\x03\x04") || content.starts_with(b"PK\x05\x06") {
+            ArchiveFormat::Zip
+        } else if content.starts_with(b"\x1f\x8b") {
+            ArchiveFormat::TarGz
+        } else if content.starts_with(b"\x28\xb5\x2f\xfd") {
+            ArchiveFormat::TarZstd
+        } else if content.starts_with(b"AMPACK\0") {
+            ArchiveFormat::Pack
+        } else if content.get(257..262) == Some(b"ustar") {
+            ArchiveFormat::Tar
+        } else {
+            return None;
+        };
+        Some(format)
+    }
+}
+
+/// A [`Source`] that reads files from an archive, whose format is detected
+/// automatically.
+///
+/// This is a single entry point for the archive formats supported by the
+/// crate (see [`ArchiveFormat`]). The format is guessed from the extension of
+/// the file, or from its content if the extension is not recognized. Each
+/// format requires the corresponding features to be enabled, and an error of
+/// kind [`Unsupported`](io::ErrorKind::Unsupported) is returned otherwise.
+///
+/// Zip and tar archives are entirely read into memory when they are opened.
+/// Packs are read with a [`Pack`](super::Pack) source, which only reads files
+/// when they are requested.
+///
+/// In archives, the path `common/position.ron` corresponds to the id
+/// `common.position` with extension `ron`.
+///
+/// ## Hot-reloading
+///
+/// An archive opened with [`ArchiveSource::open`] can be read again when the
+/// file changes on disk with [`ArchiveSource::reload`]. When it is wrapped in a
+/// [`Polling`](super::Polling) source, this is done automatically when the
+/// cache is hot-reloaded, and assets whose content changed are reloaded.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::ArchiveSource};
+///
+/// let source = ArchiveSource::open("assets.tar.gz")?;
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(any(feature = "zip", feature = "tar"))))]
+pub struct ArchiveSource {
+    format: ArchiveFormat,
+    source: RwLock<Box<dyn Source + Send + Sync>>,
+    origin: Option<Origin>,
+}
+
+/// The file an archive was opened from.
+struct Origin {
+    path: PathBuf,
+    stamp: Mutex<Stamp>,
+}
+
+impl ArchiveSource {
+    /// Opens the archive at the given path.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the file cannot be read, if its format is not
+    /// recognized or not supported, or if it is not a valid archive.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<ArchiveSource> {
+        let path = path.as_ref();
+
+        let format = match ArchiveFormat::from_path(path) {
+            Some(format) => format,
+            None => {
+                let mut start = Vec::new();
+                fs::File::open(path)?.take(262).read_to_end(&mut start)?;
+                ArchiveFormat::detect(&start).ok_or_else(unknown_format)?
+            }
+        };
+
+        let stamp = Stamp::of(path)?;
+        let source = open_file(path, format)?;
+
+        Ok(ArchiveSource {
+            format,
+            source: RwLock::new(source),
+            origin: Some(Origin {
+                path: path.to_owned(),
+                stamp: Mutex::new(stamp),
+            }),
+        })
+    }
+
+    /// Reads an archive from memory, detecting its format from its content.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the format is not recognized or not supported,
+    /// or if the content is not a valid archive.
+    pub fn from_bytes(content: Vec<u8>) -> io::Result<ArchiveSource> {
+        let format = ArchiveFormat::detect(&content).ok_or_else(unknown_format)?;
+        Self::with_format(content, format)
+    }
+
+    /// Reads an archive from memory, with the given format.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the format is not supported, or if the content
+    /// is not a valid archive.
+    pub fn with_format(content: Vec<u8>, format: ArchiveFormat) -> io::Result<ArchiveSource> {
+        let source: Box<dyn Source + Send + Sync> = match format {
+            #[cfg(feature = "pack")]
+            ArchiveFormat::Pack => Box::new(super::Pack::new(io::Cursor::new(content))?),
+            _ => Box::new(to_memory(read_entries(content, format)?)),
+        };
+
+        Ok(ArchiveSource {
+            format,
+            source: RwLock::new(source),
+            origin: None,
+        })
+    }
+
+    /// Returns the format of the archive.
+    #[inline]
+    pub fn format(&self) -> ArchiveFormat {
+        self.format
+    }
+
+    /// Reads the archive again if it changed on disk since it was opened or
+    /// last reloaded.
+    ///
+    /// Returns `true` if the archive was reloaded. Archives that were not
+    /// created with [`ArchiveSource::open`] are never reloaded.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the file cannot be read or if it is not a valid
+    /// archive anymore. In this case, the previous content is kept.
+    pub fn reload(&self) -> io::Result<bool> {
+        let origin = match &self.origin {
+            Some(origin) => origin,
+            None => return Ok(false),
+        };
+
+        let mut stamp = origin.stamp.lock();
+        let new_stamp = Stamp::of(&origin.path)?;
+        if *stamp == new_stamp {
+            return Ok(false);
+        }
+
+        *self.source.write() = open_file(&origin.path, self.format)?;
+        *stamp = new_stamp;
+
+        Ok(true)
+    }
+}
+
+/// Reads the archive at the given path.
+fn open_file(path: &Path, format: ArchiveFormat) -> io::Result<Box<dyn Source + Send + Sync>> {
+    #[cfg(feature = "pack")]
+    if format == ArchiveFormat::Pack {
+        return Ok(Box::new(super::Pack::open(path)?));
+    }
+
+    Ok(Box::new(to_memory(read_entries(fs::read(path)?, format)?)))
+}
+
+fn unknown_format() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "unknown archive format")
+}
+
+#[allow(unused)]
+fn unsupported(format: &str, feature: &str) -> io::Error {
+    let msg = format!("{} archives require feature `{}`", format, feature);
+    io::Error::new(io::ErrorKind::Unsupported, msg)
+}
+
+/// Reads the files of a zip or tar archive.
+fn read_entries(content: Vec<u8>, format: ArchiveFormat) -> io::Result<HashMap<String, Vec<u8>>> {
+    #[allow(unused)]
+    let invalid = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+
+    match format {
+        #[cfg(feature = "zip")]
+        ArchiveFormat::Zip => archive::load_zip(&content).map_err(invalid),
+        #[cfg(not(feature = "zip"))]
+        ArchiveFormat::Zip => Err(unsupported("zip", "zip")),
+
+        #[cfg(feature = "tar")]
+        ArchiveFormat::Tar => archive::load_tar(&content).map_err(invalid),
+        #[cfg(not(feature = "tar"))]
+        ArchiveFormat::Tar => Err(unsupported("tar", "tar")),
+
+        #[cfg(all(feature = "tar", feature = "gzip"))]
+        ArchiveFormat::TarGz => {
+            let mut tar = Vec::new();
+            flate2::read::GzDecoder::new(&*content).read_to_end(&mut tar)?;
+            archive::load_tar(&tar).map_err(invalid)
+        }
+        #[cfg(not(all(feature = "tar", feature = "gzip")))]
+        ArchiveFormat::TarGz => Err(unsupported("tar.gz", "tar` and `gzip")),
+
+        #[cfg(all(feature = "tar", feature = "zstd"))]
+        ArchiveFormat::TarZstd => {
+            let tar = zstd_crate::stream::decode_all(&*content)?;
+            archive::load_tar(&tar).map_err(invalid)
+        }
+        #[cfg(not(all(feature = "tar", feature = "zstd")))]
+        ArchiveFormat::TarZstd => Err(unsupported("tar.zst", "tar` and `zstd")),
+
+        ArchiveFormat::Pack => Err(unsupported("pack", "pack")),
+    }
+}
+
+/// Converts paths of an archive into ids.
+fn to_memory(entries: HashMap<String, Vec<u8>>) -> Memory {
+    let memory = Memory::new();
+
+    for (path, content) in entries {
+        let (dir, name) = path.rsplit_once('/').unwrap_or(("", &path));
+        let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+
+        let id = if dir.is_empty() {
+            stem.to_owned()
+        } else {
+            format!("{}.{}", dir.replace('/', "."), stem)
+        };
+        memory.insert(&id, ext, content);
+    }
+
+    memory
+}
+
+impl Source for ArchiveSource {
+    #[inline]
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        // The content cannot borrow the inner source, which is replaced when
+        // the archive is reloaded. `Memory` and `Pack` return owned content,
+        // so this does not copy it.
+        let source = self.source.read();
+        Ok(Cow::Owned(source.read(id, ext)?.into_owned()))
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read().read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read().read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read().read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.source.read().metadata(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.read().version(id, ext)
+    }
+
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.source.read().open(id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _refresh<P: PrivateMarker>(&self) {
+        if let Err(err) = self.reload() {
+            log::warn!("Error reloading archive: {}", err);
+        }
+    }
+}
+
+impl fmt::Debug for ArchiveSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveSource").field("format", &self.format).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{
+    loader::BoxFuture,
+    utils::Mutex,
+};
+#[cfg(feature = "hot-reloading")]
+use crate::utils::PrivateMarker;
+
+use std::{
+    borrow::Cow,
+    future::Future,
+    io::Read,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, Version};
+
+
+/// An asynchronous bytes source to load assets from.
+///
+/// This is the asynchronous counterpart of [`Source`]: reading a file returns
+/// a future that can be awaited, so that network- and IO-bound sources do not
+/// block the calling thread.
+///
+/// Regular sources can be used as `AsyncSource`s with the [`Blocking`] and
+/// [`Unblock`] adapters.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::{loader::BoxFuture, source::{AsyncSource, SourceError, SourceErrorKind}};
+/// use std::borrow::Cow;
+///
+/// struct Remote;
+///
+/// impl AsyncSource for Remote {
+///     fn read<'a>(&'a self, id: &'a str, ext: &'a str) -> BoxFuture<'a, Result<Cow<'a, [u8]>, SourceError>> {
+///         Box::pin(async move {
+///             // Some asynchronous request
+///             Ok(Cow::Owned(format!("{}.{}", id, ext).into_bytes()))
+///         })
+///     }
+///
+///     fn read_dir<'a>(&'a self, _id: &'a str, _ext: &'a [&'a str]) -> BoxFuture<'a, Result<Vec<String>, SourceError>> {
+///         Box::pin(async { Err(SourceErrorKind::Unsupported.into()) })
+///     }
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub trait AsyncSource {
+    /// Try reading the source given an id and an extension.
+    ///
+    /// See [`Source::read`] for more informations.
+    fn read<'a>(&'a self, id: &'a str, ext: &'a str) -> BoxFuture<'a, Result<Cow<'a, [u8]>, SourceError>>;
+
+    /// Reads a directory given its id and an extension list.
+    ///
+    /// See [`Source::read_dir`] for more informations.
+    fn read_dir<'a>(&'a self, id: &'a str, ext: &'a [&'a str]) -> BoxFuture<'a, Result<Vec<String>, SourceError>>;
+}
+
+impl<S> AsyncSource for Box<S>
+where
+    S: AsyncSource + ?Sized,
+{
+    fn read<'a>(&'a self, id: &'a str, ext: &'a str) -> BoxFuture<'a, Result<Cow<'a, [u8]>, SourceError>> {
+        self.as_ref().read(id, ext)
+    }
+
+    fn read_dir<'a>(&'a self, id: &'a str, ext: &'a [&'a str]) -> BoxFuture<'a, Result<Vec<String>, SourceError>> {
+        self.as_ref().read_dir(id, ext)
+    }
+}
+
+/// An [`AsyncSource`] that reads a [`Source`] synchronously.
+///
+/// The returned futures complete on their first poll, and block the thread
+/// that polls them while reading. This is well suited for sources that do not
+/// perform IO, such as [`Embedded`](super::Embedded) or
+/// [`Memory`](super::Memory). For other sources, see [`Unblock`].
+///
+/// It is also a [`Source`], so it can be used as the source of an
+/// [`AssetCache`](crate::AssetCache) to load assets with
+/// [`load_async`](crate::AssetCache::load_async).
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[derive(Debug, Clone)]
+pub struct Blocking<S>(S);
+
+impl<S> Blocking<S> {
+    /// Creates a new `Blocking` from a source.
+    #[inline]
+    pub fn new(source: S) -> Blocking<S> {
+        Blocking(source)
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.0
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S: Source + Sync> AsyncSource for Blocking<S> {
+    fn read<'a>(&'a self, id: &'a str, ext: &'a str) -> BoxFuture<'a, Result<Cow<'a, [u8]>, SourceError>> {
+        Box::pin(async move { self.0.read(id, ext) })
+    }
+
+    fn read_dir<'a>(&'a self, id: &'a str, ext: &'a [&'a str]) -> BoxFuture<'a, Result<Vec<String>, SourceError>> {
+        Box::pin(async move { self.0.read_dir(id, ext) })
+    }
+}
+
+impl<S: Source> Source for Blocking<S> {
+    #[inline]
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.0.read(id, ext)
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.0.read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.0.read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.0.read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.0.metadata(id, ext)
+    }
+
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.0.open(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.0.version(id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_asset<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.0._add_asset::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_file<A: crate::Asset, P: PrivateMarker>(&self, key: &str, id: &str, ext: &str) {
+        self.0._add_file::<A, P>(key, id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_dir<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.0._add_dir::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _clear<P: PrivateMarker>(&mut self) {
+        self.0._clear::<P>()
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_compound<A: crate::Compound, P: PrivateMarker>(&self, id: &str, deps: crate::utils::DepsRecord) {
+        self.0._add_compound::<A, P>(id, deps)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _support_hot_reloading<P: PrivateMarker>(&self) -> bool {
+        self.0._support_hot_reloading::<P>()
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _refresh<P: PrivateMarker>(&self) {
+        self.0._refresh::<P>()
+    }
+}
+
+/// An [`AsyncSource`] that reads a [`Source`] on a separate thread.
+///
+/// Each read is performed on a newly spawned thread, so awaiting it never
+/// blocks the executor. This is well suited for sources that perform IO, such
+/// as [`FileSystem`](super::FileSystem), but has the cost of spawning a thread
+/// and copying the content of the file.
+///
+/// This does not depend on any async runtime.
+///
+/// It is also a [`Source`], so it can be used as the source of an
+/// [`AssetCache`](crate::AssetCache) to load assets with
+/// [`load_async`](crate::AssetCache::load_async).
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[derive(Debug)]
+pub struct Unblock<S>(Arc<S>);
+
+impl<S> Unblock<S> {
+    /// Creates a new `Unblock` from a source.
+    #[inline]
+    pub fn new(source: S) -> Unblock<S> {
+        Unblock(Arc::new(source))
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S> Clone for Unblock<S> {
+    fn clone(&self) -> Self {
+        Unblock(self.0.clone())
+    }
+}
+
+impl<S> AsyncSource for Unblock<S>
+where
+    S: Source + Send + Sync + 'static,
+{
+    fn read<'a>(&'a self, id: &'a str, ext: &'a str) -> BoxFuture<'a, Result<Cow<'a, [u8]>, SourceError>> {
+        let source = self.0.clone();
+        let (id, ext) = (id.to_owned(), ext.to_owned());
+        let task = spawn(move || source.read(&id, &ext).map(Cow::into_owned));
+        Box::pin(async move { task.await.map(Cow::Owned) })
+    }
+
+    fn read_dir<'a>(&'a self, id: &'a str, ext: &'a [&'a str]) -> BoxFuture<'a, Result<Vec<String>, SourceError>> {
+        let source = self.0.clone();
+        let id = id.to_owned();
+        let ext: Vec<String> = ext.iter().map(|&ext| ext.to_owned()).collect();
+        Box::pin(spawn(move || {
+            let ext: Vec<&str> = ext.iter().map(String::as_str).collect();
+            source.read_dir(&id, &ext)
+        }))
+    }
+}
+
+impl<S: Source> Source for Unblock<S> {
+    #[inline]
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.0.read(id, ext)
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.0.read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.0.read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.0.read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.0.metadata(id, ext)
+    }
+
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.0.open(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.0.version(id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_asset<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.0._add_asset::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_file<A: crate::Asset, P: PrivateMarker>(&self, key: &str, id: &str, ext: &str) {
+        self.0._add_file::<A, P>(key, id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_dir<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.0._add_dir::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _clear<P: PrivateMarker>(&mut self) {
+        // Threads only keep the source while reading a file
+        if let Some(source) = Arc::get_mut(&mut self.0) {
+            source._clear::<P>()
+        }
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_compound<A: crate::Compound, P: PrivateMarker>(&self, id: &str, deps: crate::utils::DepsRecord) {
+        self.0._add_compound::<A, P>(id, deps)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _support_hot_reloading<P: PrivateMarker>(&self) -> bool {
+        self.0._support_hot_reloading::<P>()
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _refresh<P: PrivateMarker>(&self) {
+        self.0._refresh::<P>()
+    }
+}
+
+struct TaskState<T> {
+    result: Option<Result<T, SourceError>>,
+    waker: Option<Waker>,
+}
+
+/// A future that completes when a thread finishes.
+struct Task<T>(Arc<Mutex<TaskState<T>>>);
+
+fn spawn<T, F>(f: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, SourceError> + Send + 'static,
+{
+    let state = Arc::new(Mutex::new(TaskState { result: None, waker: None }));
+    let thread_state = state.clone();
+
+    let spawned = thread::Builder::new()
+        .name("assets_manager source".into())
+        .spawn(move || {
+            let result = f();
+            let mut state = thread_state.lock();
+            state.result = Some(result);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+    if let Err(err) = spawned {
+        state.lock().result = Some(Err(err.into()));
+    }
+
+    Task(state)
+}
+
+impl<T> Future for Task<T> {
+    type Output = Result<T, SourceError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.0.lock();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
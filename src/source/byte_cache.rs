@@ -0,0 +1,229 @@
+use crate::utils::{HashMap, Mutex};
+
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{self, Read},
+    sync::Arc,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, Version, WritableSource};
+
+
+/// A [`Source`] that keeps the content of files read from another source in
+/// memory.
+///
+/// This is useful when reading from the inner source is slow, for example
+/// when files are downloaded or decompressed, and the same file is read
+/// several times, such as when the same file is loaded as different asset
+/// types, or when a cache is cleared.
+///
+/// The total size of the files kept in memory is limited by a capacity given
+/// in bytes. When it is exceeded, the least recently read files are removed.
+/// Files bigger than the capacity are never kept.
+///
+/// ## Invalidation
+///
+/// Files are not read again when the inner source changes. Use
+/// [`ByteCache::remove`] or [`ByteCache::clear`] to read them again. Files
+/// written with [`WritableSource`] methods are removed automatically.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// # #[cfg(feature = "http")]
+/// # {
+/// use assets_manager::{AssetCache, source::{ByteCache, Http}};
+///
+/// // Keep up to 64 MiB of downloaded files
+/// let source = ByteCache::new(Http::new("https://example.com/assets"), 64 << 20);
+/// let cache = AssetCache::with_source(source);
+/// # }
+/// ```
+pub struct ByteCache<S> {
+    source: S,
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+struct CachedFile {
+    content: Arc<[u8]>,
+    last_used: u64,
+}
+
+struct State {
+    files: HashMap<(String, String), CachedFile>,
+    size: usize,
+    clock: u64,
+}
+
+impl State {
+    fn get(&mut self, id: &str, ext: &str) -> Option<Arc<[u8]>> {
+        self.clock += 1;
+        let file = self.files.get_mut(&(id.to_owned(), ext.to_owned()))?;
+        file.last_used = self.clock;
+        Some(file.content.clone())
+    }
+
+    fn insert(&mut self, id: &str, ext: &str, content: Arc<[u8]>, capacity: usize) {
+        if content.len() > capacity {
+            return;
+        }
+
+        self.clock += 1;
+        let file = CachedFile { content, last_used: self.clock };
+        self.size += file.content.len();
+        if let Some(old) = self.files.insert((id.to_owned(), ext.to_owned()), file) {
+            self.size -= old.content.len();
+        }
+
+        while self.size > capacity {
+            let oldest = self.files.iter()
+                .min_by_key(|(_, file)| file.last_used)
+                .map(|(key, _)| key.clone());
+
+            match oldest.and_then(|key| self.files.remove(&key)) {
+                Some(file) => self.size -= file.content.len(),
+                None => break,
+            }
+        }
+    }
+
+    fn remove(&mut self, id: &str, ext: &str) {
+        if let Some(file) = self.files.remove(&(id.to_owned(), ext.to_owned())) {
+            self.size -= file.content.len();
+        }
+    }
+}
+
+impl<S> ByteCache<S> {
+    /// Creates a new `ByteCache` keeping up to `capacity` bytes in memory.
+    pub fn new(source: S, capacity: usize) -> ByteCache<S> {
+        ByteCache {
+            source,
+            capacity,
+            state: Mutex::new(State {
+                files: HashMap::new(),
+                size: 0,
+                clock: 0,
+            }),
+        }
+    }
+
+    /// Returns the maximum number of bytes kept in memory.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes currently kept in memory.
+    pub fn size(&self) -> usize {
+        self.state.lock().size
+    }
+
+    /// Returns `true` if the given file is kept in memory.
+    pub fn contains(&self, id: &str, ext: &str) -> bool {
+        self.state.lock().files.contains_key(&(id.to_owned(), ext.to_owned()))
+    }
+
+    /// Removes a file from memory, so that it is read again from the inner
+    /// source.
+    pub fn remove(&self, id: &str, ext: &str) {
+        self.state.lock().remove(id, ext);
+    }
+
+    /// Removes all files from memory.
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.files.clear();
+        state.size = 0;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: Source> ByteCache<S> {
+    fn read_shared(&self, id: &str, ext: &str) -> io::Result<Arc<[u8]>> {
+        if let Some(content) = self.state.lock().get(id, ext) {
+            return Ok(content);
+        }
+
+        // The lock is not held while reading so that other files can be read
+        // in the meantime.
+        let content: Arc<[u8]> = self.source.read(id, ext)?.into();
+        self.state.lock().insert(id, ext, content.clone(), self.capacity);
+        Ok(content)
+    }
+}
+
+impl<S: Source> Source for ByteCache<S> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        Ok(Cow::Owned(self.read_shared(id, ext)?.to_vec()))
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read_dir_entries(id, ext)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.source.metadata(id, ext)
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.version(id, ext)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        let content = self.read_shared(id, ext)?;
+        Ok(Box::new(io::Cursor::new(content)))
+    }
+}
+
+impl<S: WritableSource> WritableSource for ByteCache<S> {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        let result = self.source.write(id, ext, content);
+        ByteCache::remove(self, id, ext);
+        result
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        let result = self.source.remove(id, ext);
+        ByteCache::remove(self, id, ext);
+        result
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for ByteCache<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteCache")
+            .field("source", &self.source)
+            .field("capacity", &self.capacity)
+            .field("size", &self.size())
+            .finish()
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    borrow::Cow,
+    io,
+    str,
+};
+
+use super::{Source, SourceError, SourceErrorKind, WritableSource};
+
+
+/// Information used to check if a cached copy of a remote file is still up to
+/// date, as given by HTTP `ETag` and `Last-Modified` headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Validators {
+    /// An opaque identifier of the version of the file.
+    pub etag: Option<String>,
+
+    /// The last modification date of the file, as sent by the server.
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// Returns `true` if there is no validator.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        if let Some(etag) = &self.etag {
+            bytes.extend_from_slice(b"etag: ");
+            bytes.extend_from_slice(etag.as_bytes());
+            bytes.push(b'\n');
+        }
+        if let Some(last_modified) = &self.last_modified {
+            bytes.extend_from_slice(b"last-modified: ");
+            bytes.extend_from_slice(last_modified.as_bytes());
+            bytes.push(b'\n');
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Validators {
+        let mut validators = Validators::default();
+
+        for line in str::from_utf8(bytes).unwrap_or("").lines() {
+            if let Some(etag) = line.strip_prefix("etag: ") {
+                validators.etag = Some(etag.to_owned());
+            } else if let Some(last_modified) = line.strip_prefix("last-modified: ") {
+                validators.last_modified = Some(last_modified.to_owned());
+            }
+        }
+
+        validators
+    }
+}
+
+/// The result of [`RemoteSource::fetch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The file was modified, and its new content was downloaded.
+    Modified {
+        /// The content of the file.
+        content: Vec<u8>,
+
+        /// The validators of this version of the file.
+        validators: Validators,
+    },
+
+    /// The file was not modified since the given validators.
+    NotModified,
+}
+
+/// A [`Source`] that can download files conditionally.
+///
+/// This is used by [`CachedRemote`] to revalidate its local copies.
+pub trait RemoteSource: Source {
+    /// Fetches a file, unless it was not modified according to the given
+    /// validators.
+    ///
+    /// If `validators` is empty, the file is always downloaded.
+    fn fetch(&self, id: &str, ext: &str, validators: &Validators) -> Result<Fetched, SourceError>;
+}
+
+/// A [`Source`] that downloads files from a remote source and keeps a copy of
+/// them in a local source.
+///
+/// When a file is read, it is downloaded from the remote source and written to
+/// the local source. If a copy of the file already exists locally, it is
+/// revalidated: the remote source only sends the file if it was modified,
+/// according to its `ETag` or `Last-Modified` headers. If the remote source is
+/// unreachable, the local copy is used, so content is still available offline.
+///
+/// Validators are stored in the local source next to each file, with
+/// extension `<ext>.meta`.
+///
+/// Directories are read from the remote source, and from the local source if
+/// this fails.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "http")] {
+/// use assets_manager::{AssetCache, source::{CachedRemote, FileSystem, Http}};
+///
+/// let remote = Http::new("https://cdn.example.com/assets");
+/// let local = FileSystem::without_hot_reloading("downloads")?;
+/// let cache = AssetCache::with_source(CachedRemote::new(remote, local));
+/// # }}
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct CachedRemote<R, L> {
+    remote: R,
+    local: L,
+    revalidate: bool,
+}
+
+impl<R, L> CachedRemote<R, L> {
+    /// Creates a new `CachedRemote` from a remote source and a local source.
+    pub fn new(remote: R, local: L) -> CachedRemote<R, L> {
+        CachedRemote {
+            remote,
+            local,
+            revalidate: true,
+        }
+    }
+
+    /// Sets whether local copies are revalidated when they are read.
+    ///
+    /// If disabled, files are only downloaded when there is no local copy.
+    /// This is enabled by default.
+    pub fn revalidate(mut self, revalidate: bool) -> CachedRemote<R, L> {
+        self.revalidate = revalidate;
+        self
+    }
+
+    /// Returns a reference to the remote source.
+    #[inline]
+    pub fn remote(&self) -> &R {
+        &self.remote
+    }
+
+    /// Returns a reference to the local source.
+    #[inline]
+    pub fn local(&self) -> &L {
+        &self.local
+    }
+
+    /// Returns the remote and the local sources.
+    #[inline]
+    pub fn into_inner(self) -> (R, L) {
+        (self.remote, self.local)
+    }
+}
+
+impl<R: RemoteSource, L: WritableSource> CachedRemote<R, L> {
+    /// Downloads a file, and stores it in the local source.
+    ///
+    /// The file is downloaded even if there is an up-to-date local copy.
+    pub fn download(&self, id: &str, ext: &str) -> Result<Vec<u8>, SourceError> {
+        match self.remote.fetch(id, ext, &Validators::default())? {
+            Fetched::Modified { content, validators } => {
+                self.store(id, ext, &content, &validators)?;
+                Ok(content)
+            }
+            Fetched::NotModified => Err(io::Error::other("unexpected unmodified response").into()),
+        }
+    }
+
+    fn store(&self, id: &str, ext: &str, content: &[u8], validators: &Validators) -> Result<(), SourceError> {
+        self.local.write(id, ext, content)?;
+        self.local.write(id, &meta_ext(ext), &validators.to_bytes())
+    }
+
+    fn remove_local(&self, id: &str, ext: &str) {
+        let _ = self.local.remove(id, ext);
+        let _ = self.local.remove(id, &meta_ext(ext));
+    }
+}
+
+fn meta_ext(ext: &str) -> String {
+    format!("{}.meta", ext)
+}
+
+impl<R: RemoteSource, L: WritableSource> Source for CachedRemote<R, L> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let local = match self.local.read(id, ext) {
+            Ok(content) => content,
+            Err(err) if err.kind() == SourceErrorKind::NotFound => {
+                return self.download(id, ext).map(Cow::Owned);
+            }
+            Err(err) => return Err(err),
+        };
+
+        if !self.revalidate {
+            return Ok(local);
+        }
+
+        let validators = match self.local.read(id, &meta_ext(ext)) {
+            Ok(meta) => Validators::from_bytes(&meta),
+            Err(_) => Validators::default(),
+        };
+
+        match self.remote.fetch(id, ext, &validators) {
+            Ok(Fetched::NotModified) => Ok(local),
+            Ok(Fetched::Modified { content, validators }) => {
+                self.store(id, ext, &content, &validators)?;
+                Ok(Cow::Owned(content))
+            }
+            Err(err) if err.kind() == SourceErrorKind::NotFound => {
+                // The file was removed from the remote source
+                self.remove_local(id, ext);
+                Err(err)
+            }
+            Err(_err) => {
+                #[cfg(feature = "log")]
+                log::warn!("Cannot revalidate \"{}\", using local copy: {}", id, _err);
+                Ok(local)
+            }
+        }
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        match self.remote.read_dir(id, ext) {
+            Ok(entries) => Ok(entries),
+            Err(_) => self.local.read_dir(id, ext),
+        }
+    }
+}
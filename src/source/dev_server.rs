@@ -0,0 +1,370 @@
+use std::{
+    borrow::Cow,
+    convert::TryInto,
+    fmt,
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::utils::{HashMap, RwLock};
+
+use super::{DirEntry, Memory, Metadata, Source, SourceError, SourceErrorKind, Version};
+
+
+/// A [`Source`] that receives its files from a development asset server over
+/// WebSocket.
+///
+/// The server pushes the content of all files when the connection is opened,
+/// and then each time a file is modified or removed. Files are kept in
+/// memory, so reading them does not require any round-trip to the server.
+///
+/// This enables hot-reloading on targets where files cannot be watched, such
+/// as WebAssembly, mobile or consoles, with the asset directory being watched
+/// on the development machine instead. This source is meant to be used during
+/// development only.
+///
+/// ## Protocol
+///
+/// The server sends binary messages, encoded as described in [`DevMessage`].
+/// Messages sent by the client are reserved for future use.
+///
+/// On WebAssembly, the source is returned before the first files are
+/// received, so the cache should not be used before [`is_ready`] returns
+/// `true`. On other targets, [`connect`] waits for the initial files.
+///
+/// [`is_ready`]: Self::is_ready
+/// [`connect`]: Self::connect
+///
+/// ## Hot-reloading
+///
+/// Wrap this source in a [`Polling`](super::Polling) source to reload assets
+/// when files are pushed. Checking files for changes is cheap, so the
+/// interval can be set to zero.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::DevServer};
+///
+/// let source = DevServer::connect("ws://192.168.1.10:8080")?;
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "dev-server")))]
+pub struct DevServer {
+    url: String,
+    shared: Arc<Shared>,
+}
+
+/// The state shared with the connection.
+struct Shared {
+    files: Memory,
+    versions: RwLock<HashMap<(String, String), Version>>,
+    ready: AtomicBool,
+    connected: AtomicBool,
+}
+
+impl Shared {
+    fn handle(&self, message: &[u8]) {
+        let message = match DevMessage::decode(message) {
+            Ok(message) => message,
+            Err(err) => {
+                log::warn!("Invalid message from dev server: {}", err);
+                return;
+            }
+        };
+
+        match message {
+            DevMessage::Write { id, ext, content } => {
+                self.versions.write().insert((id.to_owned(), ext.to_owned()), Version::hash_of(content));
+                self.files.insert(id, ext, content);
+            },
+            DevMessage::Remove { id, ext } => {
+                self.files.remove(id, ext);
+                self.versions.write().remove(&(id.to_owned(), ext.to_owned()));
+            },
+            DevMessage::Clear => {
+                self.files.clear();
+                self.versions.write().clear();
+            },
+            DevMessage::Ready => self.ready.store(true, Ordering::Release),
+        }
+    }
+}
+
+impl DevServer {
+    /// Connects to a development asset server.
+    ///
+    /// The URL must use the `ws` scheme, eg `ws://localhost:8080`.
+    ///
+    /// On WebAssembly, this function returns as soon as the connection is
+    /// initiated. On other targets, it blocks until the server sent the
+    /// initial files, and then keeps receiving updates in a background
+    /// thread.
+    pub fn connect<U: Into<String>>(url: U) -> io::Result<DevServer> {
+        let url = url.into();
+        let shared = Arc::new(Shared {
+            files: Memory::new(),
+            versions: RwLock::new(HashMap::new()),
+            ready: AtomicBool::new(false),
+            connected: AtomicBool::new(true),
+        });
+
+        connect(&url, shared.clone())?;
+
+        Ok(DevServer { url, shared })
+    }
+
+    /// Returns the URL of the server.
+    #[inline]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns `true` if the initial files were received.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.shared.ready.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the connection to the server is still open.
+    ///
+    /// Files received before the connection was closed can still be read.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.shared.connected.load(Ordering::Acquire)
+    }
+}
+
+impl Source for DevServer {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.shared.files.read(id, ext)
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.shared.files.read_dir(id, ext)
+    }
+
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.shared.files.read_dir_recursive(id, ext)
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.shared.files.read_dir_entries(id, ext)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.shared.files.metadata(id, ext)
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        let versions = self.shared.versions.read();
+        let version = versions.get(&(id.to_owned(), ext.to_owned()));
+        version.cloned().ok_or_else(|| SourceErrorKind::NotFound.into())
+    }
+}
+
+impl fmt::Debug for DevServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DevServer")
+            .field("url", &self.url)
+            .field("connected", &self.is_connected())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A message sent by a development asset server to a [`DevServer`] source.
+///
+/// Each message is sent as a binary WebSocket message, which starts with a
+/// byte giving its kind:
+/// - `0`: a file was written. It is followed by the id and the extension of
+///   the file, each prefixed by its length as a little-endian `u32`, and then
+///   by the content of the file up to the end of the message.
+/// - `1`: a file was removed. It is followed by the id and the extension of
+///   the file, encoded as above.
+/// - `2`: all files were removed.
+/// - `3`: all files were sent after the connection was opened.
+///
+/// The server is expected to send all files followed by `Ready` when a client
+/// connects.
+#[cfg_attr(docsrs, doc(cfg(feature = "dev-server")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevMessage<'a> {
+    /// A file was created or modified.
+    Write {
+        /// The id of the file.
+        id: &'a str,
+        /// The extension of the file.
+        ext: &'a str,
+        /// The new content of the file.
+        content: &'a [u8],
+    },
+
+    /// A file was removed.
+    Remove {
+        /// The id of the file.
+        id: &'a str,
+        /// The extension of the file.
+        ext: &'a str,
+    },
+
+    /// All files were removed.
+    Clear,
+
+    /// The initial files were all sent.
+    Ready,
+}
+
+impl<'a> DevMessage<'a> {
+    /// Encodes the message.
+    pub fn encode(&self) -> Vec<u8> {
+        fn push_str(buf: &mut Vec<u8>, s: &str) {
+            buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            buf.extend_from_slice(s.as_bytes());
+        }
+
+        let mut buf = Vec::new();
+        match *self {
+            DevMessage::Write { id, ext, content } => {
+                buf.push(0);
+                push_str(&mut buf, id);
+                push_str(&mut buf, ext);
+                buf.extend_from_slice(content);
+            },
+            DevMessage::Remove { id, ext } => {
+                buf.push(1);
+                push_str(&mut buf, id);
+                push_str(&mut buf, ext);
+            },
+            DevMessage::Clear => buf.push(2),
+            DevMessage::Ready => buf.push(3),
+        }
+        buf
+    }
+
+    /// Decodes a message.
+    ///
+    /// An error of kind [`InvalidData`](io::ErrorKind::InvalidData) is
+    /// returned if the message is malformed.
+    pub fn decode(bytes: &'a [u8]) -> io::Result<DevMessage<'a>> {
+        fn invalid() -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, "malformed dev server message")
+        }
+
+        fn take_str<'a>(bytes: &mut &'a [u8]) -> io::Result<&'a str> {
+            if bytes.len() < 4 {
+                return Err(invalid());
+            }
+            let (len, rest) = bytes.split_at(4);
+            let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+            if rest.len() < len {
+                return Err(invalid());
+            }
+            let (s, rest) = rest.split_at(len);
+            *bytes = rest;
+            std::str::from_utf8(s).map_err(|_| invalid())
+        }
+
+        let (&kind, mut rest) = bytes.split_first().ok_or_else(invalid)?;
+        let message = match kind {
+            0 => {
+                let id = take_str(&mut rest)?;
+                let ext = take_str(&mut rest)?;
+                DevMessage::Write { id, ext, content: rest }
+            },
+            1 => {
+                let id = take_str(&mut rest)?;
+                let ext = take_str(&mut rest)?;
+                if !rest.is_empty() {
+                    return Err(invalid());
+                }
+                DevMessage::Remove { id, ext }
+            },
+            2 if rest.is_empty() => DevMessage::Clear,
+            3 if rest.is_empty() => DevMessage::Ready,
+            _ => return Err(invalid()),
+        };
+
+        if let DevMessage::Write { id, ext, .. } | DevMessage::Remove { id, ext } = message {
+            super::validate_id(id, ext)?;
+        }
+
+        Ok(message)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn connect(url: &str, shared: Arc<Shared>) -> io::Result<()> {
+    use tungstenite::Message;
+
+    fn to_io_error(err: tungstenite::Error) -> io::Error {
+        match err {
+            tungstenite::Error::Io(err) => err,
+            err => io::Error::other(err),
+        }
+    }
+
+    let (mut socket, _) = tungstenite::connect(url).map_err(to_io_error)?;
+
+    // Receive initial files
+    while !shared.ready.load(Ordering::Acquire) {
+        match socket.read().map_err(to_io_error)? {
+            Message::Binary(message) => shared.handle(&message),
+            Message::Close(_) => return Err(io::Error::new(io::ErrorKind::ConnectionAborted, "dev server closed the connection")),
+            _ => (),
+        }
+    }
+
+    std::thread::Builder::new()
+        .name("assets_dev_server".to_owned())
+        .spawn(move || {
+            loop {
+                match socket.read() {
+                    Ok(Message::Binary(message)) => shared.handle(&message),
+                    Ok(_) => (),
+                    Err(tungstenite::Error::ConnectionClosed) => break,
+                    Err(err) => {
+                        log::error!("Connection to dev server lost: {}", err);
+                        break;
+                    }
+                }
+            }
+            shared.connected.store(false, Ordering::Release);
+        })?;
+
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn connect(url: &str, shared: Arc<Shared>) -> io::Result<()> {
+    use wasm_bindgen::{JsCast, closure::Closure};
+
+    let socket = web_sys::WebSocket::new(url)
+        .map_err(|err| io::Error::other(format!("cannot connect to dev server: {:?}", err)))?;
+    socket.set_binary_type(web_sys::BinaryType::Arraybuffer);
+
+    let on_message = {
+        let shared = shared.clone();
+        Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+            if let Ok(buffer) = event.data().dyn_into::<js_sys::ArrayBuffer>() {
+                shared.handle(&js_sys::Uint8Array::new(&buffer).to_vec());
+            }
+        })
+    };
+    let on_close = Closure::<dyn FnMut()>::new(move || {
+        log::error!("Connection to dev server lost");
+        shared.connected.store(false, Ordering::Release);
+    });
+
+    socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+    // The callbacks live as long as the connection
+    on_message.forget();
+    on_close.forget();
+
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+type Filter = Box<dyn Fn(&Path) -> bool>;
+type Preprocess = Box<dyn Fn(&Path, Vec<u8>) -> io::Result<Vec<u8>>>;
+
+/// Generates the Rust source of a [`RawEmbedded`](super::RawEmbedded) from a
+/// build script.
+///
+/// This is an alternative to the [`embed!`](super::embed) macro, which does
+/// not require to compile a procedural macro, and which enables to
+/// preprocess files before they are embedded.
+///
+/// The generated code is an expression, which can be included with the
+/// `include!` macro. As it refers to `assets_manager::source::RawEmbedded`,
+/// the crate itself still needs feature `embedded`.
+///
+/// ## Usage
+///
+/// In `build.rs`, with `assets_manager` as a build dependency with feature
+/// `embedded-build`:
+///
+/// ```no_run
+/// use assets_manager::source::EmbedGenerator;
+/// use std::path::Path;
+///
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+///
+/// println!("cargo:rerun-if-changed=assets");
+/// EmbedGenerator::new("assets")
+///     .filter(|path| path.extension().map_or(true, |ext| ext != "psd"))
+///     .write_to(Path::new(&out_dir).join("assets.rs"))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Then in the crate:
+///
+/// ```ignore
+/// use assets_manager::source::{Embedded, RawEmbedded};
+///
+/// static EMBEDDED: RawEmbedded<'static> = include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+///
+/// let source = Embedded::from(EMBEDDED);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-build")))]
+pub struct EmbedGenerator {
+    root: PathBuf,
+    filter: Option<Filter>,
+    preprocess: Option<Preprocess>,
+}
+
+impl EmbedGenerator {
+    /// Creates a new `EmbedGenerator` that embeds the given directory.
+    pub fn new<P: AsRef<Path>>(root: P) -> EmbedGenerator {
+        EmbedGenerator {
+            root: root.as_ref().to_owned(),
+            filter: None,
+            preprocess: None,
+        }
+    }
+
+    /// Only embeds the files and directories for which `filter` returns
+    /// `true`.
+    ///
+    /// The filter is given paths relative to the root. When a directory is
+    /// filtered out, its content is not embedded.
+    pub fn filter<F>(mut self, filter: F) -> EmbedGenerator
+    where
+        F: Fn(&Path) -> bool + 'static,
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Transforms the content of the files before they are embedded.
+    ///
+    /// The function is given the path of the file relative to the root and
+    /// its content. When this is set, files are embedded as byte literals
+    /// instead of with `include_bytes!`.
+    pub fn preprocess<F>(mut self, preprocess: F) -> EmbedGenerator
+    where
+        F: Fn(&Path, Vec<u8>) -> io::Result<Vec<u8>> + 'static,
+    {
+        self.preprocess = Some(Box::new(preprocess));
+        self
+    }
+
+    /// Generates the Rust source of the `RawEmbedded`.
+    ///
+    /// # Errors
+    ///
+    /// An error can occur if the root cannot be read, if a path is not valid
+    /// UTF-8, or if preprocessing fails.
+    pub fn generate(&self) -> io::Result<String> {
+        let root = self.root.canonicalize()?;
+        let mut content = Content {
+            files: Vec::new(),
+            dirs: BTreeMap::new(),
+        };
+
+        content.dirs.insert(String::new(), Vec::new());
+        self.read_dir(&root, &root, "", &mut content)?;
+
+        Ok(content.to_string())
+    }
+
+    /// Generates the Rust source of the `RawEmbedded` and writes it to a
+    /// file.
+    ///
+    /// The file is only written if its content changed, to avoid needless
+    /// recompilations.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let code = self.generate()?;
+
+        match fs::read(path) {
+            Ok(old) if old == code.as_bytes() => Ok(()),
+            _ => fs::write(path, code),
+        }
+    }
+
+    fn read_dir(&self, root: &Path, path: &Path, id: &str, content: &mut Content) -> io::Result<()> {
+        let mut entries = fs::read_dir(path)?.map(|e| Ok(e?.path())).collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+
+        for path in entries {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if let Some(filter) = &self.filter {
+                if !filter(relative) {
+                    continue;
+                }
+            }
+
+            let stem = path.file_stem().and_then(|s| s.to_str()).ok_or_else(|| invalid_path(&path))?;
+            let this_id = super::join_id(id, stem);
+
+            if path.is_dir() {
+                content.dirs.entry(this_id.clone()).or_default();
+                self.read_dir(root, &path, &this_id, content)?;
+            } else if path.is_file() {
+                let ext = match path.extension() {
+                    Some(ext) => ext.to_str().ok_or_else(|| invalid_path(&path))?,
+                    None => "",
+                };
+                let path_str = path.to_str().ok_or_else(|| invalid_path(&path))?.to_owned();
+                let preprocessed = match &self.preprocess {
+                    Some(preprocess) => Some(preprocess(relative, fs::read(&path)?)?),
+                    None => None,
+                };
+
+                let dir = content.dirs.get_mut(id).expect("file without directory");
+                dir.push((stem.to_owned(), ext.to_owned()));
+                content.files.push(File {
+                    id: this_id,
+                    ext: ext.to_owned(),
+                    path: path_str,
+                    preprocessed,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct File {
+    id: String,
+    ext: String,
+    path: String,
+    preprocessed: Option<Vec<u8>>,
+}
+
+struct Content {
+    files: Vec<File>,
+    dirs: BTreeMap<String, Vec<(String, String)>>,
+}
+
+impl fmt::Display for Content {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "assets_manager::source::RawEmbedded {{")?;
+
+        writeln!(f, "    files: &[")?;
+        for file in &self.files {
+            match &file.preprocessed {
+                None => writeln!(f, "        (({:?}, {:?}), include_bytes!({:?}) as &[u8]),", file.id, file.ext, file.path)?,
+                Some(content) => {
+                    // `include_bytes!` is still used so that the crate is
+                    // rebuilt when the file changes.
+                    writeln!(f, "        (({:?}, {:?}), {{", file.id, file.ext)?;
+                    writeln!(f, "            const _: &[u8] = include_bytes!({:?});", file.path)?;
+                    writeln!(f, "            {} as &[u8]", ByteString(content))?;
+                    writeln!(f, "        }}),")?;
+                }
+            }
+        }
+        writeln!(f, "    ],")?;
+
+        writeln!(f, "    dirs: &[")?;
+        for (id, entries) in &self.dirs {
+            write!(f, "        ({:?}, &[", id)?;
+            for (stem, ext) in entries {
+                write!(f, "({:?}, {:?}), ", stem, ext)?;
+            }
+            writeln!(f, "] as &[(&str, &str)]),")?;
+        }
+        writeln!(f, "    ],")?;
+
+        writeln!(f, "    compression: assets_manager::source::Compression::None,")?;
+        writeln!(f, "}}")
+    }
+}
+
+impl fmt::Debug for EmbedGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmbedGenerator")
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
+
+fn invalid_path(path: &Path) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{}: path is not valid UTF-8", path.display()))
+}
+
+/// Formats bytes as a Rust byte string literal.
+pub(super) struct ByteString<'a>(pub &'a [u8]);
+
+impl fmt::Display for ByteString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("b\"")?;
+        for &b in self.0 {
+            match b {
+                b'"' | b'\\' => write!(f, "\\{}", b as char)?,
+                b' '..=b'~' => f.write_char(b as char)?,
+                _ => write!(f, "\\x{:02x}", b)?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
@@ -1,10 +1,11 @@
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io,
+    sync::OnceLock,
 };
 
-use super::Source;
+use super::{is_in_dir, join_id, sub_dirs, DirEntry, Source, SourceError};
 
 
 /// The raw representation of embedded files. The common way to create one is the
@@ -24,7 +25,57 @@ pub struct RawEmbedded<'a> {
 
     /// A list of directory, represented by their id, with the list of files
     /// they contain.
+    ///
+    /// Missing directories are added when creating an `Embedded` source: the
+    /// parents of all directories are registered, and files that are not
+    /// listed in any directory are added to their parent directory.
     pub dirs: &'a [(&'a str, &'a [(&'a str, &'a str)])],
+
+    /// How the content of the files is compressed.
+    pub compression: Compression,
+}
+
+/// The compression of embedded files.
+///
+/// Files can be compressed at compile time by passing the `compress` argument
+/// to the [`embed!`](`super::embed`) macro, for example
+/// `embed!("assets", compress = "zstd")`. They are then decompressed when they
+/// are first read, and kept in memory afterwards.
+///
+/// Compression requires features `embedded-deflate` or `embedded-zstd`.
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// Files are not compressed.
+    None,
+
+    /// Files are compressed with raw Deflate.
+    #[cfg(feature = "embedded-deflate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-deflate")))]
+    Deflate,
+
+    /// Files are compressed with Zstandard.
+    #[cfg(feature = "embedded-zstd")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "embedded-zstd")))]
+    Zstd,
+}
+
+impl Compression {
+    fn decompress(self, content: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(content.to_owned()),
+            #[cfg(feature = "embedded-deflate")]
+            Compression::Deflate => {
+                use std::io::Read;
+
+                let mut decompressed = Vec::new();
+                flate2::read::DeflateDecoder::new(content).read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+            #[cfg(feature = "embedded-zstd")]
+            Compression::Zstd => zstd_crate::stream::decode_all(content),
+        }
+    }
 }
 
 /// A [`Source`] which is embedded in the binary. It is created using a
@@ -44,6 +95,9 @@ pub struct RawEmbedded<'a> {
 /// these reasons, you should only use this source for release builds. It also
 /// tends to creates large binarie, which increases memory usage.
 ///
+/// Compressing embedded files reduces the size of the binary, at the cost of
+/// decompressing them at runtime. See [`Compression`] for details.
+///
 /// ## Usage
 ///
 /// ```no_run
@@ -55,28 +109,88 @@ pub struct RawEmbedded<'a> {
 #[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
 #[derive(Clone, Debug)]
 pub struct Embedded<'a> {
-    files: HashMap<(&'a str, &'a str), &'a [u8]>,
-    dirs: HashMap<&'a str, &'a [(&'a str, &'a str)]>,
+    files: HashMap<(&'a str, &'a str), (&'a [u8], usize)>,
+    dirs: HashMap<&'a str, Cow<'a, [(&'a str, &'a str)]>>,
+    compression: Compression,
+    decompressed: Box<[OnceLock<Vec<u8>>]>,
 }
 
 impl<'a> From<RawEmbedded<'a>> for Embedded<'a> {
     fn from(raw: RawEmbedded<'a>) -> Embedded<'a> {
+        let files = raw.files.iter().enumerate().map(|(i, &(key, content))| (key, (content, i)));
+
+        let decompressed = match raw.compression {
+            Compression::None => Box::default(),
+            _ => raw.files.iter().map(|_| OnceLock::new()).collect(),
+        };
+
+        let mut dirs = raw.dirs.iter().map(|&(id, files)| (id, Cow::Borrowed(files))).collect();
+        complete_dirs(&mut dirs, raw.files);
+
         Embedded {
-            files: raw.files.iter().copied().collect(),
-            dirs: raw.dirs.iter().copied().collect(),
+            files: files.collect(),
+            dirs,
+            compression: raw.compression,
+            decompressed,
+        }
+    }
+}
+
+type Dirs<'a> = HashMap<&'a str, Cow<'a, [(&'a str, &'a str)]>>;
+
+/// Registers the directories that are missing from `dirs`, so that the
+/// embedded tree can be read at every depth.
+fn complete_dirs<'a>(dirs: &mut Dirs<'a>, files: &[((&'a str, &'a str), &'a [u8])]) {
+    let listed: HashSet<(String, &str)> = dirs.iter()
+        .flat_map(|(&dir, files)| files.iter().map(move |&(name, ext)| (join_id(dir, name), ext)))
+        .collect();
+
+    for &((id, ext), _) in files {
+        if !listed.contains(&(id.to_owned(), ext)) {
+            let (dir, name) = id.rsplit_once('.').unwrap_or(("", id));
+            dirs.entry(dir).or_insert(Cow::Borrowed(&[])).to_mut().push((name, ext));
+        }
+    }
+
+    dirs.entry("").or_insert(Cow::Borrowed(&[]));
+    let ids: Vec<&'a str> = dirs.keys().copied().collect();
+    for mut id in ids {
+        while let Some((parent, _)) = id.rsplit_once('.') {
+            if dirs.contains_key(parent) {
+                break;
+            }
+            dirs.insert(parent, Cow::Borrowed(&[]));
+            id = parent;
         }
     }
 }
 
+impl<'a> Embedded<'a> {
+    /// Creates an [`EmbeddedBuilder`] to register embedded files one by one.
+    #[inline]
+    pub fn builder() -> EmbeddedBuilder<'a> {
+        EmbeddedBuilder::new()
+    }
+}
+
 impl<'a> Source for Embedded<'a> {
-    fn read(&self, id: &str, ext: &str) -> io::Result<Cow<[u8]>> {
-        match self.files.get(&(id, ext)) {
-            Some(content) => Ok(Cow::Borrowed(content)),
-            None => Err(io::ErrorKind::NotFound.into()),
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<[u8]>, SourceError> {
+        let &(content, index) = self.files.get(&(id, ext)).ok_or(io::ErrorKind::NotFound)?;
+
+        if self.compression == Compression::None {
+            return Ok(Cow::Borrowed(content));
         }
+
+        let cached = &self.decompressed[index];
+        if let Some(content) = cached.get() {
+            return Ok(Cow::Borrowed(content));
+        }
+
+        let content = self.compression.decompress(content)?;
+        Ok(Cow::Borrowed(cached.get_or_init(|| content)))
     }
 
-    fn read_dir(&self, id: &str, ext: &[&str]) -> io::Result<Vec<String>> {
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
         let dir = self.dirs.get(id).ok_or(io::ErrorKind::NotFound)?;
 
         Ok(dir.iter().copied()
@@ -85,4 +199,110 @@ impl<'a> Source for Embedded<'a> {
             .collect()
         )
     }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let dir = self.dirs.get(id).ok_or(io::ErrorKind::NotFound)?;
+
+        let files = dir.iter().copied()
+            .filter(|(_, file_ext)| ext.contains(file_ext))
+            .map(|(id, _)| DirEntry::File(id.to_owned()));
+
+        Ok(files.chain(sub_dirs(self.dirs.keys().copied(), id)).collect())
+    }
+
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        if !self.dirs.contains_key(id) {
+            return Err(io::ErrorKind::NotFound.into());
+        }
+
+        let mut entries = Vec::new();
+
+        for (&dir_id, files) in &self.dirs {
+            if !is_in_dir(dir_id, id) {
+                continue;
+            }
+
+            for &(name, file_ext) in files.iter() {
+                if ext.contains(&file_ext) {
+                    entries.push(join_id(dir_id, name));
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// A builder to create an [`Embedded`] source from individual files.
+///
+/// This is useful when files are not all in a single directory, for example
+/// to embed files included with `include_bytes!` or generated at runtime.
+/// Parent directories of the files are registered automatically, so
+/// `read_dir` works at every level.
+///
+/// Files registered with the builder are not compressed.
+///
+/// ## Example
+///
+/// ```
+/// use assets_manager::{AssetCache, source::Embedded};
+///
+/// let source = Embedded::builder()
+///     .file("common.position", "ron", include_bytes!("../../assets/common/position.ron"))
+///     .file("test.b", "x", b"-7")
+///     .build();
+///
+/// let cache = AssetCache::with_source(source);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
+#[derive(Clone, Debug, Default)]
+pub struct EmbeddedBuilder<'a> {
+    files: HashMap<(&'a str, &'a str), &'a [u8]>,
+    dirs: HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+}
+
+impl<'a> EmbeddedBuilder<'a> {
+    /// Creates an empty builder.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file given its id, its extension and its content.
+    ///
+    /// If a file with the same id and extension was already added, its
+    /// content is replaced.
+    pub fn file(mut self, id: &'a str, ext: &'a str, content: &'a [u8]) -> Self {
+        if self.files.insert((id, ext), content).is_none() {
+            let (dir, name) = id.rsplit_once('.').unwrap_or(("", id));
+            self.add_dir(dir).push((name, ext));
+        }
+        self
+    }
+
+    /// Adds a directory given its id, even if it contains no file.
+    pub fn dir(mut self, id: &'a str) -> Self {
+        self.add_dir(id);
+        self
+    }
+
+    fn add_dir(&mut self, id: &'a str) -> &mut Vec<(&'a str, &'a str)> {
+        if !self.dirs.contains_key(id) && !id.is_empty() {
+            let parent = id.rsplit_once('.').map_or("", |(parent, _)| parent);
+            self.add_dir(parent);
+        }
+        self.dirs.entry(id).or_default()
+    }
+
+    /// Creates the `Embedded` source.
+    pub fn build(self) -> Embedded<'a> {
+        let files = self.files.into_iter().enumerate().map(|(i, (key, content))| (key, (content, i)));
+
+        Embedded {
+            files: files.collect(),
+            dirs: self.dirs.into_iter().map(|(id, files)| (id, Cow::Owned(files))).collect(),
+            compression: Compression::None,
+            decompressed: Box::default(),
+        }
+    }
 }
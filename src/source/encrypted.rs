@@ -0,0 +1,171 @@
+#[cfg(feature = "hot-reloading")]
+use crate::utils::PrivateMarker;
+
+use chacha20poly1305::{aead::{Aead, KeyInit}, ChaCha20Poly1305, Nonce};
+
+use std::{
+    borrow::Cow,
+    fmt,
+    io,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, Version};
+
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// A [`Source`] that decrypts the files of another source.
+///
+/// Files are encrypted with ChaCha20-Poly1305, with the key given at
+/// construction. They are made of the 12-byte nonce followed by the encrypted
+/// data and the 16-byte authentication tag, which is the same format as
+/// [`EncryptedLoader`](crate::loader::EncryptedLoader). Such files can be
+/// created with [`EncryptedSource::encrypt`].
+///
+/// If a file was not encrypted with the right key or if it was tampered with,
+/// reading it returns an error of kind
+/// [`InvalidData`](io::ErrorKind::InvalidData). Directories are read from the
+/// inner source as is.
+///
+/// This enables encrypting assets at rest once for all loaders, for example
+/// by wrapping a [`Pack`](super::Pack). Note that the key has to be stored in
+/// the program to decrypt assets, so this can only be used as obfuscation.
+///
+/// ## Hot-reloading
+///
+/// This source supports hot-reloading if the inner source does.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{EncryptedSource, FileSystem}};
+///
+/// const KEY: [u8; 32] = *b"an example very very secret key.";
+///
+/// let source = EncryptedSource::new(FileSystem::new("assets")?, KEY);
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+#[derive(Clone)]
+pub struct EncryptedSource<S> {
+    source: S,
+    cipher: ChaCha20Poly1305,
+}
+
+impl<S> EncryptedSource<S> {
+    /// Creates a new `EncryptedSource`, decrypting files of `source` with the
+    /// given 256-bit key.
+    pub fn new(source: S, key: [u8; 32]) -> EncryptedSource<S> {
+        EncryptedSource {
+            source,
+            cipher: ChaCha20Poly1305::new(&key.into()),
+        }
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Encrypts a file with the key of the source, so it can be read by it.
+    ///
+    /// A nonce must never be used twice with the same key, so it should be
+    /// generated randomly for each file.
+    pub fn encrypt(&self, nonce: [u8; NONCE_LEN], content: &[u8]) -> io::Result<Vec<u8>> {
+        let encrypted = self.cipher.encrypt(Nonce::from_slice(&nonce), content)
+            .map_err(|_| io::Error::other("failed to encrypt file"))?;
+        Ok([&nonce[..], &encrypted].concat())
+    }
+
+    /// Decrypts a file encrypted with the key of the source.
+    pub fn decrypt(&self, content: &[u8]) -> io::Result<Vec<u8>> {
+        if content.len() < NONCE_LEN + TAG_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "encrypted file is too short"));
+        }
+        let (nonce, encrypted) = content.split_at(NONCE_LEN);
+
+        self.cipher.decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "failed to decrypt file"))
+    }
+}
+
+impl<S: Source> Source for EncryptedSource<S> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let content = self.source.read(id, ext)?;
+        Ok(Cow::Owned(self.decrypt(&content)?))
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir(id, ext)
+    }
+
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir_recursive(id, ext)
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read_dir_entries(id, ext)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let metadata = self.source.metadata(id, ext)?;
+        let len = metadata.len.checked_sub((NONCE_LEN + TAG_LEN) as u64)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "encrypted file is too short"))?;
+
+        Ok(Metadata { len, ..metadata })
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.version(id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_asset<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.source._add_asset::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_file<A: crate::Asset, P: PrivateMarker>(&self, key: &str, id: &str, ext: &str) {
+        self.source._add_file::<A, P>(key, id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_dir<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.source._add_dir::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _clear<P: PrivateMarker>(&mut self) {
+        self.source._clear::<P>()
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_compound<A: crate::Compound, P: PrivateMarker>(&self, id: &str, deps: crate::utils::DepsRecord) {
+        self.source._add_compound::<A, P>(id, deps)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _support_hot_reloading<P: PrivateMarker>(&self) -> bool {
+        self.source._support_hot_reloading::<P>()
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _refresh<P: PrivateMarker>(&self) {
+        self.source._refresh::<P>()
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for EncryptedSource<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedSource").field("source", &self.source).finish_non_exhaustive()
+    }
+}
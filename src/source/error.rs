@@ -0,0 +1,282 @@
+use std::{error::Error, fmt, io};
+
+use crate::BoxedError;
+
+use super::InvalidId;
+
+
+/// The kind of a [`SourceError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SourceErrorKind {
+    /// The file or the directory does not exist.
+    NotFound,
+
+    /// The source refused to give access to the file.
+    PermissionDenied,
+
+    /// The id or the extension is not valid, see
+    /// [`validate_id`](super::validate_id).
+    InvalidId,
+
+    /// The content of the file could not be decoded, eg it could not be
+    /// decompressed, decrypted or verified.
+    Decode,
+
+    /// An archive is malformed.
+    Archive,
+
+    /// The operation is not supported by the source.
+    Unsupported,
+
+    /// Another error, such as a network or an I/O error.
+    Other,
+}
+
+impl SourceErrorKind {
+    fn from_io(kind: io::ErrorKind) -> SourceErrorKind {
+        match kind {
+            io::ErrorKind::NotFound => SourceErrorKind::NotFound,
+            io::ErrorKind::PermissionDenied => SourceErrorKind::PermissionDenied,
+            io::ErrorKind::InvalidData => SourceErrorKind::Decode,
+            io::ErrorKind::Unsupported => SourceErrorKind::Unsupported,
+            _ => SourceErrorKind::Other,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            SourceErrorKind::NotFound => "not found",
+            SourceErrorKind::PermissionDenied => "permission denied",
+            SourceErrorKind::InvalidId => "invalid id",
+            SourceErrorKind::Decode => "cannot decode file",
+            SourceErrorKind::Archive => "malformed archive",
+            SourceErrorKind::Unsupported => "unsupported operation",
+            SourceErrorKind::Other => "other error",
+        }
+    }
+}
+
+impl fmt::Display for SourceErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// An error returned by a [`Source`](super::Source).
+///
+/// It gives the [kind](SourceErrorKind) of the error, the error that caused
+/// it, and when they are known, the id and the extension of the file and the
+/// type of the source that failed.
+///
+/// It can be converted from and to an [`io::Error`], so I/O errors can be
+/// propagated with `?` when implementing a source.
+pub struct SourceError(Box<Inner>);
+
+struct Inner {
+    kind: SourceErrorKind,
+    source_name: Option<&'static str>,
+    id: Option<Box<str>>,
+    ext: Option<Box<str>>,
+    error: Option<BoxedError>,
+}
+
+impl SourceError {
+    /// Creates a new error from its kind and the error that caused it.
+    pub fn new<E: Into<BoxedError>>(kind: SourceErrorKind, error: E) -> SourceError {
+        SourceError::with_error(kind, Some(error.into()))
+    }
+
+    fn with_error(kind: SourceErrorKind, error: Option<BoxedError>) -> SourceError {
+        SourceError(Box::new(Inner {
+            kind,
+            source_name: None,
+            id: None,
+            ext: None,
+            error,
+        }))
+    }
+
+    /// Creates an error with the same kind and context as this one, but with
+    /// another cause.
+    pub(crate) fn with_cause(&self, error: Option<BoxedError>) -> SourceError {
+        SourceError(Box::new(Inner {
+            kind: self.0.kind,
+            source_name: self.0.source_name,
+            id: self.0.id.clone(),
+            ext: self.0.ext.clone(),
+            error,
+        }))
+    }
+
+    /// Adds the id of the file or the directory to the error.
+    ///
+    /// If it was already set, it is not replaced.
+    pub fn with_id(mut self, id: &str) -> SourceError {
+        if self.0.id.is_none() {
+            self.0.id = Some(id.into());
+        }
+        self
+    }
+
+    /// Adds the id and the extension of the file to the error.
+    ///
+    /// If they were already set, they are not replaced.
+    pub fn with_file(mut self, id: &str, ext: &str) -> SourceError {
+        if self.0.id.is_none() {
+            self.0.id = Some(id.into());
+            self.0.ext = Some(ext.into());
+        }
+        self
+    }
+
+    /// Adds the type of the source to the error.
+    ///
+    /// If it was already set, it is not replaced, so the innermost source is
+    /// kept when a source wraps another one.
+    pub fn with_source<S: ?Sized>(mut self) -> SourceError {
+        if self.0.source_name.is_none() {
+            self.0.source_name = Some(std::any::type_name::<S>());
+        }
+        self
+    }
+
+    /// The kind of the error.
+    #[inline]
+    pub fn kind(&self) -> SourceErrorKind {
+        self.0.kind
+    }
+
+    /// The id of the file or the directory that could not be read, if it is
+    /// known.
+    #[inline]
+    pub fn id(&self) -> Option<&str> {
+        self.0.id.as_deref()
+    }
+
+    /// The extension of the file that could not be read, if it is known.
+    #[inline]
+    pub fn ext(&self) -> Option<&str> {
+        self.0.ext.as_deref()
+    }
+
+    /// The type name of the source that failed, if it is known.
+    #[inline]
+    pub fn source_name(&self) -> Option<&'static str> {
+        self.0.source_name
+    }
+
+    /// The error that caused this one, if any.
+    #[inline]
+    pub fn error(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.0.error.as_deref()
+    }
+
+    /// Unwraps the error that caused this one, if any.
+    #[inline]
+    pub fn into_inner(self) -> Option<BoxedError> {
+        self.0.error
+    }
+
+    /// Returns the corresponding [`io::ErrorKind`].
+    ///
+    /// If the error was caused by an I/O error, its kind is kept.
+    pub fn io_kind(&self) -> io::ErrorKind {
+        if let Some(err) = self.0.error.as_deref().and_then(|err| err.downcast_ref::<io::Error>()) {
+            return err.kind();
+        }
+
+        match self.0.kind {
+            SourceErrorKind::NotFound => io::ErrorKind::NotFound,
+            SourceErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
+            SourceErrorKind::InvalidId => io::ErrorKind::InvalidInput,
+            SourceErrorKind::Decode | SourceErrorKind::Archive => io::ErrorKind::InvalidData,
+            SourceErrorKind::Unsupported => io::ErrorKind::Unsupported,
+            SourceErrorKind::Other => io::ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Debug for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceError")
+            .field("kind", &self.0.kind)
+            .field("source_name", &self.0.source_name)
+            .field("id", &self.0.id)
+            .field("ext", &self.0.ext)
+            .field("error", &self.0.error)
+            .finish()
+    }
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(id) = &self.0.id {
+            write!(f, "\"{}\"", id)?;
+            if let Some(ext) = &self.0.ext {
+                write!(f, " (extension \"{}\")", ext)?;
+            }
+            if let Some(name) = self.0.source_name {
+                write!(f, " from {}", name)?;
+            }
+            f.write_str(": ")?;
+        }
+
+        match &self.0.error {
+            Some(err) => fmt::Display::fmt(err, f),
+            None => f.write_str(self.0.kind.as_str()),
+        }
+    }
+}
+
+impl Error for SourceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.0.error {
+            Some(err) => Some(&**err),
+            None => None,
+        }
+    }
+}
+
+impl From<SourceErrorKind> for SourceError {
+    #[inline]
+    fn from(kind: SourceErrorKind) -> SourceError {
+        SourceError::with_error(kind, None)
+    }
+}
+
+impl From<io::ErrorKind> for SourceError {
+    #[inline]
+    fn from(kind: io::ErrorKind) -> SourceError {
+        SourceError::from(io::Error::from(kind))
+    }
+}
+
+impl From<io::Error> for SourceError {
+    fn from(err: io::Error) -> SourceError {
+        // Get back errors that were converted to `io::Error`
+        if err.get_ref().is_some_and(|e| e.is::<SourceError>() || e.is::<InvalidId>()) {
+            let inner = err.into_inner().unwrap();
+            return match inner.downcast::<SourceError>() {
+                Ok(err) => *err,
+                Err(inner) => SourceError::new(SourceErrorKind::InvalidId, inner),
+            };
+        }
+
+        let kind = SourceErrorKind::from_io(err.kind());
+        SourceError::new(kind, err)
+    }
+}
+
+impl From<InvalidId> for SourceError {
+    #[inline]
+    fn from(err: InvalidId) -> SourceError {
+        SourceError::new(SourceErrorKind::InvalidId, err)
+    }
+}
+
+impl From<SourceError> for io::Error {
+    fn from(err: SourceError) -> io::Error {
+        io::Error::new(err.io_kind(), err)
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    borrow::Cow,
+    io::Read,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, SourceErrorKind, Version, WritableSource};
+
+/// A [`Source`] that reads from a primary source, and falls back to a second
+/// one when a file is not found.
+///
+/// For example, this enables to read loose files during development, and to
+/// fall back to embedded files for anything that is missing.
+///
+/// Unlike [`Overlay`](super::Overlay), directories are not merged: a directory
+/// is read from the primary source if it exists there, and from the fallback
+/// source otherwise.
+///
+/// The fallback source is only used when the primary source returns an error
+/// of kind [`NotFound`](SourceErrorKind::NotFound). Other errors are returned
+/// as is.
+///
+/// ## Usage
+///
+/// ```no_run
+/// # cfg_if::cfg_if! { if #[cfg(feature = "embedded")] {
+/// use assets_manager::{AssetCache, source::{embed, Embedded, FallbackSource, FileSystem}};
+///
+/// let source = FallbackSource::new(
+///     FileSystem::new("assets")?,
+///     Embedded::from(embed!("assets")),
+/// );
+/// let cache = AssetCache::with_source(source);
+/// # }}
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FallbackSource<A, B> {
+    primary: A,
+    fallback: B,
+}
+
+impl<A, B> FallbackSource<A, B> {
+    /// Creates a new `FallbackSource` from a primary and a fallback source.
+    #[inline]
+    pub fn new(primary: A, fallback: B) -> FallbackSource<A, B> {
+        FallbackSource { primary, fallback }
+    }
+
+    /// Returns a reference to the primary source.
+    #[inline]
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Returns a reference to the fallback source.
+    #[inline]
+    pub fn fallback(&self) -> &B {
+        &self.fallback
+    }
+
+    /// Returns the primary and the fallback sources.
+    #[inline]
+    pub fn into_inner(self) -> (A, B) {
+        (self.primary, self.fallback)
+    }
+}
+
+impl<A: Source, B: Source> Source for FallbackSource<A, B> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        match self.primary.read(id, ext) {
+            Err(err) if err.kind() == SourceErrorKind::NotFound => self.fallback.read(id, ext),
+            result => result,
+        }
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        match self.primary.read_dir(id, ext) {
+            Err(err) if err.kind() == SourceErrorKind::NotFound => self.fallback.read_dir(id, ext),
+            result => result,
+        }
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        match self.primary.read_dir_entries(id, ext) {
+            Err(err) if err.kind() == SourceErrorKind::NotFound => self.fallback.read_dir_entries(id, ext),
+            result => result,
+        }
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        match self.primary.metadata(id, ext) {
+            Err(err) if err.kind() == SourceErrorKind::NotFound => self.fallback.metadata(id, ext),
+            result => result,
+        }
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        match self.primary.version(id, ext) {
+            Err(err) if err.kind() == SourceErrorKind::NotFound => self.fallback.version(id, ext),
+            result => result,
+        }
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        match self.primary.open(id, ext) {
+            Err(err) if err.kind() == SourceErrorKind::NotFound => self.fallback.open(id, ext),
+            result => result,
+        }
+    }
+}
+
+/// Files are always written to and removed from the primary source.
+impl<A: WritableSource, B: Source> WritableSource for FallbackSource<A, B> {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        self.primary.write(id, ext, content)
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        self.primary.remove(id, ext)
+    }
+}
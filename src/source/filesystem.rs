@@ -6,6 +6,7 @@ use crate::{
         CompoundReloadInfos,
         HotReloader,
         UpdateMessage,
+        find_symlinks,
     },
     utils::PrivateMarker,
 };
@@ -13,15 +14,19 @@ use crate::{
 #[cfg(doc)]
 use crate::AssetCache;
 
+#[cfg(feature = "mmap")]
+use crate::utils::{HashMap, Mutex};
+
 use std::{
     borrow::Cow,
+    ffi::OsString,
     fmt,
     fs,
-    io,
+    io::{self, Read},
     path::{Path, PathBuf},
 };
 
-use super::Source;
+use super::{DirEntry, Metadata, Source, SourceError, SourceErrorKind, Version, WritableSource};
 
 
 #[inline]
@@ -33,13 +38,74 @@ pub fn extension_of(path: &Path) -> Option<&str> {
 }
 
 #[inline]
-fn has_extension(path: &Path, ext: &[&str]) -> bool {
+fn has_extension(path: &Path, ext: &[&str], ignore_case: bool) -> bool {
     match extension_of(path) {
+        Some(file_ext) if ignore_case => ext.iter().any(|ext| eq_ignore_case(ext, file_ext)),
         Some(file_ext) => ext.contains(&file_ext),
         None => false,
     }
 }
 
+#[inline]
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || a.to_lowercase() == b.to_lowercase()
+}
+
+fn path_in(root: &Path, id: &str, ext: &str) -> io::Result<PathBuf> {
+    super::validate_id(id, ext)?;
+
+    let mut path = root.to_owned();
+    path.extend(id.split('.'));
+    path.set_extension(ext);
+    Ok(path)
+}
+
+/// Returns the directory where an application should store its user data,
+/// getting environment variables with `var`.
+pub(super) fn user_data_path(app_name: &str, var: impl Fn(&str) -> Option<OsString>) -> io::Result<PathBuf> {
+    if app_name.is_empty() || app_name.contains(&['/', '\\', ':', '\0'][..]) || app_name == ".." {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid application name"));
+    }
+
+    let absolute = |name| var(name).map(PathBuf::from).filter(|path| path.is_absolute());
+    let not_found = |name| io::Error::new(io::ErrorKind::NotFound, format!("{} is not set", name));
+
+    let base = if cfg!(windows) {
+        absolute("APPDATA").ok_or_else(|| not_found("APPDATA"))?
+    } else if cfg!(target_os = "macos") {
+        absolute("HOME").ok_or_else(|| not_found("HOME"))?.join("Library/Application Support")
+    } else {
+        match absolute("XDG_DATA_HOME") {
+            Some(path) => path,
+            None => absolute("HOME").ok_or_else(|| not_found("HOME"))?.join(".local/share"),
+        }
+    };
+
+    Ok(base.join(app_name))
+}
+
+/// Returns an error if a component of the path below the root is a symbolic
+/// link.
+fn check_no_symlink(root: &Path, path: &Path) -> io::Result<()> {
+    for ancestor in path.ancestors().take_while(|&p| p != root) {
+        if let Ok(metadata) = fs::symlink_metadata(ancestor) {
+            if metadata.file_type().is_symlink() {
+                return Err(io::ErrorKind::NotFound.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `link` points to `dir` or to one of its parents.
+fn is_ancestor_link(link: &Path, dir: &Path) -> bool {
+    match (fs::canonicalize(link), fs::canonicalize(dir)) {
+        (Ok(target), Ok(dir)) => dir.starts_with(target),
+        _ => true,
+    }
+}
+
 /// A [`Source`] to load assets from a directory in the file system.
 ///
 /// This is the default `Source` of [`AssetCache`].
@@ -49,12 +115,57 @@ fn has_extension(path: &Path, ext: &[&str]) -> bool {
 /// This source supports hot-reloading: when a file is edited, the corresponding
 /// assets are reloaded when [`AssetCache::hot_reload`] is called.
 ///
+/// The [version](Source::version) of a file is a hash of its content, so
+/// saving a file without changing it does not reload assets.
+///
+/// ## Case sensitivity
+///
+/// By default, ids are resolved with the case sensitivity of the underlying
+/// file system. With [`FileSystem::case_insensitive`], ids are resolved
+/// ignoring case even on case-sensitive file systems, so that an asset saved
+/// as `Texture.PNG` can be loaded as `texture` with extension `png`. This is
+/// useful when assets are authored on Windows or macOS and used on Linux.
+///
+/// ## Several roots
+///
+/// A `FileSystem` can read from several directories with
+/// [`FileSystem::with_roots`]. Files are searched in each root in order, and
+/// the first one found is used. Directories are merged: reading a directory
+/// lists files from all roots. New files are written to the first root, unless
+/// they already exist in another one.
+///
+/// When hot-reloading is used, all roots are watched. An asset is reloaded
+/// when the file it was loaded from is modified.
+///
+/// ## Symbolic links
+///
+/// By default, symbolic links are followed like regular files and
+/// directories. With [`FileSystem::follow_symlinks`], they can be ignored
+/// instead: files reached through a link are not found, and links are not
+/// listed when reading directories.
+///
+/// Hot-reloading does not always see changes made to the targets of links,
+/// depending on the platform and on whether the target is a file or a
+/// directory. Use [`FileSystem::watch_symlink_targets`] to watch them
+/// explicitly.
+///
+/// ## Memory-mapped files
+///
+/// With feature `mmap`, [`FileSystem::memory_mapped`] makes large files be
+/// mapped in memory instead of being copied when they are read.
+///
 /// ## WebAssembly
 ///
 /// This source does not work in WebAssembly, because there is no file system.
 /// When called, it always returns an error.
 pub struct FileSystem {
-    path: PathBuf,
+    roots: Vec<PathBuf>,
+    case_insensitive: bool,
+    follow_symlinks: bool,
+    watch_symlink_targets: bool,
+
+    #[cfg(feature = "mmap")]
+    mapped: Option<MappedFiles>,
 
     #[cfg(feature = "hot-reloading")]
     pub(crate) reloader: Option<HotReloader>,
@@ -74,7 +185,7 @@ impl FileSystem {
     /// If hot-reloading fails to start (if feature `hot-reloading` is used),
     /// an error is logged and this function returns `Ok`.
     pub fn new<P: AsRef<Path>>(path: P) -> io::Result<FileSystem> {
-        Self::_new(path.as_ref(), true)
+        Self::_new(&[path.as_ref()], true)
     }
 
     /// Same as `new`, but does not start hot-reloading.
@@ -82,16 +193,157 @@ impl FileSystem {
     /// If feature `hot-reloading` is not enabled, this function is equivalent
     /// to `new`.
     pub fn without_hot_reloading<P: AsRef<Path>>(path: P) -> io::Result<FileSystem> {
-        Self::_new(path.as_ref(), false)
+        Self::_new(&[path.as_ref()], false)
+    }
+
+    /// Creates a new `FileSystem` from several directories, searched in order.
+    ///
+    /// # Errors
+    ///
+    /// An error can occur if one of the roots is not a valid readable
+    /// directory, or if no root is given.
+    ///
+    /// If hot-reloading fails to start (if feature `hot-reloading` is used),
+    /// an error is logged and this function returns `Ok`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use assets_manager::{AssetCache, source::FileSystem};
+    ///
+    /// // Game assets take priority over engine assets
+    /// let source = FileSystem::with_roots(["game/assets", "engine/assets"])?;
+    /// let cache = AssetCache::with_source(source);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_roots<I, P>(roots: I) -> io::Result<FileSystem>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let roots: Vec<P> = roots.into_iter().collect();
+        let roots: Vec<&Path> = roots.iter().map(|p| p.as_ref()).collect();
+        Self::_new(&roots, true)
+    }
+
+    /// Same as `with_roots`, but does not start hot-reloading.
+    ///
+    /// If feature `hot-reloading` is not enabled, this function is equivalent
+    /// to `with_roots`.
+    pub fn with_roots_without_hot_reloading<I, P>(roots: I) -> io::Result<FileSystem>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let roots: Vec<P> = roots.into_iter().collect();
+        let roots: Vec<&Path> = roots.iter().map(|p| p.as_ref()).collect();
+        Self::_new(&roots, false)
+    }
+
+    /// Creates a new `FileSystem` from a directory relative to the directory
+    /// of the running executable.
+    ///
+    /// This is useful when the assets are shipped next to the executable, as
+    /// it does not depend on the working directory the program is started
+    /// from.
+    ///
+    /// # Errors
+    ///
+    /// An error can occur if the path of the executable cannot be found, or
+    /// for the same reasons as [`FileSystem::new`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use assets_manager::{AssetCache, source::FileSystem};
+    ///
+    /// let source = FileSystem::relative_to_exe("assets")?;
+    /// let cache = AssetCache::with_source(source);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn relative_to_exe<P: AsRef<Path>>(path: P) -> io::Result<FileSystem> {
+        let exe = std::env::current_exe()?;
+        let dir = exe.parent().ok_or(io::ErrorKind::NotFound)?;
+        Self::new(dir.join(path))
+    }
+
+    /// Creates a new `FileSystem` from a directory relative to the directory
+    /// given by the `CARGO_MANIFEST_DIR` environment variable.
+    ///
+    /// Cargo sets this variable when running a program with `cargo run` or
+    /// `cargo test`, so this is useful during development to find assets in
+    /// the package directory whatever the working directory is. The variable
+    /// is read at runtime, so it is generally not set in distributed builds,
+    /// where [`FileSystem::relative_to_exe`] is more appropriate.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind [`NotFound`](io::ErrorKind::NotFound) is returned if
+    /// `CARGO_MANIFEST_DIR` is not set. Other errors can occur for the same
+    /// reasons as [`FileSystem::new`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use assets_manager::{AssetCache, source::FileSystem};
+    ///
+    /// let source = FileSystem::relative_to_manifest("assets")?;
+    /// let cache = AssetCache::with_source(source);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn relative_to_manifest<P: AsRef<Path>>(path: P) -> io::Result<FileSystem> {
+        let dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "CARGO_MANIFEST_DIR is not set"))?;
+        Self::new(Path::new(&dir).join(path))
+    }
+
+    /// Creates a new `FileSystem` in the directory where the platform expects
+    /// an application to store user data, creating it if needed.
+    ///
+    /// This is intended for assets written by the user, such as settings or
+    /// saved games. The directory is:
+    ///
+    /// - `$XDG_DATA_HOME/<app_name>` or `$HOME/.local/share/<app_name>` on
+    ///   Linux and other Unix-like systems.
+    /// - `$HOME/Library/Application Support/<app_name>` on macOS.
+    /// - `%APPDATA%\<app_name>` on Windows.
+    ///
+    /// # Errors
+    ///
+    /// An error can occur if `app_name` is empty or contains path separators,
+    /// if the required environment variables are not set, if the directory
+    /// cannot be created, or for the same reasons as [`FileSystem::new`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use assets_manager::{AssetCache, source::FileSystem};
+    ///
+    /// let source = FileSystem::user_data_dir("my_game")?;
+    /// let saves = AssetCache::with_source(source);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn user_data_dir(app_name: &str) -> io::Result<FileSystem> {
+        let path = user_data_path(app_name, |name| std::env::var_os(name))?;
+        fs::create_dir_all(&path)?;
+        Self::new(path)
     }
 
-    fn _new(path: &Path, _hot_reloading: bool) -> io::Result<FileSystem> {
-        let path = path.canonicalize()?;
-        let _ = path.read_dir()?;
+    fn _new(paths: &[&Path], _hot_reloading: bool) -> io::Result<FileSystem> {
+        if paths.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no root directory given"));
+        }
+
+        let mut roots = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path.canonicalize()?;
+            let _ = path.read_dir()?;
+            roots.push(path);
+        }
 
         #[cfg(feature = "hot-reloading")]
         let reloader = if _hot_reloading {
-            match HotReloader::start(&path) {
+            match HotReloader::start(&roots) {
                 Ok(r) => Some(r),
                 Err(err) => {
                     log::error!("Unable to start hot-reloading: {}", err);
@@ -103,7 +355,13 @@ impl FileSystem {
         };
 
         Ok(FileSystem {
-            path,
+            roots,
+            case_insensitive: false,
+            follow_symlinks: true,
+            watch_symlink_targets: false,
+
+            #[cfg(feature = "mmap")]
+            mapped: None,
 
             #[cfg(feature = "hot-reloading")]
             reloader,
@@ -112,62 +370,450 @@ impl FileSystem {
 
     /// Gets the path of the source's root.
     ///
+    /// If the source has several roots, this is the first one.
+    ///
     /// The path is currently given as absolute, but this may change in the future.
     #[inline]
     pub fn root(&self) -> &Path {
-        &self.path
+        &self.roots[0]
+    }
+
+    /// Gets the paths of the source's roots, in the order they are searched.
+    ///
+    /// The paths are currently given as absolute, but this may change in the
+    /// future.
+    #[inline]
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Enables or disables case-insensitive resolution of ids.
+    ///
+    /// When enabled, a file whose path does not exist as is is searched by
+    /// comparing names ignoring case. If several files match, an exact match
+    /// is preferred, then the first one in lexicographic order.
+    ///
+    /// This is disabled by default, and has a cost when the exact path does
+    /// not exist, as directories have to be listed.
+    ///
+    /// ```no_run
+    /// use assets_manager::source::FileSystem;
+    ///
+    /// let source = FileSystem::new("assets")?.case_insensitive(true);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn case_insensitive(mut self, enabled: bool) -> FileSystem {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Returns `true` if ids are resolved ignoring case.
+    #[inline]
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Sets whether symbolic links are followed when reading files and
+    /// directories.
+    ///
+    /// When disabled, a file is not found if its path goes through a link, and
+    /// links are not listed when reading directories. This is enabled by
+    /// default.
+    ///
+    /// ```no_run
+    /// use assets_manager::source::FileSystem;
+    ///
+    /// let source = FileSystem::new("assets")?.follow_symlinks(false);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn follow_symlinks(mut self, enabled: bool) -> FileSystem {
+        self.follow_symlinks = enabled;
+        self
+    }
+
+    /// Returns `true` if symbolic links are followed.
+    #[inline]
+    pub fn follows_symlinks(&self) -> bool {
+        self.follow_symlinks
+    }
+
+    /// Sets whether hot-reloading watches the targets of symbolic links.
+    ///
+    /// When enabled, the links currently in the directory are searched, and
+    /// changes to their targets reload the assets loaded through them. Links
+    /// created afterwards are not taken into account. This is disabled by
+    /// default.
+    ///
+    /// This has no effect if hot-reloading is disabled.
+    ///
+    /// ```no_run
+    /// use assets_manager::source::FileSystem;
+    ///
+    /// let source = FileSystem::new("assets")?.watch_symlink_targets(true);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn watch_symlink_targets(mut self, enabled: bool) -> FileSystem {
+        #[cfg(feature = "hot-reloading")]
+        if enabled != self.watch_symlink_targets {
+            if let Some(reloader) = &self.reloader {
+                if enabled {
+                    let links = self.roots.iter().flat_map(|root| find_symlinks(root)).collect();
+                    reloader.send_update(UpdateMessage::WatchLinks(links));
+                } else {
+                    reloader.send_update(UpdateMessage::UnwatchLinks);
+                }
+            }
+        }
+
+        self.watch_symlink_targets = enabled;
+        self
+    }
+
+    /// Returns `true` if hot-reloading watches the targets of symbolic links.
+    #[inline]
+    pub fn watches_symlink_targets(&self) -> bool {
+        self.watch_symlink_targets
+    }
+
+    /// Enables or disables memory-mapping of large files.
+    ///
+    /// When enabled, [`read`](Source::read) maps files of at least 16 KiB in
+    /// memory and returns borrowed bytes, which avoids copying them. Maps are
+    /// kept until the source is dropped, and a file is mapped again when its
+    /// size or modification time changes. This is disabled by default.
+    ///
+    /// # Safety
+    ///
+    /// Mapped files must not be modified nor truncated while the bytes
+    /// returned by `read` are in use, by this process or another one. This
+    /// includes modifications made by a text editor during hot-reloading.
+    ///
+    /// Files written with [`WritableSource::write`] are replaced by a new file
+    /// instead of being modified, so this source can safely write files that
+    /// it mapped.
+    ///
+    /// On Windows, mapped files cannot be modified nor removed until the
+    /// source is dropped.
+    ///
+    /// ```no_run
+    /// use assets_manager::source::FileSystem;
+    ///
+    /// // Safety: asset files are not modified while the game runs
+    /// let source = unsafe { FileSystem::new("assets")?.memory_mapped(true) };
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub unsafe fn memory_mapped(mut self, enabled: bool) -> FileSystem {
+        self.mapped = if enabled { Some(MappedFiles::new()) } else { None };
+        self
+    }
+
+    /// Returns `true` if large files are memory-mapped.
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    #[inline]
+    pub fn is_memory_mapped(&self) -> bool {
+        self.mapped.is_some()
     }
 
     /// Returns the path of the (eventual) file represented by an id and an
     /// extension.
-    pub fn path_of(&self, id: &str, ext: &str) -> PathBuf {
-        let mut path = self.path.clone();
-        path.extend(id.split('.'));
-        path.set_extension(ext);
-        path
+    ///
+    /// If the source has several roots, the path is in the first one. This does
+    /// not take case-insensitivity nor other roots into account, see
+    /// [`FileSystem::resolve`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`InvalidInput`](io::ErrorKind::InvalidInput)
+    /// if the id or the extension could designate a file outside of the root
+    /// (see [`validate_id`](super::validate_id)).
+    pub fn path_of(&self, id: &str, ext: &str) -> io::Result<PathBuf> {
+        path_in(self.root(), id, ext)
     }
-}
 
-impl Source for FileSystem {
-    fn read(&self, id: &str, ext: &str) -> io::Result<Cow<[u8]>> {
-        let path = self.path_of(id, ext);
-        fs::read(path).map(Into::into)
+    /// Returns the path of the file represented by an id and an extension,
+    /// resolving it ignoring case if the source is case-insensitive.
+    ///
+    /// If the source has several roots, this is the path in the first root
+    /// where the file exists, or in the first root if it exists nowhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`NotFound`](io::ErrorKind::NotFound) if
+    /// the source is case-insensitive and no matching file exists, or if
+    /// symbolic links are not followed and the path goes through one.
+    ///
+    /// Returns an error of kind [`InvalidInput`](io::ErrorKind::InvalidInput)
+    /// if the id or the extension is not valid.
+    pub fn resolve(&self, id: &str, ext: &str) -> io::Result<PathBuf> {
+        let (first, others) = self.roots.split_first().unwrap();
+        let resolved = self.resolve_in(first, id, ext);
+
+        if others.is_empty() || matches!(&resolved, Ok(path) if path.exists()) {
+            return resolved;
+        }
+
+        for root in others {
+            match self.resolve_in(root, id, ext) {
+                Ok(path) if path.exists() => return Ok(path),
+                _ => (),
+            }
+        }
+
+        resolved
     }
 
-    fn read_dir(&self, id: &str, ext: &[&str]) -> io::Result<Vec<String>> {
-        let dir_path = self.path_of(id, "");
-        let entries = fs::read_dir(dir_path)?;
+    fn resolve_in(&self, root: &Path, id: &str, ext: &str) -> io::Result<PathBuf> {
+        let path = self.resolve_case(root, id, ext)?;
+
+        if !self.follow_symlinks {
+            check_no_symlink(root, &path)?;
+        }
+
+        Ok(path)
+    }
+
+    fn resolve_case(&self, root: &Path, id: &str, ext: &str) -> io::Result<PathBuf> {
+        let path = path_in(root, id, ext)?;
+
+        if !self.case_insensitive || path.exists() {
+            return Ok(path);
+        }
+
+        let relative = path.strip_prefix(root).map_err(|_| io::ErrorKind::NotFound)?;
+        let mut resolved = root.to_owned();
 
+        for component in relative.components() {
+            let name = component.as_os_str();
+            let exact = resolved.join(name);
+            if exact.exists() {
+                resolved = exact;
+                continue;
+            }
+
+            let name = name.to_str().ok_or(io::ErrorKind::NotFound)?;
+            let found = fs::read_dir(&resolved)?
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter(|entry| eq_ignore_case(entry, name))
+                .min();
+
+            match found {
+                Some(found) => resolved.push(found),
+                None => return Err(io::ErrorKind::NotFound.into()),
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Reads a directory in all roots.
+    ///
+    /// See `list_dir` for the meaning of `prefix`.
+    fn read_dirs(&self, id: &str, prefix: Option<&str>, ext: &[&str]) -> io::Result<Vec<String>> {
+        self.merge_dirs(id, |dir_path, loaded| self.list_dir(dir_path, prefix, ext, loaded))
+    }
+
+    /// Calls `list` on the directory with the given id in each root, and
+    /// merges the results.
+    fn merge_dirs<T: Ord>(&self, id: &str, mut list: impl FnMut(&Path, &mut Vec<T>) -> io::Result<()>) -> io::Result<Vec<T>> {
         let mut loaded = Vec::new();
+        let mut found = false;
+        let mut error = None;
+
+        for root in &self.roots {
+            let result = self.resolve_in(root, id, "")
+                .and_then(|dir_path| list(&dir_path, &mut loaded));
+
+            match result {
+                Ok(()) => found = true,
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
 
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
+        // Files may exist in several roots
+        if self.roots.len() > 1 {
+            loaded.sort();
+            loaded.dedup();
+        }
+
+        match error {
+            Some(err) if !found => Err(err),
+            _ => Ok(loaded),
+        }
+    }
+
+    /// Lists the subdirectories of a directory and its files with one of the
+    /// given extensions.
+    fn list_entries(&self, dir_path: &Path, ext: &[&str], entries: &mut Vec<DirEntry>) -> io::Result<()> {
+        for entry in fs::read_dir(dir_path)?.flatten() {
+            match entry.file_type() {
+                Ok(file_type) if !self.follow_symlinks && file_type.is_symlink() => continue,
+                Ok(_) => (),
+                Err(_) => continue,
+            }
+
+            let path = entry.path();
+
+            if path.is_dir() {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    entries.push(DirEntry::Directory(name.into()));
+                }
+            } else if path.is_file() && has_extension(&path, ext, self.case_insensitive) {
+                if let Some(name) = path.file_stem().and_then(|n| n.to_str()) {
+                    entries.push(DirEntry::File(name.into()));
+                }
+            }
+        }
+
+        Ok(())
+    }
 
-                if !has_extension(&path, ext) {
+    /// Lists the files of a directory with one of the given extensions.
+    ///
+    /// If `prefix` is given, subdirectories are listed too, and files are
+    /// pushed as ids starting with this prefix. Otherwise, only file stems
+    /// are pushed.
+    fn list_dir(&self, dir_path: &Path, prefix: Option<&str>, ext: &[&str], loaded: &mut Vec<String>) -> io::Result<()> {
+        let entries = fs::read_dir(dir_path)?;
+
+        for entry in entries.flatten() {
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(_) => continue,
+            };
+            if !self.follow_symlinks && file_type.is_symlink() {
+                continue;
+            }
+
+            let path = entry.path();
+
+            if let Some(prefix) = prefix {
+                if path.is_dir() {
+                    let name = match path.file_name().and_then(|n| n.to_str()) {
+                        Some(name) => name,
+                        None => continue,
+                    };
+
+                    // Do not loop on links to a parent directory
+                    if file_type.is_symlink() && is_ancestor_link(&path, dir_path) {
+                        continue;
+                    }
+
+                    let prefix = if prefix.is_empty() { name.to_owned() } else { format!("{}.{}", prefix, name) };
+                    let _ = self.list_dir(&path, Some(&prefix), ext, loaded);
                     continue;
                 }
+            }
 
-                let name = match path.file_stem().and_then(|n| n.to_str()) {
-                    Some(name) => name,
-                    None => continue,
-                };
+            if !has_extension(&path, ext, self.case_insensitive) {
+                continue;
+            }
+
+            let name = match path.file_stem().and_then(|n| n.to_str()) {
+                Some(name) => name,
+                None => continue,
+            };
 
-                if path.is_file() {
-                    loaded.push(name.into());
+            if path.is_file() {
+                match prefix {
+                    Some("") | None => loaded.push(name.into()),
+                    Some(prefix) => loaded.push(format!("{}.{}", prefix, name)),
                 }
             }
         }
 
-        Ok(loaded)
+        Ok(())
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn watched_path(&self, id: &str, ext: &str) -> Option<PathBuf> {
+        self.resolve(id, ext).or_else(|_| self.path_of(id, ext)).ok()
+    }
+
+    /// The version of a file with the given content.
+    ///
+    /// The watcher uses it to know whether a file really changed.
+    pub(crate) fn version_of(content: &[u8]) -> Version {
+        Version::hash_of(content)
+    }
+}
+
+impl Source for FileSystem {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<[u8]>, SourceError> {
+        let path = self.resolve(id, ext)?;
+
+        #[cfg(feature = "mmap")]
+        if let Some(mapped) = &self.mapped {
+            return Ok(mapped.read(path)?);
+        }
+
+        Ok(fs::read(path)?.into())
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        Ok(self.read_dirs(id, None, ext)?)
+    }
+
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        Ok(self.read_dirs(id, Some(id), ext)?)
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        Ok(self.merge_dirs(id, |dir_path, entries| self.list_entries(dir_path, ext, entries))?)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let metadata = fs::metadata(self.resolve(id, ext)?)?;
+        if !metadata.is_file() {
+            return Err(io::ErrorKind::NotFound.into());
+        }
+
+        Ok(Metadata {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        Ok(FileSystem::version_of(&self.read(id, ext)?))
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        let file = fs::File::open(self.resolve(id, ext)?)?;
+        if !file.metadata()?.is_file() {
+            return Err(io::ErrorKind::NotFound.into());
+        }
+
+        Ok(Box::new(file))
     }
 
     #[cfg(feature = "hot-reloading")]
     fn _add_asset<A: Asset, P: PrivateMarker>(&self, id: &str) {
         if let Some(reloader) = &self.reloader {
             for ext in A::EXTENSIONS {
-                let path = self.path_of(id, ext);
-                let msg = UpdateMessage::AddAsset(AssetReloadInfos::of::<A>(path, id.into()));
+                let path = match self.watched_path(id, ext) {
+                    Some(path) => path,
+                    None => continue,
+                };
+                let version = self.version(id, ext).ok();
+                let msg = UpdateMessage::AddAsset(AssetReloadInfos::of::<A>(path, id.into(), version));
+                reloader.send_update(msg);
+            }
+        }
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_file<A: Asset, P: PrivateMarker>(&self, key: &str, id: &str, ext: &str) {
+        if let Some(reloader) = &self.reloader {
+            if let Some(path) = self.watched_path(id, ext) {
+                let version = self.version(id, ext).ok();
+                let msg = UpdateMessage::AddAsset(AssetReloadInfos::of::<A>(path, key.into(), version));
                 reloader.send_update(msg);
             }
         }
@@ -176,9 +822,15 @@ impl Source for FileSystem {
     #[cfg(feature = "hot-reloading")]
     fn _add_dir<A: Asset, P: PrivateMarker>(&self, id: &str) {
         if let Some(reloader) = &self.reloader {
-            let path = self.path_of(id, "");
-            let msg = UpdateMessage::AddDir(AssetReloadInfos::of::<A>(path, id.into()), A::EXTENSIONS);
-            reloader.send_update(msg);
+            // Files can be added to the directory in any root
+            for root in &self.roots {
+                let path = match self.resolve_in(root, id, "").or_else(|_| path_in(root, id, "")) {
+                    Ok(path) => path,
+                    Err(_) => continue,
+                };
+                let msg = UpdateMessage::AddDir(AssetReloadInfos::of::<A>(path, id.into(), None), A::EXTENSIONS);
+                reloader.send_update(msg);
+            }
         }
     }
 
@@ -203,8 +855,123 @@ impl Source for FileSystem {
     }
 }
 
+impl WritableSource for FileSystem {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        let path = match self.resolve(id, ext) {
+            Ok(path) => path,
+            Err(_) => self.path_of(id, ext)?,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Mapped files must not be truncated, so the content is written to a
+        // new file that replaces the previous one.
+        #[cfg(feature = "mmap")]
+        if self.mapped.is_some() {
+            return Ok(replace_file(&path, content)?);
+        }
+
+        Ok(fs::write(path, content)?)
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        let path = self.resolve(id, ext)?;
+        if path.is_dir() {
+            return Err(SourceErrorKind::NotFound.into());
+        }
+        Ok(fs::remove_file(path)?)
+    }
+}
+
+/// Writes a file to a temporary path next to it, and renames it to its final
+/// path, so the content of the previous file is left untouched.
+#[cfg(feature = "mmap")]
+fn replace_file(path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(format!(".{}.tmp", std::process::id()));
+    let temp = path.with_file_name(name);
+
+    let result = fs::write(&temp, content).and_then(|()| fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
 impl fmt::Debug for FileSystem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("FileSystem").field("root", &self.path).finish()
+        f.debug_struct("FileSystem")
+            .field("roots", &self.roots)
+            .field("case_insensitive", &self.case_insensitive)
+            .field("follow_symlinks", &self.follow_symlinks)
+            .field("watch_symlink_targets", &self.watch_symlink_targets)
+            .finish_non_exhaustive()
+    }
+}
+
+
+/// Files below this size are read instead of being mapped, which is faster.
+#[cfg(feature = "mmap")]
+const MMAP_MIN_LEN: u64 = 16 * 1024;
+
+/// A file mapped in memory, with the metadata it had when it was mapped.
+#[cfg(feature = "mmap")]
+struct MappedFile {
+    map: memmap2::Mmap,
+    len: u64,
+    modified: Option<std::time::SystemTime>,
+}
+
+/// Memory maps of the files read by a `FileSystem`.
+///
+/// Maps are never dropped before the source, because bytes borrowed from them
+/// may still be in use. This includes maps of files that changed since.
+#[cfg(feature = "mmap")]
+struct MappedFiles {
+    files: Mutex<HashMap<PathBuf, MappedFile>>,
+    outdated: Mutex<Vec<memmap2::Mmap>>,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedFiles {
+    fn new() -> MappedFiles {
+        MappedFiles {
+            files: Mutex::new(HashMap::new()),
+            outdated: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn read(&self, path: PathBuf) -> io::Result<Cow<'_, [u8]>> {
+        let mut file = fs::File::open(&path)?;
+        let metadata = file.metadata()?;
+        let len = metadata.len();
+        let modified = metadata.modified().ok();
+
+        if len < MMAP_MIN_LEN {
+            let mut content = Vec::with_capacity(len as usize);
+            file.read_to_end(&mut content)?;
+            return Ok(Cow::Owned(content));
+        }
+
+        let mut files = self.files.lock();
+        let mapped = match files.get(&path) {
+            Some(mapped) if mapped.len == len && mapped.modified == modified => mapped,
+            _ => {
+                // Safety: upheld by the caller of `FileSystem::memory_mapped`
+                let map = unsafe { memmap2::Mmap::map(&file)? };
+                let new = MappedFile { map, len, modified };
+                if let Some(old) = files.insert(path.clone(), new) {
+                    self.outdated.lock().push(old.map);
+                }
+                &files[&path]
+            }
+        };
+
+        // Safety: the mapped memory does not move when the `Mmap` is moved,
+        // and it lives as long as `self`.
+        let bytes = unsafe { std::slice::from_raw_parts(mapped.map.as_ptr(), mapped.map.len()) };
+        Ok(Cow::Borrowed(bytes))
     }
 }
@@ -0,0 +1,224 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    io,
+    time::Duration,
+};
+
+use super::{Fetched, RemoteSource, Source, SourceError, SourceErrorKind, Validators, Version, url_of, validate_id};
+
+
+/// A [`Source`] to load assets from a web server over HTTP.
+///
+/// Each asset is fetched from a URL built from a base URL, the id of the asset
+/// and its extension. For example, with the base URL
+/// `https://cdn.example.com/assets`, the asset `common.position` with extension
+/// `ron` is fetched from `https://cdn.example.com/assets/common/position.ron`.
+///
+/// Requests are blocking, so you may want to load assets from another thread.
+///
+/// ## Directories
+///
+/// HTTP has no standard way to list a directory, so reading directories is
+/// not supported and always returns an error.
+///
+/// ## Caching
+///
+/// Downloaded files are not stored. Use [`CachedRemote`](super::CachedRemote)
+/// to keep a local copy of them.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::Http};
+/// use std::time::Duration;
+///
+/// let source = Http::builder("https://cdn.example.com/assets")
+///     .timeout(Duration::from_secs(10))
+///     .header("Authorization", "Bearer my-token")
+///     .build();
+/// let cache = AssetCache::with_source(source);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+#[derive(Clone)]
+pub struct Http {
+    base_url: String,
+    agent: ureq::Agent,
+    headers: Vec<(String, String)>,
+}
+
+impl Http {
+    /// Creates a new `Http` source from a base URL, with the default
+    /// configuration.
+    ///
+    /// The default configuration has a timeout of 30 seconds for each request.
+    pub fn new<U: Into<String>>(base_url: U) -> Http {
+        Http::builder(base_url).build()
+    }
+
+    /// Creates a builder to configure a new `Http` source.
+    pub fn builder<U: Into<String>>(base_url: U) -> HttpBuilder {
+        HttpBuilder {
+            base_url: base_url.into(),
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: None,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Gets the base URL of the source.
+    #[inline]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the URL of the (eventual) file represented by an id and an
+    /// extension.
+    pub fn url_of(&self, id: &str, ext: &str) -> String {
+        url_of(&self.base_url, id, ext)
+    }
+}
+
+impl Http {
+    fn request(&self, id: &str, ext: &str) -> io::Result<ureq::RequestBuilder<ureq::typestate::WithoutBody>> {
+        validate_id(id, ext)?;
+
+        let mut request = self.agent.get(&self.url_of(id, ext));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        Ok(request)
+    }
+}
+
+pub(super) fn read_body(response: &mut ureq::http::Response<ureq::Body>) -> io::Result<Vec<u8>> {
+    response.body_mut().with_config().limit(u64::MAX).read_to_vec().map_err(to_io_error)
+}
+
+impl Source for Http {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let mut response = self.request(id, ext)?.call().map_err(to_io_error)?;
+        Ok(Cow::Owned(read_body(&mut response)?))
+    }
+
+    fn read_dir(&self, _: &str, _: &[&str]) -> Result<Vec<String>, SourceError> {
+        Err(SourceError::new(SourceErrorKind::Unsupported, "cannot read directories over HTTP"))
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        validate_id(id, ext)?;
+
+        let mut request = self.agent.head(&self.url_of(id, ext));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request.call().map_err(to_io_error)?;
+
+        // Prefer validators given by the server to downloading the file
+        let header = |name| {
+            let value = response.headers().get(name)?;
+            value.to_str().ok().map(str::to_owned)
+        };
+        match header("etag").or_else(|| header("last-modified")) {
+            Some(tag) => Ok(Version::from_tag(tag)),
+            None => Ok(Version::hash_of(&self.read(id, ext)?)),
+        }
+    }
+}
+
+impl RemoteSource for Http {
+    fn fetch(&self, id: &str, ext: &str, validators: &Validators) -> Result<Fetched, SourceError> {
+        let mut request = self.request(id, ext)?;
+        if let Some(etag) = &validators.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+
+        let mut response = request.call().map_err(to_io_error)?;
+        if response.status() == 304 {
+            return Ok(Fetched::NotModified);
+        }
+
+        let header = |name| {
+            let value = response.headers().get(name)?;
+            value.to_str().ok().map(str::to_owned)
+        };
+        let validators = Validators {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+        };
+
+        let content = read_body(&mut response)?;
+        Ok(Fetched::Modified { content, validators })
+    }
+}
+
+impl fmt::Debug for Http {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Http").field("base_url", &self.base_url).finish()
+    }
+}
+
+/// A builder to configure an [`Http`] source.
+///
+/// It is created with [`Http::builder`].
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+#[derive(Debug, Clone)]
+pub struct HttpBuilder {
+    base_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+}
+
+impl HttpBuilder {
+    /// Sets the maximum duration of a request, including reading the body.
+    ///
+    /// `None` disables the timeout. The default is 30 seconds.
+    pub fn timeout<T: Into<Option<Duration>>>(mut self, timeout: T) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Sets the maximum duration to connect to the server.
+    ///
+    /// `None` disables the timeout, which is the default.
+    pub fn connect_timeout<T: Into<Option<Duration>>>(mut self, timeout: T) -> Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
+    /// Adds a header to send with each request.
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Creates the `Http` source.
+    pub fn build(self) -> Http {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(self.timeout)
+            .timeout_connect(self.connect_timeout)
+            .build()
+            .into();
+
+        Http {
+            base_url: self.base_url,
+            agent,
+            headers: self.headers,
+        }
+    }
+}
+
+pub(super) fn to_io_error(err: ureq::Error) -> io::Error {
+    use io::ErrorKind;
+
+    match err {
+        ureq::Error::StatusCode(404) | ureq::Error::StatusCode(410) => io::Error::new(ErrorKind::NotFound, err),
+        ureq::Error::StatusCode(401) | ureq::Error::StatusCode(403) => io::Error::new(ErrorKind::PermissionDenied, err),
+        ureq::Error::Timeout(_) => io::Error::new(ErrorKind::TimedOut, err),
+        err => err.into_io(),
+    }
+}
@@ -0,0 +1,153 @@
+use include_dir_crate::Dir;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io,
+    path::Path,
+};
+
+use super::{is_in_dir, join_id, sub_dirs, DirEntry, Metadata, Source, SourceError};
+
+
+/// A [`Source`] which reads files embedded with the [`include_dir`] crate.
+///
+/// This is an alternative to [`Embedded`](super::Embedded) for projects that
+/// already use `include_dir`. Ids are given by the paths of files relative to
+/// the given directory, so a directory obtained with [`Dir::get_dir`] can be
+/// used to read only a part of the embedded files.
+///
+/// Files or directories whose name is not valid UTF-8 are ignored.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```ignore
+/// use assets_manager::{AssetCache, source::IncludeDir};
+/// use include_dir::{include_dir, Dir};
+///
+/// static ASSETS: Dir = include_dir!("$CARGO_MANIFEST_DIR/assets");
+///
+/// let cache = AssetCache::with_source(IncludeDir::from(&ASSETS));
+/// ```
+///
+/// [`include_dir`]: https://docs.rs/include_dir
+#[cfg_attr(docsrs, doc(cfg(feature = "include_dir")))]
+#[derive(Clone, Debug)]
+pub struct IncludeDir<'a> {
+    files: HashMap<String, HashMap<String, &'a [u8]>>,
+    dirs: HashMap<String, Vec<(String, String)>>,
+}
+
+impl<'a> From<&Dir<'a>> for IncludeDir<'a> {
+    fn from(dir: &Dir<'a>) -> IncludeDir<'a> {
+        let mut source = IncludeDir {
+            files: HashMap::new(),
+            dirs: HashMap::new(),
+        };
+        source.add_dir(dir, dir.path(), String::new());
+        source
+    }
+}
+
+impl<'a> IncludeDir<'a> {
+    fn add_dir(&mut self, dir: &Dir<'a>, root: &Path, id: String) {
+        let mut entries = Vec::new();
+
+        for file in dir.files() {
+            let path = file.path();
+            let stem = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(stem) => stem,
+                None => continue,
+            };
+            let ext = match path.extension() {
+                Some(ext) => match ext.to_str() {
+                    Some(ext) => ext,
+                    None => continue,
+                },
+                None => "",
+            };
+
+            self.files
+                .entry(join_id(&id, stem))
+                .or_default()
+                .insert(ext.to_owned(), file.contents());
+            entries.push((stem.to_owned(), ext.to_owned()));
+        }
+
+        for sub_dir in dir.dirs() {
+            let name = sub_dir.path().strip_prefix(root).ok()
+                .and_then(|path| path.file_name())
+                .and_then(|name| name.to_str());
+
+            if let Some(name) = name {
+                self.add_dir(sub_dir, root, join_id(&id, name));
+            }
+        }
+
+        self.dirs.insert(id, entries);
+    }
+}
+
+impl Source for IncludeDir<'_> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let content = self.files.get(id)
+            .and_then(|files| files.get(ext))
+            .ok_or(io::ErrorKind::NotFound)?;
+
+        Ok(Cow::Borrowed(content))
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let dir = self.dirs.get(id).ok_or(io::ErrorKind::NotFound)?;
+
+        Ok(dir.iter()
+            .filter(|(_, file_ext)| ext.contains(&file_ext.as_str()))
+            .map(|(id, _)| id.clone())
+            .collect()
+        )
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let dir = self.dirs.get(id).ok_or(io::ErrorKind::NotFound)?;
+
+        let files = dir.iter()
+            .filter(|(_, file_ext)| ext.contains(&file_ext.as_str()))
+            .map(|(id, _)| DirEntry::File(id.clone()));
+
+        Ok(files.chain(sub_dirs(self.dirs.keys().map(String::as_str), id)).collect())
+    }
+
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        if !self.dirs.contains_key(id) {
+            return Err(io::ErrorKind::NotFound.into());
+        }
+
+        let mut entries = Vec::new();
+
+        for (dir_id, files) in &self.dirs {
+            if !is_in_dir(dir_id, id) {
+                continue;
+            }
+
+            for (name, file_ext) in files {
+                if ext.contains(&file_ext.as_str()) {
+                    entries.push(join_id(dir_id, name));
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let content = self.files.get(id)
+            .and_then(|files| files.get(ext))
+            .ok_or(io::ErrorKind::NotFound)?;
+
+        Ok(Metadata::new(content.len() as u64))
+    }
+}
@@ -0,0 +1,214 @@
+#[cfg(feature = "hot-reloading")]
+use crate::utils::PrivateMarker;
+
+use log::Level;
+
+use std::{
+    borrow::Cow,
+    io::Read,
+    time::Instant,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, SourceErrorKind, Version, WritableSource};
+
+
+/// A [`Source`] that logs every access to another source.
+///
+/// Each call to [`read`](Source::read), [`read_dir`](Source::read_dir) and
+/// [`metadata`](Source::metadata) emits a log record with the id, the
+/// extension, the number of bytes or entries returned and the time it took.
+/// This helps finding out why loading is slow without modifying the source.
+///
+/// Records are emitted with the [`log`] crate, at level `Debug` by default, or
+/// `Warn` when an error other than [`NotFound`](SourceErrorKind::NotFound)
+/// occurs. Subscribers of the `tracing` crate can collect them with
+/// `tracing-log`.
+///
+/// ## Hot-reloading
+///
+/// This source supports hot-reloading if the inner source does.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{FileSystem, Instrumented}};
+///
+/// let source = Instrumented::new(FileSystem::new("assets")?);
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+#[derive(Debug, Clone)]
+pub struct Instrumented<S> {
+    source: S,
+    level: Level,
+}
+
+impl<S> Instrumented<S> {
+    /// Creates a new `Instrumented` source, logging at level `Debug`.
+    #[inline]
+    pub fn new(source: S) -> Instrumented<S> {
+        Instrumented {
+            source,
+            level: Level::Debug,
+        }
+    }
+
+    /// Sets the level of the records emitted by this source.
+    #[inline]
+    pub fn with_level(mut self, level: Level) -> Instrumented<S> {
+        self.level = level;
+        self
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    fn level_of<T>(&self, result: &Result<T, SourceError>) -> Level {
+        match result {
+            Err(err) if err.kind() != SourceErrorKind::NotFound => Level::Warn.min(self.level),
+            _ => self.level,
+        }
+    }
+}
+
+impl<S: Source> Source for Instrumented<S> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let start = Instant::now();
+        let result = self.source.read(id, ext);
+        let elapsed = start.elapsed();
+
+        match &result {
+            Ok(content) => log::log!(self.level, "Read \"{}\" ({}): {} bytes in {:?}", id, ext, content.len(), elapsed),
+            Err(err) => log::log!(self.level_of(&result), "Read \"{}\" ({}): {} in {:?}", id, ext, err, elapsed),
+        }
+
+        result
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let start = Instant::now();
+        let result = self.source.read_dir(id, ext);
+        let elapsed = start.elapsed();
+
+        match &result {
+            Ok(entries) => log::log!(self.level, "Read directory \"{}\" ({:?}): {} entries in {:?}", id, ext, entries.len(), elapsed),
+            Err(err) => log::log!(self.level_of(&result), "Read directory \"{}\" ({:?}): {} in {:?}", id, ext, err, elapsed),
+        }
+
+        result
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let start = Instant::now();
+        let result = self.source.read_dir_entries(id, ext);
+        let elapsed = start.elapsed();
+
+        match &result {
+            Ok(entries) => log::log!(self.level, "Read directory entries \"{}\" ({:?}): {} entries in {:?}", id, ext, entries.len(), elapsed),
+            Err(err) => log::log!(self.level_of(&result), "Read directory entries \"{}\" ({:?}): {} in {:?}", id, ext, err, elapsed),
+        }
+
+        result
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let start = Instant::now();
+        let result = self.source.metadata(id, ext);
+        let elapsed = start.elapsed();
+
+        match &result {
+            Ok(metadata) => log::log!(self.level, "Metadata of \"{}\" ({}): {} bytes in {:?}", id, ext, metadata.len, elapsed),
+            Err(err) => log::log!(self.level_of(&result), "Metadata of \"{}\" ({}): {} in {:?}", id, ext, err, elapsed),
+        }
+
+        result
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.version(id, ext)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        let start = Instant::now();
+        let result = self.source.open(id, ext);
+        let elapsed = start.elapsed();
+
+        match &result {
+            Ok(_) => log::log!(self.level, "Opened \"{}\" ({}) in {:?}", id, ext, elapsed),
+            Err(err) => log::log!(self.level_of(&result), "Opened \"{}\" ({}): {} in {:?}", id, ext, err, elapsed),
+        }
+
+        result
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_asset<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.source._add_asset::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_file<A: crate::Asset, P: PrivateMarker>(&self, key: &str, id: &str, ext: &str) {
+        self.source._add_file::<A, P>(key, id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_dir<A: crate::Asset, P: PrivateMarker>(&self, id: &str) {
+        self.source._add_dir::<A, P>(id)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _clear<P: PrivateMarker>(&mut self) {
+        self.source._clear::<P>()
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _add_compound<A: crate::Compound, P: PrivateMarker>(&self, id: &str, deps: crate::utils::DepsRecord) {
+        self.source._add_compound::<A, P>(id, deps)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _support_hot_reloading<P: PrivateMarker>(&self) -> bool {
+        self.source._support_hot_reloading::<P>()
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _refresh<P: PrivateMarker>(&self) {
+        self.source._refresh::<P>()
+    }
+}
+
+impl<S: WritableSource> WritableSource for Instrumented<S> {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        let start = Instant::now();
+        let result = self.source.write(id, ext, content);
+        let elapsed = start.elapsed();
+
+        match &result {
+            Ok(()) => log::log!(self.level, "Wrote \"{}\" ({}): {} bytes in {:?}", id, ext, content.len(), elapsed),
+            Err(err) => log::log!(self.level_of(&result), "Wrote \"{}\" ({}): {} in {:?}", id, ext, err, elapsed),
+        }
+
+        result
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        let result = self.source.remove(id, ext);
+
+        match &result {
+            Ok(()) => log::log!(self.level, "Removed \"{}\" ({})", id, ext),
+            Err(err) => log::log!(self.level_of(&result), "Removed \"{}\" ({}): {}", id, ext, err),
+        }
+
+        result
+    }
+}
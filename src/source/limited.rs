@@ -0,0 +1,191 @@
+use crate::utils::Mutex;
+
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{self, Read},
+    time::{Duration, Instant},
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, Version};
+
+
+/// A [`Source`] that limits the size of the files read from another source,
+/// and optionally the rate at which bytes are read.
+///
+/// This is useful when loading untrusted content, such as mods downloaded by
+/// players, so that a huge file cannot exhaust the memory of the program.
+///
+/// The size of files is first checked with [`Source::metadata`], so sources
+/// that know the size of their files without reading them, such as
+/// [`FileSystem`](super::FileSystem) or `Pack`, do not read files that are too
+/// big. Otherwise, files are read with [`Source::open`], and reading stops as
+/// soon as the limit is exceeded. In both cases, an error of kind
+/// [`InvalidData`](io::ErrorKind::InvalidData) is returned.
+///
+/// Note that the limit only protects memory for sources that do one of these.
+/// The default implementation of [`Source::open`] reads the whole file first,
+/// so sources that do not override it and whose metadata requires reading the
+/// file, such as HTTP sources, still read big files entirely before they are
+/// rejected.
+///
+/// When a rate limit is set, reads fail with an error of kind
+/// [`WouldBlock`](io::ErrorKind::WouldBlock) once the given number of bytes
+/// was read during the current period.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{FileSystem, Limited}};
+/// use std::time::Duration;
+///
+/// // Mod files cannot be bigger than 16 MiB, and at most 64 MiB can be read
+/// // per second.
+/// let source = Limited::new(FileSystem::new("mods")?, 16 << 20)
+///     .with_rate_limit(64 << 20, Duration::from_secs(1));
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Limited<S> {
+    source: S,
+    max_size: u64,
+    rate: Option<Rate>,
+}
+
+struct Rate {
+    bytes: u64,
+    period: Duration,
+    state: Mutex<(Instant, u64)>,
+}
+
+impl Rate {
+    /// Returns `true` if more bytes can be read in the current period.
+    fn check(&self) -> bool {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        if now.duration_since(state.0) >= self.period {
+            *state = (now, 0);
+        }
+        state.1 < self.bytes
+    }
+
+    fn consume(&self, bytes: u64) {
+        let mut state = self.state.lock();
+        state.1 = state.1.saturating_add(bytes);
+    }
+}
+
+impl<S> Limited<S> {
+    /// Creates a new `Limited` source, which cannot read files bigger than
+    /// `max_size` bytes.
+    #[inline]
+    pub fn new(source: S, max_size: u64) -> Limited<S> {
+        Limited {
+            source,
+            max_size,
+            rate: None,
+        }
+    }
+
+    /// Limits reads to `bytes` bytes per `period`.
+    ///
+    /// A read is only rejected if the limit was already reached before it, so
+    /// a single file can exceed the limit.
+    pub fn with_rate_limit(mut self, bytes: u64, period: Duration) -> Limited<S> {
+        self.rate = Some(Rate {
+            bytes,
+            period,
+            state: Mutex::new((Instant::now(), 0)),
+        });
+        self
+    }
+
+    /// Returns the maximum size of a file.
+    #[inline]
+    pub fn max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    fn too_large(&self, id: &str, ext: &str) -> io::Error {
+        let msg = format!("file \"{}\" with extension \"{}\" is bigger than {} bytes", id, ext, self.max_size);
+        io::Error::new(io::ErrorKind::InvalidData, msg)
+    }
+}
+
+impl<S: Source> Source for Limited<S> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        if let Some(rate) = &self.rate {
+            if !rate.check() {
+                return Err(io::Error::new(io::ErrorKind::WouldBlock, "read rate limit exceeded").into());
+            }
+        }
+
+        if let Ok(metadata) = self.source.metadata(id, ext) {
+            if metadata.len > self.max_size {
+                return Err(self.too_large(id, ext).into());
+            }
+        }
+
+        let mut content = Vec::new();
+        self.source.open(id, ext)?.take(self.max_size.saturating_add(1)).read_to_end(&mut content)?;
+
+        if let Some(rate) = &self.rate {
+            rate.consume(content.len() as u64);
+        }
+        if content.len() as u64 > self.max_size {
+            return Err(self.too_large(id, ext).into());
+        }
+
+        Ok(Cow::Owned(content))
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.source.metadata(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.version(id, ext)
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for Limited<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Limited")
+            .field("source", &self.source)
+            .field("max_size", &self.max_size)
+            .finish_non_exhaustive()
+    }
+}
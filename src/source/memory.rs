@@ -0,0 +1,237 @@
+use crate::utils::{HashMap, RwLock};
+
+use std::{
+    borrow::Cow,
+    fmt,
+    io,
+};
+
+use super::{is_in_dir, DirEntry, Metadata, Source, SourceError, WritableSource};
+
+
+/// A [`Source`] which files are stored in memory.
+///
+/// Files can be added, modified and removed at runtime, which makes this
+/// source especially useful for tests, as they do not need fixture files on
+/// disk.
+///
+/// Directories do not exist by themselves: a directory exists if it contains
+/// at least one file, directly or in a subdirectory. The root directory always
+/// exists.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading: modifying a file does not
+/// update assets already loaded in a cache.
+///
+/// ## Usage
+///
+/// ```
+/// use assets_manager::{Asset, AssetCache, loader, source::Memory};
+///
+/// struct Greeting(String);
+///
+/// impl From<String> for Greeting {
+///     fn from(s: String) -> Greeting {
+///         Greeting(s)
+///     }
+/// }
+///
+/// impl Asset for Greeting {
+///     const EXTENSION: &'static str = "txt";
+///     type Loader = loader::LoadFrom<String, loader::StringLoader>;
+/// }
+///
+/// let source = Memory::new();
+/// source.insert("common.greeting", "txt", "Hello World!");
+///
+/// let cache = AssetCache::with_source(source);
+/// let greeting = cache.load::<Greeting>("common.greeting")?;
+/// assert_eq!(greeting.read().0, "Hello World!");
+/// # Ok::<(), assets_manager::Error>(())
+/// ```
+pub struct Memory {
+    files: RwLock<HashMap<String, HashMap<String, Vec<u8>>>>,
+}
+
+impl Memory {
+    /// Creates a new empty `Memory` source.
+    #[inline]
+    pub fn new() -> Memory {
+        Memory {
+            files: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Inserts a file given its id and its extension.
+    ///
+    /// If the file already exists, its content is replaced and the previous
+    /// one is returned.
+    pub fn insert<C: Into<Vec<u8>>>(&self, id: &str, ext: &str, content: C) -> Option<Vec<u8>> {
+        let mut files = self.files.write();
+        let exts = files.entry(id.to_owned()).or_insert_with(HashMap::new);
+        exts.insert(ext.to_owned(), content.into())
+    }
+
+    /// Removes a file given its id and its extension.
+    ///
+    /// Returns the content of the file if it existed.
+    pub fn remove(&self, id: &str, ext: &str) -> Option<Vec<u8>> {
+        let mut files = self.files.write();
+        let exts = files.get_mut(id)?;
+        let content = exts.remove(ext);
+        if exts.is_empty() {
+            files.remove(id);
+        }
+        content
+    }
+
+    /// Returns `true` if the source contains a file with the given id and
+    /// extension.
+    pub fn contains(&self, id: &str, ext: &str) -> bool {
+        let files = self.files.read();
+        files.get(id).is_some_and(|exts| exts.contains_key(ext))
+    }
+
+    /// Removes all files.
+    pub fn clear(&self) {
+        self.files.write().clear();
+    }
+}
+
+impl Default for Memory {
+    #[inline]
+    fn default() -> Memory {
+        Memory::new()
+    }
+}
+
+impl Source for Memory {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let files = self.files.read();
+        match files.get(id).and_then(|exts| exts.get(ext)) {
+            Some(content) => Ok(Cow::Owned(content.clone())),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let files = self.files.read();
+        let mut found = id.is_empty();
+        let mut entries = Vec::new();
+
+        for (file_id, exts) in files.iter() {
+            let name = if id.is_empty() {
+                file_id.as_str()
+            } else {
+                match file_id.strip_prefix(id).and_then(|s| s.strip_prefix('.')) {
+                    Some(name) => name,
+                    None => continue,
+                }
+            };
+
+            found = true;
+            if !name.contains('.') && exts.keys().any(|e| ext.contains(&e.as_str())) {
+                entries.push(name.to_owned());
+            }
+        }
+
+        if found {
+            Ok(entries)
+        } else {
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let files = self.files.read();
+        let mut found = id.is_empty();
+        let mut entries = Vec::new();
+
+        for (file_id, exts) in files.iter() {
+            let dir_id = file_id.rsplit_once('.').map_or("", |(dir, _)| dir);
+            if !is_in_dir(dir_id, id) {
+                continue;
+            }
+
+            found = true;
+            if exts.keys().any(|e| ext.contains(&e.as_str())) {
+                entries.push(file_id.clone());
+            }
+        }
+
+        if found {
+            Ok(entries)
+        } else {
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let files = self.files.read();
+        let mut found = id.is_empty();
+        let mut entries = Vec::new();
+
+        for (file_id, exts) in files.iter() {
+            let name = if id.is_empty() {
+                file_id.as_str()
+            } else {
+                match file_id.strip_prefix(id).and_then(|s| s.strip_prefix('.')) {
+                    Some(name) => name,
+                    None => continue,
+                }
+            };
+
+            found = true;
+            if let Some((dir, _)) = name.split_once('.') {
+                entries.push(DirEntry::Directory(dir.to_owned()));
+            } else if exts.keys().any(|e| ext.contains(&e.as_str())) {
+                entries.push(DirEntry::File(name.to_owned()));
+            }
+        }
+
+        if found {
+            // A directory is found once per file in it
+            entries.sort();
+            entries.dedup();
+            Ok(entries)
+        } else {
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let files = self.files.read();
+        match files.get(id).and_then(|exts| exts.get(ext)) {
+            Some(content) => Ok(Metadata::new(content.len() as u64)),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+}
+
+impl WritableSource for Memory {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        self.insert(id, ext, content);
+        Ok(())
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        match Memory::remove(self, id, ext) {
+            Some(_) => Ok(()),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+}
+
+impl fmt::Debug for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let files = self.files.read();
+        let mut list = f.debug_list();
+        for (id, exts) in files.iter() {
+            for ext in exts.keys() {
+                list.entry(&format_args!("{}.{}", id, ext));
+            }
+        }
+        list.finish()
+    }
+}
@@ -4,19 +4,22 @@
 //! files containing the assets are loaded. The main usage usage of this trait
 //! is with an [`AssetCache`].
 //!
-//! This module also contains two built-in sources: [`FileSystem`] and
-//! [`Embedded`].
+//! This module also contains built-in sources, such as [`FileSystem`] and
+//! [`Embedded`], and combinators, such as [`Overlay`] and [`Router`], to
+//! build a source from other ones.
 //!
 //! # Hot-reloading
 //!
 //! Hot-reloading enable assets to be reloaded automatically when the source it
-//! was loaded from was modified. It is only supported for the [`FileSystem`]
-//! source at the moment.
+//! was loaded from was modified. The [`FileSystem`] source is watched for
+//! changes, and any other source can be checked for changes regularly by
+//! wrapping it in a `Polling` source.
 //!
 //! # Using a different source depending on the target platform
 //!
 //! There is no file system on WebAssembly, so you can for example choose to
-//! embed your assets on this platform:
+//! embed your assets on this platform, or to download them from the server
+//! with `Fetch` (feature `web`):
 //!
 //! ```no_run
 //! use assets_manager::{AssetCache, source};
@@ -34,19 +37,135 @@
 #[cfg(feature = "hot-reloading")]
 use crate::utils::PrivateMarker;
 
-use std::{borrow::Cow, io};
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{self, Read},
+    time::SystemTime,
+};
 
 #[cfg(doc)]
 use crate::AssetCache;
 
+mod error;
+pub use error::{SourceError, SourceErrorKind};
+
 mod filesystem;
 pub use filesystem::FileSystem;
 
 
+mod alias;
+pub use alias::AliasSource;
+
+mod any;
+pub use any::AnySource;
+
+mod byte_cache;
+pub use byte_cache::ByteCache;
+
+mod cached_remote;
+pub use cached_remote::{CachedRemote, Fetched, RemoteSource, Validators};
+
+mod fallback;
+pub use fallback::FallbackSource;
+
+mod limited;
+pub use limited::Limited;
+
+mod memory;
+pub use memory::Memory;
+
+mod mounted;
+pub use mounted::Mounted;
+
+mod overlay;
+pub use overlay::Overlay;
+
+mod router;
+pub use router::Router;
+
+mod swappable;
+pub use swappable::SwappableSource;
+
+#[cfg(any(feature = "zip", feature = "tar"))]
+mod archive;
+#[cfg(any(feature = "zip", feature = "tar"))]
+pub use archive::{ArchiveFormat, ArchiveSource};
+
+#[cfg(feature = "async")]
+mod async_source;
+#[cfg(feature = "async")]
+pub use async_source::{AsyncSource, Blocking, Unblock};
+
+#[cfg(feature = "encryption")]
+mod encrypted;
+#[cfg(feature = "encryption")]
+pub use encrypted::EncryptedSource;
+
+#[cfg(feature = "include_dir")]
+mod included;
+#[cfg(feature = "include_dir")]
+pub use included::IncludeDir;
+
+#[cfg(feature = "log")]
+mod instrumented;
+#[cfg(feature = "log")]
+pub use instrumented::Instrumented;
+
+#[cfg(feature = "hot-reloading")]
+mod polling;
+#[cfg(feature = "hot-reloading")]
+pub use polling::Polling;
+
+#[cfg(feature = "pack")]
+mod pack;
+#[cfg(feature = "pack")]
+pub use pack::{Pack, PackCompression, PackWriter};
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::Sqlite;
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::{Http, HttpBuilder};
+
+#[cfg(feature = "dev-server")]
+mod dev_server;
+#[cfg(feature = "dev-server")]
+pub use dev_server::{DevMessage, DevServer};
+
+#[cfg(feature = "s3")]
+mod s3;
+#[cfg(feature = "s3")]
+pub use s3::{S3, S3Builder, S3Credentials};
+
+#[cfg(all(feature = "android", target_os = "android"))]
+mod android;
+#[cfg(all(feature = "android", target_os = "android"))]
+pub use android::AndroidAssets;
+
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+mod web;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub use web::Fetch;
+
 #[cfg(feature = "embedded")]
 mod embedded;
 #[cfg(feature = "embedded")]
-pub use embedded::{Embedded, RawEmbedded};
+pub use embedded::{Compression, Embedded, EmbeddedBuilder, RawEmbedded};
+
+#[cfg(feature = "test-utils")]
+mod temp;
+#[cfg(feature = "test-utils")]
+pub use temp::{TempSource, TempSourceBuilder};
+
+#[cfg(feature = "embedded-build")]
+mod embed_build;
+#[cfg(feature = "embedded-build")]
+pub use embed_build::EmbedGenerator;
 
 /// Embed a directory in the binary
 ///
@@ -64,6 +183,42 @@ pub use embedded::{Embedded, RawEmbedded};
 /// let embedded = Embedded::from(EMBEDDED);
 /// let cache = AssetCache::with_source(embedded);
 /// ```
+///
+/// ## Embedding several directories
+///
+/// A list of directories can be given instead of a single one. Their content
+/// is merged, and if a file exists in several of them, the one from the first
+/// directory is used.
+///
+/// ```ignore
+/// static EMBEDDED: RawEmbedded<'static> = embed!(["assets", "target/baked"]);
+/// ```
+///
+/// ## Filtering files
+///
+/// The `include` and `exclude` arguments take lists of glob patterns to select
+/// the files to embed. Patterns without `/` are matched against file names,
+/// and other ones against paths relative to the embedded directory. If
+/// `include` is given, only matching files are embedded. Excluded directories
+/// are skipped entirely, and directories left without files are not embedded.
+///
+/// ```ignore
+/// static EMBEDDED: RawEmbedded<'static> = embed!(
+///     "assets",
+///     include = ["*.ron", "*.png"],
+///     exclude = ["**/raw/**"],
+/// );
+/// ```
+///
+/// ## Compression
+///
+/// Files can be compressed at compile time with the `compress` argument, which
+/// can be `"deflate"` (feature `embedded-deflate`) or `"zstd"` (feature
+/// `embedded-zstd`). See [`Compression`] for more informations.
+///
+/// ```ignore
+/// static EMBEDDED: RawEmbedded<'static> = embed!("assets", compress = "zstd");
+/// ```
 #[cfg(feature = "embedded")]
 #[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
 pub use assets_manager_macros::embed;
@@ -79,7 +234,10 @@ pub trait Source {
     ///
     /// If no error occurs, this function returns an `Cow`, which can be useful
     /// to avoid allocations.
-    fn read(&self, id: &str, ext: &str) -> io::Result<Cow<[u8]>>;
+    ///
+    /// Sources that turn ids into paths should reject ids that could escape
+    /// them, see [`validate_id`].
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<[u8]>, SourceError>;
 
     /// Reads a directory given its id and an extension list.
     ///
@@ -104,7 +262,155 @@ pub trait Source {
     /// assert_eq!(dir_content, ["giant_bat", "goblin"]);
     /// # Ok::<(), std::io::Error>(())
     /// ```
-    fn read_dir(&self, id: &str, ext: &[&str]) -> io::Result<Vec<String>>;
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError>;
+
+    /// Reads a directory and its subdirectories given its id and an extension
+    /// list.
+    ///
+    /// If no error occurs, this function returns the fully qualified ids of
+    /// files that have at least one of the given extensions, in the directory
+    /// and in all its subdirectories.
+    ///
+    /// The default implementation lists subdirectories with
+    /// [`read_dir_entries`], so sources that cannot list subdirectories only
+    /// return files of the directory itself. Subdirectories that cannot be
+    /// read are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{FileSystem, Source};
+    ///
+    /// let fs = FileSystem::new("assets")?;
+    /// let mut ids = fs.read_dir_recursive("example", &["ron"])?;
+    /// ids.sort();
+    ///
+    /// assert!(ids.contains(&String::from("example.monsters.goblin")));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`read_dir_entries`]: Source::read_dir_entries
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let mut ids = Vec::new();
+
+        for entry in self.read_dir_entries(id, ext)? {
+            match entry {
+                DirEntry::File(name) => ids.push(join_id(id, &name)),
+                DirEntry::Directory(name) => {
+                    if let Ok(sub_ids) = self.read_dir_recursive(&join_id(id, &name), ext) {
+                        ids.extend(sub_ids);
+                    }
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Reads a directory given its id and an extension list, listing its
+    /// subdirectories too.
+    ///
+    /// If no error occurs, this function returns the files of the directory
+    /// that have at least one of the given extensions, as [`read_dir`] does,
+    /// and the names of all its subdirectories.
+    ///
+    /// The default implementation cannot list subdirectories, so it only
+    /// returns files, using [`read_dir`]. Sources that can list subdirectories
+    /// are encouraged to override it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{DirEntry, FileSystem, Source};
+    ///
+    /// let fs = FileSystem::new("assets")?;
+    /// let entries = fs.read_dir_entries("example", &["ron"])?;
+    ///
+    /// assert!(entries.contains(&DirEntry::Directory(String::from("monsters"))));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// [`read_dir`]: Source::read_dir
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let files = self.read_dir(id, ext)?;
+        Ok(files.into_iter().map(DirEntry::File).collect())
+    }
+
+    /// Returns metadata about a file given its id and its extension.
+    ///
+    /// This can be used for cache invalidation or progress reporting without
+    /// reading the file. The default implementation reads the whole file to
+    /// get its size, so sources are encouraged to override it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{FileSystem, Source};
+    ///
+    /// let fs = FileSystem::new("assets")?;
+    /// let metadata = fs.metadata("example.monsters.goblin", "ron")?;
+    /// assert!(metadata.len > 0);
+    /// assert!(metadata.modified.is_some());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let content = self.read(id, ext)?;
+        Ok(Metadata::new(content.len() as u64))
+    }
+
+    /// Opens a file given its id and its extension, to read it as a stream.
+    ///
+    /// This enables huge files to be given to a
+    /// [`StreamLoader`](crate::loader::StreamLoader) without being entirely
+    /// read into memory first. The default implementation reads the whole file
+    /// with [`read`](Source::read), so sources that can stream files are
+    /// encouraged to override it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{FileSystem, Source};
+    /// use std::io::Read;
+    ///
+    /// let fs = FileSystem::new("assets")?;
+    /// let mut content = String::new();
+    /// fs.open("example.monsters.goblin", "ron")?.read_to_string(&mut content)?;
+    /// assert!(content.contains("Goblin"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        let content = self.read(id, ext)?.into_owned();
+        Ok(Box::new(io::Cursor::new(content)))
+    }
+
+    /// Returns an opaque value that changes when the content of a file
+    /// changes.
+    ///
+    /// This can be an HTTP entity tag, a modification time or a hash of the
+    /// content. Two equal versions of a file mean that its content did not
+    /// change, so it does not need to be reloaded. The default implementation
+    /// uses the modification time given by [`metadata`](Source::metadata) if
+    /// there is one, and a hash of the content otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use assets_manager::source::{Memory, Source};
+    ///
+    /// let memory = Memory::new();
+    /// memory.insert("example.greeting", "txt", "Hello");
+    /// let version = memory.version("example.greeting", "txt")?;
+    ///
+    /// memory.insert("example.greeting", "txt", "Hi");
+    /// assert_ne!(memory.version("example.greeting", "txt")?, version);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        match self.metadata(id, ext)? {
+            Metadata { len, modified: Some(modified) } => Ok(Version::from_modified(modified, len)),
+            Metadata { modified: None, .. } => Ok(Version::hash_of(&self.read(id, ext)?)),
+        }
+    }
 
     #[cfg(feature = "hot-reloading")]
     #[doc(hidden)]
@@ -114,6 +420,12 @@ pub trait Source {
     #[doc(hidden)]
     fn _add_dir<A: crate::Asset, P: PrivateMarker>(&self, _: &str) where Self: Sized {}
 
+    /// Registers the file `id` with extension `ext`, loaded as an `A` stored
+    /// with id `key` in the cache.
+    #[cfg(feature = "hot-reloading")]
+    #[doc(hidden)]
+    fn _add_file<A: crate::Asset, P: PrivateMarker>(&self, _key: &str, _id: &str, _ext: &str) where Self: Sized {}
+
     #[cfg(feature = "hot-reloading")]
     #[doc(hidden)]
     fn _clear<P: PrivateMarker>(&mut self) where Self: Sized {}
@@ -127,18 +439,322 @@ pub trait Source {
     fn _support_hot_reloading<P: PrivateMarker>(&self) -> bool where Self: Sized {
         false
     }
+
+    #[cfg(feature = "hot-reloading")]
+    #[doc(hidden)]
+    fn _refresh<P: PrivateMarker>(&self) where Self: Sized {}
+}
+
+/// Returns `true` if `dir_id` is `parent` or one of its subdirectories.
+fn is_in_dir(dir_id: &str, parent: &str) -> bool {
+    parent.is_empty() || match dir_id.strip_prefix(parent) {
+        Some(rest) => rest.is_empty() || rest.starts_with('.'),
+        None => false,
+    }
+}
+
+/// Joins the id of a directory and the name of a file in it.
+fn join_id(dir_id: &str, name: &str) -> String {
+    if dir_id.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", dir_id, name)
+    }
+}
+
+/// Returns the direct subdirectories of `id` among the ids of `dirs`.
+#[cfg(any(feature = "embedded", feature = "include_dir", feature = "pack"))]
+fn sub_dirs<'a, I>(dirs: I, id: &'a str) -> impl Iterator<Item = DirEntry> + 'a
+where
+    I: IntoIterator<Item = &'a str>,
+    I::IntoIter: 'a,
+{
+    dirs.into_iter().filter_map(move |dir_id| {
+        let (parent, name) = dir_id.rsplit_once('.').unwrap_or(("", dir_id));
+        if parent == id && !dir_id.is_empty() {
+            Some(DirEntry::Directory(name.to_owned()))
+        } else {
+            None
+        }
+    })
+}
+
+/// Identifies a version of a file on disk.
+#[cfg(any(feature = "pack", feature = "zip", feature = "tar"))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+#[cfg(any(feature = "pack", feature = "zip", feature = "tar"))]
+impl Stamp {
+    fn of(path: &std::path::Path) -> io::Result<Stamp> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(Stamp {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// Builds the URL of a file from a base URL, an id and an extension.
+#[cfg(any(feature = "http", all(feature = "web", target_arch = "wasm32")))]
+fn url_of(base_url: &str, id: &str, ext: &str) -> String {
+    let mut url = base_url.trim_end_matches('/').to_owned();
+    for component in id.split('.') {
+        url.push('/');
+        url.push_str(component);
+    }
+    if !ext.is_empty() {
+        url.push('.');
+        url.push_str(ext);
+    }
+    url
 }
 
 impl<S> Source for Box<S>
 where
     S: Source + ?Sized,
 {
-    fn read(&self, id: &str, ext: &str) -> io::Result<Cow<[u8]>> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<[u8]>, SourceError> {
         self.as_ref().read(id, ext)
     }
 
-    fn read_dir(&self, dir: &str, ext: &[&str]) -> io::Result<Vec<String>> {
+    fn read_dir(&self, dir: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
         self.as_ref().read_dir(dir, ext)
     }
+
+    fn read_dir_recursive(&self, dir: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.as_ref().read_dir_recursive(dir, ext)
+    }
+
+    fn read_dir_entries(&self, dir: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.as_ref().read_dir_entries(dir, ext)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.as_ref().metadata(id, ext)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.as_ref().open(id, ext)
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.as_ref().version(id, ext)
+    }
+}
+
+/// An entry of a directory of a [`Source`].
+///
+/// This is returned by [`Source::read_dir_entries`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DirEntry {
+    /// A file, given by its stem (without extension nor dir prefix).
+    File(String),
+
+    /// A subdirectory, given by its name.
+    Directory(String),
+}
+
+impl DirEntry {
+    /// Returns the name of the entry.
+    #[inline]
+    pub fn name(&self) -> &str {
+        match self {
+            DirEntry::File(name) | DirEntry::Directory(name) => name,
+        }
+    }
+
+    /// Returns `true` if the entry is a file.
+    #[inline]
+    pub fn is_file(&self) -> bool {
+        matches!(self, DirEntry::File(_))
+    }
+
+    /// Returns `true` if the entry is a directory.
+    #[inline]
+    pub fn is_dir(&self) -> bool {
+        matches!(self, DirEntry::Directory(_))
+    }
+}
+
+/// Checks that an id and an extension can be safely turned into a path.
+///
+/// An id is valid if it is empty, or if all its components (separated by
+/// `.`) are non-empty and contain neither a path separator (`/` or `\`), nor
+/// `:`, nor a nul byte. The same goes for the extension, which may contain
+/// dots. This prevents ids that come from users, such as mod manifests or
+/// console commands, from reading files outside of a source.
+///
+/// Sources that map ids to paths or URLs use this function and return an
+/// error of kind [`InvalidId`](SourceErrorKind::InvalidId) containing an
+/// [`InvalidId`] when it fails.
+///
+/// # Example
+///
+/// ```
+/// use assets_manager::source::validate_id;
+///
+/// assert!(validate_id("common.position", "ron").is_ok());
+/// assert!(validate_id("common..position", "ron").is_err());
+/// assert!(validate_id("common./etc/passwd", "").is_err());
+/// assert!(validate_id("common", "ron/../x").is_err());
+/// ```
+pub fn validate_id(id: &str, ext: &str) -> Result<(), InvalidId> {
+    fn is_valid(s: &str) -> bool {
+        s.is_empty() || s.split('.').all(|component| {
+            !component.is_empty() && !component.contains(&['/', '\\', ':', '\0'][..])
+        })
+    }
+
+    if is_valid(id) && is_valid(ext) {
+        Ok(())
+    } else {
+        Err(InvalidId {
+            id: id.to_owned(),
+            ext: ext.to_owned(),
+        })
+    }
+}
+
+/// The error returned when an id or an extension is not valid.
+///
+/// See [`validate_id`] for what makes an id valid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidId {
+    id: String,
+    ext: String,
+}
+
+impl InvalidId {
+    /// Returns the invalid id.
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the extension given with the id.
+    #[inline]
+    pub fn ext(&self) -> &str {
+        &self.ext
+    }
+}
+
+impl fmt::Display for InvalidId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid id \"{}\" with extension \"{}\"", self.id, self.ext)
+    }
+}
+
+impl std::error::Error for InvalidId {}
+
+impl From<InvalidId> for io::Error {
+    fn from(err: InvalidId) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidInput, err)
+    }
+}
+
+/// Metadata about a file of a [`Source`].
+///
+/// This is returned by [`Source::metadata`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Metadata {
+    /// The size of the file, in bytes.
+    pub len: u64,
+
+    /// The last modification time of the file, if the source knows it.
+    pub modified: Option<SystemTime>,
+}
+
+impl Metadata {
+    /// Creates a new `Metadata` for a file of the given size, without
+    /// modification time.
+    #[inline]
+    pub fn new(len: u64) -> Metadata {
+        Metadata { len, modified: None }
+    }
+
+    /// Returns `true` if the file is known to have been modified after the
+    /// given time.
+    #[inline]
+    pub fn is_newer_than(&self, time: SystemTime) -> bool {
+        self.modified.is_some_and(|modified| modified > time)
+    }
+}
+
+/// An opaque version of a file of a [`Source`].
+///
+/// This is returned by [`Source::version`]. Versions can only be compared
+/// with each other: if two versions of a file are equal, its content did not
+/// change.
+///
+/// Versions are only meaningful within a process, they should not be
+/// persisted.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Version(VersionRepr);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum VersionRepr {
+    Hash(u64),
+    Modified(SystemTime, u64),
+    Tag(Box<str>),
+}
+
+impl Version {
+    /// Creates a version from the content of a file.
+    pub fn hash_of(content: &[u8]) -> Version {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        content.hash(&mut hasher);
+        Version(VersionRepr::Hash(hasher.finish()))
+    }
+
+    /// Creates a version from the modification time and the size of a file.
+    #[inline]
+    pub fn from_modified(modified: SystemTime, len: u64) -> Version {
+        Version(VersionRepr::Modified(modified, len))
+    }
+
+    /// Creates a version from an arbitrary tag, such as an HTTP entity tag.
+    #[inline]
+    pub fn from_tag<T: Into<String>>(tag: T) -> Version {
+        Version(VersionRepr::Tag(tag.into().into_boxed_str()))
+    }
+}
+
+/// A [`Source`] that can be written to.
+///
+/// This enables saving assets with [`AssetCache::save`], and more generally
+/// storing settings, editor output or downloaded content.
+///
+/// Note that writing to a source does not update the assets already loaded in
+/// a cache.
+pub trait WritableSource: Source {
+    /// Writes the content of a file given an id and an extension.
+    ///
+    /// The file is created if it does not exist, and replaced otherwise.
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError>;
+
+    /// Removes a file given an id and an extension.
+    ///
+    /// An error of kind [`NotFound`](SourceErrorKind::NotFound) is returned if
+    /// the file does not exist.
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError>;
+}
+
+impl<S> WritableSource for Box<S>
+where
+    S: WritableSource + ?Sized,
+{
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        self.as_ref().write(id, ext, content)
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        self.as_ref().remove(id, ext)
+    }
 }
 
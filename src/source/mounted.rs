@@ -0,0 +1,161 @@
+use std::{
+    borrow::Cow,
+    io::Read,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, SourceErrorKind, Version, WritableSource};
+
+
+/// A [`Source`] that exposes another source under an id prefix.
+///
+/// For example, if a source is mounted at `dlc1`, its file `maps.forest` is
+/// read with the id `dlc1.maps.forest`. Ids outside of the mount point do not
+/// exist in this source.
+///
+/// This is useful to put several sources in the same cache without id
+/// collisions, typically with an [`Overlay`](super::Overlay).
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{FileSystem, Mounted, Overlay}};
+///
+/// let source = Overlay::new(FileSystem::new("assets")?)
+///     .with_layer(Mounted::new("dlc1", FileSystem::new("dlc/dlc1")?));
+///
+/// // Files of "dlc/dlc1" are loaded with ids starting with `dlc1.`
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mounted<S> {
+    prefix: String,
+    source: S,
+}
+
+impl<S> Mounted<S> {
+    /// Mounts a source under the given prefix.
+    ///
+    /// The prefix is an id, such as `dlc1` or `packs.extra`. An empty prefix
+    /// mounts the source at the root.
+    pub fn new<P: Into<String>>(prefix: P, source: S) -> Mounted<S> {
+        Mounted {
+            prefix: prefix.into(),
+            source,
+        }
+    }
+
+    /// Returns the prefix under which the source is mounted.
+    #[inline]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Converts an id of this source into an id of the inner source.
+    ///
+    /// Returns `None` if the id is outside of the mount point.
+    pub fn strip_prefix<'a>(&self, id: &'a str) -> Option<&'a str> {
+        if self.prefix.is_empty() {
+            return Some(id);
+        }
+
+        let rest = id.strip_prefix(&*self.prefix)?;
+        if rest.is_empty() {
+            Some(rest)
+        } else {
+            rest.strip_prefix('.')
+        }
+    }
+
+    /// Returns `true` if the id is a parent directory of the mount point.
+    fn is_parent_dir(&self, id: &str) -> bool {
+        id.is_empty() || self.prefix.strip_prefix(id).is_some_and(|rest| rest.starts_with('.'))
+    }
+
+    /// Returns the name of the subdirectory of a parent directory of the mount
+    /// point that leads to it.
+    fn child_of_parent_dir(&self, id: &str) -> Option<&str> {
+        let rest = if id.is_empty() { &*self.prefix } else { self.prefix.strip_prefix(id)?.strip_prefix('.')? };
+        rest.split('.').next()
+    }
+}
+
+impl<S: Source> Source for Mounted<S> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) if !id.is_empty() => self.source.read(id, ext),
+            _ => Err(SourceErrorKind::NotFound.into()),
+        }
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) => self.source.read_dir(id, ext),
+            None if self.is_parent_dir(id) => Ok(Vec::new()),
+            None => Err(SourceErrorKind::NotFound.into()),
+        }
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) => self.source.read_dir_entries(id, ext),
+            None => match self.child_of_parent_dir(id) {
+                Some(name) => Ok(vec![DirEntry::Directory(name.to_owned())]),
+                None => Err(SourceErrorKind::NotFound.into()),
+            },
+        }
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) if !id.is_empty() => self.source.metadata(id, ext),
+            _ => Err(SourceErrorKind::NotFound.into()),
+        }
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) if !id.is_empty() => self.source.version(id, ext),
+            _ => Err(SourceErrorKind::NotFound.into()),
+        }
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) if !id.is_empty() => self.source.open(id, ext),
+            _ => Err(SourceErrorKind::NotFound.into()),
+        }
+    }
+}
+
+impl<S: WritableSource> WritableSource for Mounted<S> {
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) if !id.is_empty() => self.source.write(id, ext, content),
+            _ => Err(SourceError::new(SourceErrorKind::InvalidId, "id outside of the mount point")),
+        }
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        match self.strip_prefix(id) {
+            Some(id) if !id.is_empty() => self.source.remove(id, ext),
+            _ => Err(SourceErrorKind::NotFound.into()),
+        }
+    }
+}
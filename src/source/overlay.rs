@@ -0,0 +1,190 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fmt,
+    io::Read,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, SourceErrorKind, Version};
+
+
+/// A [`Source`] that stacks several sources on top of each other.
+///
+/// When a file is read, the layers are tried from the topmost to the bottom
+/// one, and the first one that has the file is used. When a directory is read,
+/// the entries of all layers are merged.
+///
+/// This is typically used for mod support, where files of the mods override
+/// the base files of the game.
+///
+/// ## Errors
+///
+/// A layer that returns an error of kind [`NotFound`](SourceErrorKind::NotFound)
+/// is skipped. Any other error is returned immediately, so a broken file in
+/// a mod is not silently replaced by the base one.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{FileSystem, Overlay}};
+///
+/// let source = Overlay::new(FileSystem::new("assets")?)
+///     .with_layer(FileSystem::new("user")?)
+///     .with_layer(FileSystem::new("mods")?);
+///
+/// // Files are read from "mods", then "user", then "assets"
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Overlay {
+    layers: Vec<Box<dyn Source + Send + Sync>>,
+}
+
+impl Overlay {
+    /// Creates a new `Overlay` with a base layer.
+    pub fn new<S: Source + Send + Sync + 'static>(base: S) -> Overlay {
+        Overlay { layers: vec![Box::new(base)] }
+    }
+
+    /// Adds a layer on top of the existing ones.
+    pub fn push<S: Source + Send + Sync + 'static>(&mut self, layer: S) {
+        self.layers.push(Box::new(layer));
+    }
+
+    /// Adds a layer on top of the existing ones.
+    ///
+    /// This is the same as [`push`](Self::push), but it can be chained.
+    pub fn with_layer<S: Source + Send + Sync + 'static>(mut self, layer: S) -> Overlay {
+        self.push(layer);
+        self
+    }
+
+    /// Returns the number of layers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if there is no layer.
+    ///
+    /// This only happens if all layers were removed with [`pop`](Self::pop).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Removes the topmost layer and returns it.
+    pub fn pop(&mut self) -> Option<Box<dyn Source + Send + Sync>> {
+        self.layers.pop()
+    }
+}
+
+impl Source for Overlay {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        for layer in self.layers.iter().rev() {
+            match layer.read(id, ext) {
+                Err(err) if err.kind() == SourceErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+
+        Err(SourceErrorKind::NotFound.into())
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let mut found = false;
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+
+        for layer in self.layers.iter().rev() {
+            let layer_entries = match layer.read_dir(id, ext) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == SourceErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+
+            found = true;
+            for entry in layer_entries {
+                if seen.insert(entry.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        if found {
+            Ok(entries)
+        } else {
+            Err(SourceErrorKind::NotFound.into())
+        }
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let mut found = false;
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+
+        for layer in self.layers.iter().rev() {
+            let layer_entries = match layer.read_dir_entries(id, ext) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == SourceErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+
+            found = true;
+            for entry in layer_entries {
+                if seen.insert(entry.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        if found {
+            Ok(entries)
+        } else {
+            Err(SourceErrorKind::NotFound.into())
+        }
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        for layer in self.layers.iter().rev() {
+            match layer.metadata(id, ext) {
+                Err(err) if err.kind() == SourceErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+
+        Err(SourceErrorKind::NotFound.into())
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        for layer in self.layers.iter().rev() {
+            match layer.version(id, ext) {
+                Err(err) if err.kind() == SourceErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+
+        Err(SourceErrorKind::NotFound.into())
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        for layer in self.layers.iter().rev() {
+            match layer.open(id, ext) {
+                Err(err) if err.kind() == SourceErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+
+        Err(SourceErrorKind::NotFound.into())
+    }
+}
+
+impl fmt::Debug for Overlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Overlay").field("layers", &self.layers.len()).finish()
+    }
+}
@@ -0,0 +1,594 @@
+use crate::utils::{HashMap, Mutex, RwLock};
+
+#[cfg(feature = "hot-reloading")]
+use crate::utils::PrivateMarker;
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt,
+    fs,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, SourceErrorKind, Stamp};
+
+
+const MAGIC: &[u8; 7] = b"AMPACK\0";
+const VERSION: u8 = 2;
+
+/// The compression of files in a [`Pack`].
+#[cfg_attr(docsrs, doc(cfg(feature = "pack")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackCompression {
+    /// Files are not compressed.
+    None,
+
+    /// Files are compressed with raw Deflate.
+    Deflate,
+
+    /// Files are compressed with Zstandard.
+    #[cfg(feature = "zstd")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+    Zstd,
+}
+
+impl PackCompression {
+    fn from_byte(byte: u8) -> io::Result<PackCompression> {
+        match byte {
+            0 => Ok(PackCompression::None),
+            1 => Ok(PackCompression::Deflate),
+            #[cfg(feature = "zstd")]
+            2 => Ok(PackCompression::Zstd),
+            #[cfg(not(feature = "zstd"))]
+            2 => Err(io::Error::new(io::ErrorKind::Unsupported, "zstd compression requires feature `zstd`")),
+            _ => Err(invalid_data("unknown compression")),
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            PackCompression::None => 0,
+            PackCompression::Deflate => 1,
+            #[cfg(feature = "zstd")]
+            PackCompression::Zstd => 2,
+        }
+    }
+
+    fn compress(self, content: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            PackCompression::None => Ok(content.to_owned()),
+            PackCompression::Deflate => {
+                let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(content)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "zstd")]
+            PackCompression::Zstd => zstd_crate::stream::encode_all(content, 19),
+        }
+    }
+
+    fn decompress(self, content: Vec<u8>, size: u64) -> io::Result<Vec<u8>> {
+        match self {
+            PackCompression::None => Ok(content),
+            _ => {
+                // Read one more byte than expected to detect wrong sizes
+                let mut decompressed = Vec::new();
+                self.decoder(content)?.take(size.saturating_add(1)).read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+        }
+    }
+
+    fn decoder(self, content: Vec<u8>) -> io::Result<Box<dyn Read + Send>> {
+        let content = io::Cursor::new(content);
+        Ok(match self {
+            PackCompression::None => Box::new(content),
+            PackCompression::Deflate => Box::new(flate2::read::DeflateDecoder::new(content)),
+            #[cfg(feature = "zstd")]
+            PackCompression::Zstd => Box::new(zstd_crate::stream::read::Decoder::new(content)?),
+        })
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid pack: {}", msg))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_string<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = read_u32(reader)? as usize;
+    let mut buf = Vec::new();
+    reader.by_ref().take(len as u64).read_to_end(&mut buf)?;
+    if buf.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    String::from_utf8(buf).map_err(|_| invalid_data("non UTF-8 id"))
+}
+
+fn write_string<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
+    writer.write_all(&(s.len() as u32).to_le_bytes())?;
+    writer.write_all(s.as_bytes())
+}
+
+fn parent_of(id: &str) -> (&str, &str) {
+    match id.rfind('.') {
+        Some(pos) => (&id[..pos], &id[pos+1..]),
+        None => ("", id),
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    compression: PackCompression,
+    offset: u64,
+    len: u64,
+    /// The size of the file once decompressed.
+    size: u64,
+}
+
+struct Index {
+    data_start: u64,
+    files: HashMap<(String, String), Entry>,
+    dirs: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Index {
+    fn read<R: Read + Seek>(reader: &mut R) -> io::Result<Index> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if magic[..7] != MAGIC[..] {
+            return Err(invalid_data("bad magic number"));
+        }
+        if magic[7] != VERSION {
+            return Err(invalid_data("unsupported version"));
+        }
+
+        let count = read_u32(reader)?;
+        let mut files = HashMap::new();
+        let mut dirs = HashMap::new();
+        dirs.insert(String::new(), Vec::new());
+
+        for _ in 0..count {
+            let id = read_string(reader)?;
+            let ext = read_string(reader)?;
+
+            let mut compression = [0];
+            reader.read_exact(&mut compression)?;
+            let compression = PackCompression::from_byte(compression[0])?;
+
+            let offset = read_u64(reader)?;
+            let len = read_u64(reader)?;
+            let size = read_u64(reader)?;
+
+            let (mut dir, name) = parent_of(&id);
+            dirs.entry(dir.to_owned()).or_insert_with(Vec::new).push((name.to_owned(), ext.clone()));
+            while !dir.is_empty() {
+                dir = parent_of(dir).0;
+                dirs.entry(dir.to_owned()).or_insert_with(Vec::new);
+            }
+
+            files.insert((id, ext), Entry { compression, offset, len, size });
+        }
+
+        let data_start = reader.stream_position()?;
+
+        Ok(Index { data_start, files, dirs })
+    }
+}
+
+/// The file a pack was opened from, to read it again when it changes.
+struct Origin<R> {
+    path: PathBuf,
+    open: fn(&Path) -> io::Result<R>,
+    stamp: Mutex<Stamp>,
+}
+
+/// A [`Source`] that reads files from a pack file.
+///
+/// A pack is a single file containing an index of files, followed by their
+/// content, each file being optionally compressed. This enables to distribute
+/// assets as a single file, without embedding them in the executable, and with
+/// less overhead than a zip archive. Packs are created with a [`PackWriter`].
+///
+/// The index is read when the pack is opened, and the content of files is read
+/// when they are requested. The index stores the decompressed size of files,
+/// so a [`Limited`](super::Limited) source can reject big files without
+/// decompressing them.
+///
+/// ## Hot-reloading
+///
+/// A pack opened with [`Pack::open`] can be read again when the file changes
+/// on disk with [`Pack::reload`]. When it is wrapped in a
+/// [`Polling`](super::Polling) source, this is done automatically when
+/// the cache is hot-reloaded, and assets whose content changed are reloaded:
+///
+/// ```no_run
+/// # #[cfg(feature = "hot-reloading")]
+/// # {
+/// use assets_manager::{AssetCache, source::{Pack, Polling}};
+///
+/// let pack = Pack::open("assets.pack")?;
+/// let cache = AssetCache::with_source(Polling::new(pack));
+///
+/// loop {
+///     cache.hot_reload();
+///     // ...
+/// }
+/// # }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::Pack};
+///
+/// let pack = Pack::open("assets.pack")?;
+/// let cache = AssetCache::with_source(pack);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "pack")))]
+pub struct Pack<R = fs::File> {
+    reader: Mutex<R>,
+    index: RwLock<Index>,
+    origin: Option<Origin<R>>,
+}
+
+impl Pack<fs::File> {
+    /// Opens the pack file at the given path.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the file cannot be read or if it is not a valid
+    /// pack.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Pack<fs::File>> {
+        let path = path.as_ref();
+        let stamp = Stamp::of(path)?;
+        let mut pack = Pack::new(fs::File::open(path)?)?;
+
+        pack.origin = Some(Origin {
+            path: path.to_owned(),
+            open: |path| fs::File::open(path),
+            stamp: Mutex::new(stamp),
+        });
+        Ok(pack)
+    }
+}
+
+impl<R: Read + Seek> Pack<R> {
+    /// Reads a pack from a reader.
+    ///
+    /// Use [`io::Cursor`] to read a pack from memory.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the reader fails or if it does not contain a
+    /// valid pack.
+    pub fn new(mut reader: R) -> io::Result<Pack<R>> {
+        let index = Index::read(&mut reader)?;
+
+        Ok(Pack {
+            reader: Mutex::new(reader),
+            index: RwLock::new(index),
+            origin: None,
+        })
+    }
+
+    /// Reads the pack file again if it changed on disk since it was opened or
+    /// last reloaded.
+    ///
+    /// Returns `true` if the pack was reloaded. Packs that were not created
+    /// with [`Pack::open`] are never reloaded.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the file cannot be read or if it is not a valid
+    /// pack anymore. In this case, the previous content is kept.
+    pub fn reload(&self) -> io::Result<bool> {
+        let origin = match &self.origin {
+            Some(origin) => origin,
+            None => return Ok(false),
+        };
+
+        let mut stamp = origin.stamp.lock();
+        let new_stamp = Stamp::of(&origin.path)?;
+        if *stamp == new_stamp {
+            return Ok(false);
+        }
+
+        let mut reader = (origin.open)(&origin.path)?;
+        let new_index = Index::read(&mut reader)?;
+
+        // Locks are taken in the same order as in `read`
+        let mut index = self.index.write();
+        *self.reader.lock() = reader;
+        *index = new_index;
+        *stamp = new_stamp;
+
+        Ok(true)
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R> Pack<R> {
+    /// Returns the number of files in the pack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.index.read().files.len()
+    }
+
+    /// Returns `true` if the pack contains no files.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.index.read().files.is_empty()
+    }
+
+    /// Returns `true` if the pack contains the given file.
+    pub fn contains(&self, id: &str, ext: &str) -> bool {
+        self.index.read().files.contains_key(&(id.to_owned(), ext.to_owned()))
+    }
+}
+
+impl<R: Read + Seek> Pack<R> {
+    /// Reads the content of a file as it is stored in the pack.
+    fn read_raw(&self, id: &str, ext: &str) -> io::Result<(Vec<u8>, PackCompression, u64)> {
+        let index = self.index.read();
+        let entry = index.files.get(&(id.to_owned(), ext.to_owned())).ok_or(io::ErrorKind::NotFound)?;
+
+        let start = index.data_start.checked_add(entry.offset).ok_or_else(|| invalid_data("bad offset"))?;
+
+        let mut content = Vec::new();
+        {
+            let mut reader = self.reader.lock();
+            reader.seek(SeekFrom::Start(start))?;
+            reader.by_ref().take(entry.len).read_to_end(&mut content)?;
+        }
+
+        if content.len() as u64 != entry.len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        Ok((content, entry.compression, entry.size))
+    }
+}
+
+impl<R: Read + Seek> Source for Pack<R> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let (content, compression, size) = self.read_raw(id, ext)?;
+        let content = compression.decompress(content, size)?;
+
+        if content.len() as u64 != size {
+            return Err(invalid_data("wrong file size").into());
+        }
+
+        Ok(Cow::Owned(content))
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        let (content, compression, _) = self.read_raw(id, ext)?;
+        Ok(compression.decoder(content)?)
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let index = self.index.read();
+        let dir = index.dirs.get(id).ok_or(io::ErrorKind::NotFound)?;
+
+        Ok(dir.iter()
+            .filter(|(_, file_ext)| ext.contains(&file_ext.as_str()))
+            .map(|(name, _)| name.clone())
+            .collect()
+        )
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let index = self.index.read();
+        let dir = index.dirs.get(id).ok_or(io::ErrorKind::NotFound)?;
+
+        let mut entries: Vec<_> = dir.iter()
+            .filter(|(_, file_ext)| ext.contains(&file_ext.as_str()))
+            .map(|(name, _)| DirEntry::File(name.clone()))
+            .collect();
+
+        // Every parent directory of a file is in the index
+        entries.extend(super::sub_dirs(index.dirs.keys().map(String::as_str), id));
+
+        // A file is found once per extension
+        entries.sort();
+        entries.dedup();
+        Ok(entries)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let index = self.index.read();
+        let entry = index.files.get(&(id.to_owned(), ext.to_owned())).ok_or(io::ErrorKind::NotFound)?;
+        Ok(Metadata::new(entry.size))
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _refresh<P: PrivateMarker>(&self) {
+        if let Err(err) = self.reload() {
+            log::warn!("Error reloading pack: {}", err);
+        }
+    }
+}
+
+impl<R> fmt::Debug for Pack<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pack")
+            .field("files", &self.index.read().files.keys())
+            .finish()
+    }
+}
+
+/// Builds a [`Pack`] file.
+///
+/// Files are added in memory, and written with [`PackWriter::write`]. They
+/// are compressed with the compression set when they are added. Files are
+/// written in order of id, so the same files always give the same pack.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::source::{PackCompression, PackWriter};
+///
+/// let mut writer = PackWriter::new();
+/// writer.set_compression(PackCompression::Deflate);
+/// writer.add_dir("assets")?;
+/// writer.write_to_file("assets.pack")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "pack")))]
+#[derive(Debug)]
+pub struct PackWriter {
+    compression: PackCompression,
+    files: BTreeMap<(String, String), (PackCompression, Vec<u8>, u64)>,
+}
+
+impl Default for PackWriter {
+    fn default() -> PackWriter {
+        PackWriter::new()
+    }
+}
+
+impl PackWriter {
+    /// Creates an empty `PackWriter`, without compression.
+    pub fn new() -> PackWriter {
+        PackWriter {
+            compression: PackCompression::None,
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the compression used for files added from now on.
+    pub fn set_compression(&mut self, compression: PackCompression) {
+        self.compression = compression;
+    }
+
+    /// Adds a file given its id and its extension.
+    ///
+    /// If a file with the same id and extension was already added, it is
+    /// replaced.
+    pub fn add(&mut self, id: &str, ext: &str, content: &[u8]) -> io::Result<()> {
+        let size = content.len() as u64;
+        let content = self.compression.compress(content)?;
+        self.files.insert((id.to_owned(), ext.to_owned()), (self.compression, content, size));
+        Ok(())
+    }
+
+    /// Adds a file read from a [`Source`].
+    pub fn add_from_source<S: Source + ?Sized>(&mut self, source: &S, id: &str, ext: &str) -> io::Result<()> {
+        let content = source.read(id, ext)?;
+        self.add(id, ext, &content)
+    }
+
+    /// Adds the files of a directory of a [`Source`] that have one of the
+    /// given extensions.
+    ///
+    /// Subdirectories are not added.
+    pub fn add_source_dir<S: Source + ?Sized>(&mut self, source: &S, id: &str, ext: &[&str]) -> io::Result<()> {
+        for name in source.read_dir(id, ext)? {
+            let file_id = if id.is_empty() { name } else { format!("{}.{}", id, name) };
+
+            for &ext in ext {
+                match source.read(&file_id, ext) {
+                    Ok(content) => self.add(&file_id, ext, &content)?,
+                    Err(err) if err.kind() == SourceErrorKind::NotFound => (),
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds all files of a directory of the file system, recursively.
+    ///
+    /// Files are given ids relative to this directory, as a
+    /// [`FileSystem`](super::FileSystem) source would.
+    pub fn add_dir<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.add_dir_inner(path.as_ref(), "")
+    }
+
+    fn add_dir_inner(&mut self, path: &Path, id: &str) -> io::Result<()> {
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+
+            let stem = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(stem) => stem,
+                None => continue,
+            };
+            let this_id = if id.is_empty() { stem.to_owned() } else { format!("{}.{}", id, stem) };
+
+            if path.is_dir() {
+                self.add_dir_inner(&path, &this_id)?;
+            } else if path.is_file() {
+                let ext = match super::filesystem::extension_of(&path) {
+                    Some(ext) => ext,
+                    None => continue,
+                };
+                self.add(&this_id, ext, &fs::read(&path)?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of files added.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if no files were added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Writes the pack.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(self.files.len() as u32).to_le_bytes())?;
+
+        let mut offset = 0u64;
+        for ((id, ext), (compression, content, size)) in &self.files {
+            write_string(&mut writer, id)?;
+            write_string(&mut writer, ext)?;
+            writer.write_all(&[compression.to_byte()])?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&(content.len() as u64).to_le_bytes())?;
+            writer.write_all(&size.to_le_bytes())?;
+            offset += content.len() as u64;
+        }
+
+        for (_, content, _) in self.files.values() {
+            writer.write_all(content)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Writes the pack to a file, which is created or replaced.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = fs::File::create(path)?;
+        self.write(io::BufWriter::new(file))
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    fmt,
+    io::{self, Read},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Asset,
+    AssetCache,
+    Compound,
+    hot_reloading::{Ext, LoadFn, LocalCache, ReloadFn, dependencies::Dependencies, load, reload},
+    utils::{DepsRecord, HashMap, Key, Mutex, OwnedKey, Private, PrivateMarker},
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, SwappableSource, Version, WritableSource};
+
+
+/// A [`Source`] that enables hot-reloading for any other source by polling
+/// it.
+///
+/// Hot-reloading of a [`FileSystem`](super::FileSystem) relies on
+/// notifications of the operating system, which are not available for most
+/// sources (archives, databases, network...). This source instead remembers
+/// the files of the assets loaded from it and checks them for changes when
+/// [`AssetCache::hot_reload`](AssetCache#method.hot_reload-1) is called.
+///
+/// A file is considered changed when its [`Version`] changes, see
+/// [`Source::version`]. Directories are read again to find added and removed files.
+///
+/// Polling can be expensive, so files are checked at most once per interval,
+/// which is one second by default.
+///
+/// ## Usage
+///
+/// ```
+/// use assets_manager::{Asset, AssetCache, loader, source::{Memory, Polling}};
+/// use std::time::Duration;
+///
+/// struct Greeting(String);
+///
+/// impl From<String> for Greeting {
+///     fn from(s: String) -> Greeting {
+///         Greeting(s)
+///     }
+/// }
+///
+/// impl Asset for Greeting {
+///     const EXTENSION: &'static str = "txt";
+///     type Loader = loader::LoadFrom<String, loader::StringLoader>;
+/// }
+///
+/// let memory = Memory::new();
+/// memory.insert("common.greeting", "txt", "Hello World!");
+///
+/// let source = Polling::new(memory).with_interval(Duration::from_secs(0));
+/// let cache = AssetCache::with_source(source);
+/// let greeting = cache.load::<Greeting>("common.greeting")?;
+///
+/// cache.source().inner().insert("common.greeting", "txt", "Hi!");
+///
+/// // Call this regularly, eg once per frame
+/// cache.hot_reload();
+/// assert_eq!(greeting.read().0, "Hi!");
+/// # Ok::<(), assets_manager::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "hot-reloading")))]
+pub struct Polling<S> {
+    source: S,
+    interval: Duration,
+    updates: Mutex<Vec<Update<S>>>,
+    state: Mutex<State<S>>,
+}
+
+impl<S> Polling<S> {
+    /// Creates a new `Polling` source, checking files at most once per second.
+    #[inline]
+    pub fn new(source: S) -> Polling<S> {
+        Polling {
+            source,
+            interval: Duration::from_secs(1),
+            updates: Mutex::new(Vec::new()),
+            state: Mutex::new(State::new()),
+        }
+    }
+
+    /// Sets the minimum interval between two checks of the files.
+    #[inline]
+    pub fn with_interval(mut self, interval: Duration) -> Polling<S> {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the minimum interval between two checks of the files.
+    #[inline]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S> fmt::Debug for Polling<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Polling")
+            .field("source", &self.source)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Source> Source for Polling<S> {
+    #[inline]
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.source.read(id, ext)
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.source.metadata(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.version(id, ext)
+    }
+
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.source.open(id, ext)
+    }
+
+    fn _add_asset<A: Asset, P: PrivateMarker>(&self, id: &str) {
+        // Files are checked now so changes made before the next poll are seen
+        let files = A::EXTENSIONS.iter().map(|&ext| (ext.into(), self.source.version(id, ext).ok())).collect();
+        let update = Update::Asset(id.into(), OwnedKey::new::<A>(id.into()), load::<A>, files);
+        self.updates.lock().push(update);
+    }
+
+    fn _add_file<A: Asset, P: PrivateMarker>(&self, key: &str, id: &str, ext: &str) {
+        let files = vec![(ext.to_owned().into(), self.source.version(id, ext).ok())];
+        let update = Update::Asset(id.into(), OwnedKey::new::<A>(key.into()), load::<A>, files);
+        self.updates.lock().push(update);
+    }
+
+    fn _add_dir<A: Asset, P: PrivateMarker>(&self, id: &str) {
+        let entries = read_dir(&self.source, id, A::EXTENSIONS).unwrap_or_default();
+        let update = Update::Dir(id.into(), TypeId::of::<A>(), WatchedDir {
+            load: load::<A>,
+            ext: A::EXTENSIONS,
+            entries,
+        });
+        self.updates.lock().push(update);
+    }
+
+    fn _clear<P: PrivateMarker>(&mut self) {
+        self.updates.lock().clear();
+        self.state.lock().clear();
+    }
+
+    fn _add_compound<A: Compound, P: PrivateMarker>(&self, id: &str, deps: DepsRecord) {
+        let key = OwnedKey::new::<A>(id.into());
+        let update = Update::Compound(key, deps, reload::<A, Polling<S>>);
+        self.updates.lock().push(update);
+    }
+
+    #[inline]
+    fn _support_hot_reloading<P: PrivateMarker>(&self) -> bool {
+        true
+    }
+}
+
+impl<S: WritableSource> WritableSource for Polling<S> {
+    #[inline]
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        self.source.write(id, ext, content)
+    }
+
+    #[inline]
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        self.source.remove(id, ext)
+    }
+}
+
+impl<S: Source> AssetCache<Polling<S>> {
+    /// Reloads changed assets.
+    ///
+    /// The files of the assets loaded from the cache are checked for changes,
+    /// unless they were already checked less than the source's interval ago.
+    /// This function should be called regularly, eg once per frame.
+    ///
+    /// Unlike with a [`FileSystem`](crate::source::FileSystem), this function
+    /// does the whole work of finding changed files, so it can be slow if
+    /// many assets are loaded.
+    pub fn hot_reload(&self) {
+        self.source().poll(self, false);
+    }
+}
+
+impl<S: Source> AssetCache<Polling<SwappableSource<S>>> {
+    /// Replaces the inner source of the cache, and returns the previous one.
+    ///
+    /// Unlike [`set_source`](AssetCache::set_source), this only requires a
+    /// shared reference to the cache. Files are checked immediately, and
+    /// assets whose content changed are reloaded from the new source. Assets
+    /// whose files do not exist in the new source keep their current value.
+    pub fn swap_source(&self, source: S) -> S {
+        let old = self.source().inner().swap(source);
+        self.source().poll(self, true);
+        old
+    }
+}
+
+
+/// A message registering something to watch.
+///
+/// The invariant is that the `TypeId` is the same as the one of the value
+/// returned by the `LoadFn`.
+///
+/// Assets are given the id of their files and the key of their cache entry,
+/// which usually have the same id.
+enum Update<S> {
+    Asset(Arc<str>, OwnedKey, LoadFn, Vec<(Cow<'static, str>, Option<Version>)>),
+    Dir(Arc<str>, TypeId, WatchedDir),
+    Compound(OwnedKey, DepsRecord, ReloadFn<Polling<S>>),
+}
+
+/// A watched file and the cache entries loaded from it.
+struct WatchedFile {
+    version: Option<Version>,
+    types: Vec<(OwnedKey, LoadFn)>,
+}
+
+/// A watched directory for a given type, with its last known content.
+struct WatchedDir {
+    load: LoadFn,
+    ext: Ext,
+    entries: Vec<String>,
+}
+
+struct State<S> {
+    last_poll: Option<Instant>,
+    files: HashMap<(Arc<str>, Cow<'static, str>), WatchedFile>,
+    dirs: HashMap<OwnedKey, WatchedDir>,
+    deps: Dependencies<Polling<S>>,
+}
+
+impl<S> State<S> {
+    fn new() -> Self {
+        State {
+            last_poll: None,
+            files: HashMap::new(),
+            dirs: HashMap::new(),
+            deps: Dependencies::new(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.files.clear();
+        self.dirs.clear();
+        self.deps = Dependencies::new();
+    }
+}
+
+impl<S: Source> State<S> {
+    fn add_asset(&mut self, id: Arc<str>, key: OwnedKey, load: LoadFn, files: Vec<(Cow<'static, str>, Option<Version>)>) {
+        for (ext, version) in files {
+            let file = self.files.entry((id.clone(), ext)).or_insert(WatchedFile {
+                version,
+                types: Vec::new(),
+            });
+
+            if file.types.iter().all(|(k, _)| *k != key) {
+                file.types.push((key.clone(), load));
+            }
+        }
+    }
+
+    fn apply(&mut self, update: Update<S>) {
+        match update {
+            Update::Asset(id, key, load, files) => self.add_asset(id, key, load, files),
+            Update::Dir(id, type_id, dir) => {
+                self.dirs.entry(OwnedKey::new_with(id, type_id)).or_insert(dir);
+            },
+            Update::Compound(key, deps, reload) => self.deps.insert(key, deps.0, Some(reload)),
+        }
+    }
+
+    /// Checks the watched files and directories and collect the changes.
+    fn poll(&mut self, source: &S, cache: &mut LocalCache) {
+        for ((id, ext), file) in self.files.iter_mut() {
+            let version = source.version(id, ext).ok();
+            if version == file.version {
+                continue;
+            }
+            file.version = version;
+
+            // Removed files are not reloaded
+            if file.version.is_none() {
+                continue;
+            }
+
+            let content = match source.read(id, ext) {
+                Ok(content) => content,
+                Err(err) => {
+                    log::warn!("Error reading \"{}\": {}", id, err);
+                    continue;
+                },
+            };
+
+            for (key, load) in &file.types {
+                if let Some(asset) = load(content.clone(), ext, id, id) {
+                    // Safety: the `TypeId` was given with the `LoadFn`
+                    unsafe { cache.reload(key.clone(), asset) };
+                }
+            }
+        }
+
+        let mut added = Vec::new();
+
+        for (key, dir) in self.dirs.iter_mut() {
+            let entries = match read_dir(source, key.id(), dir.ext) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    log::warn!("Error reading directory \"{}\": {}", key.id(), err);
+                    continue;
+                },
+            };
+
+            for name in entries.iter().filter(|e| dir.entries.binary_search(e).is_err()) {
+                let id = join_id(key.id(), name);
+
+                for &ext in dir.ext {
+                    if let Ok(content) = source.read(&id, ext) {
+                        if let Some(asset) = (dir.load)(content, ext, &id, &id) {
+                            let asset_key = OwnedKey::new_with(id.clone(), Key::type_id(key));
+                            // Safety: the `TypeId` was given with the `LoadFn`
+                            unsafe { cache.reload(asset_key.clone(), asset) };
+                            cache.add(key.clone(), id.clone());
+                            let files = dir.ext.iter().map(|&ext| (ext.into(), source.version(&id, ext).ok())).collect();
+                            added.push((id, asset_key, dir.load, files));
+                        }
+                        break;
+                    }
+                }
+            }
+
+            for name in dir.entries.iter().filter(|e| entries.binary_search(e).is_err()) {
+                cache.remove(key.clone(), join_id(key.id(), name));
+            }
+
+            dir.entries = entries;
+        }
+
+        for (id, key, load, files) in added {
+            self.add_asset(id, key, load, files);
+        }
+    }
+}
+
+impl<S: Source> Polling<S> {
+    fn poll(&self, cache: &AssetCache<Polling<S>>, force: bool) {
+        let mut state = self.state.lock();
+
+        let now = Instant::now();
+        if let Some(last_poll) = state.last_poll {
+            if !force && now.duration_since(last_poll) < self.interval {
+                return;
+            }
+        }
+        state.last_poll = Some(now);
+
+        self.source._refresh::<Private>();
+
+        let updates = std::mem::take(&mut *self.updates.lock());
+        for update in updates {
+            state.apply(update);
+        }
+
+        let mut local_cache = LocalCache::new();
+        state.poll(&self.source, &mut local_cache);
+        local_cache.update(&mut state.deps, cache);
+    }
+}
+
+/// Reads a directory, returning sorted entries.
+fn read_dir<S: Source>(source: &S, id: &str, ext: &[&str]) -> io::Result<Vec<String>> {
+    let mut entries = source.read_dir(id, ext)?;
+    entries.sort();
+    entries.dedup();
+    Ok(entries)
+}
+
+fn join_id(dir_id: &str, name: &str) -> Arc<str> {
+    if dir_id.is_empty() {
+        name.into()
+    } else {
+        format!("{}.{}", dir_id, name).into()
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    io::Read,
+};
+
+use super::{is_in_dir, DirEntry, Metadata, Source, SourceError, SourceErrorKind, Version};
+
+
+/// A [`Source`] that dispatches ids to different sources depending on their
+/// prefix.
+///
+/// Each route associates a prefix, such as `ui` or `levels.forest`, to a
+/// source. An id is read from the source of the longest prefix that contains
+/// it, and only from this one. A route with an empty prefix catches all ids
+/// that do not match another route.
+///
+/// Ids are given unchanged to the inner sources. Use a
+/// [`Mounted`](super::Mounted) source as a route to remove the prefix.
+///
+/// Unlike an [`Overlay`](super::Overlay), which tries each of its layers in
+/// turn, only one source is accessed for each id.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{FileSystem, Memory, Router}};
+///
+/// let source = Router::new()
+///     .with_route("", FileSystem::new("assets")?)
+///     .with_route("levels", FileSystem::new("levels")?)
+///     .with_route("generated", Memory::new());
+///
+/// // `levels.forest` is read from the "levels" directory, and `ui.button`
+/// // from the "assets" directory.
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<(String, Box<dyn Source + Send + Sync>)>,
+}
+
+impl Router {
+    /// Creates a new `Router` without any route.
+    #[inline]
+    pub fn new() -> Router {
+        Router { routes: Vec::new() }
+    }
+
+    /// Adds a route, replacing the previous one with the same prefix.
+    pub fn insert<P, S>(&mut self, prefix: P, source: S)
+    where
+        P: Into<String>,
+        S: Source + Send + Sync + 'static,
+    {
+        let prefix = prefix.into();
+        let source = Box::new(source);
+
+        match self.routes.iter_mut().find(|(p, _)| *p == prefix) {
+            Some(route) => route.1 = source,
+            None => self.routes.push((prefix, source)),
+        }
+    }
+
+    /// Adds a route, replacing the previous one with the same prefix.
+    ///
+    /// This is the same as [`insert`](Self::insert), but it can be chained.
+    pub fn with_route<P, S>(mut self, prefix: P, source: S) -> Router
+    where
+        P: Into<String>,
+        S: Source + Send + Sync + 'static,
+    {
+        self.insert(prefix, source);
+        self
+    }
+
+    /// Removes the route with the given prefix and returns its source.
+    pub fn remove(&mut self, prefix: &str) -> Option<Box<dyn Source + Send + Sync>> {
+        let index = self.routes.iter().position(|(p, _)| p == prefix)?;
+        Some(self.routes.remove(index).1)
+    }
+
+    /// Returns the number of routes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Returns `true` if there is no route.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Returns the source an id is dispatched to.
+    pub fn route(&self, id: &str) -> Option<&(dyn Source + Send + Sync)> {
+        self.routes.iter()
+            .filter(|(prefix, _)| is_in_dir(id, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, source)| &**source)
+    }
+
+    fn route_or_not_found(&self, id: &str) -> Result<&(dyn Source + Send + Sync), SourceError> {
+        self.route(id).ok_or_else(|| SourceErrorKind::NotFound.into())
+    }
+
+    /// Returns the names of the subdirectories of `id` that lead to a route.
+    fn child_routes<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.routes.iter().filter_map(move |(prefix, _)| {
+            let rest = if id.is_empty() { &**prefix } else { prefix.strip_prefix(id)?.strip_prefix('.')? };
+            rest.split('.').next().filter(|name| !name.is_empty())
+        })
+    }
+}
+
+impl Source for Router {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.route_or_not_found(id)?.read(id, ext)
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        let result = self.route_or_not_found(id).and_then(|source| source.read_dir(id, ext));
+
+        // Parent directories of routes always exist
+        match result {
+            Err(err) if err.kind() == SourceErrorKind::NotFound && self.child_routes(id).next().is_some() => Ok(Vec::new()),
+            result => result,
+        }
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let result = self.route_or_not_found(id).and_then(|source| source.read_dir_entries(id, ext));
+
+        let (mut entries, mut found) = match result {
+            Ok(entries) => (entries, true),
+            Err(err) if err.kind() == SourceErrorKind::NotFound => (Vec::new(), false),
+            Err(err) => return Err(err),
+        };
+
+        for name in self.child_routes(id) {
+            found = true;
+            let entry = DirEntry::Directory(name.to_owned());
+            if !entries.contains(&entry) {
+                entries.push(entry);
+            }
+        }
+
+        if found {
+            Ok(entries)
+        } else {
+            Err(SourceErrorKind::NotFound.into())
+        }
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.route_or_not_found(id)?.metadata(id, ext)
+    }
+
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.route_or_not_found(id)?.version(id, ext)
+    }
+
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.route_or_not_found(id)?.open(id, ext)
+    }
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefixes: Vec<_> = self.routes.iter().map(|(prefix, _)| prefix).collect();
+        f.debug_struct("Router").field("routes", &prefixes).finish()
+    }
+}
@@ -0,0 +1,630 @@
+use std::{
+    borrow::Cow,
+    convert::TryInto,
+    fmt,
+    io,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use super::{
+    DirEntry, Fetched, Metadata, RemoteSource, Source, SourceError, Validators, validate_id,
+    http::{read_body, to_io_error},
+};
+
+
+/// A [`Source`] to load assets from an S3-compatible object storage.
+///
+/// Each asset is stored as an object in a bucket, under an optional prefix.
+/// For example, with the prefix `assets`, the asset `common.position` with
+/// extension `ron` is the object `assets/common/position.ron`.
+///
+/// This works with Amazon S3 and with services with a compatible API, such as
+/// Google Cloud Storage (with HMAC keys), MinIO or Cloudflare R2. Requests are
+/// signed with AWS Signature Version 4 when credentials are given, and are
+/// anonymous otherwise, which is enough for public buckets.
+///
+/// Requests are blocking, so you may want to load assets from another thread.
+///
+/// ## Caching
+///
+/// Downloaded files are not stored. Use [`CachedRemote`](super::CachedRemote)
+/// to keep a local copy of them.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::{S3, S3Credentials}};
+///
+/// let source = S3::builder("https://s3.eu-west-3.amazonaws.com", "my-game")
+///     .region("eu-west-3")
+///     .prefix("assets")
+///     .credentials(S3Credentials::from_env()?)
+///     .build();
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "s3")))]
+#[derive(Clone)]
+pub struct S3 {
+    /// `scheme://host[:port]`
+    origin: String,
+    host: String,
+    /// Path of the bucket root, empty with virtual-hosted-style requests
+    bucket_path: String,
+    bucket: String,
+    prefix: String,
+    region: String,
+    credentials: Option<S3Credentials>,
+    agent: ureq::Agent,
+}
+
+impl S3 {
+    /// Creates a builder to configure a new `S3` source, given the endpoint of
+    /// the service (eg `https://s3.us-east-1.amazonaws.com`) and the name of
+    /// the bucket.
+    pub fn builder<E: Into<String>, B: Into<String>>(endpoint: E, bucket: B) -> S3Builder {
+        S3Builder {
+            endpoint: endpoint.into(),
+            bucket: bucket.into(),
+            prefix: String::new(),
+            region: String::from("us-east-1"),
+            credentials: None,
+            path_style: true,
+            timeout: Some(Duration::from_secs(30)),
+        }
+    }
+
+    /// Gets the name of the bucket.
+    #[inline]
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// Gets the prefix of the keys of the objects.
+    #[inline]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Gets the region of the bucket.
+    #[inline]
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Returns the key of the (eventual) object represented by an id and an
+    /// extension.
+    pub fn key_of(&self, id: &str, ext: &str) -> String {
+        let mut key = self.dir_key(id);
+        key.pop();
+        if !ext.is_empty() {
+            key.push('.');
+            key.push_str(ext);
+        }
+        key
+    }
+
+    /// Returns the prefix of the keys of objects in a directory, ending with
+    /// a `/` unless it is empty.
+    fn dir_key(&self, id: &str) -> String {
+        let mut key = self.prefix.clone();
+        if !key.is_empty() {
+            key.push('/');
+        }
+        if !id.is_empty() {
+            for component in id.split('.') {
+                key.push_str(component);
+                key.push('/');
+            }
+        }
+        key
+    }
+
+    fn request(&self, method: &str, key: &str, query: &[(&str, &str)]) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
+        let mut path = self.bucket_path.clone();
+        if !key.is_empty() || path.is_empty() {
+            path.push('/');
+            uri_encode(key, false, &mut path);
+        }
+
+        let mut query: Vec<_> = query.iter().map(|&(name, value)| {
+            let mut pair = String::new();
+            uri_encode(name, true, &mut pair);
+            pair.push('=');
+            uri_encode(value, true, &mut pair);
+            pair
+        }).collect();
+        query.sort();
+        let query = query.join("&");
+
+        let mut url = format!("{}{}", self.origin, path);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+
+        let mut request = match method {
+            "HEAD" => self.agent.head(&url),
+            _ => self.agent.get(&url),
+        };
+
+        if let Some(credentials) = &self.credentials {
+            let headers = sign(credentials, &self.region, SystemTime::now(), &SignedRequest {
+                method,
+                host: &self.host,
+                path: &path,
+                query: &query,
+            });
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+        }
+
+        request
+    }
+
+    fn list(&self, prefix: &str, token: Option<&str>) -> io::Result<String> {
+        let mut query = vec![("list-type", "2"), ("delimiter", "/"), ("prefix", prefix)];
+        if let Some(token) = token {
+            query.push(("continuation-token", token));
+        }
+
+        // Listing uses the root of the bucket
+        let mut response = self.request("GET", "", &query).call().map_err(to_io_error)?;
+        let body = read_body(&mut response)?;
+        String::from_utf8(body).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Lists the names of the objects and of the subdirectories directly under
+    /// `prefix`, going through all pages.
+    fn list_dir(&self, prefix: &str) -> io::Result<(Vec<String>, Vec<String>)> {
+        let mut files = Vec::new();
+        let mut dirs = Vec::new();
+        let mut token = None;
+
+        loop {
+            let list = self.list(prefix, token.as_deref())?;
+
+            // Subdirectories are listed in `CommonPrefixes`, which does not
+            // contain `Key` elements
+            for key in xml_elements(&list, "Key") {
+                match key.strip_prefix(prefix) {
+                    Some(name) if !name.is_empty() && !name.contains('/') => files.push(name.to_owned()),
+                    _ => (),
+                }
+            }
+            for common in xml_elements(&list, "CommonPrefixes") {
+                for dir in xml_elements(&common, "Prefix") {
+                    match dir.strip_prefix(prefix).and_then(|d| d.strip_suffix('/')) {
+                        Some(name) if !name.is_empty() && !name.contains('/') => dirs.push(name.to_owned()),
+                        _ => (),
+                    }
+                }
+            }
+
+            match xml_elements(&list, "IsTruncated").first().map(String::as_str) {
+                Some("true") => token = xml_elements(&list, "NextContinuationToken").pop(),
+                _ => break,
+            }
+            if token.is_none() {
+                break;
+            }
+        }
+
+        Ok((files, dirs))
+    }
+}
+
+impl Source for S3 {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        validate_id(id, ext)?;
+        let key = self.key_of(id, ext);
+        let mut response = self.request("GET", &key, &[]).call().map_err(to_io_error)?;
+        Ok(Cow::Owned(read_body(&mut response)?))
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        validate_id(id, "")?;
+        let (files, _) = self.list_dir(&self.dir_key(id))?;
+
+        Ok(files.iter()
+            .map(|name| name.rsplit_once('.').unwrap_or((name, "")))
+            .filter(|(_, file_ext)| ext.contains(file_ext))
+            .map(|(stem, _)| stem.to_owned())
+            .collect()
+        )
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        validate_id(id, "")?;
+        let (files, dirs) = self.list_dir(&self.dir_key(id))?;
+
+        // Directories only exist through the objects they contain
+        if !id.is_empty() && files.is_empty() && dirs.is_empty() {
+            return Err(io::ErrorKind::NotFound.into());
+        }
+
+        let mut entries: Vec<_> = files.iter()
+            .map(|name| name.rsplit_once('.').unwrap_or((name, "")))
+            .filter(|(_, file_ext)| ext.contains(file_ext))
+            .map(|(stem, _)| DirEntry::File(stem.to_owned()))
+            .collect();
+        entries.extend(dirs.into_iter().map(DirEntry::Directory));
+        Ok(entries)
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        validate_id(id, ext)?;
+        let key = self.key_of(id, ext);
+        let response = self.request("HEAD", &key, &[]).call().map_err(to_io_error)?;
+
+        let header = |name| response.headers().get(name)?.to_str().ok();
+        let len = header("content-length").and_then(|len| len.parse().ok()).unwrap_or(0);
+        let modified = header("last-modified").and_then(parse_http_date);
+
+        Ok(Metadata { len, modified })
+    }
+}
+
+impl RemoteSource for S3 {
+    fn fetch(&self, id: &str, ext: &str, validators: &Validators) -> Result<Fetched, SourceError> {
+        validate_id(id, ext)?;
+        let key = self.key_of(id, ext);
+        let mut request = self.request("GET", &key, &[]);
+        if let Some(etag) = &validators.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+
+        let mut response = request.call().map_err(to_io_error)?;
+        if response.status() == 304 {
+            return Ok(Fetched::NotModified);
+        }
+
+        let header = |name| {
+            let value = response.headers().get(name)?;
+            value.to_str().ok().map(str::to_owned)
+        };
+        let validators = Validators {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+        };
+
+        let content = read_body(&mut response)?;
+        Ok(Fetched::Modified { content, validators })
+    }
+}
+
+impl fmt::Debug for S3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3")
+            .field("origin", &self.origin)
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("region", &self.region)
+            .finish()
+    }
+}
+
+/// A builder to configure an [`S3`] source.
+///
+/// It is created with [`S3::builder`].
+#[cfg_attr(docsrs, doc(cfg(feature = "s3")))]
+#[derive(Debug, Clone)]
+pub struct S3Builder {
+    endpoint: String,
+    bucket: String,
+    prefix: String,
+    region: String,
+    credentials: Option<S3Credentials>,
+    path_style: bool,
+    timeout: Option<Duration>,
+}
+
+impl S3Builder {
+    /// Sets the prefix of the keys of the objects, like a root directory.
+    ///
+    /// The default is no prefix.
+    pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = prefix.into().trim_matches('/').to_owned();
+        self
+    }
+
+    /// Sets the region of the bucket, used to sign requests.
+    ///
+    /// The default is `us-east-1`. Google Cloud Storage uses `auto`.
+    pub fn region<R: Into<String>>(mut self, region: R) -> Self {
+        self.region = region.into();
+        self
+    }
+
+    /// Sets the credentials used to sign requests.
+    ///
+    /// Without credentials, requests are anonymous.
+    pub fn credentials(mut self, credentials: S3Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Sets whether the bucket is given in the path of URLs
+    /// (`https://endpoint/bucket/key`) or in the host name
+    /// (`https://bucket.endpoint/key`).
+    ///
+    /// The default is `true`, which is supported by most services.
+    pub fn path_style(mut self, path_style: bool) -> Self {
+        self.path_style = path_style;
+        self
+    }
+
+    /// Sets the maximum duration of a request, including reading the body.
+    ///
+    /// `None` disables the timeout. The default is 30 seconds.
+    pub fn timeout<T: Into<Option<Duration>>>(mut self, timeout: T) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Creates the `S3` source.
+    pub fn build(self) -> S3 {
+        let endpoint = self.endpoint.trim_end_matches('/');
+        let (scheme, host) = endpoint.split_once("://").unwrap_or(("https", endpoint));
+
+        let (host, bucket_path) = if self.path_style {
+            (host.to_owned(), format!("/{}", self.bucket))
+        } else {
+            (format!("{}.{}", self.bucket, host), String::new())
+        };
+
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+
+        S3 {
+            origin: format!("{}://{}", scheme, host),
+            host,
+            bucket_path,
+            bucket: self.bucket,
+            prefix: self.prefix,
+            region: self.region,
+            credentials: self.credentials,
+            agent,
+        }
+    }
+}
+
+/// Credentials to sign requests to an [`S3`] source.
+#[cfg_attr(docsrs, doc(cfg(feature = "s3")))]
+#[derive(Clone)]
+pub struct S3Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl S3Credentials {
+    /// Creates credentials from an access key id and a secret access key.
+    pub fn new<I: Into<String>, S: Into<String>>(access_key_id: I, secret_access_key: S) -> S3Credentials {
+        S3Credentials {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+        }
+    }
+
+    /// Sets the session token of temporary credentials.
+    pub fn with_session_token<T: Into<String>>(mut self, session_token: T) -> S3Credentials {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Reads credentials from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`
+    /// and `AWS_SESSION_TOKEN` environment variables.
+    ///
+    /// Returns an error if one of the first two is not set.
+    pub fn from_env() -> io::Result<S3Credentials> {
+        let var = |name| std::env::var(name).map_err(|err| {
+            io::Error::new(io::ErrorKind::NotFound, format!("{}: {}", name, err))
+        });
+
+        let credentials = S3Credentials::new(var("AWS_ACCESS_KEY_ID")?, var("AWS_SECRET_ACCESS_KEY")?);
+        Ok(match std::env::var("AWS_SESSION_TOKEN") {
+            Ok(token) => credentials.with_session_token(token),
+            Err(_) => credentials,
+        })
+    }
+
+    /// Gets the access key id.
+    #[inline]
+    pub fn access_key_id(&self) -> &str {
+        &self.access_key_id
+    }
+}
+
+impl fmt::Debug for S3Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+
+/// The parts of a request that are signed.
+pub(super) struct SignedRequest<'a> {
+    pub(super) method: &'a str,
+    pub(super) host: &'a str,
+    /// Already URI-encoded
+    pub(super) path: &'a str,
+    /// Already URI-encoded and sorted
+    pub(super) query: &'a str,
+}
+
+/// Returns the headers to add to a request to sign it with AWS Signature
+/// Version 4.
+fn sign(credentials: &S3Credentials, region: &str, now: SystemTime, request: &SignedRequest) -> Vec<(&'static str, String)> {
+    let datetime = format_amz_date(now);
+    let date = &datetime[..8];
+    let payload_hash = hex(&Sha256::digest(b""));
+
+    let mut headers = vec![
+        ("host", request.host.to_owned()),
+        ("x-amz-content-sha256", payload_hash.clone()),
+        ("x-amz-date", datetime.clone()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+
+    let canonical = canonical_request(request, &headers, &payload_hash);
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let signature = signature(&credentials.secret_access_key, &datetime, &scope, &canonical);
+
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature,
+    );
+
+    // The host header is set by the HTTP client
+    headers.remove(0);
+    headers.push(("authorization", authorization));
+    headers
+}
+
+/// `headers` must be lowercase and sorted by name.
+pub(super) fn canonical_request(request: &SignedRequest, headers: &[(&str, String)], payload_hash: &str) -> String {
+    let mut canonical = format!("{}\n{}\n{}\n", request.method, request.path, request.query);
+    for (name, value) in headers {
+        canonical.push_str(name);
+        canonical.push(':');
+        canonical.push_str(value.trim());
+        canonical.push('\n');
+    }
+    canonical.push('\n');
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    canonical.push_str(&signed_headers);
+    canonical.push('\n');
+    canonical.push_str(payload_hash);
+    canonical
+}
+
+/// `scope` is `date/region/service/aws4_request`.
+pub(super) fn signature(secret: &str, datetime: &str, scope: &str, canonical_request: &str) -> String {
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        datetime, scope, hex(&Sha256::digest(canonical_request.as_bytes())),
+    );
+
+    let mut key = format!("AWS4{}", secret).into_bytes();
+    for part in scope.split('/') {
+        key = hmac_sha256(&key, part.as_bytes());
+    }
+    hex(&hmac_sha256(&key, string_to_sign.as_bytes()))
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        s.push(DIGITS[(b >> 4) as usize] as char);
+        s.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    s
+}
+
+/// URI-encodes a string as required by AWS. `/` is kept in paths.
+fn uri_encode(s: &str, encode_slash: bool, out: &mut String) {
+    for &b in s.as_bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            b'/' if !encode_slash => out.push('/'),
+            _ => {
+                out.push('%');
+                out.push_str(&format!("{:02X}", b));
+            }
+        }
+    }
+}
+
+/// Returns the text content of the elements with the given name.
+///
+/// This is not a real XML parser, but the responses of S3 are simple enough.
+fn xml_elements(xml: &str, name: &str) -> Vec<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+
+    xml.split(open.as_str()).skip(1).filter_map(|rest| {
+        let content = &rest[..rest.find(close.as_str())?];
+        Some(content
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"))
+    }).collect()
+}
+
+
+/// Formats a time as `YYYYMMDD'T'HHMMSS'Z'`.
+pub(super) fn format_amz_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let secs = secs % 86400;
+    format!("{:04}{:02}{:02}T{:02}{:02}{:02}Z", year, month, day, secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Parses a date like `Wed, 21 Oct 2015 07:28:00 GMT`.
+pub(super) fn parse_http_date(date: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+    let mut parts = date.split_whitespace().skip(1);
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|&m| m == month)? as u32 + 1;
+    let year: i64 = parts.next()?.parse().ok()?;
+
+    let mut hms = parts.next()?.split(':').map(|n| n.parse::<u64>().ok());
+    let (h, m, s) = (hms.next()??, hms.next()??, hms.next()??);
+
+    let days: u64 = days_from_civil(year, month, day).try_into().ok()?;
+    let secs = days * 86400 + h * 3600 + m * 60 + s;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Converts a number of days since the Unix epoch to a date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Converts a date to a number of days since the Unix epoch.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
@@ -0,0 +1,219 @@
+use crate::utils::Mutex;
+
+use rusqlite::{Connection, OptionalExtension};
+
+use std::{
+    borrow::Cow,
+    fmt,
+    io,
+    path::Path,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, WritableSource};
+
+
+fn to_io_error(err: rusqlite::Error) -> io::Error {
+    io::Error::other(err)
+}
+
+/// Quotes an SQL identifier.
+fn quote(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// A [`Source`] that reads files from a table of a SQLite database.
+///
+/// The table must have the following columns:
+/// - `id`: the id of the file, as text
+/// - `ext`: the extension of the file, as text
+/// - `data`: the content of the file, as a blob
+///
+/// Such a table can be created with [`Sqlite::create_table`]. Directories
+/// do not exist by themselves: a directory exists if it contains at least
+/// one file, directly or in a subdirectory. The root directory always exists.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::Sqlite};
+///
+/// let source = Sqlite::open("editor.db", "assets")?;
+/// let cache = AssetCache::with_source(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
+pub struct Sqlite {
+    conn: Mutex<Connection>,
+    table: String,
+}
+
+impl Sqlite {
+    /// Opens the database at the given path, reading files from `table`.
+    pub fn open<P: AsRef<Path>>(path: P, table: &str) -> io::Result<Sqlite> {
+        let conn = Connection::open(path).map_err(to_io_error)?;
+        Ok(Sqlite::from_connection(conn, table))
+    }
+
+    /// Creates a new `Sqlite` source from an existing connection, reading
+    /// files from `table`.
+    pub fn from_connection(conn: Connection, table: &str) -> Sqlite {
+        Sqlite {
+            conn: Mutex::new(conn),
+            table: quote(table),
+        }
+    }
+
+    /// Creates the table of the source if it does not exist.
+    pub fn create_table(&self) -> io::Result<()> {
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {} (id TEXT NOT NULL, ext TEXT NOT NULL, data BLOB NOT NULL, PRIMARY KEY (id, ext))",
+            self.table,
+        );
+        self.conn.lock().execute(&sql, []).map_err(to_io_error)?;
+        Ok(())
+    }
+
+    /// Returns the underlying connection.
+    pub fn into_inner(self) -> Connection {
+        self.conn.into_inner()
+    }
+}
+
+impl Source for Sqlite {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let sql = format!("SELECT data FROM {} WHERE id = ?1 AND ext = ?2", self.table);
+        let conn = self.conn.lock();
+
+        let content: Option<Vec<u8>> = conn
+            .prepare_cached(&sql)
+            .and_then(|mut stmt| stmt.query_row((id, ext), |row| row.get(0)).optional())
+            .map_err(to_io_error)?;
+
+        match content {
+            Some(content) => Ok(Cow::Owned(content)),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        // All ids starting with "dir." are between "dir." and "dir/"
+        let (start, end) = if id.is_empty() {
+            (String::new(), String::from("\u{10FFFF}"))
+        } else {
+            (format!("{}.", id), format!("{}/", id))
+        };
+
+        let sql = format!("SELECT id, ext FROM {} WHERE id >= ?1 AND id < ?2", self.table);
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare_cached(&sql).map_err(to_io_error)?;
+        let rows = stmt
+            .query_map((&start, &end), |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(to_io_error)?;
+
+        let mut exists = id.is_empty();
+        let mut entries = Vec::new();
+
+        for row in rows {
+            let (file_id, file_ext) = row.map_err(to_io_error)?;
+            exists = true;
+
+            let name = &file_id[start.len()..];
+            if !name.contains('.') && ext.contains(&file_ext.as_str()) {
+                entries.push(name.to_owned());
+            }
+        }
+
+        if exists {
+            Ok(entries)
+        } else {
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        let (start, end) = if id.is_empty() {
+            (String::new(), String::from("\u{10FFFF}"))
+        } else {
+            (format!("{}.", id), format!("{}/", id))
+        };
+
+        let sql = format!("SELECT id, ext FROM {} WHERE id >= ?1 AND id < ?2", self.table);
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare_cached(&sql).map_err(to_io_error)?;
+        let rows = stmt
+            .query_map((&start, &end), |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(to_io_error)?;
+
+        let mut exists = id.is_empty();
+        let mut entries = Vec::new();
+
+        for row in rows {
+            let (file_id, file_ext) = row.map_err(to_io_error)?;
+            exists = true;
+
+            let name = &file_id[start.len()..];
+            if let Some((dir, _)) = name.split_once('.') {
+                entries.push(DirEntry::Directory(dir.to_owned()));
+            } else if ext.contains(&file_ext.as_str()) {
+                entries.push(DirEntry::File(name.to_owned()));
+            }
+        }
+
+        if exists {
+            // A directory is found once per file in it
+            entries.sort();
+            entries.dedup();
+            Ok(entries)
+        } else {
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        let sql = format!("SELECT length(data) FROM {} WHERE id = ?1 AND ext = ?2", self.table);
+        let conn = self.conn.lock();
+
+        let len: Option<i64> = conn
+            .prepare_cached(&sql)
+            .and_then(|mut stmt| stmt.query_row((id, ext), |row| row.get(0)).optional())
+            .map_err(to_io_error)?;
+
+        match len {
+            Some(len) => Ok(Metadata::new(len as u64)),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+}
+
+impl WritableSource for Sqlite {
+    /// Writes a file in the table.
+    ///
+    /// This requires a unique constraint on `(id, ext)`, as created by
+    /// [`Sqlite::create_table`].
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        let sql = format!("INSERT OR REPLACE INTO {} (id, ext, data) VALUES (?1, ?2, ?3)", self.table);
+        self.conn.lock().execute(&sql, (id, ext, content)).map_err(to_io_error)?;
+        Ok(())
+    }
+
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        let sql = format!("DELETE FROM {} WHERE id = ?1 AND ext = ?2", self.table);
+        let removed = self.conn.lock().execute(&sql, (id, ext)).map_err(to_io_error)?;
+
+        if removed != 0 {
+            Ok(())
+        } else {
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+}
+
+impl fmt::Debug for Sqlite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sqlite").field("table", &self.table).finish()
+    }
+}
@@ -0,0 +1,153 @@
+use crate::utils::RwLock;
+
+#[cfg(feature = "hot-reloading")]
+use crate::utils::PrivateMarker;
+
+use std::{
+    borrow::Cow,
+    fmt,
+    io::Read,
+    mem,
+};
+
+use super::{DirEntry, Metadata, Source, SourceError, Version, WritableSource};
+
+/// A [`Source`] whose inner source can be replaced through a shared reference.
+///
+/// This enables to switch sources while the cache is in use, for example from
+/// embedded defaults to a downloaded content pack. To switch between
+/// different kinds of sources, use an [`AnySource`](super::AnySource).
+///
+/// Because the inner source can be replaced at any time, files are always
+/// returned as owned bytes.
+///
+/// ## Hot-reloading
+///
+/// When this source is wrapped in a [`Polling`](super::Polling) source,
+/// [`AssetCache::swap_source`](crate::AssetCache#method.swap_source) replaces
+/// the source and reloads the assets already in the cache from the new one.
+/// Otherwise, cached assets keep the value they were loaded with.
+///
+/// ## Usage
+///
+/// ```
+/// # #[cfg(feature = "hot-reloading")]
+/// # {
+/// use assets_manager::{Asset, AssetCache, loader, source::{AnySource, Memory, Polling, SwappableSource}};
+///
+/// struct Greeting(String);
+///
+/// impl From<String> for Greeting {
+///     fn from(s: String) -> Greeting {
+///         Greeting(s)
+///     }
+/// }
+///
+/// impl Asset for Greeting {
+///     const EXTENSION: &'static str = "txt";
+///     type Loader = loader::LoadFrom<String, loader::StringLoader>;
+/// }
+///
+/// let defaults = Memory::new();
+/// defaults.insert("common.greeting", "txt", "Hello World!");
+///
+/// let source = Polling::new(SwappableSource::new(AnySource::new(defaults)));
+/// let cache = AssetCache::with_source(source);
+/// let greeting = cache.load::<Greeting>("common.greeting")?;
+///
+/// let content_pack = Memory::new();
+/// content_pack.insert("common.greeting", "txt", "Hi!");
+/// cache.swap_source(AnySource::new(content_pack));
+/// assert_eq!(greeting.read().0, "Hi!");
+/// # }
+/// # Ok::<(), assets_manager::Error>(())
+/// ```
+pub struct SwappableSource<S> {
+    source: RwLock<S>,
+}
+
+impl<S> SwappableSource<S> {
+    /// Creates a new `SwappableSource` from an initial source.
+    #[inline]
+    pub fn new(source: S) -> SwappableSource<S> {
+        SwappableSource {
+            source: RwLock::new(source),
+        }
+    }
+
+    /// Replaces the inner source, and returns the previous one.
+    ///
+    /// Assets already in the cache are not reloaded, see
+    /// [`AssetCache::swap_source`](crate::AssetCache#method.swap_source) for
+    /// this.
+    #[inline]
+    pub fn swap(&self, source: S) -> S {
+        mem::replace(&mut *self.source.write(), source)
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source.into_inner()
+    }
+}
+
+impl<S: Source> Source for SwappableSource<S> {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        let source = self.source.read();
+        Ok(Cow::Owned(source.read(id, ext)?.into_owned()))
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read().read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.source.read().read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.source.read().read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.source.read().metadata(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.source.read().version(id, ext)
+    }
+
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.source.read().open(id, ext)
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    fn _refresh<P: PrivateMarker>(&self) {
+        self.source.read()._refresh::<P>()
+    }
+}
+
+impl<S: WritableSource> WritableSource for SwappableSource<S> {
+    #[inline]
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        self.source.read().write(id, ext, content)
+    }
+
+    #[inline]
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        self.source.read().remove(id, ext)
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for SwappableSource<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwappableSource").field("source", &*self.source.read()).finish()
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::SystemTime,
+};
+
+use super::{DirEntry, FileSystem, Metadata, Source, SourceError, Version, WritableSource};
+
+
+/// A [`Source`] backed by a temporary directory, to write tests.
+///
+/// The directory is created in the system's temporary directory, populated
+/// with the given files, and removed when the source is dropped. This enables
+/// to test code that loads assets without fixture files in the repository.
+///
+/// ## Hot-reloading
+///
+/// This source does not support hot-reloading by itself, but it is writable,
+/// so files can be modified during a test. To test hot-reloading, create a
+/// [`FileSystem`] watching the directory with [`file_system`], or wrap this
+/// source in a [`Polling`](super::Polling) source.
+///
+/// [`file_system`]: Self::file_system
+///
+/// ## Usage
+///
+/// ```
+/// use assets_manager::source::{Source, TempSource};
+///
+/// let source = TempSource::builder()
+///     .file("common.greeting", "txt", "Hello World!")
+///     .file("common.numbers.one", "txt", "1")
+///     .build()?;
+///
+/// assert_eq!(&*source.read("common.greeting", "txt")?, b"Hello World!");
+/// assert_eq!(source.read_dir("common.numbers", &["txt"])?, ["one"]);
+///
+/// // The directory is removed here
+/// drop(source);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub struct TempSource {
+    path: PathBuf,
+    fs: FileSystem,
+}
+
+impl TempSource {
+    /// Creates a new `TempSource` with an empty directory.
+    pub fn new() -> io::Result<TempSource> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let nanos = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+        let base = std::env::temp_dir();
+
+        loop {
+            let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let path = base.join(format!("assets_manager-{}-{}-{}", std::process::id(), count, nanos));
+
+            match fs::create_dir(&path) {
+                Ok(()) => {
+                    let fs = match FileSystem::without_hot_reloading(&path) {
+                        Ok(fs) => fs,
+                        Err(err) => {
+                            let _ = fs::remove_dir_all(&path);
+                            return Err(err);
+                        }
+                    };
+                    return Ok(TempSource { path, fs });
+                },
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Creates a builder to populate a `TempSource`.
+    #[inline]
+    pub fn builder() -> TempSourceBuilder {
+        TempSourceBuilder::new()
+    }
+
+    /// Returns the path of the temporary directory.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Creates a `FileSystem` source from the temporary directory.
+    ///
+    /// This source is watched if feature `hot-reloading` is enabled, which
+    /// enables to test hot-reloading. It must not be used after the
+    /// `TempSource` is dropped.
+    pub fn file_system(&self) -> io::Result<FileSystem> {
+        FileSystem::new(&self.path)
+    }
+
+    /// Creates a directory given its id.
+    ///
+    /// This is useful to test empty directories.
+    pub fn create_dir(&self, id: &str) -> io::Result<()> {
+        super::validate_id(id, "")?;
+
+        let mut path = self.fs.root().to_owned();
+        path.extend(id.split('.').filter(|s| !s.is_empty()));
+        fs::create_dir_all(path)
+    }
+}
+
+impl Source for TempSource {
+    #[inline]
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        self.fs.read(id, ext)
+    }
+
+    #[inline]
+    fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.fs.read_dir(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_recursive(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+        self.fs.read_dir_recursive(id, ext)
+    }
+
+    #[inline]
+    fn read_dir_entries(&self, id: &str, ext: &[&str]) -> Result<Vec<DirEntry>, SourceError> {
+        self.fs.read_dir_entries(id, ext)
+    }
+
+    #[inline]
+    fn metadata(&self, id: &str, ext: &str) -> Result<Metadata, SourceError> {
+        self.fs.metadata(id, ext)
+    }
+
+    #[inline]
+    fn open(&self, id: &str, ext: &str) -> Result<Box<dyn Read + Send>, SourceError> {
+        self.fs.open(id, ext)
+    }
+
+    #[inline]
+    fn version(&self, id: &str, ext: &str) -> Result<Version, SourceError> {
+        self.fs.version(id, ext)
+    }
+}
+
+impl WritableSource for TempSource {
+    #[inline]
+    fn write(&self, id: &str, ext: &str, content: &[u8]) -> Result<(), SourceError> {
+        self.fs.write(id, ext, content)
+    }
+
+    #[inline]
+    fn remove(&self, id: &str, ext: &str) -> Result<(), SourceError> {
+        self.fs.remove(id, ext)
+    }
+}
+
+impl Drop for TempSource {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+impl fmt::Debug for TempSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TempSource").field("path", &self.path).finish()
+    }
+}
+
+/// A builder to populate a [`TempSource`].
+///
+/// It is created with [`TempSource::builder`].
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+#[derive(Debug, Clone, Default)]
+pub struct TempSourceBuilder {
+    files: Vec<(String, String, Vec<u8>)>,
+    dirs: Vec<String>,
+}
+
+impl TempSourceBuilder {
+    /// Creates a new empty builder.
+    #[inline]
+    pub fn new() -> TempSourceBuilder {
+        TempSourceBuilder::default()
+    }
+
+    /// Adds a file given its id, its extension and its content.
+    ///
+    /// Parent directories are created as needed.
+    pub fn file<C: Into<Vec<u8>>>(mut self, id: &str, ext: &str, content: C) -> TempSourceBuilder {
+        self.files.push((id.to_owned(), ext.to_owned(), content.into()));
+        self
+    }
+
+    /// Adds several files given as `(id, ext, content)` tuples.
+    pub fn files<'a, I, C>(mut self, files: I) -> TempSourceBuilder
+    where
+        I: IntoIterator<Item = (&'a str, &'a str, C)>,
+        C: Into<Vec<u8>>,
+    {
+        for (id, ext, content) in files {
+            self = self.file(id, ext, content);
+        }
+        self
+    }
+
+    /// Adds an empty directory given its id.
+    pub fn dir(mut self, id: &str) -> TempSourceBuilder {
+        self.dirs.push(id.to_owned());
+        self
+    }
+
+    /// Creates the temporary directory and writes the files.
+    pub fn build(&self) -> io::Result<TempSource> {
+        let source = TempSource::new()?;
+
+        for id in &self.dirs {
+            source.create_dir(id)?;
+        }
+        for (id, ext, content) in &self.files {
+            source.write(id, ext, content)?;
+        }
+
+        Ok(source)
+    }
+}
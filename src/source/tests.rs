@@ -6,6 +6,89 @@ fn source_object_safe() {
     let _: &dyn Source = &Box::new(s);
 }
 
+#[test]
+fn validate_id() {
+    assert!(super::validate_id("", "").is_ok());
+    assert!(super::validate_id("common.position", "ron").is_ok());
+    assert!(super::validate_id("archive", "tar.gz").is_ok());
+
+    for id in &["..", "a..b", ".a", "a.", "a/b", "a\\b", "c:.windows", "a\0b"] {
+        let err = super::validate_id(id, "x").unwrap_err();
+        assert_eq!(err.id(), *id);
+    }
+    assert!(super::validate_id("a", "x/../y").is_err());
+    assert!(super::validate_id("a", ".x").is_err());
+
+    let err = io::Error::from(super::validate_id("..", "").unwrap_err());
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn version() {
+    assert_eq!(Version::hash_of(b"abc"), Version::hash_of(b"abc"));
+    assert_ne!(Version::hash_of(b"abc"), Version::hash_of(b"abd"));
+    assert_eq!(Version::from_tag("\"v1\""), Version::from_tag(String::from("\"v1\"")));
+
+    let time = std::time::SystemTime::UNIX_EPOCH;
+    assert_ne!(Version::from_modified(time, 1), Version::from_modified(time, 2));
+}
+
+#[test]
+fn source_error() {
+    let err = SourceError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+    assert_eq!(err.kind(), SourceErrorKind::NotFound);
+    assert_eq!(err.io_kind(), io::ErrorKind::NotFound);
+    assert_eq!(err.to_string(), "no such file");
+
+    let err = err.with_file("a.b", "x").with_source::<FileSystem>().with_file("c", "y");
+    assert_eq!(err.id(), Some("a.b"));
+    assert_eq!(err.ext(), Some("x"));
+    assert!(err.source_name().unwrap().ends_with("FileSystem"));
+    assert!(err.to_string().starts_with("\"a.b\" (extension \"x\") from "));
+    assert!(err.to_string().ends_with("FileSystem: no such file"));
+
+    // Converting to `io::Error` and back keeps the context
+    let err = SourceError::from(io::Error::from(err));
+    assert_eq!(err.kind(), SourceErrorKind::NotFound);
+    assert_eq!(err.id(), Some("a.b"));
+
+    let err = SourceError::from(super::validate_id("..", "").unwrap_err());
+    assert_eq!(err.kind(), SourceErrorKind::InvalidId);
+    let err = SourceError::from(io::Error::from(super::validate_id("..", "").unwrap_err()));
+    assert_eq!(err.kind(), SourceErrorKind::InvalidId);
+
+    let err = SourceError::from(SourceErrorKind::Unsupported);
+    assert!(err.error().is_none());
+    assert_eq!(err.io_kind(), io::ErrorKind::Unsupported);
+    assert_eq!(err.to_string(), "unsupported operation");
+}
+
+/// Checks `read_dir_recursive` on the `test_recursive` directory.
+fn check_read_dir_recursive(source: &dyn Source) {
+    let mut ids = source.read_dir_recursive("test_recursive", &["x"]).unwrap();
+    ids.sort();
+    assert_eq!(ids, ["test_recursive.a", "test_recursive.sub.b", "test_recursive.sub.deep.c"]);
+
+    let mut ids = source.read_dir_recursive("test_recursive.sub", &["x", "txt"]).unwrap();
+    ids.sort();
+    assert_eq!(ids, ["test_recursive.sub.b", "test_recursive.sub.deep.c", "test_recursive.sub.notes"]);
+
+    assert!(source.read_dir_recursive("test_recursive.not_found", &["x"]).is_err());
+}
+
+/// Checks `read_dir_entries` on the `test_recursive` directory.
+fn check_read_dir_entries(source: &dyn Source) {
+    let mut entries = source.read_dir_entries("test_recursive", &["x"]).unwrap();
+    entries.sort();
+    assert_eq!(entries, [DirEntry::File("a".into()), DirEntry::Directory("sub".into())]);
+
+    let mut entries = source.read_dir_entries("test_recursive.sub", &["txt"]).unwrap();
+    entries.sort();
+    assert_eq!(entries, [DirEntry::File("notes".into()), DirEntry::Directory("deep".into())]);
+
+    assert!(source.read_dir_entries("test_recursive.not_found", &["x"]).is_err());
+}
+
 macro_rules! test_source {
     ($source:expr) => {
         #[test]
@@ -29,6 +112,32 @@ macro_rules! test_source {
             dir.sort();
             assert_eq!(dir, ["a", "b", "cache"]);
         }
+
+        #[test]
+        fn metadata() {
+            let source = $source;
+            assert_eq!(source.metadata("test.b", "x").unwrap().len, 2);
+            assert!(source.metadata("test.not_found", "x").is_err());
+        }
+
+        #[test]
+        fn open() {
+            let source = $source;
+
+            let mut content = Vec::new();
+            std::io::Read::read_to_end(&mut source.open("test.b", "x").unwrap(), &mut content).unwrap();
+            assert_eq!(content, b"-7");
+            assert!(source.open("test.not_found", "x").is_err());
+        }
+
+        #[test]
+        fn version() {
+            let source = $source;
+            let version = source.version("test.b", "x").unwrap();
+            assert_eq!(source.version("test.b", "x").unwrap(), version);
+            assert_ne!(source.version("test.a", "x").unwrap(), version);
+            assert!(source.version("test.not_found", "x").is_err());
+        }
     }
 }
 
@@ -49,7 +158,218 @@ mod filesystem {
             path
         };
 
-        assert_eq!(path, fs.path_of("test.a", "x"));
+        assert_eq!(path, fs.path_of("test.a", "x").unwrap());
+    }
+
+    #[test]
+    fn relative_constructors() {
+        let fs = FileSystem::relative_to_manifest("assets").unwrap();
+        assert_eq!(fs.root(), FileSystem::new("assets").unwrap().root());
+
+        let exe_dir = std::env::current_exe().unwrap().parent().unwrap().canonicalize().unwrap();
+        assert_eq!(FileSystem::relative_to_exe("").unwrap().root(), exe_dir);
+        assert!(FileSystem::relative_to_exe("not_found").is_err());
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn user_data_dir() {
+        use crate::source::filesystem::user_data_path;
+        use std::path::Path;
+
+        let env = |home: &'static str, xdg: &'static str| {
+            move |name: &str| match name {
+                "HOME" if !home.is_empty() => Some(home.into()),
+                "XDG_DATA_HOME" if !xdg.is_empty() => Some(xdg.into()),
+                _ => None,
+            }
+        };
+
+        assert_eq!(user_data_path("game", env("/home/me", "")).unwrap(), Path::new("/home/me/.local/share/game"));
+        assert_eq!(user_data_path("game", env("/home/me", "/data")).unwrap(), Path::new("/data/game"));
+        assert_eq!(user_data_path("game", env("/home/me", "relative")).unwrap(), Path::new("/home/me/.local/share/game"));
+        assert_eq!(user_data_path("game", env("", "")).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(user_data_path("../game", env("/home/me", "")).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(user_data_path("", env("/home/me", "")).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn path_traversal() {
+        let fs = FileSystem::new("assets/test").unwrap();
+
+        for id in &["..", "..a", "x./etc/passwd", "x..y", "/etc/passwd"] {
+            assert_eq!(fs.read(id, "x").unwrap_err().kind(), SourceErrorKind::InvalidId);
+            assert_eq!(fs.path_of(id, "x").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        }
+        assert_eq!(fs.read_dir("..", &["x"]).unwrap_err().kind(), SourceErrorKind::InvalidId);
+        assert_eq!(fs.read("a", "/../b.x").unwrap_err().kind(), SourceErrorKind::InvalidId);
+    }
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&FileSystem::new("assets").unwrap());
+        check_read_dir_entries(&FileSystem::new("assets").unwrap());
+    }
+
+    #[test]
+    fn several_roots() {
+        let game = std::env::temp_dir().join(format!("assets_manager_roots_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&game);
+        std::fs::create_dir_all(game.join("test")).unwrap();
+        std::fs::write(game.join("test/b.x"), "game").unwrap();
+        std::fs::write(game.join("test/d.x"), "d").unwrap();
+
+        let fs = FileSystem::with_roots([&game, std::path::Path::new("assets")]).unwrap();
+        assert_eq!(fs.roots().len(), 2);
+        assert_eq!(fs.root(), game.canonicalize().unwrap());
+
+        assert_eq!(&*fs.read("test.b", "x").unwrap(), b"game");
+        assert_eq!(&*fs.read("test.a", "x").unwrap(), b"Error");
+        assert_eq!(fs.metadata("test.b", "x").unwrap().len, 4);
+        assert!(fs.read("test.not_found", "x").is_err());
+
+        assert_eq!(fs.read_dir("test", &["x"]).unwrap(), ["a", "b", "cache", "d"]);
+        assert!(!fs.read_dir("common", &["ron"]).unwrap().is_empty());
+        assert!(fs.read_dir("not_found", &["x"]).is_err());
+
+        // New files are written to the first root, existing ones in place
+        fs.write("test.e", "x", b"e").unwrap();
+        assert!(game.join("test/e.x").exists());
+        fs.write("test.d", "x", b"1").unwrap();
+        assert_eq!(std::fs::read(game.join("test/d.x")).unwrap(), b"1");
+
+        assert!(FileSystem::with_roots(Vec::<std::path::PathBuf>::new()).is_err());
+
+        std::fs::remove_dir_all(&game).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks() {
+        let root = std::env::temp_dir().join(format!("assets_manager_symlinks_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("assets")).unwrap();
+        std::fs::create_dir_all(root.join("shared")).unwrap();
+        std::fs::write(root.join("shared/a.x"), "42").unwrap();
+        std::fs::write(root.join("assets/b.x"), "-7").unwrap();
+        std::os::unix::fs::symlink(root.join("shared"), root.join("assets/shared")).unwrap();
+        std::os::unix::fs::symlink(root.join("shared/a.x"), root.join("assets/a.x")).unwrap();
+
+        let fs = FileSystem::new(root.join("assets")).unwrap();
+        assert!(fs.follows_symlinks());
+        assert_eq!(&*fs.read("shared.a", "x").unwrap(), b"42");
+        assert_eq!(&*fs.read("a", "x").unwrap(), b"42");
+        let mut dir = fs.read_dir("", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b"]);
+
+        let fs = fs.follow_symlinks(false);
+        assert!(fs.read("shared.a", "x").is_err());
+        assert!(fs.read("a", "x").is_err());
+        assert!(fs.metadata("a", "x").is_err());
+        assert!(fs.read_dir("shared", &["x"]).is_err());
+        assert_eq!(&*fs.read("b", "x").unwrap(), b"-7");
+        assert_eq!(fs.read_dir("", &["x"]).unwrap(), ["b"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn write_remove() {
+        let dir = std::env::temp_dir().join(format!("assets_manager_write_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let fs = FileSystem::without_hot_reloading(&dir).unwrap();
+
+        fs.write("sub.file", "txt", b"content").unwrap();
+        assert_eq!(&*fs.read("sub.file", "txt").unwrap(), b"content");
+
+        fs.remove("sub.file", "txt").unwrap();
+        assert_eq!(fs.read("sub.file", "txt").unwrap_err().kind(), SourceErrorKind::NotFound);
+        assert_eq!(fs.remove("sub.file", "txt").unwrap_err().kind(), SourceErrorKind::NotFound);
+        assert_eq!(fs.remove("sub", "").unwrap_err().kind(), SourceErrorKind::NotFound);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn metadata_modified() {
+        let fs = FileSystem::new("assets").unwrap();
+        let metadata = fs.metadata("test.b", "x").unwrap();
+
+        assert!(metadata.modified.is_some());
+        assert!(metadata.is_newer_than(std::time::UNIX_EPOCH));
+        assert_eq!(fs.metadata("test", "").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let fs = FileSystem::new("assets/test_case").unwrap();
+        assert!(!fs.is_case_insensitive());
+        assert_eq!(fs.read("sounds.explosion", "ogg").unwrap_err().kind(), SourceErrorKind::NotFound);
+
+        let fs = fs.case_insensitive(true);
+        assert_eq!(&*fs.read("sounds.explosion", "ogg").unwrap(), b"boom");
+        assert_eq!(&*fs.read("SOUNDS.Explosion", "Ogg").unwrap(), b"boom");
+        assert_eq!(&*fs.read("readme", "TXT").unwrap(), b"hello");
+        assert_eq!(fs.read("sounds.missing", "ogg").unwrap_err().kind(), SourceErrorKind::NotFound);
+
+        assert_eq!(fs.read_dir("sounds", &["ogg"]).unwrap(), ["Explosion"]);
+        assert_eq!(fs.resolve("sounds.explosion", "ogg").unwrap(), fs.path_of("Sounds.Explosion", "OGG").unwrap());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn memory_mapped() {
+        let dir = std::env::temp_dir().join(format!("assets_manager_mmap_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("big.bin"), vec![1; 32 * 1024]).unwrap();
+        std::fs::write(dir.join("small.bin"), b"small").unwrap();
+
+        let fs = FileSystem::without_hot_reloading(&dir).unwrap();
+        let fs = unsafe { fs.memory_mapped(true) };
+        assert!(fs.is_memory_mapped());
+
+        let content = fs.read("big", "bin").unwrap();
+        assert!(matches!(content, Cow::Borrowed(_)));
+        assert_eq!(content.len(), 32 * 1024);
+        assert!(content.iter().all(|&b| b == 1));
+
+        let small = fs.read("small", "bin").unwrap();
+        assert!(matches!(small, Cow::Owned(_)));
+        assert_eq!(&*small, b"small");
+
+        // A changed file is mapped again
+        drop(content);
+        std::fs::write(dir.join("big.bin"), vec![2; 64 * 1024]).unwrap();
+        let content = fs.read("big", "bin").unwrap();
+        assert_eq!(content.len(), 64 * 1024);
+        assert!(content.iter().all(|&b| b == 2));
+
+        drop(content);
+        drop(fs);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(all(feature = "mmap", unix))]
+    #[test]
+    fn memory_mapped_write() {
+        let dir = std::env::temp_dir().join(format!("assets_manager_mmap_write_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("big.bin"), vec![1; 32 * 1024]).unwrap();
+
+        let fs = FileSystem::without_hot_reloading(&dir).unwrap();
+        let fs = unsafe { fs.memory_mapped(true) };
+
+        // Mapped bytes are still valid after the file is written
+        let content = fs.read("big", "bin").unwrap();
+        fs.write("big", "bin", &[2; 16]).unwrap();
+        assert!(content.iter().all(|&b| b == 1));
+        assert_eq!(&*fs.read("big", "bin").unwrap(), [2; 16]);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        drop(content);
+        drop(fs);
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
 
@@ -60,4 +380,1617 @@ mod embedded {
     static RAW: RawEmbedded<'static> = embed!("assets");
 
     test_source!(Embedded::from(RAW));
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&Embedded::from(RAW));
+        check_read_dir_entries(&Embedded::from(RAW));
+    }
+
+    #[test]
+    fn nested_dirs() {
+        // Only the deepest directory is listed
+        let source = Embedded::from(RawEmbedded {
+            files: &[
+                (("test_recursive.a", "x"), b"1"),
+                (("test_recursive.sub.b", "x"), b"2"),
+                (("test_recursive.sub.notes", "txt"), b""),
+                (("test_recursive.sub.deep.c", "x"), b"3"),
+            ],
+            dirs: &[("test_recursive.sub.deep", &[("c", "x")])],
+            compression: Compression::None,
+        });
+
+        check_read_dir_recursive(&source);
+        check_read_dir_entries(&source);
+        assert_eq!(source.read_dir("test_recursive.sub.deep", &["x"]).unwrap(), ["c"]);
+        assert_eq!(source.read_dir_entries("", &["x"]).unwrap(), [DirEntry::Directory("test_recursive".into())]);
+        assert!(source.read_dir("test_recursive.sub.not_found", &["x"]).is_err());
+    }
+
+    mod builder {
+        use super::*;
+
+        fn builder() -> EmbeddedBuilder<'static> {
+            Embedded::builder()
+                .file("test.a", "x", include_bytes!("../../assets/test/a.x"))
+                .file("test.b", "x", include_bytes!("../../assets/test/b.x"))
+                .file("test.cache", "x", include_bytes!("../../assets/test/cache.x"))
+                .file("test_recursive.a", "x", b"1")
+                .file("test_recursive.sub.b", "x", b"2")
+                .file("test_recursive.sub.notes", "txt", b"")
+                .file("test_recursive.sub.deep.c", "x", b"3")
+        }
+
+        test_source!(builder().build());
+
+        #[test]
+        fn read_dir_recursive() {
+            check_read_dir_recursive(&builder().build());
+            check_read_dir_entries(&builder().build());
+        }
+
+        #[test]
+        fn dirs() {
+            let source = builder().dir("empty.sub").file("test.b", "x", b"8").build();
+
+            assert_eq!(&*source.read("test.b", "x").unwrap(), b"8");
+            assert_eq!(source.read_dir("test", &["x"]).unwrap().len(), 3);
+            assert!(source.read_dir("empty.sub", &["x"]).unwrap().is_empty());
+            assert_eq!(source.read_dir_entries("empty", &["x"]).unwrap(), [DirEntry::Directory("sub".into())]);
+            assert!(source.read_dir("empty.not_found", &["x"]).is_err());
+        }
+    }
+
+    #[test]
+    fn filtered() {
+        static FILTERED: RawEmbedded<'static> = embed!(
+            "assets",
+            include = ["*.x", "common/*"],
+            exclude = ["test_overlay", "example/**", "**/cache.*"],
+        );
+        let source = Embedded::from(FILTERED);
+
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+        assert!(source.read("common.position", "ron").is_ok());
+        assert!(source.read("test.cache", "x").is_err());
+        assert!(source.read("example.hot", "x").is_err());
+        assert!(source.read("test_case.README", "txt").is_err());
+        assert!(source.read_dir("test_overlay", &["x"]).is_err());
+
+        let mut dir = source.read_dir("test", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b"]);
+
+        // Directories without matching files are dropped
+        assert!(source.read_dir("test.shaders", &["glsl"]).is_err());
+        assert!(source.read_dir("test.gltf", &["gltf"]).is_err());
+        assert!(source.read_dir("test.hot_dir", &["x"]).is_ok());
+        let entries = source.read_dir_entries("test", &["x"]).unwrap();
+        assert!(entries.contains(&DirEntry::Directory("hot_asset".into())));
+        assert!(!entries.contains(&DirEntry::Directory("tiled".into())));
+    }
+
+    #[test]
+    fn several_dirs() {
+        static MERGED: RawEmbedded<'static> = embed!(["assets/test_overlay", "assets"]);
+        let source = Embedded::from(MERGED);
+
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"42");
+        assert_eq!(&*source.read("test.d", "x").unwrap(), b"5");
+        assert_eq!(&*source.read("test.a", "x").unwrap(), b"Error");
+
+        let mut dir = source.read_dir("test", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b", "cache", "d"]);
+    }
+
+    #[cfg(feature = "embedded-deflate")]
+    mod deflate {
+        use super::*;
+
+        static DEFLATE: RawEmbedded<'static> = embed!("assets", compress = "deflate");
+
+        test_source!(Embedded::from(DEFLATE));
+
+        #[test]
+        fn compressed() {
+            assert_eq!(DEFLATE.compression, Compression::Deflate);
+
+            let raw = DEFLATE.files.iter().find(|(key, _)| *key == ("test.b", "x")).unwrap().1;
+            assert_ne!(raw, b"-7");
+
+            let source = Embedded::from(DEFLATE);
+            assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+            assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+        }
+    }
+
+    #[cfg(feature = "embedded-zstd")]
+    mod zstd {
+        use super::*;
+
+        static ZSTD: RawEmbedded<'static> = embed!("assets", compress = "zstd",);
+
+        test_source!(Embedded::from(ZSTD));
+
+        #[test]
+        fn compressed() {
+            assert_eq!(ZSTD.compression, Compression::Zstd);
+
+            let source = Embedded::from(ZSTD);
+            assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+            assert_eq!(Embedded::from(RAW).read("test.cache", "x").unwrap(), source.read("test.cache", "x").unwrap());
+        }
+    }
+}
+
+#[cfg(feature = "tar")]
+mod archive {
+    use super::*;
+    use std::fs;
+
+    const FILES: &[&str] = &[
+        "test/a.x",
+        "test/b.x",
+        "test/cache.x",
+        "test_recursive/a.x",
+        "test_recursive/sub/b.x",
+        "test_recursive/sub/notes.txt",
+        "test_recursive/sub/deep/c.x",
+    ];
+
+    fn tar() -> Vec<u8> {
+        let mut builder = tar_crate::Builder::new(Vec::new());
+        for path in FILES {
+            builder.append_path_with_name(std::path::Path::new("assets").join(path), path).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    test_source!(ArchiveSource::from_bytes(tar()).unwrap());
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&ArchiveSource::from_bytes(tar()).unwrap());
+        check_read_dir_entries(&ArchiveSource::from_bytes(tar()).unwrap());
+    }
+
+    #[test]
+    fn detect() {
+        use std::path::Path;
+
+        assert_eq!(ArchiveFormat::from_path(Path::new("a/assets.TAR.GZ")), Some(ArchiveFormat::TarGz));
+        assert_eq!(ArchiveFormat::from_path(Path::new("assets.tzst")), Some(ArchiveFormat::TarZstd));
+        assert_eq!(ArchiveFormat::from_path(Path::new("assets.zip")), Some(ArchiveFormat::Zip));
+        assert_eq!(ArchiveFormat::from_path(Path::new("assets")), None);
+
+        assert_eq!(ArchiveFormat::detect(&tar()), Some(ArchiveFormat::Tar));
+        assert_eq!(ArchiveFormat::detect(b"PK\x03\x04"), Some(ArchiveFormat::Zip));
+        assert_eq!(ArchiveFormat::detect(b"hello"), None);
+        assert_eq!(ArchiveSource::from_bytes(b"hello".to_vec()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn open_file() {
+        let dir = std::env::temp_dir().join(format!("assets_manager_archive_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // The format is detected from the content without a known extension
+        let path = dir.join("assets.bin");
+        fs::write(&path, tar()).unwrap();
+        let source = ArchiveSource::open(&path).unwrap();
+        assert_eq!(source.format(), ArchiveFormat::Tar);
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+
+        #[cfg(feature = "gzip")]
+        {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+            std::io::Write::write_all(&mut encoder, &tar()).unwrap();
+            let path = dir.join("assets.tar.gz");
+            fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+            let source = ArchiveSource::open(&path).unwrap();
+            assert_eq!(source.format(), ArchiveFormat::TarGz);
+            assert_eq!(&*source.read("test_recursive.sub.deep.c", "x").unwrap(), &*fs::read("assets/test_recursive/sub/deep/c.x").unwrap());
+        }
+
+        #[cfg(feature = "zstd")]
+        {
+            let path = dir.join("assets.tar.zst");
+            fs::write(&path, zstd_crate::stream::encode_all(&*tar(), 0).unwrap()).unwrap();
+            assert_eq!(ArchiveSource::open(&path).unwrap().format(), ArchiveFormat::TarZstd);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn zip() {
+        use std::io::Write;
+
+        let mut writer = zip_crate::ZipWriter::new(io::Cursor::new(Vec::new()));
+        for path in FILES {
+            writer.start_file(*path, zip_crate::write::SimpleFileOptions::default()).unwrap();
+            writer.write_all(&fs::read(std::path::Path::new("assets").join(path)).unwrap()).unwrap();
+        }
+        let content = writer.finish().unwrap().into_inner();
+
+        let source = ArchiveSource::from_bytes(content).unwrap();
+        assert_eq!(source.format(), ArchiveFormat::Zip);
+        check_read_dir_recursive(&source);
+    }
+}
+
+#[cfg(feature = "embedded-build")]
+mod embed_build {
+    use super::*;
+
+    #[test]
+    fn generate() {
+        let code = EmbedGenerator::new("assets/test_recursive").generate().unwrap();
+
+        assert!(code.starts_with("assets_manager::source::RawEmbedded {"));
+        assert!(code.contains("((\"sub.deep.c\", \"x\"), include_bytes!("));
+        assert!(code.contains("(\"sub\", &[(\"b\", \"x\"), (\"notes\", \"txt\"), ] as &[(&str, &str)]),"));
+        assert!(code.contains("(\"sub.deep\", &["));
+        assert!(code.contains("(\"\", &[(\"a\", \"x\"), ] as &[(&str, &str)]),"));
+    }
+
+    #[test]
+    fn filter_and_preprocess() {
+        let code = EmbedGenerator::new("assets/test_recursive")
+            .filter(|path| path != std::path::Path::new("sub/deep"))
+            .preprocess(|_, content| Ok([&b"\"\\\n"[..], &content].concat()))
+            .generate()
+            .unwrap();
+
+        assert!(!code.contains("sub.deep"));
+        assert!(code.contains("b\"\\\"\\\\\\x0a"));
+
+        assert!(EmbedGenerator::new("assets/not_found").generate().is_err());
+    }
+}
+
+#[cfg(feature = "encryption")]
+mod encrypted {
+    use super::*;
+
+    const KEY: [u8; 32] = [42; 32];
+
+    fn encrypted() -> EncryptedSource<Memory> {
+        let plain = FileSystem::new("assets").unwrap();
+        let source = EncryptedSource::new(Memory::new(), KEY);
+
+        for name in ["a", "b", "cache"] {
+            let id = format!("test.{}", name);
+            let content = source.encrypt(rand::random(), &plain.read(&id, "x").unwrap()).unwrap();
+            source.inner().insert(&id, "x", content);
+        }
+        source
+    }
+
+    test_source!(encrypted());
+
+    #[test]
+    fn wrong_key() {
+        let source = EncryptedSource::new(encrypted().into_inner(), [0; 32]);
+
+        let err = source.read("test.b", "x").unwrap_err();
+        assert_eq!(err.kind(), SourceErrorKind::Decode);
+    }
+
+    #[test]
+    fn tampered() {
+        let source = encrypted();
+        let mut content = source.inner().read("test.b", "x").unwrap().into_owned();
+        content[15] ^= 1;
+        source.inner().insert("test.b", "x", content);
+
+        let err = source.read("test.b", "x").unwrap_err();
+        assert_eq!(err.kind(), SourceErrorKind::Decode);
+    }
+}
+
+#[cfg(feature = "include_dir")]
+mod included {
+    use super::*;
+    // The macro refers to the crate by its name
+    use include_dir_crate as include_dir;
+
+    static ASSETS: include_dir::Dir<'static> = include_dir::include_dir!("$CARGO_MANIFEST_DIR/assets");
+
+    test_source!(IncludeDir::from(&ASSETS));
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&IncludeDir::from(&ASSETS));
+        check_read_dir_entries(&IncludeDir::from(&ASSETS));
+    }
+
+    #[test]
+    fn sub_dir() {
+        let source = IncludeDir::from(ASSETS.get_dir("test").unwrap());
+
+        assert_eq!(&*source.read("b", "x").unwrap(), b"-7");
+        assert!(source.read("test.b", "x").is_err());
+
+        let mut dir = source.read_dir("", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b", "cache"]);
+    }
+}
+
+#[cfg(feature = "http")]
+mod http {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    /// Starts a minimal HTTP server serving the `assets` directory.
+    ///
+    /// Requests to `/slow/...` are answered after one second, and requests
+    /// without the `x-token: secret` header are rejected. Files are sent with
+    /// their length as `ETag`.
+    fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let path = line.split(' ').nth(1).unwrap_or("/").to_owned();
+
+                    let mut authorized = false;
+                    let mut if_none_match = None;
+                    loop {
+                        line.clear();
+                        reader.read_line(&mut line).unwrap();
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        authorized |= line.trim().eq_ignore_ascii_case("x-token: secret");
+                        if let Some((name, value)) = line.trim().split_once(": ") {
+                            if name.eq_ignore_ascii_case("if-none-match") {
+                                if_none_match = Some(value.to_owned());
+                            }
+                        }
+                    }
+
+                    if path.starts_with("/slow/") {
+                        thread::sleep(Duration::from_secs(1));
+                    }
+
+                    let (status, body) = if !authorized {
+                        ("403 Forbidden", Vec::new())
+                    } else {
+                        match std::fs::read(format!("assets{}", path)) {
+                            Ok(body) => ("200 OK", body),
+                            Err(_) => ("404 Not Found", Vec::new()),
+                        }
+                    };
+
+                    let etag = format!("\"{}\"", body.len());
+                    if status == "200 OK" && if_none_match.as_ref() == Some(&etag) {
+                        let _ = write!(stream, "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n");
+                        return;
+                    }
+
+                    let _ = write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nETag: {}\r\nConnection: close\r\n\r\n", status, body.len(), etag);
+                    let _ = stream.write_all(&body);
+                });
+            }
+        });
+
+        format!("http://{}/", addr)
+    }
+
+    #[test]
+    fn url_of() {
+        let source = Http::new("https://example.com/assets/");
+        assert_eq!(source.url_of("common.position", "ron"), "https://example.com/assets/common/position.ron");
+        assert_eq!(source.url_of("a", ""), "https://example.com/assets/a");
+    }
+
+    #[test]
+    fn read() {
+        let source = Http::builder(serve()).header("x-token", "secret").build();
+
+        let content = source.read("test.b", "x").unwrap();
+        assert_eq!(&*content, b"-7");
+
+        let err = source.read("test.not_found", "x").unwrap_err();
+        assert_eq!(err.kind(), SourceErrorKind::NotFound);
+
+        assert!(source.read_dir("test", &["x"]).is_err());
+    }
+
+    #[test]
+    fn fetch() {
+        let source = Http::builder(serve()).header("x-token", "secret").build();
+
+        let fetched = source.fetch("test.b", "x", &Validators::default()).unwrap();
+        let validators = match fetched {
+            Fetched::Modified { content, validators } => {
+                assert_eq!(content, b"-7");
+                validators
+            }
+            Fetched::NotModified => panic!("expected content"),
+        };
+        assert_eq!(validators.etag.as_deref(), Some("\"2\""));
+
+        assert_eq!(source.fetch("test.b", "x", &validators).unwrap(), Fetched::NotModified);
+    }
+
+    #[test]
+    fn cached_remote() {
+        let remote = Http::builder(serve()).header("x-token", "secret").build();
+        let source = CachedRemote::new(remote, Memory::new());
+
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+        assert!(source.local().contains("test.b", "x"));
+        assert!(source.local().contains("test.b", "x.meta"));
+
+        // The local copy is up to date, so it is used
+        source.local().insert("test.b", "x", "-8");
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-8");
+
+        // The local copy is outdated, so it is replaced
+        source.local().insert("test.b", "x.meta", "etag: \"3\"\n");
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+        assert_eq!(&*source.local().read("test.b", "x").unwrap(), b"-7");
+
+        // Files removed from the remote are removed locally
+        source.local().insert("test.removed", "x", "0");
+        assert_eq!(source.read("test.removed", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+        assert!(!source.local().contains("test.removed", "x"));
+
+        assert_eq!(source.read("test.not_found", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn cached_remote_offline() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let local = Memory::new();
+        local.insert("test.b", "x", "-7");
+        let source = CachedRemote::new(Http::new(url), local);
+
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+        assert!(source.read("test.a", "x").is_err());
+        assert_eq!(source.read_dir("test", &["x"]).unwrap(), ["b"]);
+    }
+
+    #[test]
+    fn read_with_cache() {
+        let source = Http::builder(serve()).header("x-token", "secret").build();
+        let cache = crate::AssetCache::with_source(source);
+
+        let b = cache.load::<crate::tests::X>("test.b").unwrap();
+        assert_eq!(b.read().0, -7);
+    }
+
+    #[test]
+    fn missing_header() {
+        let source = Http::new(serve());
+        let err = source.read("test.b", "x").unwrap_err();
+        assert_eq!(err.kind(), SourceErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn timeout() {
+        let source = Http::builder(serve())
+            .header("x-token", "secret")
+            .timeout(Duration::from_millis(100))
+            .build();
+
+        let err = source.read("slow.test.b", "x").unwrap_err();
+        assert_eq!(err.io_kind(), io::ErrorKind::TimedOut);
+    }
+}
+
+#[cfg(feature = "s3")]
+mod s3 {
+    use super::*;
+    use super::super::s3::{SignedRequest, canonical_request, format_amz_date, parse_http_date, signature};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    /// Starts a minimal S3 server serving the `assets` directory as the
+    /// bucket `bucket`.
+    ///
+    /// Requests without signature are rejected, and objects are sent with a
+    /// fixed `Last-Modified` date.
+    fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let mut words = line.split(' ');
+                    let method = words.next().unwrap_or("GET").to_owned();
+                    let target = words.next().unwrap_or("/").to_owned();
+
+                    let mut authorized = false;
+                    loop {
+                        line.clear();
+                        reader.read_line(&mut line).unwrap();
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.trim().split_once(": ") {
+                            if name.eq_ignore_ascii_case("authorization") {
+                                authorized = value.starts_with("AWS4-HMAC-SHA256 Credential=key/");
+                            }
+                        }
+                    }
+
+                    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+                    let (status, body) = if !authorized {
+                        ("403 Forbidden", Vec::new())
+                    } else if path == "/bucket" {
+                        let prefix = query.split('&')
+                            .find_map(|pair| pair.strip_prefix("prefix="))
+                            .unwrap_or("")
+                            .replace("%2F", "/");
+                        let mut list = format!("<ListBucketResult><Prefix>{}</Prefix><IsTruncated>false</IsTruncated>", prefix);
+                        let entries = std::fs::read_dir(format!("assets/{}", prefix));
+                        for entry in entries.into_iter().flatten() {
+                            let entry = entry.unwrap();
+                            let name = entry.file_name().into_string().unwrap();
+                            if entry.file_type().unwrap().is_dir() {
+                                list.push_str(&format!("<CommonPrefixes><Prefix>{}{}/</Prefix></CommonPrefixes>", prefix, name));
+                            } else {
+                                list.push_str(&format!("<Contents><Key>{}{}</Key></Contents>", prefix, name));
+                            }
+                        }
+                        list.push_str("</ListBucketResult>");
+                        ("200 OK", list.into_bytes())
+                    } else {
+                        let key = path.strip_prefix("/bucket/").unwrap_or("");
+                        match std::fs::read(format!("assets/{}", key)) {
+                            Ok(body) => ("200 OK", body),
+                            Err(_) => ("404 Not Found", Vec::new()),
+                        }
+                    };
+
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {}\r\nContent-Length: {}\r\nLast-Modified: Wed, 21 Oct 2015 07:28:00 GMT\r\nConnection: close\r\n\r\n",
+                        status, body.len(),
+                    );
+                    if method != "HEAD" {
+                        let _ = stream.write_all(&body);
+                    }
+                });
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn source() -> S3 {
+        S3::builder(serve(), "bucket")
+            .prefix("test")
+            .credentials(S3Credentials::new("key", "secret"))
+            .build()
+    }
+
+    #[test]
+    fn key_of() {
+        let source = S3::builder("https://s3.example.com", "bucket").prefix("/assets/").build();
+        assert_eq!(source.key_of("common.position", "ron"), "assets/common/position.ron");
+        assert_eq!(source.key_of("a", ""), "assets/a");
+
+        let source = S3::builder("https://s3.example.com", "bucket").build();
+        assert_eq!(source.key_of("common.position", "ron"), "common/position.ron");
+    }
+
+    #[test]
+    fn signature_v4() {
+        // "get-vanilla" from the AWS Signature Version 4 test suite
+        let request = SignedRequest {
+            method: "GET",
+            host: "example.amazonaws.com",
+            path: "/",
+            query: "",
+        };
+        let headers = [
+            ("host", String::from("example.amazonaws.com")),
+            ("x-amz-date", String::from("20150830T123600Z")),
+        ];
+        let empty_hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let canonical = canonical_request(&request, &headers, empty_hash);
+
+        let signature = signature(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20150830T123600Z",
+            "20150830/us-east-1/service/aws4_request",
+            &canonical,
+        );
+        assert_eq!(signature, "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31");
+    }
+
+    #[test]
+    fn dates() {
+        let time = UNIX_EPOCH + Duration::from_secs(1440938160);
+        assert_eq!(format_amz_date(time), "20150830T123600Z");
+
+        let time = UNIX_EPOCH + Duration::from_secs(1445412480);
+        assert_eq!(parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"), Some(time));
+        assert_eq!(parse_http_date("not a date"), None);
+    }
+
+    #[test]
+    fn read() {
+        let source = source();
+
+        assert_eq!(&*source.read("b", "x").unwrap(), b"-7");
+        assert_eq!(source.read("not_found", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+
+        let metadata = source.metadata("b", "x").unwrap();
+        assert_eq!(metadata.len, 2);
+        assert_eq!(metadata.modified, Some(UNIX_EPOCH + Duration::from_secs(1445412480)));
+    }
+
+    #[test]
+    fn read_dir() {
+        let source = source();
+
+        let mut dir = source.read_dir("", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b", "cache"]);
+
+        assert_eq!(source.read_dir("hot_dir", &["x"]).unwrap(), ["a"]);
+    }
+
+    #[test]
+    fn read_dir_recursive() {
+        let source = S3::builder(serve(), "bucket")
+            .credentials(S3Credentials::new("key", "secret"))
+            .build();
+        check_read_dir_recursive(&source);
+        check_read_dir_entries(&source);
+    }
+
+    #[test]
+    fn anonymous() {
+        let source = S3::builder(serve(), "bucket").prefix("test").build();
+        let err = source.read("b", "x").unwrap_err();
+        assert_eq!(err.kind(), SourceErrorKind::PermissionDenied);
+    }
+}
+
+#[cfg(feature = "dev-server")]
+mod dev_server {
+    use super::*;
+    use std::{net::TcpListener, sync::mpsc, thread, time::Duration};
+
+    /// Starts a dev server that sends the test files, and then the messages
+    /// given to the returned channel.
+    fn dev_server() -> (DevServer, mpsc::Sender<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel::<Vec<u8>>();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+
+            let files: &[(&str, &str, &[u8])] = &[
+                ("test.a", "x", b"Error"),
+                ("test.b", "x", b"-7"),
+                ("test.cache", "x", b"10"),
+                ("test.cache", "y", b""),
+                ("test.d", "y", b""),
+                ("test.sub.e", "x", b""),
+            ];
+            for &(id, ext, content) in files {
+                let message = DevMessage::Write { id, ext, content }.encode();
+                socket.send(tungstenite::Message::binary(message)).unwrap();
+            }
+            socket.send(tungstenite::Message::binary(DevMessage::Ready.encode())).unwrap();
+
+            for message in receiver {
+                socket.send(tungstenite::Message::binary(message)).unwrap();
+            }
+            let _ = socket.close(None);
+        });
+
+        let source = DevServer::connect(format!("ws://{}", addr)).unwrap();
+        (source, sender)
+    }
+
+    /// Waits until the source received a file with the given content.
+    fn wait_for(source: &DevServer, id: &str, content: Option<&[u8]>) {
+        for _ in 0..500 {
+            if source.read(id, "x").ok().as_deref() == content {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("dev server update not received");
+    }
+
+    test_source!(dev_server().0);
+
+    #[test]
+    fn messages() {
+        let messages = [
+            DevMessage::Write { id: "a.b", ext: "tar.gz", content: b"content" },
+            DevMessage::Write { id: "a", ext: "", content: b"" },
+            DevMessage::Remove { id: "a.b", ext: "x" },
+            DevMessage::Clear,
+            DevMessage::Ready,
+        ];
+        for message in &messages {
+            assert_eq!(DevMessage::decode(&message.encode()).unwrap(), *message);
+        }
+
+        let invalid: &[&[u8]] = &[b"", b"\x04", b"\x02\x00", b"\x00\x05\x00\x00\x00a", b"\x01\x00\x00\x00\x00\x00\x00\x00\x00!"];
+        for bytes in invalid {
+            assert_eq!(DevMessage::decode(bytes).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        }
+
+        let traversal = DevMessage::Remove { id: "..", ext: "x" }.encode();
+        assert_eq!(DevMessage::decode(&traversal).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn updates() {
+        let (source, sender) = dev_server();
+        assert!(source.is_ready());
+        assert!(source.is_connected());
+        let version = source.version("test.b", "x").unwrap();
+
+        sender.send(DevMessage::Write { id: "test.b", ext: "x", content: b"42" }.encode()).unwrap();
+        wait_for(&source, "test.b", Some(b"42"));
+        assert_ne!(source.version("test.b", "x").unwrap(), version);
+
+        sender.send(DevMessage::Remove { id: "test.a", ext: "x" }.encode()).unwrap();
+        wait_for(&source, "test.a", None);
+        assert!(source.version("test.a", "x").is_err());
+
+        drop(sender);
+        for _ in 0..500 {
+            if !source.is_connected() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!source.is_connected());
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"42");
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    #[test]
+    fn hot_reloading() {
+        let (source, sender) = dev_server();
+        let cache = crate::AssetCache::with_source(Polling::new(source).with_interval(Duration::from_secs(0)));
+        let b = cache.load::<crate::tests::X>("test.b").unwrap();
+        assert_eq!(b.read().0, -7);
+
+        sender.send(DevMessage::Write { id: "test.b", ext: "x", content: b"5" }.encode()).unwrap();
+        wait_for(cache.source().inner(), "test.b", Some(b"5"));
+
+        cache.hot_reload();
+        assert_eq!(b.read().0, 5);
+    }
+}
+
+mod overlay {
+    use super::*;
+
+    fn overlay() -> Overlay {
+        Overlay::new(FileSystem::new("assets").unwrap())
+            .with_layer(FileSystem::new("assets/test_overlay").unwrap())
+    }
+
+    test_source!(Overlay::new(FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn read_topmost() {
+        let source = overlay();
+
+        assert_eq!(&*source.read("test.a", "x").unwrap(), b"Error");
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"42");
+        assert_eq!(&*source.read("test.d", "x").unwrap(), b"5");
+        assert_eq!(source.read("test.e", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn read_dir_merged() {
+        let source = overlay();
+
+        let mut dir = source.read_dir("test", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b", "cache", "d"]);
+
+        assert!(source.read_dir("not_found", &["x"]).is_err());
+    }
+
+    #[test]
+    fn read_dir_recursive() {
+        let source = Overlay::new(Memory::new()).with_layer(FileSystem::new("assets").unwrap());
+        check_read_dir_recursive(&source);
+        check_read_dir_entries(&source);
+    }
+
+    #[test]
+    fn pop_layer() {
+        let mut source = overlay();
+        assert_eq!(source.len(), 2);
+
+        source.pop();
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+    }
+}
+
+#[cfg(feature = "test-utils")]
+mod temp {
+    use super::*;
+
+    fn temp() -> TempSource {
+        TempSource::builder()
+            .files(vec![("test.a", "x", "Error"), ("test.b", "x", "-7"), ("test.cache", "x", "10")])
+            .file("test.cache", "y", "")
+            .file("test.d", "y", "")
+            .file("test.sub.e", "x", "")
+            .dir("test.empty")
+            .build()
+            .unwrap()
+    }
+
+    test_source!(temp());
+
+    #[test]
+    fn cleanup() {
+        let source = temp();
+        let path = source.path().to_owned();
+        assert!(path.join("test").join("b.x").is_file());
+        assert_eq!(source.read_dir("test.empty", &["x"]).unwrap(), Vec::<String>::new());
+
+        let other = temp();
+        assert_ne!(other.path(), path);
+
+        drop(source);
+        assert!(!path.exists());
+        assert!(other.path().exists());
+    }
+
+    #[test]
+    fn write() {
+        let source = temp();
+        source.write("new.file", "x", b"1").unwrap();
+        assert_eq!(&*source.read("new.file", "x").unwrap(), b"1");
+
+        WritableSource::remove(&source, "test.b", "x").unwrap();
+        assert!(source.read("test.b", "x").is_err());
+        assert!(source.create_dir("..").is_err());
+    }
+
+    #[cfg(feature = "hot-reloading")]
+    #[test]
+    fn hot_reloading() {
+        let cache = crate::AssetCache::with_source(Polling::new(temp()).with_interval(std::time::Duration::from_secs(0)));
+        let b = cache.load::<crate::tests::X>("test.b").unwrap();
+        assert_eq!(b.read().0, -7);
+
+        cache.source().inner().write("test.b", "x", b"5").unwrap();
+        cache.hot_reload();
+        assert_eq!(b.read().0, 5);
+    }
+}
+
+mod memory {
+    use super::*;
+
+    fn memory() -> Memory {
+        let source = Memory::new();
+        source.insert("test.a", "x", "Error");
+        source.insert("test.b", "x", "-7");
+        source.insert("test.cache", "x", "10");
+        source.insert("test.cache", "y", "");
+        source.insert("test.d", "y", "");
+        source.insert("test.sub.e", "x", "");
+        source
+    }
+
+    test_source!(memory());
+
+    #[test]
+    fn read_dir_recursive() {
+        let source = memory();
+        source.insert("test_recursive.a", "x", "1");
+        source.insert("test_recursive.sub.b", "x", "2");
+        source.insert("test_recursive.sub.deep.c", "x", "3");
+        source.insert("test_recursive.sub.notes", "txt", "hello");
+        check_read_dir_recursive(&source);
+        check_read_dir_entries(&source);
+
+        let mut ids = source.read_dir_recursive("test", &["x"]).unwrap();
+        ids.sort();
+        assert_eq!(ids, ["test.a", "test.b", "test.cache", "test.sub.e"]);
+    }
+
+    #[test]
+    fn insert_remove() {
+        let source = memory();
+
+        assert_eq!(source.insert("test.b", "x", "42"), Some(b"-7".to_vec()));
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"42");
+
+        assert_eq!(source.remove("test.b", "x"), Some(b"42".to_vec()));
+        assert!(!source.contains("test.b", "x"));
+        assert_eq!(source.read("test.b", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+        assert_eq!(source.remove("test.b", "x"), None);
+
+        source.write("test.b", "x", b"1").unwrap();
+        assert!(source.contains("test.b", "x"));
+
+        WritableSource::remove(&source, "test.b", "x").unwrap();
+        assert!(!source.contains("test.b", "x"));
+        assert_eq!(WritableSource::remove(&source, "test.b", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn read_dir_nested() {
+        let source = memory();
+
+        assert_eq!(source.read_dir("test.sub", &["x"]).unwrap(), ["e"]);
+        assert_eq!(source.read_dir("", &["x"]).unwrap(), Vec::<String>::new());
+        assert_eq!(source.read_dir("te", &["x"]).unwrap_err().kind(), SourceErrorKind::NotFound);
+
+        source.clear();
+        assert!(source.read_dir("test", &["x"]).is_err());
+    }
+
+    #[test]
+    fn version_changes() {
+        let source = memory();
+        let version = source.version("test.b", "x").unwrap();
+
+        source.insert("test.b", "x", "-7");
+        assert_eq!(source.version("test.b", "x").unwrap(), version);
+
+        source.insert("test.b", "x", "8");
+        assert_ne!(source.version("test.b", "x").unwrap(), version);
+    }
+
+    #[test]
+    fn with_cache() {
+        let cache = crate::AssetCache::with_source(memory());
+
+        let b = cache.load::<crate::tests::X>("test.b").unwrap();
+        assert_eq!(b.read().0, -7);
+    }
+}
+
+mod mounted {
+    use super::*;
+
+    fn mounted() -> Mounted<Memory> {
+        let source = Memory::new();
+        source.insert("maps.forest", "x", "1");
+        source.insert("maps.desert", "x", "2");
+        source.insert("intro", "x", "3");
+        Mounted::new("dlc.one", source)
+    }
+
+    test_source!(Mounted::new("", FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn read_prefixed() {
+        let source = mounted();
+
+        assert_eq!(&*source.read("dlc.one.maps.forest", "x").unwrap(), b"1");
+        assert_eq!(&*source.read("dlc.one.intro", "x").unwrap(), b"3");
+        assert!(source.read("maps.forest", "x").is_err());
+        assert!(source.read("dlc.onemaps.forest", "x").is_err());
+        assert!(source.read("dlc.one", "x").is_err());
+    }
+
+    #[test]
+    fn read_dir_prefixed() {
+        let source = mounted();
+
+        let mut dir = source.read_dir("dlc.one.maps", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["desert", "forest"]);
+
+        assert_eq!(source.read_dir("dlc.one", &["x"]).unwrap(), ["intro"]);
+        assert!(source.read_dir("dlc", &["x"]).unwrap().is_empty());
+        assert!(source.read_dir("", &["x"]).unwrap().is_empty());
+        assert!(source.read_dir("dl", &["x"]).is_err());
+        assert!(source.read_dir("maps", &["x"]).is_err());
+    }
+
+    #[test]
+    fn read_dir_entries() {
+        let source = mounted();
+
+        let mut entries = source.read_dir_entries("dlc.one", &["x"]).unwrap();
+        entries.sort();
+        assert_eq!(entries, [DirEntry::File("intro".into()), DirEntry::Directory("maps".into())]);
+
+        assert_eq!(source.read_dir_entries("dlc", &["x"]).unwrap(), [DirEntry::Directory("one".into())]);
+        assert_eq!(source.read_dir_entries("", &["x"]).unwrap(), [DirEntry::Directory("dlc".into())]);
+        assert!(source.read_dir_entries("dl", &["x"]).is_err());
+
+        let mut ids = source.read_dir_recursive("", &["x"]).unwrap();
+        ids.sort();
+        assert_eq!(ids, ["dlc.one.intro", "dlc.one.maps.desert", "dlc.one.maps.forest"]);
+    }
+
+    #[test]
+    fn write_prefixed() {
+        let source = mounted();
+
+        source.write("dlc.one.maps.swamp", "x", b"4").unwrap();
+        assert!(source.inner().contains("maps.swamp", "x"));
+        assert!(source.write("maps.swamp", "x", b"4").is_err());
+    }
+
+    #[test]
+    fn in_overlay() {
+        let source = Overlay::new(FileSystem::new("assets").unwrap()).with_layer(mounted());
+        let cache = crate::AssetCache::with_source(source);
+
+        assert_eq!(cache.load::<crate::tests::X>("test.b").unwrap().read().0, -7);
+        assert_eq!(cache.load::<crate::tests::X>("dlc.one.maps.desert").unwrap().read().0, 2);
+    }
+}
+
+mod fallback {
+    use super::*;
+
+    fn fallback() -> FallbackSource<Memory, FileSystem> {
+        let primary = Memory::new();
+        primary.insert("test.b", "x", "42");
+        primary.insert("other.c", "x", "1");
+        FallbackSource::new(primary, FileSystem::new("assets").unwrap())
+    }
+
+    test_source!(FallbackSource::new(Memory::new(), FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn read_fallback() {
+        let source = fallback();
+
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"42");
+        assert_eq!(&*source.read("test.a", "x").unwrap(), b"Error");
+        assert_eq!(source.read("test.e", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn read_dir_not_merged() {
+        let source = fallback();
+
+        assert_eq!(source.read_dir("other", &["x"]).unwrap(), ["c"]);
+
+        let mut dir = source.read_dir("test", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["b"]);
+    }
+
+    #[test]
+    fn write_primary() {
+        let source = fallback();
+
+        source.write("test.a", "x", b"0").unwrap();
+        assert_eq!(&*source.read("test.a", "x").unwrap(), b"0");
+        assert!(source.primary().contains("test.a", "x"));
+    }
+}
+
+mod any_source {
+    use super::*;
+
+    test_source!(AnySource::new(FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&AnySource::new(FileSystem::new("assets").unwrap()));
+    }
+
+    #[test]
+    fn downcast() {
+        let source = AnySource::new(Memory::new());
+        assert!(source.is::<Memory>());
+        assert!(source.downcast_ref::<FileSystem>().is_none());
+        assert!(source.downcast_ref::<Memory>().is_some());
+
+        let source = source.downcast::<FileSystem>().unwrap_err();
+        assert!(source.downcast::<Memory>().is_ok());
+    }
+
+    #[test]
+    fn in_cache() {
+        let memory = Memory::new();
+        memory.insert("a", "x", "42");
+
+        let cache = crate::AssetCache::with_source(AnySource::new(memory));
+        assert_eq!(cache.load::<crate::tests::X>("a").unwrap().read().0, 42);
+
+        let source: Box<dyn Source + Send + Sync> = Box::new(FileSystem::new("assets").unwrap());
+        let cache = crate::AssetCache::with_source(source);
+        assert_eq!(cache.load::<crate::tests::X>("test.b").unwrap().read().0, -7);
+    }
+}
+
+mod byte_cache {
+    use super::*;
+
+    test_source!(ByteCache::new(FileSystem::new("assets").unwrap(), 1 << 20));
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&ByteCache::new(FileSystem::new("assets").unwrap(), 1 << 20));
+    }
+
+    #[test]
+    fn cached() {
+        let memory = Memory::new();
+        memory.insert("a", "x", "abc");
+        let source = ByteCache::new(memory, 16);
+
+        assert_eq!(&*source.read("a", "x").unwrap(), b"abc");
+        assert!(source.contains("a", "x"));
+        assert_eq!(source.size(), 3);
+
+        source.inner().insert("a", "x", "def");
+        assert_eq!(&*source.read("a", "x").unwrap(), b"abc");
+
+        source.remove("a", "x");
+        assert_eq!(&*source.read("a", "x").unwrap(), b"def");
+
+        source.write("a", "x", b"ghi").unwrap();
+        assert!(!source.contains("a", "x"));
+        assert_eq!(&*source.read("a", "x").unwrap(), b"ghi");
+
+        source.clear();
+        assert_eq!(source.size(), 0);
+    }
+
+    #[test]
+    fn capacity() {
+        let memory = Memory::new();
+        memory.insert("a", "x", "aaa");
+        memory.insert("b", "x", "bbb");
+        memory.insert("c", "x", "ccc");
+        memory.insert("big", "x", "too big to fit");
+        let source = ByteCache::new(memory, 7);
+
+        source.read("a", "x").unwrap();
+        source.read("b", "x").unwrap();
+        source.read("a", "x").unwrap();
+        source.read("c", "x").unwrap();
+        assert!(source.contains("a", "x"));
+        assert!(!source.contains("b", "x"));
+        assert!(source.contains("c", "x"));
+        assert_eq!(source.size(), 6);
+
+        assert_eq!(&*source.read("big", "x").unwrap(), b"too big to fit");
+        assert!(!source.contains("big", "x"));
+        assert_eq!(source.size(), 6);
+    }
+}
+
+mod limited {
+    use super::*;
+    use std::time::Duration;
+
+    test_source!(Limited::new(FileSystem::new("assets").unwrap(), 1 << 20));
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&Limited::new(FileSystem::new("assets").unwrap(), 1 << 20));
+    }
+
+    #[test]
+    fn max_size() {
+        let memory = Memory::new();
+        memory.insert("small", "x", "abc");
+        memory.insert("big", "x", "abcdefgh");
+        let source = Limited::new(memory, 4);
+
+        assert_eq!(&*source.read("small", "x").unwrap(), b"abc");
+        assert_eq!(source.read("big", "x").unwrap_err().kind(), SourceErrorKind::Decode);
+        assert!(source.open("big", "x").is_err());
+        assert_eq!(source.read("missing", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let memory = Memory::new();
+        memory.insert("a", "x", "abc");
+        let source = Limited::new(memory, 4).with_rate_limit(5, Duration::from_secs(3600));
+
+        assert!(source.read("a", "x").is_ok());
+        assert!(source.read("a", "x").is_ok());
+        assert_eq!(source.read("a", "x").unwrap_err().io_kind(), io::ErrorKind::WouldBlock);
+
+        let source = Limited::new(source.into_inner(), 4).with_rate_limit(5, Duration::from_secs(0));
+        for _ in 0..3 {
+            assert!(source.read("a", "x").is_ok());
+        }
+    }
+}
+
+mod router {
+    use super::*;
+
+    fn router() -> Router {
+        let levels = Memory::new();
+        levels.insert("levels.forest", "x", "1");
+        levels.insert("test.b", "x", "2");
+        let deep = Memory::new();
+        deep.insert("maps.extra.deep.c", "x", "3");
+
+        Router::new()
+            .with_route("", FileSystem::new("assets").unwrap())
+            .with_route("levels", levels)
+            .with_route("maps.extra", deep)
+    }
+
+    test_source!(Router::new().with_route("", FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn read_dir_recursive() {
+        check_read_dir_recursive(&router());
+        check_read_dir_entries(&router());
+    }
+
+    #[test]
+    fn dispatch() {
+        let source = router();
+
+        assert_eq!(&*source.read("levels.forest", "x").unwrap(), b"1");
+        assert_eq!(&*source.read("maps.extra.deep.c", "x").unwrap(), b"3");
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+        assert_eq!(source.read("levels.missing", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+        assert!(Router::new().read("test.b", "x").is_err());
+    }
+
+    #[test]
+    fn route_dirs() {
+        let source = router();
+
+        assert!(source.read_dir("maps", &["x"]).unwrap().is_empty());
+        assert_eq!(source.read_dir_entries("maps", &["x"]).unwrap(), [DirEntry::Directory("extra".into())]);
+        assert!(source.read_dir_entries("", &["x"]).unwrap().contains(&DirEntry::Directory("levels".into())));
+        assert_eq!(source.read_dir_recursive("maps", &["x"]).unwrap(), ["maps.extra.deep.c"]);
+        assert!(source.read_dir("not_found", &["x"]).is_err());
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut source = router();
+        assert_eq!(source.len(), 3);
+
+        source.insert("levels", Memory::new());
+        assert_eq!(source.len(), 3);
+        assert!(source.read("levels.forest", "x").is_err());
+
+        assert!(source.remove("levels").is_some());
+        assert!(source.remove("levels").is_none());
+        assert_eq!(source.read_dir("levels", &["x"]).unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+}
+
+mod alias {
+    use super::*;
+
+    fn alias() -> AliasSource<FileSystem> {
+        AliasSource::new(FileSystem::new("assets").unwrap())
+            .with_alias("test.renamed", "test.b")
+            .with_alias("test.a", "test.b")
+            .with_fn(|id| id.strip_prefix("legacy.").map(|rest| format!("test.{}", rest)))
+    }
+
+    test_source!(AliasSource::new(FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn read_alias() {
+        let source = alias();
+
+        assert_eq!(&*source.read("test.renamed", "x").unwrap(), b"-7");
+        assert_eq!(&*source.read("test.a", "x").unwrap(), b"-7");
+        assert_eq!(&*source.read("legacy.cache", "x").unwrap(), &*source.read("test.cache", "x").unwrap());
+        assert_eq!(source.read("legacy.missing", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn read_dir_alias() {
+        let source = alias().with_alias("legacy", "test");
+
+        let mut dir = source.read_dir("legacy", &["x"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b", "cache"]);
+    }
+
+    #[test]
+    fn resolve() {
+        let mut source = alias();
+
+        assert_eq!(source.resolve("test.renamed"), "test.b");
+        assert_eq!(source.resolve("legacy.x"), "test.x");
+        assert_eq!(source.resolve("other"), "other");
+
+        assert_eq!(source.remove_alias("test.renamed").as_deref(), Some("test.b"));
+        assert_eq!(source.resolve("test.renamed"), "test.renamed");
+    }
+}
+
+mod swappable {
+    use super::*;
+
+    test_source!(SwappableSource::new(FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn swap() {
+        let source = SwappableSource::new(AnySource::new(FileSystem::new("assets").unwrap()));
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"-7");
+
+        let memory = Memory::new();
+        memory.insert("test.b", "x", "5");
+        let old = source.swap(AnySource::new(memory));
+        assert!(old.is::<FileSystem>());
+
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"5");
+        assert_eq!(source.read("test.a", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_source {
+    use super::*;
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn blocking() {
+        let source = Blocking::new(FileSystem::new("assets").unwrap());
+
+        let content = pollster::block_on(AsyncSource::read(&source, "test.b", "x")).unwrap();
+        assert_eq!(&*content, b"-7");
+
+        let mut dir = pollster::block_on(AsyncSource::read_dir(&source, "test", &["x"])).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b", "cache"]);
+    }
+
+    #[test]
+    fn unblock() {
+        let source = Unblock::new(FileSystem::new("assets").unwrap());
+        assert_send(&AsyncSource::read(&source, "test.b", "x"));
+
+        let content = pollster::block_on(AsyncSource::read(&source, "test.b", "x")).unwrap();
+        assert_eq!(&*content, b"-7");
+
+        let err = pollster::block_on(AsyncSource::read(&source, "test.missing", "x")).unwrap_err();
+        assert_eq!(err.kind(), SourceErrorKind::NotFound);
+
+        let mut dir = pollster::block_on(AsyncSource::read_dir(&source, "test", &["x"])).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b", "cache"]);
+    }
+
+    #[test]
+    fn boxed() {
+        let memory = Memory::new();
+        memory.insert("a", "b", "c");
+        let source: Box<dyn AsyncSource + Send + Sync> = Box::new(Blocking::new(memory));
+
+        let content = pollster::block_on(source.read("a", "b")).unwrap();
+        assert_eq!(&*content, b"c");
+    }
+}
+
+#[cfg(feature = "log")]
+mod instrumented {
+    use super::*;
+
+    test_source!(Instrumented::new(FileSystem::new("assets").unwrap()));
+
+    #[test]
+    fn write() {
+        let source = Instrumented::new(Memory::new());
+
+        source.write("a", "x", b"42").unwrap();
+        assert_eq!(&*source.read("a", "x").unwrap(), b"42");
+        source.remove("a", "x").unwrap();
+        assert!(source.remove("a", "x").is_err());
+    }
+}
+
+#[cfg(feature = "pack")]
+mod pack {
+    use super::*;
+    use std::{fs, io::Cursor};
+
+    fn pack(compression: PackCompression) -> Pack<Cursor<Vec<u8>>> {
+        let mut writer = PackWriter::new();
+        writer.set_compression(compression);
+        writer.add_dir("assets").unwrap();
+
+        let mut buf = Vec::new();
+        writer.write(&mut buf).unwrap();
+        Pack::new(Cursor::new(buf)).unwrap()
+    }
+
+    test_source!(pack(PackCompression::None));
+
+    #[test]
+    fn read_dir_recursive() {
+        let pack = pack(PackCompression::None);
+        check_read_dir_recursive(&pack);
+        check_read_dir_entries(&pack);
+    }
+
+    #[test]
+    fn deflate() {
+        let pack = pack(PackCompression::Deflate);
+        assert_eq!(&*pack.read("test.b", "x").unwrap(), b"-7");
+        assert_eq!(&*pack.read("common.position", "ron").unwrap(), &*fs::read("assets/common/position.ron").unwrap());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd() {
+        let pack = pack(PackCompression::Zstd);
+        assert_eq!(&*pack.read("test.b", "x").unwrap(), b"-7");
+    }
+
+    #[test]
+    fn writer() {
+        let source = Memory::new();
+        source.insert("dir.a", "txt", "a");
+        source.insert("dir.b", "txt", "b");
+        source.insert("dir.sub.c", "txt", "c");
+
+        let mut writer = PackWriter::new();
+        writer.add_source_dir(&source, "dir", &["txt"]).unwrap();
+        writer.add("dir.a", "txt", b"replaced").unwrap();
+        writer.set_compression(PackCompression::Deflate);
+        writer.add_from_source(&source, "dir.sub.c", "txt").unwrap();
+        assert_eq!(writer.len(), 3);
+
+        let mut buf = Vec::new();
+        writer.write(&mut buf).unwrap();
+        let pack = Pack::new(Cursor::new(buf)).unwrap();
+
+        assert_eq!(pack.len(), 3);
+        assert!(pack.contains("dir.b", "txt"));
+        assert_eq!(&*pack.read("dir.a", "txt").unwrap(), b"replaced");
+        assert_eq!(&*pack.read("dir.sub.c", "txt").unwrap(), b"c");
+        assert_eq!(pack.read("dir.d", "txt").unwrap_err().kind(), SourceErrorKind::NotFound);
+
+        let mut dir = pack.read_dir("dir", &["txt"]).unwrap();
+        dir.sort();
+        assert_eq!(dir, ["a", "b"]);
+        assert_eq!(pack.read_dir("", &["txt"]).unwrap(), Vec::<String>::new());
+        assert_eq!(pack.read_dir("other", &["txt"]).unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn limited() {
+        let mut writer = PackWriter::new();
+        writer.set_compression(PackCompression::Deflate);
+        writer.add("big", "x", &vec![0; 4 << 20]).unwrap();
+        writer.add("small", "x", b"abc").unwrap();
+
+        let mut buf = Vec::new();
+        writer.write(&mut buf).unwrap();
+        assert!(buf.len() < 1 << 20);
+        let pack = Pack::new(Cursor::new(buf)).unwrap();
+
+        assert_eq!(pack.metadata("big", "x").unwrap().len, 4 << 20);
+        let mut start = [1; 16];
+        pack.open("big", "x").unwrap().read_exact(&mut start).unwrap();
+        assert_eq!(start, [0; 16]);
+
+        let source = Limited::new(pack, 1 << 10);
+        assert_eq!(source.read("big", "x").unwrap_err().kind(), SourceErrorKind::Decode);
+        assert_eq!(&*source.read("small", "x").unwrap(), b"abc");
+    }
+
+    #[test]
+    fn invalid() {
+        let err = Pack::new(Cursor::new(b"PK\x03\x04 not a pack".to_vec())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // An offset past the end of the address space
+        let mut writer = PackWriter::new();
+        writer.add("a", "x", b"1").unwrap();
+        let mut buf = Vec::new();
+        writer.write(&mut buf).unwrap();
+        buf[23..31].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let pack = Pack::new(Cursor::new(buf)).unwrap();
+        assert_eq!(pack.read("a", "x").unwrap_err().io_kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::*;
+
+    fn sqlite() -> Sqlite {
+        let source = Sqlite::from_connection(rusqlite::Connection::open_in_memory().unwrap(), "assets");
+        source.create_table().unwrap();
+
+        let fs = FileSystem::new("assets").unwrap();
+        for name in ["a", "b", "cache"] {
+            let id = format!("test.{}", name);
+            source.write(&id, "x", &fs.read(&id, "x").unwrap()).unwrap();
+        }
+        source.write("test.sub.c", "x", b"0").unwrap();
+        source.write("test.d", "y", b"1").unwrap();
+        source.write("testing", "x", b"2").unwrap();
+
+        source
+    }
+
+    test_source!(sqlite());
+
+    #[test]
+    fn read_dir_recursive() {
+        let source = sqlite();
+        source.write("test_recursive.a", "x", b"1").unwrap();
+        source.write("test_recursive.sub.b", "x", b"2").unwrap();
+        source.write("test_recursive.sub.deep.c", "x", b"3").unwrap();
+        source.write("test_recursive.sub.notes", "txt", b"hello").unwrap();
+        check_read_dir_recursive(&source);
+        check_read_dir_entries(&source);
+    }
+
+    #[test]
+    fn read_dir_prefix() {
+        let source = sqlite();
+
+        assert_eq!(source.read_dir("", &["x"]).unwrap(), ["testing"]);
+        assert_eq!(source.read_dir("test.sub", &["x"]).unwrap(), ["c"]);
+        assert_eq!(source.read_dir("test.d", &["y"]).unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
+
+    #[test]
+    fn write_remove() {
+        let source = sqlite();
+
+        source.write("test.b", "x", b"5").unwrap();
+        assert_eq!(&*source.read("test.b", "x").unwrap(), b"5");
+
+        source.remove("test.b", "x").unwrap();
+        assert_eq!(source.remove("test.b", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+        assert_eq!(source.read("test.b", "x").unwrap_err().kind(), SourceErrorKind::NotFound);
+    }
 }
@@ -0,0 +1,134 @@
+use std::{
+    borrow::Cow,
+    io,
+};
+
+use js_sys::Uint8Array;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use super::{Source, SourceError, SourceErrorKind, url_of, validate_id};
+
+
+/// A [`Source`] to load assets from the web server that serves a WebAssembly
+/// application.
+///
+/// Each asset is fetched from a URL built from a base URL, the id of the asset
+/// and its extension. For example, with the base URL `assets`, the asset
+/// `common.position` with extension `ron` is fetched from
+/// `assets/common/position.ron`, relatively to the current page.
+///
+/// This source is only available on WebAssembly, and works both in the main
+/// thread and in web workers.
+///
+/// ## Synchronous and asynchronous requests
+///
+/// Browsers do not allow to block on `fetch()`, so [`Source::read`] uses a
+/// synchronous `XMLHttpRequest` instead, which blocks the current thread until
+/// the file is downloaded. [`Fetch::fetch`] uses `fetch()` and does not block,
+/// so it should be preferred to download files ahead of time.
+///
+/// ## Directories
+///
+/// HTTP has no standard way to list a directory, so reading directories is
+/// not supported and always returns an error.
+///
+/// ## Usage
+///
+/// ```no_run
+/// use assets_manager::{AssetCache, source::Fetch};
+///
+/// let cache = AssetCache::with_source(Fetch::new("assets"));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(all(feature = "web", target_arch = "wasm32"))))]
+#[derive(Debug, Clone)]
+pub struct Fetch {
+    base_url: String,
+}
+
+impl Fetch {
+    /// Creates a new `Fetch` source from a base URL.
+    ///
+    /// The URL can be relative to the current page.
+    pub fn new<U: Into<String>>(base_url: U) -> Fetch {
+        Fetch { base_url: base_url.into() }
+    }
+
+    /// Gets the base URL of the source.
+    #[inline]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the URL of the (eventual) file represented by an id and an
+    /// extension.
+    pub fn url_of(&self, id: &str, ext: &str) -> String {
+        url_of(&self.base_url, id, ext)
+    }
+
+    /// Downloads a file with `fetch()`, without blocking.
+    pub async fn fetch(&self, id: &str, ext: &str) -> Result<Vec<u8>, SourceError> {
+        validate_id(id, ext)?;
+        let url = self.url_of(id, ext);
+        let global = js_sys::global();
+
+        let promise = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+            window.fetch_with_str(&url)
+        } else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+            worker.fetch_with_str(&url)
+        } else {
+            return Err(SourceError::new(SourceErrorKind::Unsupported, "fetch() is not available"));
+        };
+
+        let response = JsFuture::from(promise).await.map_err(js_error)?;
+        let response: web_sys::Response = response.dyn_into().map_err(js_error)?;
+        if !response.ok() {
+            return Err(status_error(response.status()).into());
+        }
+
+        let buffer = JsFuture::from(response.array_buffer().map_err(js_error)?).await.map_err(js_error)?;
+        Ok(Uint8Array::new(&buffer).to_vec())
+    }
+}
+
+impl Source for Fetch {
+    fn read(&self, id: &str, ext: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+        validate_id(id, ext)?;
+        let request = web_sys::XmlHttpRequest::new().map_err(js_error)?;
+        request.open_with_async("GET", &self.url_of(id, ext), false).map_err(js_error)?;
+
+        // Synchronous requests cannot return binary data directly, so ask for
+        // text in a charset that maps each byte to a single character.
+        request.override_mime_type("text/plain; charset=x-user-defined").map_err(js_error)?;
+        request.send().map_err(js_error)?;
+
+        let status = request.status().map_err(js_error)?;
+        if !(200..300).contains(&status) {
+            return Err(status_error(status).into());
+        }
+
+        let text = request.response_text().map_err(js_error)?.unwrap_or_default();
+        Ok(Cow::Owned(text.chars().map(|c| c as u32 as u8).collect()))
+    }
+
+    fn read_dir(&self, _: &str, _: &[&str]) -> Result<Vec<String>, SourceError> {
+        Err(SourceError::new(SourceErrorKind::Unsupported, "cannot read directories over HTTP"))
+    }
+}
+
+fn status_error(status: u16) -> io::Error {
+    let kind = match status {
+        404 | 410 => io::ErrorKind::NotFound,
+        401 | 403 => io::ErrorKind::PermissionDenied,
+        _ => io::ErrorKind::Other,
+    };
+    io::Error::new(kind, format!("http status: {}", status))
+}
+
+fn js_error(err: JsValue) -> io::Error {
+    let msg = match err.dyn_ref::<js_sys::Error>() {
+        Some(err) => String::from(err.message()),
+        None => format!("{:?}", err),
+    };
+    io::Error::other(msg)
+}
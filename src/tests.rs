@@ -14,6 +14,18 @@ impl Asset for X {
     const EXTENSION: &'static str = "x";
 }
 
+pub struct XSaver;
+
+impl saver::Saver<X> for XSaver {
+    fn save(value: &X, _: &str) -> Result<Vec<u8>, BoxedError> {
+        Ok(value.0.to_string().into_bytes())
+    }
+}
+
+impl SavableAsset for X {
+    type Saver = XSaver;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct XS(pub i32);
 
@@ -47,6 +59,84 @@ impl Compound for Z {
     }
 }
 
+pub struct W(pub i32);
+
+pub struct WLoader;
+
+impl loader::ContextualLoader<W> for WLoader {
+    fn load<S: source::Source>(content: std::borrow::Cow<[u8]>, ext: &str, id: &str, cache: &AssetCache<S>) -> Result<W, BoxedError> {
+        use loader::Loader;
+
+        let n: i32 = loader::ParseLoader::load(content, ext)?;
+        let x = cache.load::<X>(id)?.read().0;
+        assert_eq!(n, x);
+        Ok(W(n))
+    }
+}
+
+impl asset::ContextualAsset for W {
+    const EXTENSION: &'static str = "x";
+    type Loader = WLoader;
+}
+
+impl Compound for W {
+    fn load<S: source::Source>(cache: &AssetCache<S>, id: &str) -> Result<W, Error> {
+        asset::load_contextual(cache, id)
+    }
+}
+
+pub struct V(pub i32);
+
+pub struct Offset(pub i32);
+
+impl loader::StatefulLoader<V> for Offset {
+    fn load(&self, content: std::borrow::Cow<[u8]>, ext: &str) -> Result<V, BoxedError> {
+        use loader::Loader;
+
+        let n: i32 = loader::ParseLoader::load(content, ext)?;
+        Ok(V(n + self.0))
+    }
+}
+
+impl asset::ContextualAsset for V {
+    const EXTENSION: &'static str = "x";
+    type Loader = loader::Stateful<Offset>;
+}
+
+impl Compound for V {
+    fn load<S: source::Source>(cache: &AssetCache<S>, id: &str) -> Result<V, Error> {
+        asset::load_contextual(cache, id)
+    }
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug, PartialEq, Eq)]
+pub struct XA(pub i32);
+
+#[cfg(feature = "async")]
+pub struct AsyncParse;
+
+#[cfg(feature = "async")]
+impl loader::Loader<XA> for AsyncParse {
+    fn load(content: std::borrow::Cow<[u8]>, ext: &str) -> Result<XA, BoxedError> {
+        let n: i32 = <loader::ParseLoader as loader::Loader<i32>>::load(content, ext)?;
+        Ok(XA(n))
+    }
+}
+
+#[cfg(feature = "async")]
+impl loader::AsyncLoader<XA> for AsyncParse {
+    fn load<'a>(content: std::borrow::Cow<'a, [u8]>, ext: &'a str) -> loader::BoxFuture<'a, Result<XA, BoxedError>> {
+        Box::pin(async move { <AsyncParse as loader::Loader<XA>>::load(content, ext) })
+    }
+}
+
+#[cfg(feature = "async")]
+impl Asset for XA {
+    const EXTENSION: &'static str = "x";
+    type Loader = AsyncParse;
+}
+
 
 mod asset_cache {
     use crate::AssetCache;
@@ -78,6 +168,205 @@ mod asset_cache {
         assert!(cache.contains::<X>("test.cache"));
     }
 
+    #[test]
+    fn load_streamed() {
+        use crate::{Asset, BoxedError, loader, source::{FileSystem, Source, SourceError}};
+        use std::{borrow::Cow, io};
+
+        // A source that can only be read as a stream
+        struct StreamOnly(FileSystem);
+
+        impl Source for StreamOnly {
+            fn read(&self, _: &str, _: &str) -> Result<Cow<'_, [u8]>, SourceError> {
+                Err(io::Error::other("not streamed").into())
+            }
+
+            fn read_dir(&self, id: &str, ext: &[&str]) -> Result<Vec<String>, SourceError> {
+                self.0.read_dir(id, ext)
+            }
+
+            fn open(&self, id: &str, ext: &str) -> Result<Box<dyn io::Read + Send>, SourceError> {
+                self.0.open(id, ext)
+            }
+        }
+
+        struct Text(String);
+
+        impl loader::StreamLoader<Text> for loader::StringLoader {
+            fn load_stream(reader: &mut dyn io::BufRead, ext: &str) -> Result<Text, BoxedError> {
+                <loader::StringLoader as loader::StreamLoader<String>>::load_stream(reader, ext).map(Text)
+            }
+        }
+
+        impl Asset for Text {
+            const EXTENSION: &'static str = "x";
+            type Loader = loader::Streamed<loader::StringLoader>;
+        }
+
+        let cache = AssetCache::with_source(StreamOnly(FileSystem::new("assets").unwrap()));
+        assert_eq!(cache.load::<Text>("test.b").unwrap().read().0, "-7");
+    }
+
+    #[test]
+    fn load_contextual() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        assert_eq!(cache.load::<super::W>("test.cache").unwrap().read().0, 42);
+        assert!(cache.contains::<X>("test.cache"));
+
+        assert!(cache.load::<super::W>("test.a").is_err());
+        assert!(cache.load::<super::W>("test.missing").is_err());
+    }
+
+    #[test]
+    fn load_stateful() {
+        let mut cache = AssetCache::new("assets").unwrap();
+
+        assert!(cache.load::<super::V>("test.cache").is_err());
+
+        cache.register_loader(super::Offset(10));
+        assert_eq!(cache.loader::<super::Offset>().unwrap().0, 10);
+        assert_eq!(cache.load::<super::V>("test.cache").unwrap().read().0, 52);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn load_async() {
+        use super::XA;
+
+        use crate::source::{Blocking, FileSystem, Unblock};
+
+        fn assert_send<T: Send>(_: &T) {}
+
+        let cache = AssetCache::with_source(Unblock::new(FileSystem::new("assets").unwrap()));
+        assert_send(&cache.load_async::<XA>("test.cache"));
+
+        let handle = pollster::block_on(cache.load_async::<XA>("test.cache")).unwrap();
+        assert_eq!(*handle.read(), XA(42));
+        assert!(cache.contains::<XA>("test.cache"));
+
+        let handle = pollster::block_on(cache.load_async::<XA>("test.cache")).unwrap();
+        assert_eq!(*handle.read(), XA(42));
+
+        assert!(pollster::block_on(cache.load_async::<XA>("test.a")).is_err());
+        assert!(pollster::block_on(cache.load_async::<XA>("test.missing")).is_err());
+
+        // Synchronous loading still works with the same source
+        assert_eq!(*cache.load::<X>("test.b").unwrap().read(), X(-7));
+
+        let cache = AssetCache::with_source(Blocking::new(FileSystem::new("assets").unwrap()));
+        let handle = pollster::block_on(cache.load_async::<XA>("test.b")).unwrap();
+        assert_eq!(*handle.read(), XA(-7));
+    }
+
+    #[test]
+    fn load_background() {
+        let cache = std::sync::Arc::new(AssetCache::new("assets").unwrap());
+
+        let handle = cache.load_background::<X>("test.b");
+        assert_eq!(handle.id(), "test.b");
+        assert_eq!(*handle.join().unwrap().read(), X(-7));
+        assert!(cache.contains::<X>("test.b"));
+
+        // Already in the cache
+        let handle = cache.load_background::<X>("test.b");
+        assert!(handle.is_finished());
+        assert_eq!(*handle.join().unwrap().read(), X(-7));
+
+        assert!(matches!(cache.load_background::<X>("test.a").join(), Err(crate::Error::Load(_))));
+        assert!(matches!(cache.load_background::<X>("test.missing").join(), Err(crate::Error::Source(_))));
+        assert!(!cache.contains::<X>("test.missing"));
+    }
+
+    #[test]
+    fn load_background_with() {
+        let cache = std::sync::Arc::new(AssetCache::new("assets").unwrap());
+
+        let pool = |task: Box<dyn FnOnce() + Send>| drop(std::thread::spawn(task));
+        let handle = cache.load_background_with::<X, _>("test.cache", &pool);
+        let asset = loop {
+            if let Some(asset) = handle.try_join() {
+                break asset.unwrap();
+            }
+        };
+        assert_eq!(*asset.read(), X(42));
+
+        // A task dropped by the pool gives an error instead of blocking
+        let dropping = |_: Box<dyn FnOnce() + Send>| ();
+        assert!(cache.load_background_with::<X, _>("test.b", &dropping).join().is_err());
+    }
+
+    #[test]
+    fn load_background_once() {
+        let cache = std::sync::Arc::new(AssetCache::new("assets").unwrap());
+
+        let tasks = std::cell::RefCell::new(Vec::new());
+        let pool = |task| tasks.borrow_mut().push(task);
+
+        let first = cache.load_background_with::<X, _>("test.b", &pool);
+        let second = cache.load_background_with::<X, _>("test.b", &pool);
+        let other = cache.load_background_with::<X, _>("test.a", &pool);
+        assert_eq!(tasks.borrow().len(), 2);
+        assert!(!first.is_finished());
+
+        for task in tasks.take() {
+            task();
+        }
+        assert!(second.is_finished());
+        assert_eq!(*first.join().unwrap().read(), X(-7));
+        assert_eq!(*second.join().unwrap().read(), X(-7));
+        assert!(other.join().is_err());
+    }
+
+    #[test]
+    fn load_background_shared_error() {
+        let cache = std::sync::Arc::new(AssetCache::new("assets").unwrap());
+
+        let tasks = std::cell::RefCell::new(Vec::new());
+        let pool = |task| tasks.borrow_mut().push(task);
+
+        let first = cache.load_background_with::<X, _>("test.a", &pool);
+        let second = cache.load_background_with::<X, _>("test.a", &pool);
+        let missing = cache.load_background_with::<X, _>("test.missing", &pool);
+        for task in tasks.take() {
+            task();
+        }
+
+        let first = first.join().unwrap_err();
+        let second = second.join().unwrap_err();
+        assert!(matches!(&second, crate::Error::Load(err) if err.id() == "test.a"));
+        assert_eq!(first.to_string(), second.to_string());
+
+        // Joining twice gives the same error again
+        let err = missing.join().unwrap_err();
+        assert!(matches!(&err, crate::Error::Source(err) if err.io_kind() == std::io::ErrorKind::NotFound));
+        assert_eq!(err.to_string(), missing.join().unwrap_err().to_string());
+        assert!(tasks.borrow().is_empty());
+    }
+
+    #[test]
+    fn load_error() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let err = match cache.load::<X>("test.a") {
+            Err(crate::Error::Load(err)) => err,
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        };
+        assert_eq!(err.id(), "test.a");
+        assert_eq!(err.ext(), "x");
+        assert!(err.loader().contains("ParseLoader"));
+        assert!(err.to_string().contains("\"test.a\""));
+
+        let err = match cache.load::<X>("test.missing") {
+            Err(crate::Error::Source(err)) => err,
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        };
+        assert_eq!(err.kind(), crate::source::SourceErrorKind::NotFound);
+        assert_eq!(err.id(), Some("test.missing"));
+        assert_eq!(err.ext(), Some("x"));
+        assert!(err.source_name().unwrap().contains("FileSystem"));
+    }
+
     #[test]
     fn load_owned() {
         let cache = AssetCache::new("assets").unwrap();
@@ -86,6 +375,21 @@ mod asset_cache {
         assert!(!cache.contains::<X>("test.cache"));
     }
 
+    #[test]
+    fn save() {
+        let dir = std::env::temp_dir().join(format!("assets_manager_save_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let cache = AssetCache::new(&dir).unwrap();
+        cache.save("nested.value", &X(7)).unwrap();
+        assert_eq!(cache.load_owned::<X>("nested.value").unwrap(), X(7));
+
+        cache.save("nested.value", &X(-3)).unwrap();
+        assert_eq!(cache.load_owned::<X>("nested.value").unwrap(), X(-3));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn load_cached() {
         let cache = AssetCache::new("assets").unwrap();
@@ -108,6 +412,22 @@ mod asset_cache {
         assert_eq!(loaded, [-7, 42]);
     }
 
+    #[test]
+    fn load_dir_recursive() {
+        let cache = AssetCache::new("assets").unwrap();
+
+        let mut loaded: Vec<_> = cache.load_dir_recursive::<X>("test_recursive").unwrap()
+            .iter().map(|x| x.read().0).collect();
+        loaded.sort();
+        assert_eq!(loaded, [1, 2, 3]);
+
+        // Directories loaded recursively are distinct from others
+        assert!(!cache.contains_dir::<X>("test_recursive"));
+        let loaded: Vec<_> = cache.load_dir::<X>("test_recursive").unwrap()
+            .iter().map(|x| x.read().0).collect();
+        assert_eq!(loaded, [1]);
+    }
+
     #[test]
     fn load_dir_all() {
         let cache = AssetCache::new("assets").unwrap();
@@ -150,6 +470,23 @@ mod asset_cache {
         cache.remove::<X>("test.cache");
         assert!(!cache.contains::<X>("test.cache"));
     }
+
+    #[test]
+    fn set_source() {
+        use crate::source::{AnySource, FileSystem, Memory};
+
+        let mut cache = AssetCache::with_source(AnySource::new(FileSystem::new("assets").unwrap()));
+        assert_eq!(*cache.load::<X>("test.cache").unwrap().read(), X(42));
+
+        let memory = Memory::new();
+        memory.insert("test.cache", "x", "7");
+        let old = cache.set_source(AnySource::new(memory));
+        assert!(old.is::<FileSystem>());
+
+        assert!(!cache.contains::<X>("test.cache"));
+        assert_eq!(*cache.load::<X>("test.cache").unwrap().read(), X(7));
+        assert!(cache.load::<X>("test.b").is_err());
+    }
 }
 
 mod handle {
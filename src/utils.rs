@@ -77,6 +77,13 @@ impl OwnedKey {
         }
     }
 
+    /// Creates a `OwnedKey` with the given type id and id.
+    #[cfg(feature = "hot-reloading")]
+    #[inline]
+    pub fn new_with(id: Arc<str>, type_id: TypeId) -> Self {
+        Self { id, type_id }
+    }
+
     #[cfg(feature = "hot-reloading")]
     #[inline]
     pub fn id(&self) -> &str {
@@ -241,21 +248,24 @@ impl<T: ?Sized> RwLock<T> {
 }
 
 
-#[cfg(feature = "hot-reloading")]
 pub(crate) struct Mutex<T: ?Sized>(sync::Mutex<T>);
 
-#[cfg(feature = "hot-reloading")]
 impl<T> Mutex<T> {
     #[inline]
     pub fn new(inner: T) -> Self {
         Self(sync::Mutex::new(inner))
     }
+
+    #[cfg(any(feature = "pack", feature = "sqlite"))]
+    #[inline]
+    pub fn into_inner(self) -> T {
+        wrap(self.0.into_inner())
+    }
 }
 
-#[cfg(feature = "hot-reloading")]
 impl<T: ?Sized> Mutex<T> {
     #[inline]
-    pub fn lock(&self) -> sync::MutexGuard<T> {
+    pub fn lock(&self) -> sync::MutexGuard<'_, T> {
         wrap(self.0.lock())
     }
 }